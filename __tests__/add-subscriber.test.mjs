@@ -10,6 +10,8 @@ let publishSubscriberEvent;
 let EVENT_TYPES;
 let mockGetTenant;
 let mockFormatResponse;
+let mockFindSuppressionEntry;
+let mockClearOptOutEntry;
 
 async function loadIsolated() {
   await jest.isolateModulesAsync(async () => {
@@ -93,6 +95,15 @@ async function loadIsolated() {
       createLogger: jest.fn().mockReturnValue({ info: jest.fn(), warn: jest.fn(), error: jest.fn() }),
     }));
 
+    // suppression list (not suppressed by default)
+    mockFindSuppressionEntry = jest.fn().mockResolvedValue(null);
+    mockClearOptOutEntry = jest.fn().mockResolvedValue(true);
+    jest.unstable_mockModule('../functions/utils/suppression.mjs', () => ({
+      findSuppressionEntry: mockFindSuppressionEntry,
+      clearOptOutEntry: mockClearOptOutEntry,
+      isClearedByOptIn: (entry) => entry?.type === 'email' && entry?.source === 'unsubscribe',
+    }));
+
     // Import AFTER mocks, inside isolation
     ({ handler } = await import('../functions/subscribers/add-subscriber.mjs'));
     ({ UpdateItemCommand, PutItemCommand } = await import('@aws-sdk/client-dynamodb'));
@@ -251,6 +262,37 @@ describe('add-subscriber handler (isolated)', () => {
     expect(publishSubscriberEvent).not.toHaveBeenCalled();
   });
 
+  test('re-subscribing lifts the entry the address\'s own unsubscribe added', async () => {
+    mockGetTenant.mockResolvedValue({ id: 't1', list: 'list-1', subscribers: 5 });
+    mockFindSuppressionEntry.mockResolvedValue({ entry: 'back@example.com', type: 'email', reason: 'manual', source: 'unsubscribe' });
+    ddbInstance.send.mockResolvedValue({});
+
+    const res = await handler({
+      pathParameters: { tenant: 't1' },
+      body: JSON.stringify({ email: 'back@example.com' }),
+    });
+
+    expect(res && res.statusCode).toBe(201);
+    expect(mockClearOptOutEntry).toHaveBeenCalledWith('t1', 'back@example.com');
+    expect(ddbInstance.send.mock.calls[0][0].TableName).toBe('test-subscribers-table');
+    expect(publishSubscriberEvent).toHaveBeenCalledTimes(1);
+  });
+
+  test('rejects an address suppressed for any other reason', async () => {
+    mockGetTenant.mockResolvedValue({ id: 't1', list: 'list-1', subscribers: 5 });
+    mockFindSuppressionEntry.mockResolvedValue({ entry: 'example.com', type: 'domain', reason: 'legal', source: 'api' });
+
+    const res = await handler({
+      pathParameters: { tenant: 't1' },
+      body: JSON.stringify({ email: 'held@example.com' }),
+    });
+
+    expect(res && res.statusCode).toBe(409);
+    expect(mockClearOptOutEntry).not.toHaveBeenCalled();
+    expect(ddbInstance.send).not.toHaveBeenCalled();
+    expect(publishSubscriberEvent).not.toHaveBeenCalled();
+  });
+
   test('unexpected DDB error → 500', async () => {
     const tenant = { id: 't1', list: 'list-1', subscribers: 5 };
     mockGetTenant.mockResolvedValue(tenant);
@@ -5,6 +5,7 @@ let ddbSend;
 let eventBridgeSend;
 let mockGetMostRecentPublishedIssue;
 let mockIncrementIssueCounter;
+let mockAddSuppressionEntry;
 
 const loadIsolated = async () => {
   await jest.isolateModulesAsync(async () => {
@@ -12,6 +13,7 @@ const loadIsolated = async () => {
     eventBridgeSend = jest.fn();
     mockGetMostRecentPublishedIssue = jest.fn();
     mockIncrementIssueCounter = jest.fn();
+    mockAddSuppressionEntry = jest.fn(async () => true);
 
     jest.unstable_mockModule('@aws-sdk/client-dynamodb', () => ({
       DynamoDBClient: jest.fn(() => ({ send: ddbSend })),
@@ -74,6 +76,10 @@ const loadIsolated = async () => {
       incrementIssueCounter: mockIncrementIssueCounter,
     }));
 
+    jest.unstable_mockModule('../functions/utils/suppression.mjs', () => ({
+      addSuppressionEntry: mockAddSuppressionEntry,
+    }));
+
     ({ handler } = await import('../functions/subscribers/clean-bounced-subscribers.mjs'));
   });
 };
@@ -137,6 +143,10 @@ describe('clean-bounced-subscribers', () => {
       // incrementIssueCounter called once per successful removal
       expect(mockIncrementIssueCounter).toHaveBeenCalledTimes(2);
       expect(mockIncrementIssueCounter).toHaveBeenCalledWith('tenant123#42', 'cleaned');
+
+      // Both persistent failures land on the suppression list
+      expect(mockAddSuppressionEntry).toHaveBeenCalledTimes(2);
+      expect(mockAddSuppressionEntry).toHaveBeenCalledWith('tenant123', 'bounce1@example.com', { reason: 'hard-bounce', source: 'bounce-cleanup' });
     });
 
     it('should not increment counter when no published issue found', async () => {
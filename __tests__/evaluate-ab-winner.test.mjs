@@ -90,8 +90,8 @@ const baseEvent = {
   }
 };
 
-// Wires the DDB mock: GetItem(newsletter) -> GetItem(stats#v#a) -> GetItem(stats#v#b) -> UpdateItem
-const wireDdb = ({ abTest, aStats, bStats }) => {
+// Wires the DDB mock: GetItem(newsletter) -> GetItem(stats#v#<id>) per variant -> UpdateItem
+const wireDdb = ({ abTest, aStats, bStats, statsByVariant = {} }) => {
   ddbSend.mockImplementation(async (cmd) => {
     if (cmd.__type === 'GetItem') {
       const sk = unmarshall(cmd.Key).sk;
@@ -104,6 +104,10 @@ const wireDdb = ({ abTest, aStats, bStats }) => {
       if (sk === 'stats#v#b') {
         return bStats === null ? { Item: null } : { Item: marshall({ ...bStats }) };
       }
+      const extra = statsByVariant[sk.slice('stats#v#'.length)];
+      if (extra) {
+        return { Item: marshall({ ...extra }) };
+      }
     }
     return {};
   });
@@ -316,6 +320,87 @@ describe('evaluate-ab-winner', () => {
     expect(sent.detail.sendAt).toBe(winnerSendAt);
   });
 
+  it('multi-variant: evaluates every configured variant and sends the winning subject', async () => {
+    wireDdb({
+      abTest: abTestConfig({
+        variants: [
+          { variantId: 'a', subject: 'Control Subject A' },
+          { variantId: 'b', subject: 'Challenger Subject B' },
+          { variantId: 'c', subject: 'Challenger Subject C' }
+        ]
+      }),
+      aStats: { opens: 200, clicks: 10, deliveries: 1000 },
+      bStats: { opens: 210, clicks: 10, deliveries: 1000 },
+      statsByVariant: { c: { opens: 400, clicks: 20, deliveries: 1000 } }
+    });
+
+    const result = await handler(baseEvent);
+    expect(result).toBe(true);
+
+    const persisted = getUpdateAbTest();
+    expect(persisted.winnerVariantId).toBe('c');
+    expect(Object.keys(persisted.evaluation.variants)).toEqual(['a', 'b', 'c']);
+    expect(getSentEmail().detail.subject).toBe('Challenger Subject C');
+
+    const putCall = ddbSend.mock.calls.find(([cmd]) => cmd.__type === 'PutItem');
+    expect(putCall).toBeDefined();
+  });
+
+  it('adaptive: splits the hold-out across every variant by its allocation', async () => {
+    wireDdb({
+      abTest: abTestConfig({ allocation: { mode: 'epsilonGreedy', epsilon: 0.2 } }),
+      aStats: { opens: 200, clicks: 10, deliveries: 1000 },
+      bStats: { opens: 400, clicks: 20, deliveries: 1000 }
+    });
+
+    const result = await handler(baseEvent);
+    expect(result).toBe(true);
+
+    const persisted = getUpdateAbTest();
+    expect(persisted.winnerVariantId).toBe('b');
+    expect(persisted.evaluation.holdoutWeights.b).toBeCloseTo(0.9, 9);
+
+    const sent = getSentEmail();
+    expect(sent.detail.subject).toBeUndefined();
+    expect(sent.detail.variants).toEqual([
+      { variantId: 'a', subject: 'Control Subject A' },
+      { variantId: 'b', subject: 'Challenger Subject B' }
+    ]);
+    expect(sent.detail.variantWeights[0]).toBeCloseTo(0.1, 9);
+    expect(sent.detail.variantWeights[1]).toBeCloseTo(0.9, 9);
+  });
+
+  it('content: keeps the shared subject and sends the winning variant html', async () => {
+    wireDdb({
+      abTest: abTestConfig({
+        dimension: 'content',
+        variants: [
+          { variantId: 'a', content: 'Intro A' },
+          { variantId: 'b', content: 'Intro B' }
+        ]
+      }),
+      aStats: { opens: 200, clicks: 10, deliveries: 1000 },
+      bStats: { opens: 400, clicks: 20, deliveries: 1000 }
+    });
+
+    const result = await handler({
+      detail: {
+        ...baseEvent.detail,
+        sendPayload: {
+          ...baseEvent.detail.sendPayload,
+          subject: 'Shared Subject',
+          variantHtml: { a: '<p>A</p>', b: '<p>B</p>' }
+        }
+      }
+    });
+    expect(result).toBe(true);
+
+    const sent = getSentEmail();
+    expect(sent.detail.subject).toBe('Shared Subject');
+    expect(sent.detail.html).toBe('<p>B</p>');
+    expect(sent.detail.variantHtml).toBeUndefined();
+  });
+
   it('guard: status already sent => no Send Email v2 and no DDB write', async () => {
     wireDdb({
       abTest: abTestConfig({ status: 'sent' }),
@@ -9,6 +9,7 @@ let lookupGeoMock;
 let recordTimeZoneObservationMock;
 let recordActivityMock;
 let recordOpenHourMock;
+let addSuppressionEntryMock;
 
 const loadIsolated = async () => {
   await jest.isolateModulesAsync(async () => {
@@ -96,6 +97,18 @@ const loadIsolated = async () => {
       recordOpenHour: recordOpenHourMock,
     }));
 
+    addSuppressionEntryMock = jest.fn().mockResolvedValue(true);
+
+    jest.unstable_mockModule('../functions/utils/suppression.mjs', () => ({
+      addSuppressionEntry: addSuppressionEntryMock,
+    }));
+
+    // Deliveries, opens, bounces and complaints also bump one
+    // stats#provider#<provider> counter; skip the MX lookup.
+    jest.unstable_mockModule('../functions/utils/mailbox-provider.mjs', () => ({
+      classifyMailboxProvider: jest.fn().mockResolvedValue('other'),
+    }));
+
     ({ handler } = await import('../functions/handle-email-status.mjs'));
     ({ PutItemCommand, UpdateItemCommand, GetItemCommand } = await import('@aws-sdk/client-dynamodb'));
   });
@@ -137,7 +150,7 @@ describe('handle-email-status', () => {
       const result = await handler(event);
 
       expect(result).toBe(true);
-      expect(ddbSend).toHaveBeenCalledTimes(4);
+      expect(ddbSend).toHaveBeenCalledTimes(5);
 
       const trackCall = ddbSend.mock.calls[2][0];
       expect(trackCall.__type).toBe('PutItem');
@@ -258,7 +271,7 @@ describe('handle-email-status', () => {
       const result = await handler(event);
 
       expect(result).toBe(true);
-      expect(ddbSend).toHaveBeenCalledTimes(4);
+      expect(ddbSend).toHaveBeenCalledTimes(5);
 
       // First call is GetItemCommand for publishedAt
       const getCall = ddbSend.mock.calls[0][0];
@@ -548,7 +561,7 @@ describe('handle-email-status', () => {
       const result = await handler(event);
 
       expect(result).toBe(true);
-      expect(ddbSend).toHaveBeenCalledTimes(2);
+      expect(ddbSend).toHaveBeenCalledTimes(3);
 
       const captureCall = ddbSend.mock.calls[0][0];
       expect(captureCall.__type).toBe('PutItem');
@@ -559,6 +572,7 @@ describe('handle-email-status', () => {
       expect(captureCall.Item.bounceType.S).toBe('permanent');
       expect(captureCall.Item.bounceReason.S).toBe('smtp; 550 5.1.1 user unknown');
       expect(captureCall.Item.ttl.N).toBeDefined();
+      expect(addSuppressionEntryMock).toHaveBeenCalledWith('tenant123', 'bounced@example.com', { reason: 'hard-bounce', source: 'bounce' });
     });
 
     it('should categorize transient bounce as temporary', async () => {
@@ -592,6 +606,7 @@ describe('handle-email-status', () => {
       const captureCall = ddbSend.mock.calls[0][0];
       expect(captureCall.Item.bounceType.S).toBe('temporary');
       expect(captureCall.Item.bounceReason.S).toBe('smtp; 552 mailbox full');
+      expect(addSuppressionEntryMock).not.toHaveBeenCalled();
     });
 
     it('should categorize suppressed bounce correctly', async () => {
@@ -860,7 +875,7 @@ describe('handle-email-status', () => {
       const result = await handler(event);
 
       expect(result).toBe(true);
-      expect(ddbSend).toHaveBeenCalledTimes(2);
+      expect(ddbSend).toHaveBeenCalledTimes(3);
 
       const captureCall = ddbSend.mock.calls[0][0];
       expect(captureCall.__type).toBe('PutItem');
@@ -1116,7 +1131,7 @@ describe('handle-email-status', () => {
       const result = await handler(event);
 
       expect(result).toBe(true);
-      expect(ddbSend).toHaveBeenCalledTimes(2);
+      expect(ddbSend).toHaveBeenCalledTimes(3);
 
       const updateCall = ddbSend.mock.calls[1][0];
       expect(updateCall.__type).toBe('UpdateItem');
@@ -1145,7 +1160,7 @@ describe('handle-email-status', () => {
       const result = await handler(event);
 
       expect(result).toBe(true);
-      expect(ddbSend).toHaveBeenCalledTimes(2);
+      expect(ddbSend).toHaveBeenCalledTimes(3);
 
       const updateCall = ddbSend.mock.calls[1][0];
       expect(updateCall.__type).toBe('UpdateItem');
@@ -1170,7 +1185,7 @@ describe('handle-email-status', () => {
       const result = await handler(event);
 
       expect(result).toBe(true);
-      expect(ddbSend).toHaveBeenCalledTimes(1);
+      expect(ddbSend).toHaveBeenCalledTimes(2);
     });
 
     it('should handle click events', async () => {
@@ -1222,7 +1237,7 @@ describe('handle-email-status', () => {
       const result = await handler(event);
 
       expect(result).toBe(true);
-      expect(ddbSend).toHaveBeenCalledTimes(1);
+      expect(ddbSend).toHaveBeenCalledTimes(2);
 
       const updateCall = ddbSend.mock.calls[0][0];
       expect(updateCall.__type).toBe('UpdateItem');
@@ -1413,6 +1428,10 @@ describe('handle-email-status', () => {
         updateSubscriberEngagement: jest.fn().mockResolvedValue(undefined),
       }));
 
+      jest.unstable_mockModule('../functions/utils/suppression.mjs', () => ({
+        addSuppressionEntry: jest.fn().mockResolvedValue(true),
+      }));
+
       ({ handler } = await import('../functions/handle-email-status.mjs'));
     });
 
@@ -1568,6 +1587,7 @@ describe('handle-email-status', () => {
       ddbSend.mockResolvedValueOnce({}); // PutItem (track unique)
       ddbSend.mockResolvedValueOnce({}); // UpdateItem (aggregate stats)
       ddbSend.mockResolvedValueOnce({}); // UpdateItem (per-variant stats)
+      ddbSend.mockResolvedValueOnce({}); // UpdateItem (per-provider stats)
 
       const event = {
         detail: {
@@ -1587,12 +1607,12 @@ describe('handle-email-status', () => {
       const result = await handler(event);
 
       expect(result).toBe(true);
-      expect(ddbSend).toHaveBeenCalledTimes(5);
+      expect(ddbSend).toHaveBeenCalledTimes(6);
 
       const updateCalls = ddbSend.mock.calls
         .map(([command]) => command)
         .filter((command) => command.__type === 'UpdateItem');
-      expect(updateCalls).toHaveLength(2);
+      expect(updateCalls).toHaveLength(3);
 
       const aggregateCall = updateCalls.find((c) => c.Key.sk.S === 'stats');
       const variantCall = updateCalls.find((c) => c.Key.sk.S === 'stats#v#a');
@@ -1632,20 +1652,22 @@ describe('handle-email-status', () => {
       const result = await handler(event);
 
       expect(result).toBe(true);
-      expect(ddbSend).toHaveBeenCalledTimes(1);
+      expect(ddbSend).toHaveBeenCalledTimes(2);
 
       const updateCalls = ddbSend.mock.calls
         .map(([command]) => command)
         .filter((command) => command.__type === 'UpdateItem');
-      expect(updateCalls).toHaveLength(1);
+      expect(updateCalls).toHaveLength(2);
       expect(updateCalls[0].Key.sk.S).toBe('stats');
 
       const variantCall = updateCalls.find((c) => c.Key.sk.S.startsWith('stats#v#'));
       expect(variantCall).toBeUndefined();
     });
 
-    it('should ignore an invalid variant value', async () => {
+    it('should write per-variant stats for variants after b', async () => {
       ddbSend.mockResolvedValueOnce({}); // UpdateItem (aggregate stats)
+      ddbSend.mockResolvedValueOnce({}); // UpdateItem (per-variant stats)
+      ddbSend.mockResolvedValueOnce({}); // UpdateItem (per-provider stats)
 
       const event = {
         detail: {
@@ -1663,12 +1685,42 @@ describe('handle-email-status', () => {
       const result = await handler(event);
 
       expect(result).toBe(true);
-      expect(ddbSend).toHaveBeenCalledTimes(1);
+      expect(ddbSend).toHaveBeenCalledTimes(3);
 
       const updateCalls = ddbSend.mock.calls
         .map(([command]) => command)
         .filter((command) => command.__type === 'UpdateItem');
-      expect(updateCalls).toHaveLength(1);
+      const variantCall = updateCalls.find((c) => c.Key.sk.S === 'stats#v#c');
+      expect(variantCall).toBeDefined();
+      expect(variantCall.Key.pk.S).toBe('tenant123#issue-456');
+      expect(variantCall.ExpressionAttributeNames['#stat']).toBe('deliveries');
+    });
+
+    it('should ignore an invalid variant value', async () => {
+      ddbSend.mockResolvedValueOnce({}); // UpdateItem (aggregate stats)
+
+      const event = {
+        detail: {
+          eventType: 'Delivery',
+          mail: {
+            tags: {
+              referenceNumber: ['tenant123_issue-456'],
+              variant: ['g']
+            },
+            destination: ['subscriber@example.com']
+          }
+        }
+      };
+
+      const result = await handler(event);
+
+      expect(result).toBe(true);
+      expect(ddbSend).toHaveBeenCalledTimes(2);
+
+      const updateCalls = ddbSend.mock.calls
+        .map(([command]) => command)
+        .filter((command) => command.__type === 'UpdateItem');
+      expect(updateCalls).toHaveLength(2);
       expect(updateCalls[0].Key.sk.S).toBe('stats');
     });
   });
@@ -5,6 +5,7 @@ let unsubscribeUser;
 let getTenant;
 let getMostRecentPublishedIssue;
 let incrementIssueCounter;
+let addSuppressionEntry;
 
 async function loadIsolated() {
   await jest.isolateModulesAsync(async () => {
@@ -12,6 +13,7 @@ async function loadIsolated() {
     getTenant = jest.fn();
     getMostRecentPublishedIssue = jest.fn();
     incrementIssueCounter = jest.fn();
+    addSuppressionEntry = jest.fn().mockResolvedValue(true);
 
     jest.unstable_mockModule('../functions/utils/subscriber.mjs', () => ({
       unsubscribeUser,
@@ -21,6 +23,11 @@ async function loadIsolated() {
       getTenant,
     }));
 
+    jest.unstable_mockModule('../functions/utils/suppression.mjs', () => ({
+      UNSUBSCRIBE_SOURCE: 'unsubscribe',
+      addSuppressionEntry,
+    }));
+
     jest.unstable_mockModule('../functions/utils/issue-attribution.mjs', () => ({
       getMostRecentPublishedIssue,
       incrementIssueCounter,
@@ -29,7 +36,7 @@ async function loadIsolated() {
     ({ handler } = await import('../functions/subscribers/manual-unsubscribe.mjs'));
   });
 
-  return { handler, unsubscribeUser, getTenant, getMostRecentPublishedIssue, incrementIssueCounter };
+  return { handler, unsubscribeUser, getTenant, getMostRecentPublishedIssue, incrementIssueCounter, addSuppressionEntry };
 }
 
 describe('manual-unsubscribe handler', () => {
@@ -79,6 +86,10 @@ describe('manual-unsubscribe handler', () => {
         userAgent: 'Mozilla/5.0'
       })
     );
+    expect(addSuppressionEntry).toHaveBeenCalledWith('test-tenant', 'test@example.com', {
+      reason: 'manual',
+      source: 'unsubscribe'
+    });
   });
 
   test('invalid email format returns error JSON with validation message', async () => {
@@ -157,6 +168,10 @@ describe('manual-unsubscribe handler', () => {
         userAgent: 'Mozilla/5.0'
       })
     );
+    expect(addSuppressionEntry).toHaveBeenCalledWith('test-tenant', 'already@unsubscribed.com', {
+      reason: 'manual',
+      source: 'unsubscribe'
+    });
     expect(getMostRecentPublishedIssue).not.toHaveBeenCalled();
     expect(incrementIssueCounter).not.toHaveBeenCalled();
   });
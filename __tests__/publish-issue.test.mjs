@@ -5,6 +5,7 @@ let ddbSend;
 let eventBridgeSend;
 let getTenant;
 let publishIssueEvent;
+let parseMarkdown;
 
 const marshall = (obj) => {
   const result = {};
@@ -40,6 +41,9 @@ const loadIsolated = async () => {
     eventBridgeSend = jest.fn().mockResolvedValue({});
     getTenant = jest.fn().mockResolvedValue({ pk: 'tenant-1', list: 'main-list', subscribers: 100 });
     publishIssueEvent = jest.fn().mockResolvedValue(undefined);
+    parseMarkdown = jest.fn(async ({ content, issueId }) => ({
+      data: { metadata: { number: issueId, title: `Parsed ${content}` }, content: { sections: [] } }
+    }));
 
     jest.unstable_mockModule('@aws-sdk/client-dynamodb', () => ({
       DynamoDBClient: jest.fn(() => ({ send: ddbSend })),
@@ -62,6 +66,8 @@ const loadIsolated = async () => {
 
     jest.unstable_mockModule('../functions/utils/helpers.mjs', () => ({ getTenant }));
 
+    jest.unstable_mockModule('../functions/parse-md-to-json.mjs', () => ({ handler: parseMarkdown }));
+
     jest.unstable_mockModule('../functions/utils/event-publisher.mjs', () => ({
       publishIssueEvent,
       EVENT_TYPES: { ISSUE_PUBLISHED: 'ISSUE_PUBLISHED' }
@@ -192,6 +198,56 @@ describe('publish-issue', () => {
     });
   });
 
+  describe('subscriber merge fields', () => {
+    it('renders subscriber.* as merge tokens and passes the field defaults on the send', async () => {
+      const templateContent = 'Hi {{subscriber.firstName}} at {{subscriber.company}} on {{subscriber.plan}} ({{subscriber.email}})';
+
+      ddbSend.mockImplementation(async (cmd) => {
+        if (cmd.__type === 'GetItem' && cmd.Key.sk.S === 'template#tmpl-5') {
+          return { Item: marshall({ pk: 'tenant-1', sk: 'template#tmpl-5', content: templateContent }) };
+        }
+        if (cmd.__type === 'GetItem' && cmd.Key.sk.S === 'subscriber-fields') {
+          return {
+            Item: {
+              ...marshall({ pk: 'tenant-1', sk: 'subscriber-fields' }),
+              fields: {
+                L: [
+                  { M: marshall({ key: 'company', label: 'Company', type: 'string' }) },
+                  { M: marshall({ key: 'plan', label: 'Plan', type: 'enum', default: 'Free' }) }
+                ]
+              }
+            }
+          };
+        }
+        if (cmd.__type === 'Query') {
+          return { Items: [] };
+        }
+        return {};
+      });
+
+      const result = await handler({
+        data: sampleData,
+        subject: 'Subject',
+        tenantId: 'tenant-1',
+        templateId: 'tmpl-5',
+        isPreview: true,
+        email: 'preview@example.com',
+        sendAtDate: 'now'
+      });
+
+      expect(result).toEqual({ success: true });
+      const call = eventBridgeSend.mock.calls.find(([cmd]) => cmd.__type === 'PutEvents');
+      const detail = JSON.parse(call[0].Entries[0].Detail);
+      expect(detail.html).toBe('Hi __SUBSCRIBER_firstName__ at __SUBSCRIBER_company__ on __SUBSCRIBER_plan__ (__EMAIL__)');
+      expect(detail.replacements.subscriberFields).toEqual({
+        firstName: '',
+        lastName: '',
+        company: '',
+        plan: 'Free'
+      });
+    });
+  });
+
   describe('missing snippet renders empty', () => {
     it('still succeeds and renders the missing partial as an empty string', async () => {
       const templateContent = 'Start[{{> missingSnippet }}]End';
@@ -430,6 +486,47 @@ describe('publish-issue', () => {
       warnSpy.mockRestore();
     });
 
+    it('renders every content variant into the html its bucket is sent', async () => {
+      mockIssueRecord({
+        abTest: JSON.stringify({
+          dimension: 'content',
+          variants: [
+            { variantId: 'a', content: 'intro A' },
+            { variantId: 'b', content: 'intro B' }
+          ]
+        })
+      });
+
+      await handler(publishEvent);
+
+      const detail = getSentDetail();
+      expect(detail.html).toBe('DEFAULT-TEMPLATE Test Issue #42');
+      expect(detail.abTest.variants).toEqual([
+        { variantId: 'a', content: 'intro A', html: 'DEFAULT-TEMPLATE Parsed intro A #42' },
+        { variantId: 'b', content: 'intro B', html: 'DEFAULT-TEMPLATE Parsed intro B #42' }
+      ]);
+      expect(parseMarkdown).toHaveBeenCalledWith(expect.objectContaining({ content: 'intro A', issueId: 42 }));
+    });
+
+    it('parses JSON content variants and keeps the issue number', async () => {
+      mockIssueRecord({
+        abTest: JSON.stringify({
+          dimension: 'content',
+          variants: [
+            { variantId: 'a', content: JSON.stringify({ metadata: { title: 'Layout A', number: 7 } }) },
+            { variantId: 'b', content: JSON.stringify({ metadata: { title: 'Layout B' } }) }
+          ]
+        })
+      });
+
+      await handler({ ...publishEvent, contentType: 'json' });
+
+      const [a, b] = getSentDetail().abTest.variants;
+      expect(a.html).toBe('DEFAULT-TEMPLATE Layout A #42');
+      expect(b.html).toBe('DEFAULT-TEMPLATE Layout B #42');
+      expect(parseMarkdown).not.toHaveBeenCalled();
+    });
+
     it('fails open (no contentAssembly on the event) when the config read throws', async () => {
       ddbSend.mockImplementation(async (cmd) => {
         if (cmd.__type === 'GetItem' && cmd.Key?.sk?.S === 'newsletter') {
@@ -52,6 +52,12 @@ jest.unstable_mockModule('../functions/utils/subscriber.mjs', () => ({
   updateSubscriberSendMetadata: jest.fn(() => Promise.resolve())
 }));
 
+// Mock suppression list
+jest.unstable_mockModule('../functions/utils/suppression.mjs', () => ({
+  loadSuppressionList: jest.fn(() => Promise.resolve({ emails: new Set(), domains: new Set() })),
+  isSuppressedAddress: jest.fn((list, email) => list.emails.has(email) || list.domains.has(email.split('@')[1]))
+}));
+
 // Import after mocks (the real interest-assembly util is used on purpose).
 const { handler } = await import('../functions/send-email-v2.mjs');
 const { listSubscribers } = await import('../functions/utils/subscriber.mjs');
@@ -43,6 +43,12 @@ jest.unstable_mockModule('../functions/utils/subscriber.mjs', () => ({
   updateSubscriberSendMetadata: jest.fn(() => Promise.resolve())
 }));
 
+// Mock suppression list
+jest.unstable_mockModule('../functions/utils/suppression.mjs', () => ({
+  loadSuppressionList: jest.fn(() => Promise.resolve({ emails: new Set(), domains: new Set() })),
+  isSuppressedAddress: jest.fn((list, email) => list.emails.has(email) || list.domains.has(email.split('@')[1]))
+}));
+
 // The local-send utils module is intentionally NOT mocked: it is pure and this
 // suite verifies the handler's real grouping/scheduling behavior.
 
@@ -49,11 +49,18 @@ jest.unstable_mockModule('../functions/utils/subscriber.mjs', () => ({
   updateSubscriberSendMetadata: jest.fn(() => Promise.resolve())
 }));
 
+// Mock suppression list
+jest.unstable_mockModule('../functions/utils/suppression.mjs', () => ({
+  loadSuppressionList: jest.fn(() => Promise.resolve({ emails: new Set(), domains: new Set() })),
+  isSuppressedAddress: jest.fn((list, email) => list.emails.has(email) || list.domains.has(email.split('@')[1]))
+}));
+
 // Note: KEY_PATTERNS is now defined inline in send-email-v2.mjs (no longer imported from senders/types.mjs)
 
 // Import after mocks
 const { handler } = await import('../functions/send-email-v2.mjs');
 const { listSubscribers, getSubscriberByEmail, updateSubscriberSendMetadata } = await import('../functions/utils/subscriber.mjs');
+const { loadSuppressionList } = await import('../functions/utils/suppression.mjs');
 
 describe('send-email-v2', () => {
   beforeAll(() => {
@@ -399,6 +406,53 @@ describe('send-email-v2', () => {
       expect(updateSubscriberSendMetadata).toHaveBeenCalledWith('tenant-123', 'new@example.com', 'tenant-123_42');
     });
 
+    test('skips suppressed addresses and domains for list sends', async () => {
+      const event = {
+        detail: {
+          subject: 'Issue Subject',
+          html: '<p>Issue content</p>',
+          to: { list: 'main-list' },
+          from: 'sender@example.com',
+          tenantId: 'tenant-123',
+          referenceNumber: 'tenant-123_43'
+        }
+      };
+
+      ddbInstance.send.mockResolvedValueOnce({
+        Items: [{
+          unmarshalled: {
+            senderId: 'sender-123',
+            email: 'sender@example.com',
+            verificationStatus: 'verified',
+            isDefault: false
+          }
+        }]
+      });
+
+      listSubscribers.mockResolvedValue({
+        subscribers: [
+          { email: 'kept@example.com' },
+          { email: 'gone@example.com' },
+          { email: 'anyone@blocked.test' }
+        ],
+        lastEvaluatedKey: undefined
+      });
+      loadSuppressionList.mockResolvedValueOnce({
+        emails: new Set(['gone@example.com']),
+        domains: new Set(['blocked.test'])
+      });
+
+      sesInstance.send.mockResolvedValue({ MessageId: 'msg-123' });
+      ddbInstance.send.mockResolvedValueOnce({});
+
+      const result = await handler(event);
+
+      expect(loadSuppressionList).toHaveBeenCalledWith('tenant-123');
+      expect(result.recipients).toBe(1);
+      expect(sesInstance.send).toHaveBeenCalledTimes(1);
+      expect(updateSubscriberSendMetadata).toHaveBeenCalledWith('tenant-123', 'kept@example.com', 'tenant-123_43');
+    });
+
     test('continues even if metrics update fails', async () => {
       const event = {
         detail: {
@@ -554,6 +608,39 @@ describe('send-email-v2', () => {
       expect(detail.sendPayload.to.list).toBe('my-list');
     });
 
+    test('sends each content variant its own html and hands them to the evaluation', async () => {
+      listSubscribers.mockResolvedValue({ subscribers: makeSubscribers(40), lastEvaluatedKey: undefined });
+
+      await handler({
+        detail: {
+          ...abEvent.detail,
+          abTest: {
+            dimension: 'content',
+            testFraction: 0.5,
+            variants: [
+              { variantId: 'a', content: 'A', html: '<p>Layout A __EMAIL__</p>' },
+              { variantId: 'b', content: 'B', html: '<p>Layout B __EMAIL__</p>' }
+            ]
+          }
+        }
+      });
+
+      expect(sesInstance.send.mock.calls.length).toBeGreaterThan(0);
+      for (const [cmd] of sesInstance.send.mock.calls) {
+        const variant = cmd.EmailTags.find((t) => t.Name === 'variant').Value;
+        expect(cmd.Content.Simple.Subject.Data).toBe('Default subject');
+        expect(cmd.Content.Simple.Body.Html.Data).toContain(`Layout ${variant.toUpperCase()}`);
+      }
+
+      const entry = JSON.parse(schedulerInstance.send.mock.calls[0][0].Target.Input).Entries[0];
+      const { sendPayload } = JSON.parse(entry.Detail);
+      expect(sendPayload.subject).toBe('Default subject');
+      expect(sendPayload.variantHtml).toEqual({
+        a: '<p>Layout A __EMAIL__</p>',
+        b: '<p>Layout B __EMAIL__</p>'
+      });
+    });
+
     test('marks the test as testing once the sample has been sent', async () => {
       listSubscribers.mockResolvedValue({ subscribers: makeSubscribers(20), lastEvaluatedKey: undefined });
 
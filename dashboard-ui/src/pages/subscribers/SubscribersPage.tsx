@@ -20,6 +20,9 @@ import { useMediaQuery } from '@/hooks/useMediaQuery';
 import type { Segment } from '@/services/segmentService';
 import type { SubscriberTrendsResponse, SubscriberListItem } from '@/types';
 
+/** Subscribers fetched per page of the list. */
+const SUBSCRIBER_PAGE_SIZE = 100;
+
 const formatDate = (dateString: string) =>
   new Date(dateString).toLocaleDateString('en-US', {
     year: 'numeric',
@@ -105,6 +108,8 @@ export const SubscribersPage: React.FC = () => {
   const [segmentsError, setSegmentsError] = useState<string | null>(null);
   const [subscriberListError, setSubscriberListError] = useState<string | null>(null);
   const [sortDirection, setSortDirection] = useState<'asc' | 'desc'>('desc');
+  const [subscriberListNextToken, setSubscriberListNextToken] = useState<string | undefined>();
+  const [loadingMoreSubscribers, setLoadingMoreSubscribers] = useState(false);
   const [selectedSubscriber, setSelectedSubscriber] = useState<SubscriberListItem | null>(null);
 
   // Create segment modal state
@@ -172,9 +177,14 @@ export const SubscribersPage: React.FC = () => {
     try {
       setSubscriberListLoading(true);
       setSubscriberListError(null);
-      const response = await subscriberService.getList();
+      const response = await subscriberService.getList({
+        sortBy: 'addedAt',
+        sortOrder: sortDirection,
+        limit: SUBSCRIBER_PAGE_SIZE,
+      });
       if (response.success && response.data) {
         setSubscriberList(response.data.subscribers);
+        setSubscriberListNextToken(response.data.nextToken);
       } else {
         setSubscriberListError(response.error || 'Failed to load subscriber list');
       }
@@ -183,26 +193,41 @@ export const SubscribersPage: React.FC = () => {
     } finally {
       setSubscriberListLoading(false);
     }
-  }, []);
+  }, [sortDirection]);
+
+  const loadMoreSubscribers = useCallback(async () => {
+    if (!subscriberListNextToken) return;
+    setLoadingMoreSubscribers(true);
+    const response = await subscriberService.getList({
+      sortBy: 'addedAt',
+      sortOrder: sortDirection,
+      limit: SUBSCRIBER_PAGE_SIZE,
+      nextToken: subscriberListNextToken,
+    });
+    if (response.success && response.data) {
+      const page = response.data.subscribers;
+      setSubscriberList((prev) => [...prev, ...page]);
+      setSubscriberListNextToken(response.data.nextToken);
+    } else {
+      addToast({ type: 'error', title: 'Failed to load more subscribers', message: response.error || 'Something went wrong.' });
+    }
+    setLoadingMoreSubscribers(false);
+  }, [subscriberListNextToken, sortDirection, addToast]);
 
   useEffect(() => {
     loadTrends();
     loadSegments();
+  }, [loadTrends, loadSegments]);
+
+  // Re-runs when the sort direction flips; the list is sorted server-side.
+  useEffect(() => {
     loadSubscriberList();
-  }, [loadTrends, loadSegments, loadSubscriberList]);
+  }, [loadSubscriberList]);
 
   const latestIssueNumber = trendsData?.points?.[0]?.issueNumber
     ? Number(trendsData.points[0].issueNumber)
     : 0;
 
-  const sortedSubscribers = useMemo(() => {
-    return [...subscriberList].sort((a, b) => {
-      const aTime = a.addedAt ? new Date(a.addedAt).getTime() : 0;
-      const bTime = b.addedAt ? new Date(b.addedAt).getTime() : 0;
-      return sortDirection === 'asc' ? aTime - bTime : bTime - aTime;
-    });
-  }, [subscriberList, sortDirection]);
-
   const toggleSortDirection = useCallback(() => {
     setSortDirection((prev) => (prev === 'asc' ? 'desc' : 'asc'));
   }, []);
@@ -600,7 +625,7 @@ export const SubscribersPage: React.FC = () => {
           <h3 className="text-lg font-semibold text-foreground mb-4">Subscribers</h3>
           <SectionError message={subscriberListError} onRetry={loadSubscriberList} />
         </Card>
-      ) : sortedSubscribers.length === 0 ? (
+      ) : subscriberList.length === 0 ? (
         <Card padding="md">
           <h3 className="text-lg font-semibold text-foreground mb-4">Subscribers</h3>
           <p className="text-sm text-muted-foreground">No subscribers yet.</p>
@@ -609,13 +634,20 @@ export const SubscribersPage: React.FC = () => {
         <Card padding="md">
           <h3 className="text-lg font-semibold text-foreground mb-4">Subscribers</h3>
           <VirtualTable<SubscriberListItem>
-            items={sortedSubscribers}
+            items={subscriberList}
             getKey={(sub) => sub.email}
             ariaLabel="Subscribers list"
             rowHeight={isMobile ? 60 : 44}
             maxHeight={440}
             columns={isMobile ? mobileSubscriberColumns : subscriberColumns}
           />
+          {subscriberListNextToken && (
+            <div className="flex justify-center mt-4">
+              <Button variant="secondary" onClick={loadMoreSubscribers} isLoading={loadingMoreSubscribers}>
+                Load more
+              </Button>
+            </div>
+          )}
         </Card>
       )}
 
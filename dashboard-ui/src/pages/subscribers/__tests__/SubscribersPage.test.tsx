@@ -102,7 +102,7 @@ describe('SubscribersPage', () => {
     // Default: subscriber list returns empty so it doesn't interfere with other tests
     vi.mocked(subscriberService.getList).mockResolvedValue({
       success: true,
-      data: { subscribers: [], total: 0 },
+      data: { subscribers: [] },
     });
   });
 
@@ -359,4 +359,52 @@ describe('SubscribersPage', () => {
 
     expect(mockNavigate).toHaveBeenCalledWith('/segments/seg-1');
   });
+
+  it('fetches the subscriber list sorted server-side and loads the next page on demand', async () => {
+    vi.mocked(subscriberService.getCount).mockResolvedValue({
+      success: true,
+      data: { totalSubscribers: 2 },
+    });
+    vi.mocked(subscriberService.getTrends).mockResolvedValue({
+      success: true,
+      data: mockTrendsData,
+    });
+    vi.mocked(segmentService.listSegments).mockResolvedValue({
+      success: true,
+      data: { segments: mockSegments },
+    });
+    vi.mocked(subscriberService.getList)
+      .mockResolvedValueOnce({
+        success: true,
+        data: {
+          subscribers: [{ email: 'a@example.com', addedAt: '2025-01-02T00:00:00Z', lastEngagedIssue: null, suspectedBot: false }],
+          nextToken: 'page-2',
+        },
+      })
+      .mockResolvedValueOnce({
+        success: true,
+        data: {
+          subscribers: [{ email: 'b@example.com', addedAt: '2025-01-01T00:00:00Z', lastEngagedIssue: null, suspectedBot: false }],
+        },
+      });
+
+    renderPage();
+
+    expect(subscriberService.getList).toHaveBeenCalledWith({ sortBy: 'addedAt', sortOrder: 'desc', limit: 100 });
+
+    const loadMore = await screen.findByRole('button', { name: 'Load more' });
+    fireEvent.click(loadMore);
+
+    await waitFor(() => {
+      expect(subscriberService.getList).toHaveBeenLastCalledWith({
+        sortBy: 'addedAt',
+        sortOrder: 'desc',
+        limit: 100,
+        nextToken: 'page-2',
+      });
+    });
+    await waitFor(() => {
+      expect(screen.queryByRole('button', { name: 'Load more' })).not.toBeInTheDocument();
+    });
+  });
 });
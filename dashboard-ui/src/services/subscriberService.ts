@@ -1,6 +1,6 @@
 import { apiClient } from './api';
 import { validateSubscriberCountResponse, validateSubscriberTrendsResponse } from '@/utils/dataValidation';
-import type { ApiResponse, SubscriberCountResponse, SubscriberTrendsResponse, SubscriberListResponse, SubscriberDetail } from '@/types';
+import type { ApiResponse, SubscriberCountResponse, SubscriberTrendsResponse, SubscriberListResponse, SubscriberDetail, ListSubscribersParams } from '@/types';
 
 export class SubscriberService {
   /**
@@ -39,8 +39,25 @@ export class SubscriberService {
     return response;
   }
 
-  async getList(): Promise<ApiResponse<SubscriberListResponse>> {
-    return apiClient.get<SubscriberListResponse>('/subscribers');
+  /**
+   * Fetch one page of the subscriber list. Pages can come back short while
+   * more remain, so keep going until there is no `nextToken`.
+   */
+  async getList(params?: ListSubscribersParams): Promise<ApiResponse<SubscriberListResponse>> {
+    const query = new URLSearchParams();
+    if (params?.limit) query.append('limit', params.limit.toString());
+    if (params?.nextToken) query.append('nextToken', params.nextToken);
+    if (params?.sortBy) query.append('sortBy', params.sortBy);
+    if (params?.sortOrder) query.append('sortOrder', params.sortOrder);
+    if (params?.emailPrefix) query.append('emailPrefix', params.emailPrefix);
+    if (params?.suspectedBot !== undefined) query.append('suspectedBot', String(params.suspectedBot));
+    if (params?.timeZone) query.append('timeZone', params.timeZone);
+    if (params?.interestTopic) query.append('interestTopic', params.interestTopic);
+    if (params?.minInterestScore !== undefined) query.append('minInterestScore', params.minInterestScore.toString());
+    if (params?.engagedSinceIssue !== undefined) query.append('engagedSinceIssue', params.engagedSinceIssue.toString());
+    params?.tags?.forEach((tag) => query.append('tag', tag));
+    const qs = query.toString();
+    return apiClient.get<SubscriberListResponse>(`/subscribers${qs ? `?${qs}` : ''}`);
   }
 
   /**
@@ -52,7 +52,24 @@ export interface SubscriberListItem {
 
 export interface SubscriberListResponse {
   subscribers: SubscriberListItem[];
-  total: number;
+  /** Present when more subscribers may match; pass it back to get the next page. */
+  nextToken?: string;
+}
+
+export type SubscriberSortField = 'email' | 'addedAt' | 'lastEngagedIssue' | 'engagementCount';
+
+export interface ListSubscribersParams {
+  limit?: number;
+  nextToken?: string;
+  sortBy?: SubscriberSortField;
+  sortOrder?: 'asc' | 'desc';
+  emailPrefix?: string;
+  suspectedBot?: boolean;
+  timeZone?: string;
+  interestTopic?: string;
+  minInterestScore?: number;
+  engagedSinceIssue?: number;
+  tags?: string[];
 }
 
 /** A single behavioral activity entry (an open or a click), newest-first. */
@@ -1206,5 +1206,35 @@ describe('aggregate-issue-analytics', () => {
       expect(b.clickRate).toBeCloseTo(4, 5);
       expect(b.subject).toBe('Challenger');
     });
+
+    test('reads the counters of every configured variant', async () => {
+      const abTest = {
+        dimension: 'subject',
+        status: 'testing',
+        variants: [
+          { variantId: 'a', subject: 'One' },
+          { variantId: 'b', subject: 'Two' },
+          { variantId: 'c', subject: 'Three' }
+        ]
+      };
+
+      mockSend.mockImplementation(async (cmd) => {
+        const key = unmarshall(cmd.input.Key);
+        if (key.sk === 'newsletter') {
+          return { Item: marshall({ abTest: JSON.stringify(abTest) }) };
+        }
+        if (key.sk === 'stats#v#c') {
+          return { Item: marshall({ opens: 300, clicks: 30, deliveries: 1000 }) };
+        }
+        return {};
+      });
+
+      const result = await calculateAbTestSummary(new DynamoDBClient(), 'tenant1', '42');
+
+      const c = result.variants.find((v) => v.variantId === 'c');
+      expect(result.variants).toHaveLength(3);
+      expect(c.opens).toBe(300);
+      expect(c.openRate).toBeCloseTo(30, 5);
+    });
   });
 });
@@ -174,3 +174,75 @@ describe('handle-email-status interest scoring on email click', () => {
     expect(scoringUpdate.input.ExpressionAttributeNames['#topic']).toBe('ai');
   });
 });
+
+describe('handle-email-status mailbox provider stats', () => {
+  let mockSend;
+  let originalEnv;
+
+  beforeEach(() => {
+    originalEnv = process.env.TABLE_NAME;
+    process.env.TABLE_NAME = 'test-table';
+    mockSend = jest.fn().mockResolvedValue({});
+    DynamoDBClient.prototype.send = mockSend;
+    jest.clearAllMocks();
+  });
+
+  afterEach(() => {
+    process.env.TABLE_NAME = originalEnv;
+  });
+
+  const providerUpdates = () => mockSend.mock.calls
+    .map(([command]) => command)
+    .filter((command) => command instanceof UpdateItemCommand && unmarshall(command.input.Key).sk.startsWith('stats#provider#'));
+
+  test('counts a delivery against the recipient mailbox provider', async () => {
+    await handler({
+      detail: {
+        eventType: 'Delivery',
+        mail: {
+          destination: ['reader@gmail.com'],
+          tags: { referenceNumber: ['tenant123_42'] }
+        }
+      }
+    });
+
+    const updates = providerUpdates();
+    expect(updates).toHaveLength(1);
+    expect(unmarshall(updates[0].input.Key)).toEqual({ pk: 'tenant123#42', sk: 'stats#provider#gmail' });
+    expect(updates[0].input.ExpressionAttributeNames['#stat']).toBe('deliveries');
+  });
+
+  test('does not bucket sends by provider', async () => {
+    await handler({
+      detail: {
+        eventType: 'Send',
+        mail: {
+          destination: ['reader@outlook.com'],
+          tags: { referenceNumber: ['tenant123_42'] }
+        }
+      }
+    });
+
+    expect(providerUpdates()).toHaveLength(0);
+  });
+
+  test('keeps the variant and provider on the event record', async () => {
+    await handler({
+      detail: {
+        eventType: 'Complaint',
+        mail: {
+          destination: ['reader@gmail.com'],
+          tags: { referenceNumber: ['tenant123_42'], variant: ['c'] }
+        },
+        complaint: { complaintFeedbackType: 'abuse' }
+      }
+    });
+
+    const record = mockSend.mock.calls
+      .map(([command]) => command)
+      .filter((command) => command instanceof PutItemCommand)
+      .map((command) => unmarshall(command.input.Item))
+      .find((item) => item.sk.startsWith('complaint#'));
+    expect(record).toMatchObject({ variant: 'c', provider: 'gmail' });
+  });
+});
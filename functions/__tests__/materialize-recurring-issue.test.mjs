@@ -0,0 +1,221 @@
+import { jest, describe, it, expect, beforeEach, afterEach } from '@jest/globals';
+import { marshall } from '@aws-sdk/util-dynamodb';
+
+let handler;
+let localOccurrence;
+let ddbSend;
+let sfnSend;
+let publishIssueEvent;
+
+const loadIsolated = async () => {
+  await jest.isolateModulesAsync(async () => {
+    ddbSend = jest.fn();
+    sfnSend = jest.fn().mockResolvedValue({});
+    publishIssueEvent = jest.fn().mockResolvedValue();
+
+    jest.unstable_mockModule('@aws-sdk/client-dynamodb', () => ({
+      DynamoDBClient: jest.fn(() => ({ send: ddbSend })),
+      GetItemCommand: jest.fn((params) => ({ __type: 'GetItem', ...params })),
+      PutItemCommand: jest.fn((params) => ({ __type: 'PutItem', ...params })),
+      QueryCommand: jest.fn((params) => ({ __type: 'Query', ...params })),
+      UpdateItemCommand: jest.fn((params) => ({ __type: 'UpdateItem', ...params })),
+    }));
+
+    jest.unstable_mockModule('@aws-sdk/client-sfn', () => ({
+      SFNClient: jest.fn(() => ({ send: sfnSend })),
+      StartExecutionCommand: jest.fn((params) => ({ __type: 'StartExecution', ...params })),
+    }));
+
+    jest.unstable_mockModule('../utils/helpers.mjs', () => ({
+      getOctokit: jest.fn(),
+      getTenant: jest.fn().mockResolvedValue({ email: 'owner@example.com' }),
+    }));
+
+    jest.unstable_mockModule('../utils/event-publisher.mjs', () => ({
+      publishIssueEvent,
+      EVENT_TYPES: { ISSUE_DRAFT_SAVED: 'ISSUE_DRAFT_SAVED' },
+    }));
+
+    ({ handler, localOccurrence } = await import('../materialize-recurring-issue.mjs'));
+  });
+};
+
+const schedule = (overrides = {}) => ({
+  pk: 'tenant-123',
+  sk: 'recurring-schedule#01SCHEDULE',
+  scheduleId: '01SCHEDULE',
+  name: 'Weekly digest',
+  timezone: 'America/New_York',
+  contentSource: { type: 'issue', issueId: '7' },
+  autoSchedule: false,
+  publishDelayMinutes: 0,
+  enabled: true,
+  ...overrides,
+});
+
+const sourceIssue = {
+  pk: 'tenant-123#7',
+  sk: 'newsletter',
+  subject: 'Weekly digest template',
+  content: '# Hello',
+  contentType: 'markdown',
+};
+
+const event = {
+  time: '2026-03-02T13:00:04Z',
+  detail: { tenantId: 'tenant-123', scheduleId: '01SCHEDULE' },
+};
+
+const commandsOfType = (type) => ddbSend.mock.calls.map(([command]) => command).filter((command) => command.__type === type);
+
+/** Routes DynamoDB calls by command type and key. */
+const mockTable = ({ scheduleItem = schedule(), issueItem = sourceIssue, latestIssue = 41, claimFails = false, approvalPolicy = null } = {}) => {
+  ddbSend.mockImplementation(async (command) => {
+    switch (command.__type) {
+      case 'GetItem':
+        if (command.Key.sk.S.startsWith('recurring-schedule#')) {
+          return { Item: scheduleItem ? marshall(scheduleItem) : undefined };
+        }
+        if (command.Key.sk.S === 'approval-policy') {
+          return { Item: approvalPolicy ? marshall(approvalPolicy) : undefined };
+        }
+        return { Item: issueItem ? marshall(issueItem) : undefined };
+      case 'UpdateItem':
+        if (claimFails && command.UpdateExpression.includes('lastOccurrence')) {
+          const err = new Error('conditional check failed');
+          err.name = 'ConditionalCheckFailedException';
+          throw err;
+        }
+        return {};
+      case 'Query':
+        return { Items: latestIssue ? [marshall({ pk: `tenant-123#${latestIssue}` })] : [] };
+      default:
+        return {};
+    }
+  });
+};
+
+describe('materialize-recurring-issue', () => {
+  let originalEnv;
+
+  beforeEach(async () => {
+    jest.resetModules();
+    originalEnv = { ...process.env };
+    process.env.TABLE_NAME = 'newsletter-table';
+    process.env.STATE_MACHINE_ARN = 'arn:aws:states:us-east-1:123:stateMachine:stage-issue';
+    await loadIsolated();
+  });
+
+  afterEach(() => {
+    process.env = originalEnv;
+  });
+
+  it('formats the occurrence in the schedule timezone', () => {
+    expect(localOccurrence(new Date('2026-03-02T13:00:04Z'), 'America/New_York')).toEqual({
+      date: '2026-03-02',
+      key: '2026-03-02T08:00',
+    });
+    expect(localOccurrence(new Date('2026-03-02T13:00:04Z'), 'Not/AZone').key).toBe('2026-03-02T13:00');
+  });
+
+  it('creates a draft with the next issue number from an issue source', async () => {
+    mockTable();
+
+    const result = await handler(event);
+
+    expect(result).toEqual({ created: true, issueNumber: 42, occurrence: '2026-03-02T08:00', scheduled: false });
+
+    const [put] = commandsOfType('PutItem');
+    expect(put.Item.pk.S).toBe('tenant-123#42');
+    expect(put.Item.status.S).toBe('draft');
+    expect(put.Item.subject.S).toBe('Weekly digest template');
+    expect(put.Item.content.S).toBe('# Hello');
+    expect(put.Item.recurringScheduleId.S).toBe('01SCHEDULE');
+    expect(put.ConditionExpression).toBe('attribute_not_exists(pk) AND attribute_not_exists(sk)');
+
+    expect(sfnSend).not.toHaveBeenCalled();
+    expect(publishIssueEvent).toHaveBeenCalledWith(
+      'tenant-123',
+      'recurring-schedule',
+      'ISSUE_DRAFT_SAVED',
+      expect.objectContaining({ issueNumber: 42, recurringScheduleId: '01SCHEDULE' })
+    );
+  });
+
+  it('applies the subject template with the occurrence date', async () => {
+    mockTable({ scheduleItem: schedule({ subject: 'Digest for {date}' }) });
+
+    await handler(event);
+
+    expect(commandsOfType('PutItem')[0].Item.subject.S).toBe('Digest for 2026-03-02');
+  });
+
+  it('starts the publish workflow when autoSchedule is enabled', async () => {
+    mockTable({ scheduleItem: schedule({ autoSchedule: true, publishDelayMinutes: 90 }) });
+    sfnSend.mockResolvedValue({ executionArn: 'arn:aws:states:us-east-1:123:execution:stage-issue:abc' });
+
+    const result = await handler(event);
+
+    expect(result.scheduled).toBe(true);
+    expect(sfnSend).toHaveBeenCalledTimes(1);
+    const input = JSON.parse(sfnSend.mock.calls[0][0].input);
+    expect(input.issueId).toBe(42);
+    expect(input.tenant).toEqual({ id: 'tenant-123', email: 'owner@example.com' });
+    expect(input.futureDate).toBe('2026-03-02T14:30:04.000Z');
+
+    const arnUpdate = commandsOfType('UpdateItem').find((command) => command.UpdateExpression.includes('scheduleExecutionArn'));
+    expect(arnUpdate.Key.pk.S).toBe('tenant-123#42');
+    expect(arnUpdate.ExpressionAttributeValues[':arn'].S).toBe('arn:aws:states:us-east-1:123:execution:stage-issue:abc');
+  });
+
+  it('leaves the draft unscheduled when the tenant requires approval', async () => {
+    mockTable({
+      scheduleItem: schedule({ autoSchedule: true }),
+      approvalPolicy: { pk: 'tenant-123', sk: 'approval-policy', enabled: true, requiredApprovals: 1 },
+    });
+
+    const result = await handler(event);
+
+    expect(result).toEqual({ created: true, issueNumber: 42, occurrence: '2026-03-02T08:00', scheduled: false });
+    expect(sfnSend).not.toHaveBeenCalled();
+    const runUpdate = commandsOfType('UpdateItem').find((command) => command.UpdateExpression.includes('lastRun'));
+    expect(runUpdate.ExpressionAttributeValues[':run'].M.status.S).toBe('created');
+    expect(runUpdate.ExpressionAttributeValues[':run'].M.reason.S).toBe('awaiting-approval');
+  });
+
+  it('skips the occurrence when the content source has no content', async () => {
+    mockTable({ issueItem: null });
+
+    const result = await handler(event);
+
+    expect(result).toEqual({ created: false, reason: 'no-content', occurrence: '2026-03-02T08:00' });
+    expect(commandsOfType('PutItem')).toHaveLength(0);
+    const runUpdate = commandsOfType('UpdateItem').find((command) => command.UpdateExpression.includes('lastRun'));
+    expect(runUpdate.ExpressionAttributeValues[':run'].M.status.S).toBe('skipped');
+  });
+
+  it('ignores a redelivered occurrence', async () => {
+    mockTable({ claimFails: true });
+
+    const result = await handler(event);
+
+    expect(result.reason).toBe('duplicate-occurrence');
+    expect(commandsOfType('PutItem')).toHaveLength(0);
+  });
+
+  it('does nothing for a disabled schedule', async () => {
+    mockTable({ scheduleItem: schedule({ enabled: false }) });
+
+    const result = await handler(event);
+
+    expect(result).toEqual({ created: false, reason: 'disabled' });
+    expect(commandsOfType('UpdateItem')).toHaveLength(0);
+  });
+
+  it('rejects events without a tenant or schedule id', async () => {
+    const result = await handler({ detail: { tenantId: 'tenant-123' } });
+
+    expect(result).toEqual({ created: false, reason: 'missing-parameters' });
+    expect(ddbSend).not.toHaveBeenCalled();
+  });
+});
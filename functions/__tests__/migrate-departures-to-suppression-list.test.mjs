@@ -0,0 +1,109 @@
+import { jest } from '@jest/globals';
+import { DynamoDBClient, GetItemCommand, PutItemCommand, QueryCommand } from '@aws-sdk/client-dynamodb';
+import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
+import { hashEmail } from '../utils/hash-email.mjs';
+
+process.env.TABLE_NAME = 'test-table';
+process.env.SUBSCRIBERS_TABLE_NAME = 'test-subscribers-table';
+
+const { handler, planEntries } = await import('../migrate-departures-to-suppression-list.mjs');
+
+const departure = (email, reason) => ({
+  pk: 'tenant-1',
+  sk: `subscriber-departure#2025-03-01T00:00:00.000Z#${hashEmail(email)}`,
+  emailHash: hashEmail(email),
+  reason
+});
+
+describe('planEntries', () => {
+  it('skips admin removals and keeps the strongest reason per address', () => {
+    const entries = planEntries([
+      departure('a@example.com', 'encrypted-link'),
+      departure('a@example.com', 'complaint'),
+      departure('b@example.com', 'bounced'),
+      departure('c@example.com', 'manual-removal')
+    ]);
+
+    expect(entries.get(hashEmail('a@example.com'))).toEqual({ reason: 'complaint', source: 'complaint' });
+    expect(entries.get(hashEmail('b@example.com'))).toEqual({ reason: 'hard-bounce', source: 'bounce-cleanup' });
+    expect(entries.has(hashEmail('c@example.com'))).toBe(false);
+  });
+});
+
+describe('handler', () => {
+  let mockSend;
+  let subscribed;
+
+  const puts = () => mockSend.mock.calls
+    .map(([command]) => command)
+    .filter((command) => command instanceof PutItemCommand)
+    .map((command) => unmarshall(command.input.Item));
+
+  beforeEach(() => {
+    subscribed = new Set(['back@example.com']);
+    const departures = [
+      departure('left@example.com', 'manual-form'),
+      departure('back@example.com', 'encrypted-link'),
+      departure('unknown@example.com', 'complaint'),
+      departure('removed@example.com', 'manual-removal')
+    ];
+
+    mockSend = jest.fn(async (command) => {
+      const input = command.input;
+      if (command instanceof QueryCommand) {
+        if (input.IndexName === 'GSI1') {
+          return { Items: [marshall({ pk: 'tenant-1#1' })] };
+        }
+        const { ':prefix': prefix } = unmarshall(input.ExpressionAttributeValues);
+        if (prefix === 'subscriber-departure#') {
+          return { Items: departures.map((item) => marshall(item)) };
+        }
+        if (prefix === 'subscriber#') {
+          return { Items: [marshall({ email: 'back@example.com' }), marshall({ email: 'removed@example.com' })] };
+        }
+        if (prefix === 'opens#') {
+          return { Items: [marshall({ sk: 'opens#left@example.com' })] };
+        }
+      }
+      if (command instanceof GetItemCommand) {
+        if (input.TableName === 'test-subscribers-table') {
+          const { email } = unmarshall(input.Key);
+          return subscribed.has(email) ? { Item: marshall({ email }) } : {};
+        }
+        return { Item: marshall({ failedAddresses: [] }) };
+      }
+      if (command instanceof PutItemCommand) {
+        return {};
+      }
+      throw new Error(`Unexpected command: ${command?.constructor?.name}`);
+    });
+    DynamoDBClient.prototype.send = mockSend;
+  });
+
+  it('requires a tenant', async () => {
+    await expect(handler({})).resolves.toEqual({ success: false, error: 'tenantId is required' });
+  });
+
+  it('lists resolvable departed addresses and counts the rest', async () => {
+    const result = await handler({ tenantId: 'tenant-1' });
+
+    expect(result).toEqual({
+      success: true,
+      tenantId: 'tenant-1',
+      departures: 4,
+      addresses: 3,
+      added: 1,
+      alreadyListed: 0,
+      resubscribed: 1,
+      unresolved: 1
+    });
+    expect(puts()).toEqual([expect.objectContaining({
+      pk: 'tenant-1',
+      sk: 'suppression#left@example.com',
+      entry: 'left@example.com',
+      type: 'email',
+      reason: 'manual',
+      source: 'unsubscribe'
+    })]);
+  });
+});
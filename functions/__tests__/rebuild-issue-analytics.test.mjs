@@ -0,0 +1,150 @@
+import { jest, describe, it, expect, beforeEach, afterEach } from '@jest/globals';
+import { marshall } from '@aws-sdk/util-dynamodb';
+
+let handler;
+let ddbSend;
+let ebSend;
+
+const loadIsolated = async () => {
+  await jest.isolateModulesAsync(async () => {
+    ddbSend = jest.fn();
+    ebSend = jest.fn().mockResolvedValue({ FailedEntryCount: 0, Entries: [{ EventId: 'evt-1' }] });
+
+    jest.unstable_mockModule('@aws-sdk/client-dynamodb', () => ({
+      DynamoDBClient: jest.fn(() => ({ send: ddbSend })),
+      GetItemCommand: jest.fn((params) => ({ __type: 'GetItem', ...params })),
+      UpdateItemCommand: jest.fn((params) => ({ __type: 'UpdateItem', ...params })),
+    }));
+
+    jest.unstable_mockModule('@aws-sdk/client-eventbridge', () => ({
+      EventBridgeClient: jest.fn(() => ({ send: ebSend })),
+      PutEventsCommand: jest.fn((params) => ({ __type: 'PutEvents', ...params })),
+    }));
+
+    ({ handler } = await import('../rebuild-issue-analytics.mjs'));
+  });
+};
+
+const issue = (issueNumber, status = 'pending') => ({
+  issueNumber,
+  subject: `Issue ${issueNumber}`,
+  publishedAt: `2025-03-0${issueNumber}T09:00:00Z`,
+  status,
+});
+
+const job = (overrides = {}) => ({
+  pk: 'tenant-123',
+  sk: 'analytics-rebuild#01JOB',
+  jobId: '01JOB',
+  tenantId: 'tenant-123',
+  userId: 'user-1',
+  status: 'pending',
+  issues: [issue(1), issue(2), issue(3)],
+  ...overrides,
+});
+
+const updates = () => ddbSend.mock.calls.map(([command]) => command).filter((command) => command.__type === 'UpdateItem');
+
+const mockTable = ({ jobItem = job(), claimFails = false } = {}) => {
+  ddbSend.mockImplementation(async (command) => {
+    if (command.__type === 'GetItem') {
+      return { Item: jobItem ? marshall(jobItem) : undefined };
+    }
+    if (command.ConditionExpression && claimFails) {
+      const err = new Error('The conditional request failed');
+      err.name = 'ConditionalCheckFailedException';
+      throw err;
+    }
+    return {};
+  });
+};
+
+const context = (remaining = 600_000) => ({ getRemainingTimeInMillis: () => remaining });
+
+describe('rebuild-issue-analytics', () => {
+  let originalEnv;
+
+  beforeEach(async () => {
+    jest.resetModules();
+    originalEnv = { ...process.env };
+    process.env.TABLE_NAME = 'test-table';
+    process.env.PUBLISH_INTERVAL_MS = '0';
+    await loadIsolated();
+  });
+
+  afterEach(() => {
+    process.env = originalEnv;
+  });
+
+  it('re-emits ISSUE_PUBLISHED for every pending issue and completes the job', async () => {
+    mockTable();
+
+    const result = await handler({ tenantId: 'tenant-123', jobId: '01JOB' }, context());
+
+    expect(result).toEqual({ status: 'completed', queued: 3, failed: 0 });
+    expect(ebSend).toHaveBeenCalledTimes(3);
+    const detail = JSON.parse(ebSend.mock.calls[0][0].Entries[0].Detail);
+    expect(ebSend.mock.calls[0][0].Entries[0].DetailType).toBe('ISSUE_PUBLISHED');
+    expect(detail).toEqual({
+      tenantId: 'tenant-123',
+      userId: 'user-1',
+      type: 'ISSUE_PUBLISHED',
+      data: { issueNumber: 1, publishedAt: '2025-03-01T09:00:00Z', title: 'Issue 1' },
+    });
+
+    const [claim, ...rest] = updates();
+    expect(claim.ConditionExpression).toBe('#status = :pending');
+    expect(rest[0].UpdateExpression).toContain('issues[0].#status = :status');
+    expect(rest.at(-1).ExpressionAttributeValues[':status'].S).toBe('completed');
+  });
+
+  it('skips issues already queued by an earlier run', async () => {
+    mockTable({ jobItem: job({ issues: [issue(1, 'queued'), issue(2), issue(3, 'queued')] }) });
+
+    const result = await handler({ tenantId: 'tenant-123', jobId: '01JOB' }, context());
+
+    expect(result).toEqual({ status: 'completed', queued: 1, failed: 0 });
+    expect(ebSend).toHaveBeenCalledTimes(1);
+    expect(updates()[1].UpdateExpression).toContain('issues[1].#status');
+  });
+
+  it('records failed issues and keeps going', async () => {
+    mockTable();
+    ebSend
+      .mockResolvedValueOnce({ FailedEntryCount: 0, Entries: [{}] })
+      .mockResolvedValueOnce({ FailedEntryCount: 1, Entries: [{ ErrorCode: 'ThrottlingException', ErrorMessage: 'Rate exceeded' }] })
+      .mockResolvedValueOnce({ FailedEntryCount: 0, Entries: [{}] });
+
+    const result = await handler({ tenantId: 'tenant-123', jobId: '01JOB' }, context());
+
+    expect(result).toEqual({ status: 'failed', queued: 2, failed: 1 });
+    const failedUpdate = updates()[2];
+    expect(failedUpdate.UpdateExpression).toContain('issues[1].#error = :error');
+    expect(failedUpdate.ExpressionAttributeValues[':error'].S).toBe('ThrottlingException: Rate exceeded');
+  });
+
+  it('stops and marks the job interrupted when Lambda time runs low', async () => {
+    mockTable();
+
+    const result = await handler({ tenantId: 'tenant-123', jobId: '01JOB' }, context(10_000));
+
+    expect(result).toEqual({ status: 'interrupted', queued: 0, failed: 0 });
+    expect(ebSend).not.toHaveBeenCalled();
+  });
+
+  it('does nothing when the job was already claimed', async () => {
+    mockTable({ claimFails: true });
+
+    const result = await handler({ tenantId: 'tenant-123', jobId: '01JOB' }, context());
+
+    expect(result).toEqual({ status: 'skipped', reason: 'not-pending' });
+    expect(ebSend).not.toHaveBeenCalled();
+  });
+
+  it('skips unknown jobs and missing parameters', async () => {
+    mockTable({ jobItem: null });
+
+    expect(await handler({ tenantId: 'tenant-123', jobId: 'missing' }, context())).toEqual({ status: 'skipped', reason: 'job-not-found' });
+    expect(await handler({}, context())).toEqual({ status: 'skipped', reason: 'missing-parameters' });
+  });
+});
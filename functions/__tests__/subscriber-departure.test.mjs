@@ -0,0 +1,82 @@
+import { jest } from '@jest/globals';
+import { DynamoDBClient, DeleteItemCommand, PutItemCommand, UpdateItemCommand } from '@aws-sdk/client-dynamodb';
+import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
+import { hashEmail } from '../utils/hash-email.mjs';
+
+process.env.TABLE_NAME = 'test-table';
+process.env.SUBSCRIBERS_TABLE_NAME = 'test-subscribers-table';
+
+let unsubscribeUser;
+let recordSubscriberDeparture;
+let mockSend;
+let existing;
+
+const puts = () => mockSend.mock.calls
+  .map(([command]) => command)
+  .filter((command) => command instanceof PutItemCommand)
+  .map((command) => unmarshall(command.input.Item));
+
+beforeEach(async () => {
+  existing = {
+    tenantId: 'tenant-1',
+    email: 'reader@example.com',
+    addedAt: '2025-01-14T08:00:00.000Z',
+    lastEngagedIssue: 12,
+    engagementCount: 7
+  };
+  mockSend = jest.fn(async (command) => {
+    if (command instanceof DeleteItemCommand) {
+      return existing ? { Attributes: marshall(existing) } : {};
+    }
+    if (command instanceof UpdateItemCommand || command instanceof PutItemCommand) {
+      return {};
+    }
+    throw new Error(`Unexpected command: ${command?.constructor?.name}`);
+  });
+  DynamoDBClient.prototype.send = mockSend;
+
+  ({ unsubscribeUser, recordSubscriberDeparture } = await import('../utils/subscriber.mjs'));
+});
+
+describe('subscriber departures', () => {
+  it('records a hashed departure with signup and engagement data on unsubscribe', async () => {
+    const result = await unsubscribeUser('tenant-1', 'Reader@Example.com', 'manual-form');
+
+    expect(result).toEqual({ success: true, actuallyRemoved: true });
+    const [departure] = puts();
+    expect(departure).toMatchObject({
+      pk: 'tenant-1',
+      emailHash: hashEmail('reader@example.com'),
+      reason: 'manual-form',
+      addedAt: '2025-01-14T08:00:00.000Z',
+      lastEngagedIssue: 12,
+      engagementCount: 7
+    });
+    expect(departure.sk).toBe(`subscriber-departure#${departure.departedAt}#${departure.emailHash}`);
+    expect(departure).not.toHaveProperty('email');
+  });
+
+  it('records nothing when the subscriber was already gone', async () => {
+    existing = null;
+
+    const result = await unsubscribeUser('tenant-1', 'reader@example.com');
+
+    expect(result).toEqual({ success: true, actuallyRemoved: false });
+    expect(puts()).toHaveLength(0);
+  });
+
+  it('omits engagement fields the subscriber never had', async () => {
+    await recordSubscriberDeparture('tenant-1', { email: 'new@example.com', addedAt: '2025-02-01T00:00:00.000Z' }, 'bounced');
+
+    const [departure] = puts();
+    expect(departure.reason).toBe('bounced');
+    expect(departure).not.toHaveProperty('lastEngagedIssue');
+    expect(departure).not.toHaveProperty('engagementCount');
+  });
+
+  it('swallows write failures', async () => {
+    mockSend.mockRejectedValueOnce(new Error('boom'));
+
+    await expect(recordSubscriberDeparture('tenant-1', existing, 'complaint')).resolves.toBeUndefined();
+  });
+});
@@ -433,7 +433,8 @@ async function lookupSubscriberEngagements(ddb, tenantId, emails) {
  * Builds the A/B test summary for consolidated analytics/reports: per-variant
  * engagement rates plus the recorded significance verdict. Returns null when the
  * issue has no A/B test. The abTest config (with its evaluation) is read from the
- * issue record; per-variant counters come from the `stats#v#{a,b}` records.
+ * issue record; per-variant counters come from each variant's `stats#v#<id>`
+ * record.
  *
  * @param {DynamoDBClient} ddb
  * @param {string} tenantId
@@ -465,7 +466,8 @@ export async function calculateAbTestSummary(ddb, tenantId, issueNumber) {
   }
 
   const counters = {};
-  await Promise.all(['a', 'b'].map(async (variantId) => {
+  const variantIds = abTest.variants.map((variant) => variant.variantId).filter(Boolean);
+  await Promise.all(variantIds.map(async (variantId) => {
     try {
       const result = await ddb.send(new GetItemCommand({
         TableName: process.env.TABLE_NAME,
@@ -1,7 +1,7 @@
 import { DynamoDBClient, GetItemCommand, UpdateItemCommand, PutItemCommand } from '@aws-sdk/client-dynamodb';
 import { EventBridgeClient, PutEventsCommand } from '@aws-sdk/client-eventbridge';
 import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
-import { evaluateAbResult } from './utils/ab-stats.mjs';
+import { ALLOCATION_EPSILON_GREEDY, ALLOCATION_THOMPSON, allocationWeights, evaluateAbTest } from './utils/ab-stats.mjs';
 import { buildAbHistoryRecord } from './utils/ab-history.mjs';
 import { publishIssueEvent, EVENT_TYPES } from './utils/event-publisher.mjs';
 
@@ -10,8 +10,9 @@ const eventBridge = new EventBridgeClient();
 
 /**
  * Consumes an `Evaluate AB Test` EventBridge event, scores the per-variant
- * engagement stats with a two-proportion z-test, persists the decision on the
- * issue record, and sends the winning subject to the hold-out recipients.
+ * engagement stats with two-proportion z-tests, persists the decision on the
+ * issue record, and sends the hold-out recipients the winning variant, or for
+ * an adaptive (bandit) test, every variant in proportion to its allocation.
  *
  * The handler is idempotent: once the abTest status is final (`sent` or
  * `inconclusive`) it never sends again. Errors are logged and swallowed
@@ -51,10 +52,13 @@ export const handler = async (event) => {
     }
 
     try {
-      const aCounters = await getVariantCounters(issueId, 'a');
-      const bCounters = await getVariantCounters(issueId, 'b');
+      const variantIds = abTestVariantIds(abTest);
+      const counters = {};
+      for (const variantId of variantIds) {
+        counters[variantId] = await getVariantCounters(issueId, variantId);
+      }
 
-      const { winnerVariantId, status, evaluation } = evaluateAbResult(aCounters, bCounters, {
+      const { winnerVariantId, status, evaluation } = evaluateAbTest(counters, {
         winMetric: abTest.winMetric,
         confidence: abTest.confidence,
         minSamplePerVariant: abTest.minSamplePerVariant
@@ -65,20 +69,35 @@ export const handler = async (event) => {
       const isSendTime = abTest.dimension === 'sendTime';
       const winningVariant = (abTest.variants || []).find((v) => v.variantId === winningVariantId);
 
-      // Subject-line tests send the winning subject. Send-time tests keep the
-      // shared subject and deliver the hold-out at the winning send time (or
-      // immediately if that time has already passed).
-      const winningSubject = isSendTime ? sendPayload?.subject : resolveSubject(abTest, winningVariantId);
+      // Subject-line tests send the winning subject. Send-time and content
+      // tests keep the shared subject; send-time tests deliver the hold-out at
+      // the winning send time (or immediately if that time has already passed)
+      // and content tests send the winning variant's rendered html.
+      const winningSubject = abTest.dimension === 'subject'
+        ? resolveSubject(abTest, winningVariantId)
+        : sendPayload?.subject;
+      const winningHtml = sendPayload?.variantHtml?.[winningVariantId];
       const winningSendAt = isSendTime ? winningVariant?.sendAt : undefined;
       if (isSendTime && winningSendAt) {
         evaluation.winningSendAt = winningSendAt;
       }
 
-      // Enqueue the winner BEFORE marking the test final. Together with the claim
-      // above this gives: exactly one invocation ever sends (no duplicate
-      // emails), and if the publish fails the claim is released so a redelivery
-      // retries instead of the test being stuck in a final state.
-      await sendWinner(sendPayload, winningSubject, winningSendAt);
+      // Enqueue the hold-out send BEFORE marking the test final. Together with
+      // the claim above this gives: exactly one invocation ever sends (no
+      // duplicate emails), and if the publish fails the claim is released so a
+      // redelivery retries instead of the test being stuck in a final state.
+      // An adaptive test keeps every variant in the hold-out batch, weighted
+      // by the sample's results; a fixed test sends only the winner.
+      if (isAdaptive(abTest)) {
+        const weights = allocationWeights(abTest.allocation, variantIds.map((variantId) => ({
+          successes: evaluation.variants[variantId].successes,
+          trials: evaluation.variants[variantId].deliveries
+        })));
+        evaluation.holdoutWeights = Object.fromEntries(variantIds.map((variantId, i) => [variantId, weights[i]]));
+        await sendAllocated(sendPayload, abTest, variantIds, weights);
+      } else {
+        await sendWinner(sendPayload, winningSubject, winningSendAt, winningHtml);
+      }
 
       const updatedAbTest = {
         ...abTest,
@@ -90,7 +109,7 @@ export const handler = async (event) => {
 
       // Record the completed test in the tenant's cross-issue A/B history.
       // Best-effort: a failure here must not affect the (already enqueued) send.
-      await writeAbHistory(tenantId, issueNumber, updatedAbTest, { a: aCounters, b: bCounters });
+      await writeAbHistory(tenantId, issueNumber, updatedAbTest, counters);
 
       await publishIssueEvent(
         tenantId,
@@ -249,11 +268,34 @@ const getIssueAbTest = async (issueId) => {
   }
 };
 
+/**
+ * Variant ids configured on the test, in order. Falls back to the original
+ * two-variant ids for tests stored before A/B/n.
+ * @param {Object} abTest - Parsed abTest config.
+ * @returns {string[]}
+ */
+const abTestVariantIds = (abTest) => {
+  const ids = (Array.isArray(abTest.variants) ? abTest.variants : [])
+    .map((variant) => variant?.variantId)
+    .filter((variantId) => typeof variantId === 'string' && variantId);
+  return ids.length ? ids : ['a', 'b'];
+};
+
+/**
+ * Whether the test uses a bandit allocation, whose hold-out batch is split
+ * across the variants instead of going to the winner.
+ * @param {Object} abTest - Parsed abTest config.
+ * @returns {boolean}
+ */
+const isAdaptive = (abTest) =>
+  abTest.dimension !== 'sendTime'
+  && (abTest.allocation?.mode === ALLOCATION_THOMPSON || abTest.allocation?.mode === ALLOCATION_EPSILON_GREEDY);
+
 /**
  * Reads per-variant counters, defaulting any missing record or field to zero so
  * a missing variant stats row never breaks the evaluation.
  * @param {string} issueId - `${tenantId}#${issueNumber}`.
- * @param {('a'|'b')} variantId - Variant identifier.
+ * @param {string} variantId - Variant identifier.
  * @returns {Promise<{opens: number, clicks: number, deliveries: number}>}
  */
 const getVariantCounters = async (issueId, variantId) => {
@@ -293,14 +335,17 @@ const resolveSubject = (abTest, variantId) => {
 
 /**
  * Emits the `Send Email v2` event for the hold-out recipients. The detail is
- * the original sendPayload plus the winning subject, with no abTest/variants so
- * the send path treats it as a normal (non-split) send.
+ * the original sendPayload plus the winning subject (and html, for content
+ * tests), with no abTest/variants so the send path treats it as a normal
+ * (non-split) send.
  * @param {Object} sendPayload - Everything needed to send except the subject.
  * @param {string} winningSubject - The chosen subject line.
+ * @param {string} [winningSendAt] - The winning send time (send-time tests).
+ * @param {string} [winningHtml] - The winning variant's html (content tests).
  */
-const sendWinner = async (sendPayload, winningSubject, winningSendAt) => {
-  const { abTest: _abTest, variants: _variants, ...rest } = sendPayload || {};
-  const detail = { ...rest, subject: winningSubject };
+const sendWinner = async (sendPayload, winningSubject, winningSendAt, winningHtml) => {
+  const { abTest: _abTest, variants: _variants, variantHtml: _variantHtml, ...rest } = sendPayload || {};
+  const detail = { ...rest, subject: winningSubject, ...winningHtml && { html: winningHtml } };
 
   // Send-time winner: deliver at the winning time when it is still in the
   // future; otherwise send immediately (send-email-v2 schedules future sends).
@@ -316,3 +361,30 @@ const sendWinner = async (sendPayload, winningSubject, winningSendAt) => {
     }]
   }));
 };
+
+/**
+ * Emits the `Send Email v2` event for an adaptive test's hold-out batch: the
+ * variants (with their subjects or rendered html) and their weights, so the
+ * send path splits the recipients not yet sent to across every variant by
+ * those shares.
+ * @param {Object} sendPayload - Everything needed to send except the subject.
+ * @param {Object} abTest - Parsed abTest config.
+ * @param {string[]} variantIds - Variant ids, in order.
+ * @param {number[]} weights - Hold-out share per variant, aligned with variantIds.
+ */
+const sendAllocated = async (sendPayload, abTest, variantIds, weights) => {
+  const { abTest: _abTest, variants: _variants, variantHtml, ...rest } = sendPayload || {};
+  const variants = variantIds.map((variantId) => {
+    const subject = resolveSubject(abTest, variantId);
+    const html = variantHtml?.[variantId];
+    return { variantId, ...subject !== undefined && { subject }, ...html && { html } };
+  });
+
+  await eventBridge.send(new PutEventsCommand({
+    Entries: [{
+      Source: 'newsletter-service',
+      DetailType: 'Send Email v2',
+      Detail: JSON.stringify({ ...rest, variants, variantWeights: weights })
+    }]
+  }));
+};
@@ -0,0 +1,248 @@
+import { DynamoDBClient, QueryCommand, GetItemCommand, PutItemCommand, UpdateItemCommand } from '@aws-sdk/client-dynamodb';
+import { EventBridgeClient, PutEventsCommand } from '@aws-sdk/client-eventbridge';
+import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
+import {
+  ALERT_SETTINGS_SK,
+  alertId,
+  alertSortKey,
+  detectAnomalies,
+  padIssueNumber,
+  resolveAlertSettings
+} from './utils/issue-anomalies.mjs';
+
+const ddb = new DynamoDBClient();
+const eventBridge = new EventBridgeClient();
+
+const TABLE_NAME = process.env.TABLE_NAME;
+
+// An issue is evaluated once, after its first day of engagement has come in,
+// and only while it is still recent enough for an alert to be actionable.
+const EVALUATION_DELAY_MS = 24 * 60 * 60 * 1000;
+const EVALUATION_WINDOW_MS = 7 * 24 * 60 * 60 * 1000;
+// Newest stats records checked per tenant for issues due an evaluation.
+const CANDIDATE_LOOKBACK = 5;
+
+const METRIC_LABELS = {
+  bounceRate: 'Bounce rate',
+  complaintRate: 'Complaint rate',
+  openRate: 'Open rate',
+  clickRate: 'Click rate'
+};
+
+/**
+ * Scheduled sweep that checks each tenant's recently published issues for
+ * metric anomalies. Every issue published 24 hours to 7 days ago that has not
+ * been checked yet is compared against the issues published before it; each
+ * metric beyond the tenant's z-score threshold becomes an `alert#` record, and
+ * the tenant owner is emailed when the tenant opted in.
+ *
+ * The stats record is stamped with `anomalyCheckedAt` so an issue is only
+ * evaluated once, and alerts are written with a conditional put so a retried
+ * sweep never duplicates them. A failure for one tenant is logged and the
+ * sweep moves on.
+ */
+export const handler = async () => {
+  const now = new Date();
+  const tenants = await getAllTenants();
+  let issuesEvaluated = 0;
+  let alertsCreated = 0;
+
+  for (const tenant of tenants) {
+    try {
+      const result = await evaluateTenant(tenant, now);
+      issuesEvaluated += result.issuesEvaluated;
+      alertsCreated += result.alertsCreated;
+    } catch (err) {
+      console.error('Anomaly evaluation failed for tenant', { tenantId: tenant.id, error: err.message });
+    }
+  }
+
+  console.log('Anomaly evaluation complete', { tenants: tenants.length, issuesEvaluated, alertsCreated });
+  return { tenants: tenants.length, issuesEvaluated, alertsCreated };
+};
+
+export const evaluateTenant = async (tenant, now = new Date()) => {
+  const settings = resolveAlertSettings(await getAlertSettings(tenant.id));
+  if (!settings.enabled) {
+    return { issuesEvaluated: 0, alertsCreated: 0 };
+  }
+
+  const candidates = (await queryIssueStats(tenant.id, { limit: CANDIDATE_LOOKBACK }))
+    .filter((stats) => isDueForEvaluation(stats, now));
+
+  let alertsCreated = 0;
+  for (const stats of candidates) {
+    const issueNumber = Number(stats.GSI1SK);
+    const baseline = await queryIssueStats(tenant.id, {
+      limit: settings.baselineIssueCount,
+      before: issueNumber
+    });
+
+    const anomalies = detectAnomalies(stats, baseline, settings);
+    const created = [];
+    for (const anomaly of anomalies) {
+      const alert = buildAlert(issueNumber, stats.subject, anomaly, now);
+      if (await putAlert(tenant.id, alert)) {
+        created.push(alert);
+      }
+    }
+
+    if (created.length > 0) {
+      console.log('Recorded issue anomalies', { tenantId: tenant.id, issueNumber, metrics: created.map((a) => a.metric) });
+      if (settings.emailNotifications) {
+        await notifyOwner(tenant, issueNumber, stats.subject, created);
+      }
+    }
+
+    await markEvaluated(tenant.id, issueNumber, now);
+    alertsCreated += created.length;
+  }
+
+  return { issuesEvaluated: candidates.length, alertsCreated };
+};
+
+export const isDueForEvaluation = (stats, now) => {
+  if (stats.anomalyCheckedAt || !stats.publishedAt || !Number(stats.GSI1SK)) {
+    return false;
+  }
+  const publishedAt = new Date(stats.publishedAt).getTime();
+  if (Number.isNaN(publishedAt)) {
+    return false;
+  }
+  const age = now.getTime() - publishedAt;
+  return age >= EVALUATION_DELAY_MS && age <= EVALUATION_WINDOW_MS;
+};
+
+export const buildAlert = (issueNumber, subject, anomaly, now) => ({
+  alertId: alertId(issueNumber, anomaly.metric),
+  issueNumber,
+  ...(subject && { subject }),
+  ...anomaly,
+  status: 'open',
+  createdAt: now.toISOString()
+});
+
+/**
+ * Tenant records via GSI1 (GSI1PK = "tenant"), with the address the owner's
+ * notifications go to.
+ */
+const getAllTenants = async () => {
+  const tenants = [];
+  let lastKey;
+
+  do {
+    const result = await ddb.send(new QueryCommand({
+      TableName: TABLE_NAME,
+      IndexName: 'GSI1',
+      KeyConditionExpression: 'GSI1PK = :gsi1pk',
+      ExpressionAttributeValues: marshall({ ':gsi1pk': 'tenant' }),
+      ProjectionExpression: 'pk, email, createdBy',
+      ...(lastKey && { ExclusiveStartKey: lastKey })
+    }));
+
+    for (const item of result.Items || []) {
+      const record = unmarshall(item);
+      tenants.push({ id: record.pk, email: record.email || record.createdBy });
+    }
+    lastKey = result.LastEvaluatedKey;
+  } while (lastKey);
+
+  return tenants;
+};
+
+const getAlertSettings = async (tenantId) => {
+  const result = await ddb.send(new GetItemCommand({
+    TableName: TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: ALERT_SETTINGS_SK })
+  }));
+  return result.Item ? unmarshall(result.Item) : null;
+};
+
+/**
+ * Published issues' stats records, newest first, optionally only those
+ * published before issue `before`.
+ */
+const queryIssueStats = async (tenantId, { limit, before }) => {
+  const result = await ddb.send(new QueryCommand({
+    TableName: TABLE_NAME,
+    IndexName: 'GSI1',
+    KeyConditionExpression: before ? 'GSI1PK = :gsi1pk AND GSI1SK < :before' : 'GSI1PK = :gsi1pk',
+    ExpressionAttributeValues: marshall({
+      ':gsi1pk': `${tenantId}#issue`,
+      ...(before && { ':before': padIssueNumber(before) })
+    }),
+    ScanIndexForward: false,
+    Limit: limit
+  }));
+
+  return (result.Items || []).map((item) => unmarshall(item));
+};
+
+/** Writes the alert unless one already exists for the issue and metric. */
+const putAlert = async (tenantId, alert) => {
+  try {
+    await ddb.send(new PutItemCommand({
+      TableName: TABLE_NAME,
+      Item: marshall({
+        pk: tenantId,
+        sk: alertSortKey(alert.issueNumber, alert.metric),
+        ...alert
+      }),
+      ConditionExpression: 'attribute_not_exists(sk)'
+    }));
+    return true;
+  } catch (err) {
+    if (err.name === 'ConditionalCheckFailedException') {
+      return false;
+    }
+    throw err;
+  }
+};
+
+const markEvaluated = async (tenantId, issueNumber, now) => {
+  await ddb.send(new UpdateItemCommand({
+    TableName: TABLE_NAME,
+    Key: marshall({ pk: `${tenantId}#${issueNumber}`, sk: 'stats' }),
+    UpdateExpression: 'SET anomalyCheckedAt = :now',
+    ExpressionAttributeValues: marshall({ ':now': now.toISOString() })
+  }));
+};
+
+const formatRate = (value) => `${Number(value).toFixed(value < 1 ? 3 : 2)}%`;
+
+/** Best-effort: the alerts are already recorded, so a failed email is only logged. */
+const notifyOwner = async (tenant, issueNumber, subject, alerts) => {
+  if (!tenant.email) {
+    console.warn('No owner email for tenant, skipping anomaly alert email', { tenantId: tenant.id });
+    return;
+  }
+
+  const rows = alerts.map((alert) => `
+        <li><strong>${METRIC_LABELS[alert.metric] || alert.metric}:</strong> ${formatRate(alert.value)}
+          (baseline ${formatRate(alert.baselineMean)}, z-score ${alert.zScore})</li>`).join('');
+  const html = `
+      <h2>Unusual metrics on issue #${issueNumber}</h2>
+      ${subject ? `<p><strong>${subject}</strong></p>` : ''}
+      <p>Compared with your last ${alerts[0].baselineIssues} issues, these metrics are outside the usual range:</p>
+      <ul>${rows}
+      </ul>
+      <p>Acknowledge or resolve these alerts from the Alerts page of your dashboard.</p>
+    `;
+
+  try {
+    await eventBridge.send(new PutEventsCommand({
+      Entries: [{
+        Source: 'newsletter-service',
+        DetailType: 'Send Email v2',
+        Detail: JSON.stringify({
+          tenantId: tenant.id,
+          to: { email: tenant.email },
+          subject: `[Alert] Unusual metrics on issue #${issueNumber}`,
+          html
+        })
+      }]
+    }));
+  } catch (err) {
+    console.error('Failed to send anomaly alert email', { tenantId: tenant.id, issueNumber, error: err.message });
+  }
+};
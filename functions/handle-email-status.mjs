@@ -8,11 +8,19 @@ import { updateSubscriberEngagement } from './utils/subscriber-engagement.mjs';
 import { processInterestScoring } from './utils/interest-scoring.mjs';
 import { recordTimeZoneObservation } from './utils/timezone-tracking.mjs';
 import { recordActivity, recordOpenHour } from './utils/activity-timeline.mjs';
+import { classifyMailboxProvider } from './utils/mailbox-provider.mjs';
+import { addSuppressionEntry } from './utils/suppression.mjs';
+import { AB_VARIANT_IDS } from './utils/ab-variants.mjs';
 import { ulid } from 'ulid';
 import crypto from 'crypto';
 
 const ddb = new DynamoDBClient();
 
+// Stats also bucketed by the recipient's mailbox provider.
+const PROVIDER_STATS = ['deliveries', 'opens', 'bounces', 'complaints'];
+// Event types whose stat can land in a provider bucket.
+const PROVIDER_EVENT_TYPES = ['delivery', 'open', 'bounce', 'complaint'];
+
 const padIssueNumber = (issueNumber) => {
   return String(issueNumber).padStart(5, '0');
 };
@@ -28,11 +36,24 @@ export const handler = async (event) => {
 
     const issueId = referenceNumber[0].replace(/_/g, '#');
     const [tenantId, issueNumber] = issueId.split('#');
+    const eventType = detail.eventType.toLowerCase();
+    const taggedVariant = detail.mail.tags?.variant?.[0];
+    // Which per-variant and per-provider counters this event bumps. Kept on the
+    // event records too, so erasing them can take them back off those counters.
+    const buckets = {
+      variantId: AB_VARIANT_IDS.includes(taggedVariant) ? taggedVariant : null,
+      provider: PROVIDER_EVENT_TYPES.includes(eventType)
+        ? await classifyMailboxProvider(detail.mail.destination[0])
+        : null
+    };
     let stat;
     let failedEmail;
-    switch (detail.eventType.toLowerCase()) {
+    switch (eventType) {
       case 'bounce':
-        await captureBounceEvent(issueId, detail.mail.destination[0], detail.bounce);
+        await captureBounceEvent(issueId, detail.mail.destination[0], detail.bounce, buckets);
+        if (categorizeBounceType(detail.bounce) === 'permanent') {
+          await addSuppressionEntry(tenantId, detail.mail.destination[0], { reason: 'hard-bounce', source: 'bounce' });
+        }
         stat = 'bounces';
         failedEmail = detail.mail.destination[0];
         break;
@@ -47,11 +68,11 @@ export const handler = async (event) => {
         stat = 'deliveries';
         break;
       case 'complaint':
-        await captureComplaintEvent(issueId, detail.mail.destination[0], detail.complaint);
+        await captureComplaintEvent(issueId, detail.mail.destination[0], detail.complaint, buckets);
         stat = 'complaints';
         break;
       case 'open':
-        await captureOpenEvent(issueId, detail.mail.destination[0], detail.open, detail.mail.commonHeaders);
+        await captureOpenEvent(issueId, detail.mail.destination[0], detail.open, detail.mail.commonHeaders, buckets);
         const isReopen = await trackUniqueOpen(issueId, detail.mail.destination[0], detail.open);
         stat = isReopen ? 'reopens' : 'opens';
         try {
@@ -65,7 +86,7 @@ export const handler = async (event) => {
       case 'click':
         stat = 'clicks';
         await trackLinkClick(issueId, detail.click.link, detail.click.ipAddress);
-        await captureClickEvent(issueId, detail.mail.destination[0], detail.click);
+        await captureClickEvent(issueId, detail.mail.destination[0], detail.click, buckets);
         try {
           await updateSubscriberEngagement(tenantId, detail.mail.destination[0], parseInt(issueNumber, 10));
         } catch (err) {
@@ -115,9 +136,12 @@ export const handler = async (event) => {
         })
       }));
 
-      const variantId = detail.mail.tags?.variant?.[0];
-      if (variantId === 'a' || variantId === 'b') {
-        await incrementVariantStat(issueId, variantId, stat);
+      if (buckets.variantId) {
+        await incrementVariantStat(issueId, buckets.variantId, stat);
+      }
+
+      if (PROVIDER_STATS.includes(stat)) {
+        await incrementProviderStat(issueId, buckets.provider, stat);
       }
     }
 
@@ -212,7 +236,39 @@ const incrementVariantStat = async (issueId, variantId, stat) => {
   }
 };
 
-const captureOpenEvent = async (issueId, subscriberEmail, openEvent, commonHeaders) => {
+/**
+ * Bump the per-issue counter for the recipient's mailbox provider
+ * (`stats#provider#<provider>`), which backs GET /issues/:id/deliverability.
+ * Defensive — a failure here must never affect stat aggregation.
+ */
+const incrementProviderStat = async (issueId, provider, stat) => {
+  try {
+    await ddb.send(new UpdateItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({
+        pk: issueId,
+        sk: `stats#provider#${provider}`
+      }),
+      UpdateExpression: 'ADD #stat :val',
+      ExpressionAttributeNames: {
+        '#stat': stat
+      },
+      ExpressionAttributeValues: marshall({
+        ':val': 1
+      })
+    }));
+  } catch (err) {
+    console.error('Failed to increment per-provider stat', { issueId, stat, error: err.message });
+  }
+};
+
+/** The `variant` and `provider` attributes an event record carries, when set. */
+const bucketAttributes = (buckets) => ({
+  ...(buckets?.variantId && { variant: buckets.variantId }),
+  ...(buckets?.provider && { provider: buckets.provider })
+});
+
+const captureOpenEvent = async (issueId, subscriberEmail, openEvent, commonHeaders, buckets) => {
   const openedAt = openEvent?.timestamp ? new Date(openEvent.timestamp) : new Date();
   const timestamp = openedAt.toISOString();
 
@@ -257,6 +313,7 @@ const captureOpenEvent = async (issueId, subscriberEmail, openEvent, commonHeade
     device,
     country,
     timeToOpen,
+    ...bucketAttributes(buckets),
     ttl: Math.floor(Date.now() / 1000) + (90 * 24 * 60 * 60)
   };
 
@@ -266,7 +323,7 @@ const captureOpenEvent = async (issueId, subscriberEmail, openEvent, commonHeade
   }));
 };
 
-const captureBounceEvent = async (issueId, subscriberEmail, bounceEvent) => {
+const captureBounceEvent = async (issueId, subscriberEmail, bounceEvent, buckets) => {
   const bouncedAt = bounceEvent?.timestamp ? new Date(bounceEvent.timestamp) : new Date();
   const timestamp = bouncedAt.toISOString();
 
@@ -285,6 +342,7 @@ const captureBounceEvent = async (issueId, subscriberEmail, bounceEvent) => {
     subscriberEmailHash,
     bounceType,
     bounceReason,
+    ...bucketAttributes(buckets),
     ttl: Math.floor(Date.now() / 1000) + (90 * 24 * 60 * 60)
   };
 
@@ -294,7 +352,7 @@ const captureBounceEvent = async (issueId, subscriberEmail, bounceEvent) => {
   }));
 };
 
-const captureComplaintEvent = async (issueId, subscriberEmail, complaintEvent) => {
+const captureComplaintEvent = async (issueId, subscriberEmail, complaintEvent, buckets) => {
   const complainedAt = complaintEvent?.timestamp ? new Date(complaintEvent.timestamp) : new Date();
   const timestamp = complainedAt.toISOString();
 
@@ -311,6 +369,7 @@ const captureComplaintEvent = async (issueId, subscriberEmail, complaintEvent) =
     timestamp,
     subscriberEmailHash,
     complaintType,
+    ...bucketAttributes(buckets),
     ttl: Math.floor(Date.now() / 1000) + (90 * 24 * 60 * 60)
   };
 
@@ -455,7 +514,7 @@ const getStoredLinkPosition = async (issueId, link) => {
   }
 };
 
-const captureClickEvent = async (issueId, subscriberEmail, clickEvent) => {
+const captureClickEvent = async (issueId, subscriberEmail, clickEvent, buckets) => {
   const clickedAt = clickEvent?.timestamp ? new Date(clickEvent.timestamp) : new Date();
   const timestamp = clickedAt.toISOString();
 
@@ -505,6 +564,7 @@ const captureClickEvent = async (issueId, subscriberEmail, clickEvent) => {
     device,
     country,
     timeToClick,
+    ...bucketAttributes(buckets),
     ttl: Math.floor(Date.now() / 1000) + (90 * 24 * 60 * 60)
   };
 
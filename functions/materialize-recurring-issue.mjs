@@ -0,0 +1,358 @@
+import { DynamoDBClient, GetItemCommand, PutItemCommand, QueryCommand, UpdateItemCommand } from '@aws-sdk/client-dynamodb';
+import { SFNClient, StartExecutionCommand } from '@aws-sdk/client-sfn';
+import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
+import frontmatter from '@github-docs/frontmatter';
+import { getOctokit, getTenant } from './utils/helpers.mjs';
+import { publishIssueEvent, EVENT_TYPES } from './utils/event-publisher.mjs';
+
+const ddb = new DynamoDBClient();
+const sfn = new SFNClient();
+
+const DATE_PLACEHOLDER = '{date}';
+const MAX_CREATE_ATTEMPTS = 3;
+
+/**
+ * Materializes one occurrence of a recurring issue schedule. Triggered by the
+ * EventBridge schedule created through POST /recurring-schedules, which emits
+ * a RECURRING_ISSUE_OCCURRENCE event for every RRULE occurrence.
+ *
+ * The occurrence is claimed on the schedule record first (keyed by its local
+ * date and time) so a redelivered event cannot create a second draft. When
+ * the content source has nothing for the occurrence the run is recorded as
+ * skipped; otherwise a draft is created with the next issue number and, when
+ * autoSchedule is on, handed to the publish workflow. Tenants that require
+ * editorial approval get the draft only; it is scheduled once approved.
+ */
+export const handler = async (event) => {
+  const { tenantId, scheduleId } = event?.detail || {};
+
+  if (!tenantId || !scheduleId) {
+    console.error('Missing required parameters', { tenantId, scheduleId });
+    return { created: false, reason: 'missing-parameters' };
+  }
+
+  const schedule = await loadSchedule(tenantId, scheduleId);
+  if (!schedule) {
+    console.warn('Recurring schedule not found', { tenantId, scheduleId });
+    return { created: false, reason: 'schedule-not-found' };
+  }
+  if (!schedule.enabled) {
+    return { created: false, reason: 'disabled' };
+  }
+
+  const occurredAt = event.time ? new Date(event.time) : new Date();
+  const { date, key } = localOccurrence(occurredAt, schedule.timezone);
+
+  if (!(await claimOccurrence(tenantId, scheduleId, key))) {
+    console.log('Occurrence already processed', { tenantId, scheduleId, occurrence: key });
+    return { created: false, reason: 'duplicate-occurrence', occurrence: key };
+  }
+
+  const source = await loadContent(tenantId, schedule, date);
+  if (!source) {
+    console.log('No content ready for occurrence, skipping', { tenantId, scheduleId, occurrence: key });
+    await recordRun(tenantId, scheduleId, { occurrence: key, status: 'skipped', reason: 'no-content' });
+    return { created: false, reason: 'no-content', occurrence: key };
+  }
+
+  const templateId = schedule.templateId || source.templateId || null;
+  if (source.contentType === 'json' && !templateId) {
+    console.warn('JSON content requires a template, skipping', { tenantId, scheduleId, occurrence: key });
+    await recordRun(tenantId, scheduleId, { occurrence: key, status: 'skipped', reason: 'missing-template' });
+    return { created: false, reason: 'missing-template', occurrence: key };
+  }
+
+  const subject = schedule.subject
+    ? schedule.subject.replaceAll(DATE_PLACEHOLDER, date)
+    : source.subject || `${schedule.name} – ${date}`;
+
+  const issueNumber = await createDraft(tenantId, {
+    subject,
+    content: source.content,
+    contentType: source.contentType,
+    metadata: source.metadata,
+    templateId,
+    scheduleId,
+    occurrence: key
+  });
+
+  let scheduledAt = null;
+  const awaitingApproval = schedule.autoSchedule && await approvalRequired(tenantId);
+  if (awaitingApproval) {
+    console.log('Approval required, leaving draft unscheduled', { tenantId, scheduleId, issueNumber });
+  } else if (schedule.autoSchedule) {
+    const delayMinutes = Number(schedule.publishDelayMinutes || 0);
+    if (delayMinutes > 0) {
+      scheduledAt = new Date(occurredAt.getTime() + delayMinutes * 60 * 1000).toISOString();
+    }
+    await startPublishWorkflow(tenantId, issueNumber, { subject, content: source.content, contentType: source.contentType, templateId, scheduledAt });
+  }
+
+  await publishIssueEvent(
+    tenantId,
+    'recurring-schedule',
+    EVENT_TYPES.ISSUE_DRAFT_SAVED,
+    {
+      issueId: `${tenantId}#${issueNumber}`,
+      issueNumber,
+      title: subject,
+      recurringScheduleId: scheduleId,
+      occurrence: key,
+      scheduledDate: scheduledAt
+    }
+  );
+
+  const scheduled = !!schedule.autoSchedule && !awaitingApproval;
+  await recordRun(tenantId, scheduleId, {
+    occurrence: key,
+    status: scheduled ? 'scheduled' : 'created',
+    issueNumber,
+    ...awaitingApproval && { reason: 'awaiting-approval' }
+  });
+
+  console.log('Materialized recurring issue', { tenantId, scheduleId, occurrence: key, issueNumber });
+  return { created: true, issueNumber, occurrence: key, scheduled };
+};
+
+const loadSchedule = async (tenantId, scheduleId) => {
+  const result = await ddb.send(new GetItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: `recurring-schedule#${scheduleId}` })
+  }));
+
+  return result.Item ? unmarshall(result.Item) : null;
+};
+
+/**
+ * Formats the occurrence in the schedule's timezone: `date` (YYYY-MM-DD) fills
+ * {date} placeholders and `key` (YYYY-MM-DDTHH:MM) identifies the occurrence.
+ */
+export const localOccurrence = (instant, timeZone) => {
+  let parts;
+  try {
+    parts = new Intl.DateTimeFormat('en-CA', {
+      timeZone: timeZone || 'UTC',
+      year: 'numeric',
+      month: '2-digit',
+      day: '2-digit',
+      hour: '2-digit',
+      minute: '2-digit',
+      hourCycle: 'h23'
+    }).formatToParts(instant);
+  } catch (err) {
+    console.warn('Invalid schedule timezone, falling back to UTC', { timeZone, error: err.message });
+    return localOccurrence(instant, 'UTC');
+  }
+
+  const get = (type) => parts.find((part) => part.type === type)?.value;
+  const date = `${get('year')}-${get('month')}-${get('day')}`;
+  return { date, key: `${date}T${get('hour')}:${get('minute')}` };
+};
+
+const claimOccurrence = async (tenantId, scheduleId, key) => {
+  try {
+    await ddb.send(new UpdateItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({ pk: tenantId, sk: `recurring-schedule#${scheduleId}` }),
+      UpdateExpression: 'SET lastOccurrence = :key',
+      ConditionExpression: 'attribute_exists(sk) AND (attribute_not_exists(lastOccurrence) OR lastOccurrence <> :key)',
+      ExpressionAttributeValues: marshall({ ':key': key })
+    }));
+    return true;
+  } catch (err) {
+    if (err.name === 'ConditionalCheckFailedException') {
+      return false;
+    }
+    throw err;
+  }
+};
+
+const loadContent = async (tenantId, schedule, date) => {
+  const source = schedule.contentSource || {};
+  switch (source.type) {
+    case 'issue':
+      return loadIssueContent(tenantId, source.issueId);
+    case 'github':
+      return loadGitHubContent(tenantId, source, date);
+    default:
+      console.error('Unknown content source', { tenantId, type: source.type });
+      return null;
+  }
+};
+
+const loadIssueContent = async (tenantId, issueId) => {
+  const result = await ddb.send(new GetItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: `${tenantId}#${issueId}`, sk: 'newsletter' })
+  }));
+
+  if (!result.Item) {
+    return null;
+  }
+
+  const issue = unmarshall(result.Item);
+  if (!issue.content || !issue.content.trim()) {
+    return null;
+  }
+
+  return {
+    subject: issue.subject,
+    content: issue.content,
+    contentType: issue.contentType || 'markdown',
+    metadata: issue.metadata,
+    templateId: issue.templateId
+  };
+};
+
+const loadGitHubContent = async (tenantId, source, date) => {
+  const tenant = await getTenant(tenantId);
+  if (!tenant?.github?.owner || !tenant?.github?.repo) {
+    console.warn('Tenant has no connected GitHub repository', { tenantId });
+    return null;
+  }
+
+  const octokit = await getOctokit(tenantId);
+  const path = source.path.replaceAll(DATE_PLACEHOLDER, date);
+
+  let response;
+  try {
+    response = await octokit.request('GET /repos/{owner}/{repo}/contents/{path}', {
+      owner: tenant.github.owner,
+      repo: tenant.github.repo,
+      path,
+      ...source.branch && { ref: source.branch }
+    });
+  } catch (err) {
+    if (err.status === 404) {
+      return null;
+    }
+    throw err;
+  }
+
+  const content = Buffer.from(response.data.content, 'base64').toString('utf8');
+  if (!content.trim()) {
+    return null;
+  }
+
+  const { data } = frontmatter(content);
+  return {
+    subject: data?.title,
+    content,
+    contentType: 'markdown'
+  };
+};
+
+const getNextIssueNumber = async (tenantId) => {
+  const result = await ddb.send(new QueryCommand({
+    TableName: process.env.TABLE_NAME,
+    IndexName: 'GSI1',
+    KeyConditionExpression: 'GSI1PK = :gsi1pk',
+    ExpressionAttributeValues: marshall({ ':gsi1pk': `${tenantId}#newsletter` }),
+    ScanIndexForward: false,
+    Limit: 1
+  }));
+
+  const latest = result.Items?.[0] ? unmarshall(result.Items[0]) : null;
+  const current = Number(latest?.pk?.split('#')[1]);
+  return Number.isInteger(current) ? current + 1 : 1;
+};
+
+/**
+ * Writes the draft with the same shape POST /issues uses. A concurrent create
+ * can take the same number, so the conditional put is retried with a fresh
+ * number.
+ */
+const createDraft = async (tenantId, draft) => {
+  for (let attempt = 1; ; attempt++) {
+    const issueNumber = await getNextIssueNumber(tenantId);
+    const now = new Date().toISOString();
+    const item = {
+      pk: `${tenantId}#${issueNumber}`,
+      sk: 'newsletter',
+      GSI1PK: `${tenantId}#newsletter`,
+      GSI1SK: now,
+      issueNumber,
+      subject: draft.subject,
+      status: 'draft',
+      content: draft.content,
+      contentType: draft.contentType,
+      createdAt: now,
+      updatedAt: now,
+      recurringScheduleId: draft.scheduleId,
+      recurringOccurrence: draft.occurrence,
+      ...draft.metadata && { metadata: draft.metadata },
+      ...draft.templateId && { templateId: draft.templateId }
+    };
+
+    try {
+      await ddb.send(new PutItemCommand({
+        TableName: process.env.TABLE_NAME,
+        Item: marshall(item),
+        ConditionExpression: 'attribute_not_exists(pk) AND attribute_not_exists(sk)'
+      }));
+      return issueNumber;
+    } catch (err) {
+      if (err.name === 'ConditionalCheckFailedException' && attempt < MAX_CREATE_ATTEMPTS) {
+        continue;
+      }
+      throw err;
+    }
+  }
+};
+
+/** Mirrors the API's approval policy (sk "approval-policy"); missing means off. */
+const approvalRequired = async (tenantId) => {
+  const result = await ddb.send(new GetItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: 'approval-policy' })
+  }));
+
+  return !!(result.Item && unmarshall(result.Item).enabled);
+};
+
+const startPublishWorkflow = async (tenantId, issueNumber, issue) => {
+  const tenant = await getTenant(tenantId);
+  const input = {
+    content: issue.content,
+    fileName: `issue-${issueNumber}`,
+    issueId: issueNumber,
+    tenant: {
+      id: tenantId,
+      email: tenant.email
+    },
+    isPreview: false,
+    templateId: issue.templateId || null,
+    contentType: issue.contentType,
+    subject: issue.subject,
+    ...issue.scheduledAt && { futureDate: issue.scheduledAt }
+  };
+
+  const { executionArn } = await sfn.send(new StartExecutionCommand({
+    stateMachineArn: process.env.STATE_MACHINE_ARN,
+    input: JSON.stringify(input)
+  }));
+
+  // Lets POST /issues/:id/cancel and /reschedule stop this execution.
+  if (executionArn) {
+    await ddb.send(new UpdateItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({ pk: `${tenantId}#${issueNumber}`, sk: 'newsletter' }),
+      UpdateExpression: 'SET scheduleExecutionArn = :arn',
+      ExpressionAttributeValues: marshall({ ':arn': executionArn })
+    }));
+  }
+};
+
+const recordRun = async (tenantId, scheduleId, run) => {
+  try {
+    await ddb.send(new UpdateItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({ pk: tenantId, sk: `recurring-schedule#${scheduleId}` }),
+      UpdateExpression: 'SET lastRun = :run',
+      ExpressionAttributeValues: marshall({ ':run': { ...run, at: new Date().toISOString() } }),
+      ConditionExpression: 'attribute_exists(sk)'
+    }));
+  } catch (err) {
+    console.error('Failed to record recurring schedule run', { tenantId, scheduleId, error: err.message });
+  }
+};
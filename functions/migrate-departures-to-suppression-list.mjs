@@ -0,0 +1,185 @@
+import { DynamoDBClient, GetItemCommand, QueryCommand } from '@aws-sdk/client-dynamodb';
+import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
+import { sendWithRetry } from './utils/helpers.mjs';
+import { hashEmail } from './utils/hash-email.mjs';
+import { UNSUBSCRIBE_SOURCE, addSuppressionEntry } from './utils/suppression.mjs';
+
+const ddb = new DynamoDBClient();
+
+const DEPARTURE_SK_PREFIX = 'subscriber-departure#';
+
+// Departure reason → suppression entry; every other reason is an unsubscribe.
+// An admin removal ('manual-removal') never blocked re-adding and is skipped.
+const ENTRY_BY_REASON = {
+  complaint: { reason: 'complaint', source: 'complaint' },
+  bounced: { reason: 'hard-bounce', source: 'bounce-cleanup' }
+};
+const UNSUBSCRIBE_ENTRY = { reason: 'manual', source: UNSUBSCRIBE_SOURCE };
+// Strongest first, for addresses that departed more than once.
+const ENTRY_RANK = ['complaint', 'hard-bounce', 'manual'];
+
+const queryAll = async (input, operationName) => {
+  const items = [];
+  let lastKey;
+  do {
+    const result = await sendWithRetry(() => ddb.send(new QueryCommand({
+      ...input,
+      ...(lastKey && { ExclusiveStartKey: lastKey })
+    })), operationName);
+    items.push(...(result.Items || []).map((item) => unmarshall(item)));
+    lastKey = result.LastEvaluatedKey;
+  } while (lastKey);
+  return items;
+};
+
+/** The entry a departure stands for, or null when it didn't suppress. */
+export const entryForDeparture = (departure) => {
+  if (!departure?.emailHash || departure.reason === 'manual-removal') {
+    return null;
+  }
+  return ENTRY_BY_REASON[departure.reason] ?? UNSUBSCRIBE_ENTRY;
+};
+
+/** Email hash → entry to write, keeping the strongest reason per address. */
+export const planEntries = (departures) => {
+  const entries = new Map();
+  for (const departure of departures) {
+    const entry = entryForDeparture(departure);
+    if (!entry) {
+      continue;
+    }
+    const current = entries.get(departure.emailHash);
+    if (!current || ENTRY_RANK.indexOf(entry.reason) < ENTRY_RANK.indexOf(current.reason)) {
+      entries.set(departure.emailHash, entry);
+    }
+  }
+  return entries;
+};
+
+/**
+ * Addresses the newsletter table still holds for the tenant: signup event
+ * records, per-issue open markers and failed send addresses. Departure records
+ * keep only a hash, so these are the only way back to an address.
+ */
+const loadKnownAddresses = async (tenantId) => {
+  const addresses = new Set();
+  const add = (email) => {
+    if (typeof email === 'string' && email.includes('@')) {
+      addresses.add(email.trim().toLowerCase());
+    }
+  };
+
+  const signups = await queryAll({
+    TableName: process.env.TABLE_NAME,
+    KeyConditionExpression: 'pk = :pk AND begins_with(sk, :prefix)',
+    ExpressionAttributeValues: marshall({ ':pk': tenantId, ':prefix': 'subscriber#' }),
+    ProjectionExpression: 'email'
+  }, 'QuerySubscriberEvents');
+  signups.forEach((signup) => add(signup.email));
+
+  const issues = await queryAll({
+    TableName: process.env.TABLE_NAME,
+    IndexName: 'GSI1',
+    KeyConditionExpression: 'GSI1PK = :gsi1pk',
+    ExpressionAttributeValues: marshall({ ':gsi1pk': `${tenantId}#issue` }),
+    ProjectionExpression: 'pk'
+  }, 'QueryTenantIssues');
+
+  for (const pk of new Set(issues.map((issue) => issue.pk))) {
+    const opens = await queryAll({
+      TableName: process.env.TABLE_NAME,
+      KeyConditionExpression: 'pk = :pk AND begins_with(sk, :prefix)',
+      ExpressionAttributeValues: marshall({ ':pk': pk, ':prefix': 'opens#' }),
+      ProjectionExpression: 'sk'
+    }, 'QueryOpenMarkers');
+    opens.forEach((open) => add(open.sk.slice('opens#'.length)));
+
+    const stats = await sendWithRetry(() => ddb.send(new GetItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({ pk, sk: 'stats' }),
+      ProjectionExpression: 'failedAddresses'
+    })), 'GetIssueStats');
+    (stats.Item ? unmarshall(stats.Item).failedAddresses ?? [] : []).forEach(add);
+  }
+
+  return addresses;
+};
+
+const isSubscribed = async (tenantId, email) => {
+  const result = await sendWithRetry(() => ddb.send(new GetItemCommand({
+    TableName: process.env.SUBSCRIBERS_TABLE_NAME,
+    Key: marshall({ tenantId, email }),
+    ProjectionExpression: 'email'
+  })), 'GetSubscriber');
+  return Boolean(result.Item);
+};
+
+/**
+ * One-off migration that puts a tenant's unsubscribed, bounced and complained
+ * departures on the suppression list, which replaced the departure records as
+ * what keeps those addresses from being added again. Invoked with
+ * { tenantId }; safe to re-run, since existing entries are left as they are.
+ *
+ * Departure records hold only an email hash, so each is matched against the
+ * addresses the tenant's other records still hold. Addresses that have since
+ * subscribed again are skipped. Hashes no address matches are counted as
+ * unresolved and are not suppressed.
+ */
+export const handler = async (event = {}) => {
+  if (!process.env.TABLE_NAME) {
+    throw new Error('TABLE_NAME environment variable is required');
+  }
+  if (!process.env.SUBSCRIBERS_TABLE_NAME) {
+    throw new Error('SUBSCRIBERS_TABLE_NAME environment variable is required');
+  }
+
+  const { tenantId } = event;
+  if (!tenantId) {
+    return { success: false, error: 'tenantId is required' };
+  }
+
+  console.log('[MIGRATION] Moving departures onto the suppression list', { tenantId });
+
+  const departures = await queryAll({
+    TableName: process.env.TABLE_NAME,
+    KeyConditionExpression: 'pk = :pk AND begins_with(sk, :prefix)',
+    ExpressionAttributeNames: { '#reason': 'reason' },
+    ExpressionAttributeValues: marshall({ ':pk': tenantId, ':prefix': DEPARTURE_SK_PREFIX }),
+    ProjectionExpression: 'emailHash, #reason'
+  }, 'QueryDepartures');
+  const planned = planEntries(departures);
+
+  const summary = {
+    departures: departures.length,
+    addresses: planned.size,
+    added: 0,
+    alreadyListed: 0,
+    resubscribed: 0,
+    unresolved: 0
+  };
+  if (planned.size > 0) {
+    const resolved = new Map();
+    for (const email of await loadKnownAddresses(tenantId)) {
+      const hash = hashEmail(email);
+      if (planned.has(hash)) {
+        resolved.set(hash, email);
+      }
+    }
+
+    for (const [hash, entry] of planned) {
+      const email = resolved.get(hash);
+      if (!email) {
+        summary.unresolved++;
+      } else if (await isSubscribed(tenantId, email)) {
+        summary.resubscribed++;
+      } else if (await addSuppressionEntry(tenantId, email, entry)) {
+        summary.added++;
+      } else {
+        summary.alreadyListed++;
+      }
+    }
+  }
+
+  console.log('[MIGRATION] Departures migrated', { tenantId, ...summary });
+  return { success: true, tenantId, ...summary };
+};
@@ -6,10 +6,18 @@ import { getTenant } from './utils/helpers.mjs';
 import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
 import { publishIssueEvent, EVENT_TYPES } from './utils/event-publisher.mjs';
 import { renderWithSnippets } from './utils/render-template.mjs';
+import { handler as parseMarkdown } from './parse-md-to-json.mjs';
+import {
+  SUBSCRIBER_FIELDS_SK,
+  subscriberFieldDefaults,
+  subscriberTemplateData
+} from './utils/subscriber-fields.mjs';
 
 const eventBridge = new EventBridgeClient();
 const ddb = new DynamoDBClient();
 
+const EMAIL_TOKEN = '__EMAIL__';
+
 export const handler = async (state) => {
   try {
     // html-mode issues arrive pre-rendered (the master rides on data.__master)
@@ -17,9 +25,12 @@ export const handler = async (state) => {
     // Gated on contentType (not the presence of __master) so a json template whose
     // data legitimately includes a top-level __master field is never mistaken for
     // a pre-rendered master.
-    const html = state.contentType === 'html'
-      ? (state.data?.__master ?? '')
+    const { html, subscriberFields } = state.contentType === 'html'
+      ? { html: state.data?.__master ?? '' }
       : await renderTemplate(state.data, state.tenantId, state.templateId);
+    // Rendered issues carry `{{subscriber.*}}` merge tokens that send-email-v2
+    // fills in per recipient, falling back to these defaults.
+    const mergeDefaults = subscriberFields ? subscriberFieldDefaults(subscriberFields) : undefined;
 
     if (state.isPreview) {
       await sendEmail({
@@ -27,20 +38,23 @@ export const handler = async (state) => {
         html,
         to: { email: state.email },
         sendAt: state.sendAtDate,
-        tenantId: state.tenantId
+        tenantId: state.tenantId,
+        subscriberFields: mergeDefaults
       });
     } else {
       const tenant = await getTenant(state.tenantId);
       const publishedAt = new Date().toISOString();
       await setupIssueStats(tenant, state.data.metadata.number, state.subject, publishedAt);
 
-      // Send configs (abTest, localSend, contentAssembly) are persisted on the
+      // Send configs (abTest, localSend, contentAssembly, audience) are persisted on the
       // issue record by the API. Reading them here (rather than threading them
       // through the state machine) keeps every state machine entry point
       // unchanged and works for unconfigured issues by default.
-      const { abTest, localSend, contentAssembly } = await getIssueSendConfig(state.tenantId, state.data.metadata.number);
+      const { abTest, localSend, contentAssembly, audience } = await getIssueSendConfig(state.tenantId, state.data.metadata.number);
 
-      const activeAbTest = (abTest?.dimension === 'subject' || abTest?.dimension === 'sendTime') ? abTest : undefined;
+      const activeAbTest = AB_DIMENSIONS.includes(abTest?.dimension)
+        ? await renderContentVariants(abTest, state)
+        : undefined;
 
       // Local send and A/B testing both control send timing/audience split, so
       // they are mutually exclusive; A/B wins when both are configured.
@@ -76,7 +90,9 @@ export const handler = async (state) => {
         tenantId: state.tenantId,
         abTest: activeAbTest,
         localSend: activeLocalSend,
-        contentAssembly: assemblyEnabled ? { enabled: true } : undefined
+        contentAssembly: assemblyEnabled ? { enabled: true } : undefined,
+        audience: audience || undefined,
+        subscriberFields: mergeDefaults
       });
 
       await publishIssueEvent(
@@ -101,6 +117,59 @@ export const handler = async (state) => {
   }
 };
 
+const AB_DIMENSIONS = ['subject', 'sendTime', 'content'];
+
+/**
+ * Renders each variant of a content test into the HTML its bucket is sent.
+ * A variant's `content` replaces the issue body (markdown, a JSON data object
+ * or an html master, matching the issue's contentType) and its `templateId`
+ * replaces the issue's template; whatever it leaves out falls back to the
+ * issue's own. Other dimensions are returned as they are.
+ * @param {Object} abTest - The issue's A/B test config.
+ * @param {Object} state - The publish state (data, contentType, templateId, tenantId).
+ * @returns {Promise<Object>} The config with an `html` on every content variant.
+ */
+const renderContentVariants = async (abTest, state) => {
+  if (abTest.dimension !== 'content') {
+    return abTest;
+  }
+
+  const variants = [];
+  for (const variant of abTest.variants ?? []) {
+    const templateId = variant.templateId ?? state.templateId;
+    let html;
+    if (state.contentType === 'html') {
+      html = variant.content ?? state.data?.__master ?? '';
+    } else {
+      const data = variant.content === undefined
+        ? state.data
+        : await parseVariantContent(variant.content, state);
+      ({ html } = await renderTemplate(data, state.tenantId, templateId));
+    }
+    variants.push({ ...variant, html });
+  }
+
+  return { ...abTest, variants };
+};
+
+/**
+ * Parses a content variant's body into template data, the same way the state
+ * machine parses the issue's own (parse-md-to-json or parse-json-issue).
+ * @param {string} content - The variant's markdown or JSON body.
+ * @param {Object} state - The publish state.
+ * @returns {Promise<Object>} Template data, numbered as the issue.
+ */
+const parseVariantContent = async (content, state) => {
+  const issueNumber = state.data.metadata.number;
+  if (state.contentType !== 'json') {
+    const parsed = await parseMarkdown({ content, issueId: issueNumber, tenantId: state.tenantId });
+    return parsed.data;
+  }
+
+  const data = JSON.parse(content);
+  return { ...data, metadata: { ...(data.metadata ?? {}), number: issueNumber } };
+};
+
 /**
  * Renders the issue data into HTML.
  *
@@ -111,27 +180,43 @@ export const handler = async (state) => {
  *
  * When no templateId is supplied, the static default newsletter template is used.
  *
+ * Either way the data gains a `subscriber` object of merge tokens (email, first
+ * and last name, and for a tenant template the tenant's custom fields).
+ *
  * @param {Object} data - Issue data to render against.
  * @param {string} [tenantId] - Tenant identifier (required when templateId is set).
  * @param {string} [templateId] - Optional selected template identifier.
- * @returns {Promise<string>} Rendered HTML.
+ * @returns {Promise<{html: string, subscriberFields: Array}>} Rendered HTML and the custom fields it was rendered with.
  */
 const renderTemplate = async (data, tenantId, templateId) => {
+  const renderDefault = () => ({
+    html: Handlebars.compile(defaultTemplate)({ ...data, subscriber: subscriberTemplateData([], EMAIL_TOKEN) }),
+    subscriberFields: []
+  });
+
   if (!templateId) {
-    return Handlebars.compile(defaultTemplate)(data);
+    return renderDefault();
   }
 
   const templateContent = await getTemplateContent(tenantId, templateId);
   if (!templateContent) {
     console.warn(`Template '${templateId}' not found for tenant '${tenantId}', falling back to default template`);
-    return Handlebars.compile(defaultTemplate)(data);
+    return renderDefault();
   }
 
-  const snippets = await getSnippets(tenantId);
+  const [snippets, subscriberFields] = await Promise.all([
+    getSnippets(tenantId),
+    getSubscriberFields(tenantId)
+  ]);
   // Delegate to the shared renderer so the send path and the conformance test
   // exercise identical logic (snippets as partials, missing partial -> empty,
   // noEscape to mirror the Rust preview renderer in template_render.rs).
-  return renderWithSnippets(templateContent, data, snippets);
+  const html = renderWithSnippets(
+    templateContent,
+    { ...data, subscriber: subscriberTemplateData(subscriberFields, EMAIL_TOKEN) },
+    snippets
+  );
+  return { html, subscriberFields };
 };
 
 /**
@@ -157,16 +242,33 @@ const getTemplateContent = async (tenantId, templateId) => {
   return template.content ?? null;
 };
 
+/**
+ * Loads the tenant's custom subscriber field definitions.
+ * @param {string} tenantId - Tenant identifier.
+ * @returns {Promise<Array>} Field definitions, empty when none are defined.
+ */
+const getSubscriberFields = async (tenantId) => {
+  const result = await ddb.send(new GetItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({
+      pk: tenantId,
+      sk: SUBSCRIBER_FIELDS_SK
+    })
+  }));
+
+  return result.Item ? (unmarshall(result.Item).fields ?? []) : [];
+};
+
 /**
  * Loads the persisted send-time configurations for an issue, if any.
- * The API stores `abTest`, `localSend`, and `contentAssembly` as JSON strings
+ * The API stores `abTest`, `localSend`, `contentAssembly`, and `audience` as JSON strings
  * on the issue record (sk "newsletter"), mirroring how `metadata` is persisted.
  * @param {string} tenantId - Tenant identifier.
  * @param {number|string} issueNumber - Issue number.
- * @returns {Promise<{abTest: Object|null, localSend: Object|null, contentAssembly: Object|null}>} Parsed configs (null when not set/invalid).
+ * @returns {Promise<{abTest: Object|null, localSend: Object|null, contentAssembly: Object|null, audience: Object|null}>} Parsed configs (null when not set/invalid).
  */
 const getIssueSendConfig = async (tenantId, issueNumber) => {
-  const config = { abTest: null, localSend: null, contentAssembly: null };
+  const config = { abTest: null, localSend: null, contentAssembly: null, audience: null };
 
   try {
     const result = await ddb.send(new GetItemCommand({
@@ -175,7 +277,7 @@ const getIssueSendConfig = async (tenantId, issueNumber) => {
         pk: `${tenantId}#${issueNumber}`,
         sk: 'newsletter'
       }),
-      ProjectionExpression: 'abTest, localSend, contentAssembly'
+      ProjectionExpression: 'abTest, localSend, contentAssembly, audience'
     }));
 
     if (!result.Item) {
@@ -183,7 +285,7 @@ const getIssueSendConfig = async (tenantId, issueNumber) => {
     }
 
     const record = unmarshall(result.Item);
-    for (const field of ['abTest', 'localSend', 'contentAssembly']) {
+    for (const field of ['abTest', 'localSend', 'contentAssembly', 'audience']) {
       if (!record[field]) {
         continue;
       }
@@ -228,8 +330,10 @@ const getSnippets = async (tenantId) => {
  * @param {string} [params.to.email] - Individual recipient email address
  * @param {string} [params.to.list] - SES list name for bulk sending
  * @param {string} [params.sendAt] - ISO date string for scheduled sending
- * @param {Object} [params.abTest] - Optional A/B test configuration (variants, testFraction, evaluateAfterMinutes, ...)
+ * @param {Object} [params.abTest] - Optional A/B test configuration (variants, testFraction, evaluateAfterMinutes, ...); content variants carry their rendered html
  * @param {Object} [params.contentAssembly] - Optional interest-aware assembly flag ({ enabled: true })
+ * @param {Object} [params.audience] - Optional audience targeting (segments, interests, timezones)
+ * @param {Object} [params.subscriberFields] - Fallback text per `subscriber.*` merge field (see utils/subscriber-fields.mjs)
  */
 const sendEmail = async (params) => {
   await eventBridge.send(new PutEventsCommand({
@@ -249,9 +353,11 @@ const sendEmail = async (params) => {
         ...params.abTest && { abTest: params.abTest },
         ...params.localSend && { localSend: params.localSend },
         ...params.contentAssembly && { contentAssembly: params.contentAssembly },
+        ...params.audience && { audience: params.audience },
         replacements: {
-          emailAddress: "__EMAIL__",
-          emailAddressHash: "__EMAIL_HASH__"
+          emailAddress: EMAIL_TOKEN,
+          emailAddressHash: "__EMAIL_HASH__",
+          ...params.subscriberFields && { subscriberFields: params.subscriberFields }
         }
       })
     }]
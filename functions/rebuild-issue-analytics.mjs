@@ -0,0 +1,181 @@
+import { DynamoDBClient, GetItemCommand, UpdateItemCommand } from '@aws-sdk/client-dynamodb';
+import { EventBridgeClient, PutEventsCommand } from '@aws-sdk/client-eventbridge';
+import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
+
+const ddb = new DynamoDBClient();
+const eventBridge = new EventBridgeClient();
+
+const JOB_SK_PREFIX = 'analytics-rebuild#';
+// One ISSUE_PUBLISHED event per interval keeps the analytics aggregator
+// from being flooded when a job spans hundreds of issues.
+const PUBLISH_INTERVAL_MS = Number(process.env.PUBLISH_INTERVAL_MS || 1000);
+// Stop short of the Lambda timeout so the job can be marked interrupted.
+const TIME_RESERVE_MS = 30_000;
+
+/**
+ * Works through an analytics rebuild job created by
+ * POST /issues/analytics/rebuild. Invoked asynchronously with
+ * { tenantId, jobId }.
+ *
+ * The job is claimed by moving it from pending to running, so a duplicate
+ * invocation is a no-op. Each pending issue gets its ISSUE_PUBLISHED event
+ * re-emitted and its entry marked queued (or failed, with the error) right
+ * away, which is what makes the job resumable: a resumed run only touches
+ * issues that are still pending. The job ends completed, failed (some issues
+ * failed) or interrupted (out of time).
+ */
+export const handler = async (event, context) => {
+  const { tenantId, jobId } = event || {};
+
+  if (!tenantId || !jobId) {
+    console.error('Missing required parameters', { tenantId, jobId });
+    return { status: 'skipped', reason: 'missing-parameters' };
+  }
+
+  const job = await loadJob(tenantId, jobId);
+  if (!job) {
+    console.warn('Rebuild job not found', { tenantId, jobId });
+    return { status: 'skipped', reason: 'job-not-found' };
+  }
+
+  if (!(await claimJob(tenantId, jobId))) {
+    console.log('Rebuild job is not pending, skipping', { tenantId, jobId, status: job.status });
+    return { status: 'skipped', reason: 'not-pending' };
+  }
+
+  let queued = 0;
+  let failed = 0;
+  let interrupted = false;
+
+  for (const [index, issue] of job.issues.entries()) {
+    if (issue.status !== 'pending') {
+      continue;
+    }
+
+    if (remainingTime(context) < TIME_RESERVE_MS) {
+      interrupted = true;
+      break;
+    }
+
+    if (queued + failed > 0) {
+      await sleep(PUBLISH_INTERVAL_MS);
+    }
+
+    try {
+      await publishIssuePublished(tenantId, job.userId, issue);
+      await recordIssue(tenantId, jobId, index, { status: 'queued' });
+      queued++;
+    } catch (err) {
+      console.error('Failed to re-emit ISSUE_PUBLISHED', { tenantId, jobId, issueNumber: issue.issueNumber, error: err.message });
+      await recordIssue(tenantId, jobId, index, { status: 'failed', error: err.message });
+      failed++;
+    }
+  }
+
+  const previouslyFailed = job.issues.some((issue) => issue.status === 'failed');
+  const status = interrupted
+    ? 'interrupted'
+    : (failed > 0 || previouslyFailed ? 'failed' : 'completed');
+  await finishJob(tenantId, jobId, status);
+
+  console.log('Rebuild job finished', { tenantId, jobId, status, queued, failed });
+  return { status, queued, failed };
+};
+
+async function loadJob(tenantId, jobId) {
+  const result = await ddb.send(new GetItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: `${JOB_SK_PREFIX}${jobId}` }),
+    ConsistentRead: true
+  }));
+
+  return result.Item ? unmarshall(result.Item) : null;
+}
+
+async function claimJob(tenantId, jobId) {
+  const now = new Date().toISOString();
+  try {
+    await ddb.send(new UpdateItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({ pk: tenantId, sk: `${JOB_SK_PREFIX}${jobId}` }),
+      UpdateExpression: 'SET #status = :running, startedAt = :now, updatedAt = :now',
+      ConditionExpression: '#status = :pending',
+      ExpressionAttributeNames: { '#status': 'status' },
+      ExpressionAttributeValues: marshall({ ':running': 'running', ':pending': 'pending', ':now': now })
+    }));
+    return true;
+  } catch (err) {
+    if (err.name === 'ConditionalCheckFailedException') {
+      return false;
+    }
+    throw err;
+  }
+}
+
+async function recordIssue(tenantId, jobId, index, { status, error }) {
+  const now = new Date().toISOString();
+  const values = { ':status': status, ':now': now };
+  let updateExpression = `SET issues[${index}].#status = :status, issues[${index}].processedAt = :now, updatedAt = :now`;
+
+  if (error) {
+    updateExpression += `, issues[${index}].#error = :error`;
+    values[':error'] = error;
+  } else {
+    updateExpression += ` REMOVE issues[${index}].#error`;
+  }
+
+  await ddb.send(new UpdateItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: `${JOB_SK_PREFIX}${jobId}` }),
+    UpdateExpression: updateExpression,
+    ExpressionAttributeNames: { '#status': 'status', '#error': 'error' },
+    ExpressionAttributeValues: marshall(values)
+  }));
+}
+
+async function finishJob(tenantId, jobId, status) {
+  const now = new Date().toISOString();
+  await ddb.send(new UpdateItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: `${JOB_SK_PREFIX}${jobId}` }),
+    UpdateExpression: 'SET #status = :status, finishedAt = :now, updatedAt = :now',
+    ExpressionAttributeNames: { '#status': 'status' },
+    ExpressionAttributeValues: marshall({ ':status': status, ':now': now })
+  }));
+}
+
+/**
+ * Same event the single-issue rebuild endpoint emits. Unlike the shared
+ * event publisher this surfaces failures, so they land on the job.
+ */
+async function publishIssuePublished(tenantId, userId, issue) {
+  const result = await eventBridge.send(new PutEventsCommand({
+    Entries: [{
+      Source: 'newsletter-service',
+      DetailType: 'ISSUE_PUBLISHED',
+      Detail: JSON.stringify({
+        tenantId,
+        userId,
+        type: 'ISSUE_PUBLISHED',
+        data: {
+          issueNumber: issue.issueNumber,
+          publishedAt: issue.publishedAt,
+          title: issue.subject
+        }
+      })
+    }]
+  }));
+
+  if (result.FailedEntryCount > 0) {
+    const entry = result.Entries?.find((e) => e.ErrorCode) || {};
+    throw new Error(`${entry.ErrorCode || 'PutEventsFailed'}: ${entry.ErrorMessage || 'event was not accepted'}`);
+  }
+}
+
+function remainingTime(context) {
+  return typeof context?.getRemainingTimeInMillis === 'function'
+    ? context.getRemainingTimeInMillis()
+    : Infinity;
+}
+
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
@@ -18,6 +18,9 @@ import {
   CATCH_ALL_GROUP
 } from './utils/local-send.mjs';
 import { extractSections, prepareAssembly, assembleForSubscriber } from './utils/interest-assembly.mjs';
+import { hasAudienceFilter, filterSubscribersForAudience } from './utils/audience.mjs';
+import { applySubscriberFields } from './utils/subscriber-fields.mjs';
+import { isSuppressedAddress, loadSuppressionList } from './utils/suppression.mjs';
 
 // Key patterns for DynamoDB (previously from ./senders/types.mjs)
 const KEY_PATTERNS = {
@@ -225,6 +228,85 @@ const retrieveSubscribersPhase = async (tenantId) => {
   return subscribers;
 };
 
+/**
+ * Drop subscribers on the tenant's suppression list, by address or domain.
+ * A failure to load the list fails the send rather than risk mailing them.
+ * @param {string} tenantId - Tenant identifier
+ * @param {object[]} subscribers - Subscriber list
+ * @returns {Promise<object[]>} Subscribers not suppressed
+ */
+const filterSuppressedPhase = async (tenantId, subscribers) => {
+  const list = await loadSuppressionList(tenantId);
+  const allowed = subscribers.filter(subscriber => !isSuppressedAddress(list, subscriber.email));
+  console.log(`[SUPPRESSION] Skipped ${subscribers.length - allowed.length} suppressed subscribers`);
+  return allowed;
+};
+
+/**
+ * Load the emails that belong to any of the given segments. Member rows live
+ * in the subscribers table under `SEGMENT#<id>#MEMBER#<email>`.
+ * @param {string} tenantId - Tenant identifier
+ * @param {string[]} segmentIds - Segment ids
+ * @returns {Promise<Set<string>>} Member emails
+ */
+const loadSegmentMembers = async (tenantId, segmentIds) => {
+  const members = new Set();
+
+  for (const segmentId of segmentIds) {
+    let lastEvaluatedKey;
+    do {
+      const result = await sendWithRetry(async () => {
+        return await ddb.send(new QueryCommand({
+          TableName: process.env.SUBSCRIBERS_TABLE_NAME,
+          KeyConditionExpression: 'tenantId = :tenantId AND begins_with(email, :prefix)',
+          ExpressionAttributeValues: marshall({
+            ':tenantId': tenantId,
+            ':prefix': `SEGMENT#${segmentId}#MEMBER#`
+          }),
+          ProjectionExpression: 'subscriberEmail',
+          ...lastEvaluatedKey && { ExclusiveStartKey: lastEvaluatedKey }
+        }));
+      }, `Query members of segment ${segmentId}`);
+
+      for (const item of result.Items ?? []) {
+        const { subscriberEmail } = unmarshall(item);
+        if (subscriberEmail) {
+          members.add(subscriberEmail);
+        }
+      }
+      lastEvaluatedKey = result.LastEvaluatedKey;
+    } while (lastEvaluatedKey);
+  }
+
+  return members;
+};
+
+/**
+ * Narrow a list send to the issue's audience (segments, interests,
+ * timezones). Segment membership is read at send time so changes made after
+ * the issue was scheduled are respected. Returns the input unchanged when the
+ * issue has no audience.
+ * @param {string} tenantId - Tenant identifier
+ * @param {object[]} subscribers - Full subscriber list
+ * @param {Object|undefined} audience - Normalized audience from the event
+ * @returns {Promise<object[]>} Subscribers in the audience
+ */
+const applyAudiencePhase = async (tenantId, subscribers, audience) => {
+  if (!hasAudienceFilter(audience)) {
+    return subscribers;
+  }
+
+  const includeSegments = audience.includeSegments ?? [];
+  const memberships = {
+    included: includeSegments.length > 0 ? await loadSegmentMembers(tenantId, includeSegments) : null,
+    excluded: await loadSegmentMembers(tenantId, audience.excludeSegments ?? [])
+  };
+
+  const filtered = filterSubscribersForAudience(subscribers, audience, memberships);
+  console.log(`[AUDIENCE] ${filtered.length}/${subscribers.length} subscribers in audience`);
+  return filtered;
+};
+
 /**
  * Validate an incoming localSend config. Returns the config when usable,
  * otherwise null (with a warning) so the send falls back to a plain send —
@@ -345,7 +427,13 @@ const createLocalSendSchedule = async (payload, sendTime, referenceNumber, label
  * @returns {Promise<Object>} Fan-out summary
  */
 const fanOutLocalSendGroups = async ({ data, localSend }) => {
-  const subscribers = await retrieveSubscribersPhase(data.tenantId);
+  // Group re-entry events re-apply the audience (it stays on the payload), so
+  // this only keeps groups with no audience members from being scheduled.
+  const subscribers = await applyAudiencePhase(
+    data.tenantId,
+    await retrieveSubscribersPhase(data.tenantId),
+    data.audience
+  );
 
   const now = new Date();
   const sendAtDate = data.sendAt ? new Date(data.sendAt) : null;
@@ -546,8 +634,9 @@ const prepareAssemblyPhase = async (html, subscribers, referenceNumber) => {
  * @param {string} emailConfig.html - Email HTML body
  * @param {Object} emailConfig.replacements - Replacement tokens for personalization
  * @param {string} emailConfig.referenceNumber - Optional reference number for tracking
- * @param {string} [emailConfig.variant] - Optional A/B variant id ("a"/"b") tagged on the send
+ * @param {string} [emailConfig.variant] - Optional A/B variant id ("a".."f") tagged on the send
  * @param {Object} [emailConfig.assembly] - Optional prepared interest assembly ({ prepared, interestByEmail }) from prepareAssemblyPhase
+ * @param {Map} [emailConfig.subscribersByEmail] - Loaded subscriber records, used to fill `subscriber.*` merge tokens
  * @param {string} senderEmail - Sender email address
  * @returns {Promise<{sentCount: number, sentRecipients: string[]}>} Send stats and sent recipients
  */
@@ -578,6 +667,16 @@ const sendEmailsPhase = async (emailAddresses, emailConfig, senderEmail) => {
         );
       }
 
+      // `{{subscriber.*}}` merge tokens rendered by publish-issue. Recipients
+      // without a value get the field default carried in the replacements.
+      if (emailConfig.replacements?.subscriberFields) {
+        personalizedHtml = applySubscriberFields(
+          personalizedHtml,
+          emailConfig.subscribersByEmail?.get(email),
+          emailConfig.replacements.subscriberFields
+        );
+      }
+
       if (emailConfig.replacements?.emailAddress) {
         personalizedHtml = personalizedHtml.replace(
           new RegExp(emailConfig.replacements.emailAddress, 'g'),
@@ -703,12 +802,15 @@ const updateSubscriberTrackingPhase = async (tenantId, sentRecipients, issueIden
  * @param {string} params.tenantId
  * @param {string} params.referenceNumber - `${tenantId}_${issueNumber}`
  * @param {Object} params.abTest - Normalized A/B config (variants, evaluateAfterMinutes, ...)
- * @param {string} params.html - Rendered email HTML (with personalization placeholders)
+ * @param {string} params.subject - Shared subject line (the subject test's variants carry their own)
+ * @param {string} params.html - Rendered email HTML (with personalization placeholders); a
+ *   content test's variants carry their own, which travel as `variantHtml`
  * @param {Object} [params.replacements] - Personalization replacement tokens
  * @param {string} [params.from] - Optional sender email
  * @param {Object} params.to - Recipient config ({ list })
+ * @param {Object} [params.audience] - Audience the winner send is limited to
  */
-const scheduleAbEvaluation = async ({ tenantId, referenceNumber, abTest, subject, html, replacements, from, to }) => {
+const scheduleAbEvaluation = async ({ tenantId, referenceNumber, abTest, subject, html, replacements, from, to, audience }) => {
   if (!referenceNumber) {
     console.warn('[A/B] Skipping evaluation schedule - missing referenceNumber');
     return;
@@ -734,11 +836,15 @@ const scheduleAbEvaluation = async ({ tenantId, referenceNumber, abTest, subject
     sendPayload: {
       subject,
       html,
+      ...abTest.dimension === 'content' && {
+        variantHtml: Object.fromEntries(abTest.variants.map(variant => [variant.variantId, variant.html]))
+      },
       to: { list: to.list },
       tenantId,
       referenceNumber,
       ...replacements && { replacements },
-      ...from && { from }
+      ...from && { from },
+      ...audience && { audience }
     }
   };
 
@@ -848,6 +954,7 @@ const fanOutSendTimeVariants = async ({ data, subject, html, to, tenantId, repla
       referenceNumber: data.referenceNumber,
       ...replacements && { replacements },
       ...from && { from },
+      ...data.audience && { audience: data.audience },
       abTest,
       variantFilter: variant.variantId,
       ...variant.sendAt && { sendAt: variant.sendAt }
@@ -947,8 +1054,8 @@ export const handler = async (event) => {
       }
     }
 
-    // Recognize a managed A/B test (subject-line or send-time dimension).
-    const abTest = (data.abTest?.dimension === 'subject' || data.abTest?.dimension === 'sendTime')
+    // Recognize a managed A/B test (subject-line, send-time or content dimension).
+    const abTest = ['subject', 'sendTime', 'content'].includes(data.abTest?.dimension)
       ? data.abTest
       : null;
     const variantFilter = data.variantFilter ?? null;
@@ -959,7 +1066,7 @@ export const handler = async (event) => {
     if (abTest?.dimension === 'sendTime' && !variantFilter && to.list) {
       await executePhase('A/B Send-Time Fan-Out', async () => {
         await fanOutSendTimeVariants({ data, subject, html, to, tenantId, replacements, from, abTest });
-        await scheduleAbEvaluation({ tenantId, referenceNumber: data.referenceNumber, abTest, subject, html, replacements, from, to });
+        await scheduleAbEvaluation({ tenantId, referenceNumber: data.referenceNumber, abTest, subject, html, replacements, from, to, audience: data.audience });
         await markAbTestTesting({ tenantId, referenceNumber: data.referenceNumber, abTest });
       });
       console.log('[EXECUTION COMPLETE] Send-time A/B test fanned out to per-variant sends');
@@ -980,6 +1087,19 @@ export const handler = async (event) => {
         return await retrieveSubscribersPhase(tenantId);
       });
 
+      subscribers = await executePhase('Suppression Filter', async () => {
+        return await filterSuppressedPhase(tenantId, subscribers);
+      });
+
+      // Audience targeting narrows the list before any A/B sample or group
+      // split, so those partitions are computed over the same recipients on
+      // every fire.
+      if (hasAudienceFilter(data.audience)) {
+        subscribers = await executePhase('Audience Filter', async () => {
+          return await applyAudiencePhase(tenantId, subscribers, data.audience);
+        });
+      }
+
       // Local-send group re-entry: narrow to this group's members. Membership
       // is derived at fire time (not embedded in the event) so timezone
       // confirmations / new opens landing between fan-out and fire are
@@ -1030,29 +1150,40 @@ export const handler = async (event) => {
 
     // Phase 3: Email Sending
     // For a managed A/B test, only a deterministic hold-out *sample* receives the
-    // variants; the winner is sent to the remainder later by evaluate-ab-winner.
+    // variants; evaluate-ab-winner sends the remainder later.
     // The sample + variant split are derived from the FULL recipient list (not
     // the idempotency-filtered one) so the partition is identical across a
     // send-time test's staggered per-variant sends; already-sent recipients are
-    // then filtered out per bucket. A legacy `data.variants` payload (no
-    // hold-out) splits the eligible list directly. Otherwise a single send.
+    // then filtered out per bucket. A `data.variants` payload without an
+    // abTest (a legacy split, or an adaptive test's hold-out batch with its
+    // `variantWeights`) splits the eligible list directly. Otherwise a single
+    // send.
     const variants = abTest?.variants ?? (Array.isArray(data.variants) ? data.variants : null);
     const allEmails = subscribers.map(subscriber => subscriber.email);
     const eligibleSet = new Set(emailAddresses);
+    const subscribersByEmail = replacements?.subscriberFields
+      ? new Map(subscribers.map(subscriber => [subscriber.email, subscriber]))
+      : undefined;
     const { sentCount, sentRecipients } = await executePhase('Email Sending', async () => {
       if (variants?.length && to.list) {
         const subjectByVariant = Object.fromEntries(
           variants.map(variant => [variant.variantId, variant.subject])
         );
+        // Content variants arrive rendered (publish-issue); the rest share `html`.
+        const htmlByVariant = Object.fromEntries(
+          variants.map(variant => [variant.variantId, variant.html])
+        );
+
+        const configuredIds = variants.map(variant => variant.variantId);
 
         let buckets;
         if (abTest) {
           const testFraction = typeof abTest.testFraction === 'number' ? abTest.testFraction : 0.2;
           const { sample } = selectHoldoutSample(allEmails, data.referenceNumber, testFraction);
-          buckets = splitRecipients(sample, data.referenceNumber);
+          buckets = splitRecipients(sample, data.referenceNumber, configuredIds);
           console.log(`[A/B] Test sample: ${sample.length}/${allEmails.length} (fraction ${testFraction})${variantFilter ? `, variant ${variantFilter}` : ''}`);
         } else {
-          buckets = splitRecipients(emailAddresses, data.referenceNumber);
+          buckets = splitRecipients(emailAddresses, data.referenceNumber, configuredIds, data.variantWeights);
         }
 
         // A send-time per-variant fire targets only its own bucket.
@@ -1071,11 +1202,12 @@ export const handler = async (event) => {
 
           const result = await sendEmailsPhase(bucketRecipients, {
             subject: subjectByVariant[variantId] ?? subject,
-            html,
+            html: htmlByVariant[variantId] ?? html,
             replacements,
             referenceNumber: data.referenceNumber,
             variant: variantId,
-            ...assembly && { assembly }
+            ...assembly && { assembly },
+            ...subscribersByEmail && { subscribersByEmail }
           }, senderEmail);
 
           total += result.sentCount;
@@ -1090,7 +1222,8 @@ export const handler = async (event) => {
         html,
         replacements,
         referenceNumber: data.referenceNumber,
-        ...assembly && { assembly }
+        ...assembly && { assembly },
+        ...subscribersByEmail && { subscribersByEmail }
       }, senderEmail);
     });
 
@@ -1106,7 +1239,7 @@ export const handler = async (event) => {
       });
     }
 
-    // Phase 5: Schedule A/B winner evaluation (subject-line tests only here).
+    // Phase 5: Schedule A/B winner evaluation (subject-line and content tests).
     // The test sample has now been sent; schedule the evaluation that picks a
     // winner and sends it to the hold-out remainder. Send-time tests schedule
     // their evaluation up front during fan-out, and their per-variant fires
@@ -1117,10 +1250,12 @@ export const handler = async (event) => {
           tenantId,
           referenceNumber: data.referenceNumber,
           abTest,
+          subject,
           html,
           replacements,
           from,
-          to
+          to,
+          audience: data.audience
         });
         await markAbTestTesting({ tenantId, referenceNumber: data.referenceNumber, abTest });
       });
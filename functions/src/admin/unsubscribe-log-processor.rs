@@ -4,6 +4,7 @@ use aws_sdk_dynamodb::types::AttributeValue;
 use chrono::{Duration, Utc};
 use lambda_runtime::{run, service_fn, Error, LambdaEvent};
 use newsletter::admin::aws_clients;
+use newsletter::admin::suppression::{self, SuppressionEntry, SuppressionKind, SuppressionReason};
 use regex::Regex;
 use serde::Serialize;
 use serde_json::{json, Value};
@@ -374,6 +375,7 @@ async fn process_individual_unsubscribe(
     let success = unsubscribe_user(tenant_id, &event.email, "log-processor").await;
 
     if success {
+        suppress_address(tenant_id, &event.email).await;
         Ok(ProcessResult::success(
             event.email.clone(),
             tenant_id.to_string(),
@@ -384,6 +386,35 @@ async fn process_individual_unsubscribe(
     }
 }
 
+/// Put the address on the tenant's suppression list so it can't be
+/// re-imported or mailed. Entries already on the list are left as they are.
+/// Best-effort: the unsubscribe itself already succeeded, so a failure is
+/// logged rather than reported.
+async fn suppress_address(tenant_id: &str, email_address: &str) {
+    let table_name = match env::var("TABLE_NAME") {
+        Ok(value) => value,
+        Err(err) => {
+            tracing::warn!(error = %err, "TABLE_NAME not set; address not suppressed");
+            return;
+        }
+    };
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+
+    let entry = SuppressionEntry {
+        entry: email_address.trim().to_lowercase(),
+        kind: SuppressionKind::Email,
+        reason: SuppressionReason::Manual,
+        source: "unsubscribe".to_string(),
+        note: None,
+        created_at: Utc::now().to_rfc3339(),
+        created_by: None,
+    };
+
+    if let Err(err) = suppression::add_entry(ddb_client, &table_name, tenant_id, &entry).await {
+        tracing::warn!(error = ?err, tenant_id = %tenant_id, "Failed to add suppression entry");
+    }
+}
+
 async fn unsubscribe_user(tenant_id: &str, email_address: &str, _method: &str) -> bool {
     let table_name = match env::var("TABLE_NAME") {
         Ok(value) => value,
@@ -0,0 +1,553 @@
+//! Allocation math for multi-variant (A/B/n) tests.
+//!
+//! A test either splits its sample evenly across variants (`fixed`, the
+//! original behaviour) or adapts the split as engagement arrives using a
+//! bandit policy:
+//!
+//! - `thompson` — each variant's next-batch share is the probability that it
+//!   is the best arm, estimated by drawing from a Beta(1 + successes,
+//!   1 + failures) posterior per variant.
+//! - `epsilonGreedy` — the current leader gets `1 - epsilon` of the batch and
+//!   `epsilon` is spread evenly across every variant for exploration.
+//!
+//! It also carries the two-proportion z-test (a port of utils/ab-stats.mjs)
+//! used wherever two engagement rates are compared for significance, the
+//! Bayesian read-out of a running test (probability to beat the control,
+//! credible intervals, expected lift) and the sample-size maths behind the
+//! test planner.
+//!
+//! Everything here is pure: callers pass the counters (and an RNG for the
+//! Monte Carlo estimate) so the maths is unit-testable without DynamoDB.
+
+use rand::Rng;
+
+pub const ALLOCATION_FIXED: &str = "fixed";
+pub const ALLOCATION_THOMPSON: &str = "thompson";
+pub const ALLOCATION_EPSILON_GREEDY: &str = "epsilonGreedy";
+
+/// Number of posterior draws used to estimate Thompson allocation shares
+/// and the Bayesian read-out.
+/// 10k draws puts the Monte Carlo error well under one percentage point,
+/// which is finer than any batch split we act on.
+const THOMPSON_DRAWS: usize = 10_000;
+
+/// Success/trial counts for one variant on the test's win metric.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArmCounts {
+    pub successes: i64,
+    pub trials: i64,
+}
+
+impl ArmCounts {
+    /// Observed rate, or 0 when nothing has been delivered yet.
+    pub fn rate(&self) -> f64 {
+        if self.trials > 0 {
+            self.successes.clamp(0, self.trials) as f64 / self.trials as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Beta posterior parameters under a uniform Beta(1, 1) prior. Successes
+    /// are clamped to the trial count so over-counted opens (e.g. machine
+    /// opens recorded before the delivery event) cannot yield a negative beta.
+    pub fn posterior(&self) -> (f64, f64) {
+        let trials = self.trials.max(0);
+        let successes = self.successes.clamp(0, trials);
+        (1.0 + successes as f64, 1.0 + (trials - successes) as f64)
+    }
+}
+
+/// Even split across `n` variants (the `fixed` allocation mode).
+pub fn fixed_allocation(n: usize) -> Vec<f64> {
+    if n == 0 {
+        return Vec::new();
+    }
+    vec![1.0 / n as f64; n]
+}
+
+/// Epsilon-greedy shares: the arm with the highest observed rate receives
+/// `1 - epsilon` plus its exploration slice. Ties go to the earliest arm
+/// (the control), so a test with no data yet behaves like a fixed split plus
+/// a small tilt towards the control.
+pub fn epsilon_greedy_allocation(arms: &[ArmCounts], epsilon: f64) -> Vec<f64> {
+    if arms.is_empty() {
+        return Vec::new();
+    }
+    if arms.iter().all(|arm| arm.trials <= 0) {
+        return fixed_allocation(arms.len());
+    }
+
+    let epsilon = epsilon.clamp(0.0, 1.0);
+    let explore = epsilon / arms.len() as f64;
+    let leader = leader_index(arms);
+
+    arms.iter()
+        .enumerate()
+        .map(|(i, _)| {
+            if i == leader {
+                (1.0 - epsilon) + explore
+            } else {
+                explore
+            }
+        })
+        .collect()
+}
+
+/// Thompson-sampling shares: the fraction of posterior draws in which each
+/// arm had the highest sampled rate.
+pub fn thompson_allocation<R: Rng + ?Sized>(arms: &[ArmCounts], rng: &mut R) -> Vec<f64> {
+    if arms.is_empty() {
+        return Vec::new();
+    }
+
+    let posteriors: Vec<(f64, f64)> = arms.iter().map(ArmCounts::posterior).collect();
+    let mut wins = vec![0usize; arms.len()];
+    for _ in 0..THOMPSON_DRAWS {
+        let mut best = 0;
+        let mut best_draw = f64::MIN;
+        for (i, (alpha, beta)) in posteriors.iter().enumerate() {
+            let draw = sample_beta(rng, *alpha, *beta);
+            if draw > best_draw {
+                best_draw = draw;
+                best = i;
+            }
+        }
+        wins[best] += 1;
+    }
+
+    wins.into_iter()
+        .map(|w| w as f64 / THOMPSON_DRAWS as f64)
+        .collect()
+}
+
+/// Index of the arm with the highest observed rate (first wins on ties).
+pub fn leader_index(arms: &[ArmCounts]) -> usize {
+    let mut best = 0;
+    for (i, arm) in arms.iter().enumerate().skip(1) {
+        if arm.rate() > arms[best].rate() {
+            best = i;
+        }
+    }
+    best
+}
+
+/// Bayesian read-out for one variant, measured against the control
+/// (`arms[0]`). Rates and lift are fractions, not percentages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantPosterior {
+    /// Posterior mean rate.
+    pub mean: f64,
+    /// Equal-tailed credible interval at the requested level.
+    pub credible_interval: (f64, f64),
+    /// P(rate > control rate); `None` for the control itself.
+    pub prob_beat_control: Option<f64>,
+    /// P(this variant has the highest rate of all).
+    pub prob_best: f64,
+    /// Posterior mean of `rate / control rate - 1`; `None` for the control.
+    pub expected_lift: Option<f64>,
+}
+
+/// Summarizes each arm's Beta posterior by Monte Carlo, using the same
+/// uniform prior as Thompson allocation. `credible_level` is the interval
+/// mass (e.g. the test's `confidence`).
+pub fn posterior_summaries<R: Rng + ?Sized>(
+    arms: &[ArmCounts],
+    credible_level: f64,
+    rng: &mut R,
+) -> Vec<VariantPosterior> {
+    if arms.is_empty() {
+        return Vec::new();
+    }
+
+    let posteriors: Vec<(f64, f64)> = arms.iter().map(ArmCounts::posterior).collect();
+    let mut draws: Vec<Vec<f64>> = vec![Vec::with_capacity(THOMPSON_DRAWS); arms.len()];
+    let mut beats_control = vec![0usize; arms.len()];
+    let mut best = vec![0usize; arms.len()];
+    let mut lift_sums = vec![0.0; arms.len()];
+    let mut sample = vec![0.0; arms.len()];
+
+    for _ in 0..THOMPSON_DRAWS {
+        for (i, (alpha, beta)) in posteriors.iter().enumerate() {
+            sample[i] = sample_beta(rng, *alpha, *beta);
+            draws[i].push(sample[i]);
+        }
+        let mut leader = 0;
+        for i in 1..arms.len() {
+            if sample[i] > sample[0] {
+                beats_control[i] += 1;
+            }
+            // The Beta support excludes 0, so the ratio is always finite.
+            lift_sums[i] += sample[i] / sample[0] - 1.0;
+            if sample[i] > sample[leader] {
+                leader = i;
+            }
+        }
+        best[leader] += 1;
+    }
+
+    let tail = (1.0 - credible_level.clamp(0.0, 1.0)) / 2.0;
+    let draws_f = THOMPSON_DRAWS as f64;
+    draws
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut samples)| {
+            samples.sort_by(f64::total_cmp);
+            let quantile =
+                |q: f64| samples[((q * (draws_f - 1.0)).round() as usize).min(samples.len() - 1)];
+            let (alpha, beta) = posteriors[i];
+            VariantPosterior {
+                mean: alpha / (alpha + beta),
+                credible_interval: (quantile(tail), quantile(1.0 - tail)),
+                prob_beat_control: (i > 0).then(|| beats_control[i] as f64 / draws_f),
+                prob_best: best[i] as f64 / draws_f,
+                expected_lift: (i > 0).then(|| lift_sums[i] / draws_f),
+            }
+        })
+        .collect()
+}
+
+/// Deliveries each variant needs for a two-sided two-proportion test to
+/// detect a `relative_lift` over `baseline_rate` (both fractions) with the
+/// given confidence and power. `comparisons` is the number of variants
+/// tested against the control; the significance level is split across them
+/// (Bonferroni) so A/B/n tests aren't judged more leniently than A/B.
+/// Returns `None` for rates outside (0, 1) or a lift that leaves that range.
+pub fn required_sample_per_variant(
+    baseline_rate: f64,
+    relative_lift: f64,
+    confidence: f64,
+    power: f64,
+    comparisons: usize,
+) -> Option<i64> {
+    let p1 = baseline_rate;
+    let p2 = baseline_rate * (1.0 + relative_lift);
+    if !(p1 > 0.0 && p1 < 1.0 && p2 > 0.0 && p2 < 1.0) || p1 == p2 {
+        return None;
+    }
+
+    let alpha = (1.0 - confidence) / comparisons.max(1) as f64;
+    let z_alpha = normal_quantile(1.0 - alpha / 2.0);
+    let z_beta = normal_quantile(power);
+    let pooled = (p1 + p2) / 2.0;
+    let numerator = z_alpha * (2.0 * pooled * (1.0 - pooled)).sqrt()
+        + z_beta * (p1 * (1.0 - p1) + p2 * (1.0 - p2)).sqrt();
+    Some((numerator.powi(2) / (p2 - p1).powi(2)).ceil() as i64)
+}
+
+/// Smallest relative lift `required_sample_per_variant` can detect with
+/// `sample_per_variant` deliveries per variant, to 0.1% precision. `None`
+/// when even doubling the rate would need more.
+pub fn detectable_lift(
+    baseline_rate: f64,
+    sample_per_variant: i64,
+    confidence: f64,
+    power: f64,
+    comparisons: usize,
+) -> Option<f64> {
+    let needs = |lift: f64| {
+        required_sample_per_variant(baseline_rate, lift, confidence, power, comparisons)
+    };
+    // Cap the search where the lifted rate would reach 1.
+    let max_lift = (1.0 / baseline_rate - 1.0).min(1.0) - 1e-9;
+    if sample_per_variant <= 0 || needs(max_lift).is_none_or(|n| n > sample_per_variant) {
+        return None;
+    }
+
+    let (mut low, mut high) = (0.0, max_lift);
+    while high - low > 0.0005 {
+        let mid = (low + high) / 2.0;
+        match needs(mid) {
+            Some(n) if n <= sample_per_variant => high = mid,
+            _ => low = mid,
+        }
+    }
+    Some((high * 1000.0).ceil() / 1000.0)
+}
+
+/// Inverse standard normal CDF (Acklam's rational approximation, relative
+/// error below 1.2e-9).
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.383_577_518_672_69e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Two-proportion z-test of `b` against `a` on their rates. Returns the
+/// z-score (positive when `b` is higher) and the two-tailed p-value;
+/// degenerate inputs (no trials, zero variance) return (0, 1).
+pub fn two_proportion_z_test(a: &ArmCounts, b: &ArmCounts) -> (f64, f64) {
+    if a.trials <= 0 || b.trials <= 0 {
+        return (0.0, 1.0);
+    }
+
+    let successes_a = a.successes.clamp(0, a.trials) as f64;
+    let successes_b = b.successes.clamp(0, b.trials) as f64;
+    let (n_a, n_b) = (a.trials as f64, b.trials as f64);
+    let pooled = (successes_a + successes_b) / (n_a + n_b);
+    let se = (pooled * (1.0 - pooled) * (1.0 / n_a + 1.0 / n_b)).sqrt();
+    if !se.is_finite() || se == 0.0 {
+        return (0.0, 1.0);
+    }
+
+    let z = (b.rate() - a.rate()) / se;
+    (z, 2.0 * (1.0 - normal_cdf(z.abs())))
+}
+
+/// Standard normal CDF.
+pub fn normal_cdf(z: f64) -> f64 {
+    0.5 * (1.0 + erf(z / std::f64::consts::SQRT_2))
+}
+
+/// Abramowitz & Stegun 7.1.26 (max error ~1.5e-7), the same approximation
+/// the JS evaluator uses so both sides agree on borderline results.
+fn erf(x: f64) -> f64 {
+    let sign = x.signum();
+    let x = x.abs();
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    sign * (1.0 - poly * (-x * x).exp())
+}
+
+/// Draws from Beta(alpha, beta) as X / (X + Y) with X ~ Gamma(alpha) and
+/// Y ~ Gamma(beta).
+pub fn sample_beta<R: Rng + ?Sized>(rng: &mut R, alpha: f64, beta: f64) -> f64 {
+    let x = sample_gamma(rng, alpha);
+    let y = sample_gamma(rng, beta);
+    if x + y <= 0.0 {
+        return 0.5;
+    }
+    x / (x + y)
+}
+
+/// Marsaglia–Tsang Gamma(shape, 1) sampler. Shapes below 1 use the standard
+/// boost: Gamma(a) = Gamma(a + 1) * U^(1/a).
+fn sample_gamma<R: Rng + ?Sized>(rng: &mut R, shape: f64) -> f64 {
+    if shape < 1.0 {
+        let u: f64 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+        return sample_gamma(rng, shape + 1.0) * u.powf(1.0 / shape);
+    }
+
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = sample_standard_normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u: f64 = rng.gen();
+        if u < 1.0 - 0.0331 * x.powi(4) || u.ln() < 0.5 * x * x + d * (1.0 - v + v.ln()) {
+            return d * v;
+        }
+    }
+}
+
+/// Box–Muller standard normal draw.
+fn sample_standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1: f64 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn arm(successes: i64, trials: i64) -> ArmCounts {
+        ArmCounts { successes, trials }
+    }
+
+    fn sum(shares: &[f64]) -> f64 {
+        shares.iter().sum()
+    }
+
+    #[test]
+    fn test_two_proportion_z_test() {
+        let (z, p) = two_proportion_z_test(&arm(200, 1000), &arm(260, 1000));
+        assert!(z > 0.0);
+        assert!((z - 3.19).abs() < 0.01, "z = {}", z);
+        assert!(p < 0.01);
+
+        let (z, p) = two_proportion_z_test(&arm(30, 100), &arm(28, 100));
+        assert!(z < 0.0);
+        assert!(p > 0.5);
+
+        assert_eq!(two_proportion_z_test(&arm(0, 0), &arm(5, 10)), (0.0, 1.0));
+        assert_eq!(two_proportion_z_test(&arm(0, 10), &arm(0, 10)), (0.0, 1.0));
+        assert!((normal_cdf(1.959964) - 0.975).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_posterior_summaries_favour_clear_winner() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let summaries = posterior_summaries(
+            &[arm(200, 1000), arm(260, 1000), arm(205, 1000)],
+            0.95,
+            &mut rng,
+        );
+
+        assert_eq!(summaries.len(), 3);
+        assert_eq!(summaries[0].prob_beat_control, None);
+        assert_eq!(summaries[0].expected_lift, None);
+        assert!(summaries[1].prob_beat_control.unwrap() > 0.99);
+        assert!(summaries[1].prob_best > 0.95);
+        let lift = summaries[1].expected_lift.unwrap();
+        assert!((lift - 0.30).abs() < 0.03, "lift = {}", lift);
+        let (low, high) = summaries[1].credible_interval;
+        assert!(low < 0.26 && 0.26 < high && high - low < 0.06);
+        assert!(
+            (sum(&summaries.iter().map(|s| s.prob_best).collect::<Vec<_>>()) - 1.0).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn test_posterior_summaries_stay_uncertain_on_small_samples() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let summaries = posterior_summaries(&[arm(4, 20), arm(6, 20)], 0.95, &mut rng);
+        let p = summaries[1].prob_beat_control.unwrap();
+        assert!(p > 0.6 && p < 0.9, "p = {}", p);
+        let (low, high) = summaries[1].credible_interval;
+        assert!(high - low > 0.3);
+    }
+
+    #[test]
+    fn test_normal_quantile() {
+        assert!((normal_quantile(0.975) - 1.959964).abs() < 1e-5);
+        assert!((normal_quantile(0.8) - 0.841621).abs() < 1e-5);
+        assert!((normal_quantile(0.01) + 2.326348).abs() < 1e-5);
+        assert_eq!(normal_quantile(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_required_sample_per_variant() {
+        // 20% -> 22% at 95% confidence / 80% power is the textbook ~6,500.
+        let n = required_sample_per_variant(0.2, 0.1, 0.95, 0.8, 1).unwrap();
+        assert!((6400..=6600).contains(&n), "n = {}", n);
+        // More comparisons tighten alpha and need more sample.
+        assert!(required_sample_per_variant(0.2, 0.1, 0.95, 0.8, 3).unwrap() > n);
+        assert_eq!(required_sample_per_variant(0.0, 0.1, 0.95, 0.8, 1), None);
+        assert_eq!(required_sample_per_variant(0.6, 1.0, 0.95, 0.8, 1), None);
+    }
+
+    #[test]
+    fn test_detectable_lift_inverts_required_sample() {
+        let lift = detectable_lift(0.2, 6500, 0.95, 0.8, 1).unwrap();
+        assert!((lift - 0.1).abs() < 0.002, "lift = {}", lift);
+        assert_eq!(detectable_lift(0.2, 5, 0.95, 0.8, 1), None);
+    }
+
+    #[test]
+    fn test_fixed_allocation_splits_evenly() {
+        let shares = fixed_allocation(4);
+        assert_eq!(shares, vec![0.25; 4]);
+        assert!(fixed_allocation(0).is_empty());
+    }
+
+    #[test]
+    fn test_epsilon_greedy_favours_leader() {
+        let shares = epsilon_greedy_allocation(&[arm(10, 100), arm(30, 100), arm(20, 100)], 0.3);
+        assert!((shares[1] - 0.8).abs() < 1e-9);
+        assert!((shares[0] - 0.1).abs() < 1e-9);
+        assert!((shares[2] - 0.1).abs() < 1e-9);
+        assert!((sum(&shares) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_epsilon_greedy_without_data_is_fixed() {
+        let shares = epsilon_greedy_allocation(&[arm(0, 0), arm(0, 0), arm(0, 0)], 0.1);
+        assert_eq!(shares, fixed_allocation(3));
+    }
+
+    #[test]
+    fn test_thompson_concentrates_on_clear_winner() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let shares =
+            thompson_allocation(&[arm(100, 1000), arm(200, 1000), arm(110, 1000)], &mut rng);
+        assert!((sum(&shares) - 1.0).abs() < 1e-9);
+        assert!(
+            shares[1] > 0.99,
+            "clear winner should take the batch: {shares:?}"
+        );
+    }
+
+    #[test]
+    fn test_thompson_is_roughly_even_without_data() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let shares = thompson_allocation(&[arm(0, 0), arm(0, 0), arm(0, 0), arm(0, 0)], &mut rng);
+        for share in shares {
+            assert!(
+                (share - 0.25).abs() < 0.03,
+                "share {share} too far from even"
+            );
+        }
+    }
+
+    #[test]
+    fn test_posterior_clamps_overcounted_successes() {
+        assert_eq!(arm(12, 10).posterior(), (11.0, 1.0));
+        assert_eq!(arm(5, 0).posterior(), (1.0, 1.0));
+        assert_eq!(arm(12, 10).rate(), 1.0);
+    }
+
+    #[test]
+    fn test_sample_beta_mean() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let n = 20_000;
+        let mean: f64 = (0..n).map(|_| sample_beta(&mut rng, 2.0, 8.0)).sum::<f64>() / n as f64;
+        assert!((mean - 0.2).abs() < 0.01, "Beta(2, 8) mean was {mean}");
+    }
+}
@@ -0,0 +1,697 @@
+//! Anomaly alerts on issue metrics.
+//!
+//! The evaluate-issue-anomalies Lambda compares each issue, a day after it is
+//! published, with the issues published before it (the same window
+//! `/issues/trends` reads) and records an `alert#<issue>#<metric>` record under
+//! the tenant for every bounce, complaint, open or click rate whose z-score is
+//! past the tenant's threshold. This module lists those alerts, moves them from
+//! `open` to `acknowledged` to `resolved`, and manages the `alert-settings`
+//! record the evaluator reads. Keep the defaults in sync with
+//! functions/utils/issue-anomalies.mjs.
+
+use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use newsletter::admin::{auth, auth::UserContext, aws_clients, error::AppError, response};
+use serde::{Deserialize, Serialize};
+use serde_dynamo::from_item;
+use std::collections::HashMap;
+use std::env;
+
+// ── Constants ──────────────────────────────────────────────────────────
+
+const SETTINGS_SK: &str = "alert-settings";
+const ALERT_SK_PREFIX: &str = "alert#";
+
+const STATUS_OPEN: &str = "open";
+const STATUS_ACKNOWLEDGED: &str = "acknowledged";
+const STATUS_RESOLVED: &str = "resolved";
+const STATUSES: [&str; 3] = [STATUS_OPEN, STATUS_ACKNOWLEDGED, STATUS_RESOLVED];
+
+const METRICS: [&str; 4] = ["bounceRate", "complaintRate", "openRate", "clickRate"];
+
+const DEFAULT_Z_SCORE_THRESHOLD: f64 = 3.0;
+const MIN_Z_SCORE_THRESHOLD: f64 = 1.0;
+const MAX_Z_SCORE_THRESHOLD: f64 = 10.0;
+const DEFAULT_BASELINE_ISSUE_COUNT: u32 = 10;
+const MIN_BASELINE_ISSUE_COUNT: u32 = 3;
+const MAX_BASELINE_ISSUE_COUNT: u32 = 50;
+
+const DEFAULT_LIST_LIMIT: usize = 50;
+const MAX_LIST_LIMIT: usize = 100;
+const NOTE_MAX_LEN: usize = 1000;
+
+// ── Data types ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct AlertSettings {
+    enabled: bool,
+    z_score_threshold: f64,
+    /// How many previously published issues make up the baseline.
+    baseline_issue_count: u32,
+    /// Email the tenant owner when an issue raises alerts.
+    email_notifications: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_by: Option<String>,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        AlertSettings {
+            enabled: true,
+            z_score_threshold: DEFAULT_Z_SCORE_THRESHOLD,
+            baseline_issue_count: DEFAULT_BASELINE_ISSUE_COUNT,
+            email_notifications: false,
+            updated_at: None,
+            updated_by: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateAlertSettingsRequest {
+    enabled: Option<bool>,
+    z_score_threshold: Option<f64>,
+    baseline_issue_count: Option<i64>,
+    email_notifications: Option<bool>,
+}
+
+#[derive(Deserialize, Default)]
+struct AlertActionRequest {
+    note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Alert {
+    alert_id: String,
+    issue_number: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    subject: Option<String>,
+    metric: String,
+    /// `spike` for bounce and complaint rate, `drop` for open and click rate.
+    direction: String,
+    /// The issue's rate, in percent.
+    value: f64,
+    baseline_mean: f64,
+    baseline_std_dev: f64,
+    z_score: f64,
+    /// The tenant's threshold when the alert was raised.
+    threshold: f64,
+    baseline_issues: i64,
+    status: String,
+    created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    acknowledged_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    acknowledged_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resolved_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resolved_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resolution_note: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct AlertCounts {
+    open: usize,
+    acknowledged: usize,
+    resolved: usize,
+}
+
+#[derive(Serialize)]
+struct ListAlertsResponse {
+    alerts: Vec<Alert>,
+    counts: AlertCounts,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AlertAction {
+    Acknowledge,
+    Resolve,
+}
+
+// ── Validation ─────────────────────────────────────────────────────────
+
+/// Merges a PUT body into the current settings and validates the result.
+fn apply_settings_update(
+    current: &AlertSettings,
+    body: &UpdateAlertSettingsRequest,
+) -> Result<AlertSettings, AppError> {
+    let z_score_threshold = match body.z_score_threshold {
+        Some(z)
+            if !z.is_finite() || !(MIN_Z_SCORE_THRESHOLD..=MAX_Z_SCORE_THRESHOLD).contains(&z) =>
+        {
+            return Err(AppError::BadRequest(format!(
+                "zScoreThreshold must be between {} and {}",
+                MIN_Z_SCORE_THRESHOLD, MAX_Z_SCORE_THRESHOLD
+            )));
+        }
+        Some(z) => z,
+        None => current.z_score_threshold,
+    };
+
+    let baseline_issue_count = match body.baseline_issue_count {
+        Some(n)
+            if n < i64::from(MIN_BASELINE_ISSUE_COUNT)
+                || n > i64::from(MAX_BASELINE_ISSUE_COUNT) =>
+        {
+            return Err(AppError::BadRequest(format!(
+                "baselineIssueCount must be between {} and {}",
+                MIN_BASELINE_ISSUE_COUNT, MAX_BASELINE_ISSUE_COUNT
+            )));
+        }
+        Some(n) => n as u32,
+        None => current.baseline_issue_count,
+    };
+
+    Ok(AlertSettings {
+        enabled: body.enabled.unwrap_or(current.enabled),
+        z_score_threshold,
+        baseline_issue_count,
+        email_notifications: body
+            .email_notifications
+            .unwrap_or(current.email_notifications),
+        updated_at: current.updated_at.clone(),
+        updated_by: current.updated_by.clone(),
+    })
+}
+
+/// Splits an alert id (`<issueNumber>-<metric>`) into its parts.
+fn parse_alert_id(alert_id: &str) -> Result<(i32, &str), AppError> {
+    let invalid = || AppError::BadRequest(format!("Invalid alert ID '{}'", alert_id));
+    let (issue_number, metric) = alert_id.split_once('-').ok_or_else(invalid)?;
+    let issue_number = issue_number
+        .parse::<i32>()
+        .ok()
+        .filter(|n| *n > 0)
+        .ok_or_else(invalid)?;
+    if !METRICS.contains(&metric) {
+        return Err(invalid());
+    }
+    Ok((issue_number, metric))
+}
+
+fn alert_sk(issue_number: i32, metric: &str) -> String {
+    format!("{}{:05}#{}", ALERT_SK_PREFIX, issue_number, metric)
+}
+
+/// Acknowledging only applies to open alerts; resolving applies to anything
+/// not already resolved.
+fn check_transition(status: &str, action: AlertAction) -> Result<(), AppError> {
+    match (status, action) {
+        (STATUS_RESOLVED, _) => Err(AppError::Conflict("Alert is already resolved".to_string())),
+        (STATUS_ACKNOWLEDGED, AlertAction::Acknowledge) => Err(AppError::Conflict(
+            "Alert is already acknowledged".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn normalize_note(note: Option<&str>) -> Result<Option<String>, AppError> {
+    match note.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(n) if n.chars().count() > NOTE_MAX_LEN => Err(AppError::BadRequest(format!(
+            "note cannot be longer than {} characters",
+            NOTE_MAX_LEN
+        ))),
+        other => Ok(other.map(str::to_string)),
+    }
+}
+
+fn count_alerts(alerts: &[Alert]) -> AlertCounts {
+    let mut counts = AlertCounts::default();
+    for alert in alerts {
+        match alert.status.as_str() {
+            STATUS_OPEN => counts.open += 1,
+            STATUS_ACKNOWLEDGED => counts.acknowledged += 1,
+            STATUS_RESOLVED => counts.resolved += 1,
+            _ => {}
+        }
+    }
+    counts
+}
+
+// ── Settings endpoints ─────────────────────────────────────────────────
+
+pub async fn get_alert_settings(event: Request) -> Result<Response<Body>, Error> {
+    match handle_get_alert_settings(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_get_alert_settings(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&user_context)?;
+
+    response::format_response(200, load_settings(&tenant_id).await?)
+}
+
+pub async fn update_alert_settings(event: Request) -> Result<Response<Body>, Error> {
+    match handle_update_alert_settings(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_update_alert_settings(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&user_context)?;
+
+    let body: UpdateAlertSettingsRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+
+    let current = load_settings(&tenant_id).await?;
+    let mut settings = apply_settings_update(&current, &body)?;
+    settings.updated_at = Some(chrono::Utc::now().to_rfc3339());
+    settings.updated_by = Some(user_context.email.clone());
+
+    save_settings(&tenant_id, &settings).await?;
+
+    tracing::info!(
+        tenant_id = %tenant_id,
+        enabled = settings.enabled,
+        z_score_threshold = settings.z_score_threshold,
+        "Alert settings updated"
+    );
+
+    response::format_response(200, settings)
+}
+
+// ── Alert endpoints ────────────────────────────────────────────────────
+
+pub async fn list_alerts(event: Request) -> Result<Response<Body>, Error> {
+    match handle_list_alerts(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_list_alerts(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&user_context)?;
+
+    let query_params = event.query_string_parameters();
+    let status = query_params.first("status").map(str::to_string);
+    if let Some(status) = &status {
+        if !STATUSES.contains(&status.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Invalid status. Must be one of: {}",
+                STATUSES.join(", ")
+            )));
+        }
+    }
+    let limit = match query_params.first("limit") {
+        Some(raw) => raw
+            .parse::<usize>()
+            .ok()
+            .filter(|n| (1..=MAX_LIST_LIMIT).contains(n))
+            .ok_or_else(|| {
+                AppError::BadRequest(format!("limit must be between 1 and {}", MAX_LIST_LIMIT))
+            })?,
+        None => DEFAULT_LIST_LIMIT,
+    };
+
+    let all_alerts = query_alerts(&tenant_id).await?;
+    let counts = count_alerts(&all_alerts);
+    let alerts = all_alerts
+        .into_iter()
+        .filter(|alert| status.as_deref().is_none_or(|s| alert.status == s))
+        .take(limit)
+        .collect();
+
+    response::format_response(200, ListAlertsResponse { alerts, counts })
+}
+
+pub async fn acknowledge_alert(
+    event: Request,
+    alert_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_alert_action(event, alert_id, AlertAction::Acknowledge).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+pub async fn resolve_alert(
+    event: Request,
+    alert_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_alert_action(event, alert_id, AlertAction::Resolve).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_alert_action(
+    event: Request,
+    alert_id: Option<String>,
+    action: AlertAction,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&user_context)?;
+
+    let alert_id =
+        alert_id.ok_or_else(|| AppError::BadRequest("Alert ID is required".to_string()))?;
+    let (issue_number, metric) = parse_alert_id(&alert_id)?;
+    let sk = alert_sk(issue_number, metric);
+
+    let body: AlertActionRequest = if event.body().is_empty() {
+        AlertActionRequest::default()
+    } else {
+        serde_json::from_slice(event.body())
+            .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?
+    };
+    let note = match action {
+        AlertAction::Resolve => normalize_note(body.note.as_deref())?,
+        AlertAction::Acknowledge => None,
+    };
+
+    let alert = load_alert(&tenant_id, &sk).await?;
+    check_transition(&alert.status, action)?;
+
+    let updated = update_alert_status(
+        &tenant_id,
+        &sk,
+        &alert.status,
+        action,
+        &user_context.email,
+        note,
+    )
+    .await?;
+
+    tracing::info!(
+        tenant_id = %tenant_id,
+        alert_id = %alert_id,
+        status = %updated.status,
+        "Alert status updated"
+    );
+
+    response::format_response(200, updated)
+}
+
+// ── Persistence ────────────────────────────────────────────────────────
+
+fn require_tenant(user_context: &UserContext) -> Result<String, AppError> {
+    user_context
+        .tenant_id
+        .clone()
+        .ok_or_else(|| AppError::Unauthorized("Tenant access required".to_string()))
+}
+
+fn table_name() -> Result<String, AppError> {
+    env::var("TABLE_NAME").map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))
+}
+
+async fn load_settings(tenant_id: &str) -> Result<AlertSettings, AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let result = client
+        .get_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(tenant_id.to_string()))
+        .key("sk", AttributeValue::S(SETTINGS_SK.to_string()))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to load alert settings: {}", e)))?;
+
+    match result.item {
+        Some(item) => from_item(item).map_err(|e| {
+            AppError::InternalError(format!("Failed to deserialize alert settings: {}", e))
+        }),
+        None => Ok(AlertSettings::default()),
+    }
+}
+
+async fn save_settings(tenant_id: &str, settings: &AlertSettings) -> Result<(), AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let mut item: HashMap<String, AttributeValue> =
+        serde_dynamo::to_item(settings).map_err(|e| {
+            AppError::InternalError(format!("Failed to serialize alert settings: {}", e))
+        })?;
+    item.insert("pk".to_string(), AttributeValue::S(tenant_id.to_string()));
+    item.insert("sk".to_string(), AttributeValue::S(SETTINGS_SK.to_string()));
+
+    client
+        .put_item()
+        .table_name(table_name()?)
+        .set_item(Some(item))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to save alert settings: {}", e)))?;
+
+    Ok(())
+}
+
+/// Every alert of the tenant, newest issue first.
+async fn query_alerts(tenant_id: &str) -> Result<Vec<Alert>, AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let table_name = table_name()?;
+    let mut alerts = Vec::new();
+    let mut exclusive_start_key = None;
+
+    loop {
+        let result = client
+            .query()
+            .table_name(&table_name)
+            .key_condition_expression("pk = :pk AND begins_with(sk, :prefix)")
+            .expression_attribute_values(":pk", AttributeValue::S(tenant_id.to_string()))
+            .expression_attribute_values(":prefix", AttributeValue::S(ALERT_SK_PREFIX.to_string()))
+            .scan_index_forward(false)
+            .set_exclusive_start_key(exclusive_start_key.take())
+            .send()
+            .await
+            .map_err(|e| AppError::AwsError(format!("Failed to query alerts: {}", e)))?;
+
+        for item in result.items() {
+            match from_item::<_, Alert>(item.clone()) {
+                Ok(alert) => alerts.push(alert),
+                Err(e) => tracing::warn!(
+                    tenant_id = %tenant_id,
+                    error = %e,
+                    "Skipping unreadable alert record"
+                ),
+            }
+        }
+
+        match result.last_evaluated_key() {
+            Some(key) if !key.is_empty() => exclusive_start_key = Some(key.clone()),
+            _ => break,
+        }
+    }
+
+    Ok(alerts)
+}
+
+async fn load_alert(tenant_id: &str, sk: &str) -> Result<Alert, AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let result = client
+        .get_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(tenant_id.to_string()))
+        .key("sk", AttributeValue::S(sk.to_string()))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to load alert: {}", e)))?;
+
+    let item = result
+        .item
+        .ok_or_else(|| AppError::NotFound("Alert not found".to_string()))?;
+    from_item(item)
+        .map_err(|e| AppError::InternalError(format!("Failed to deserialize alert: {}", e)))
+}
+
+/// Moves the alert on from `current_status`. The condition on the status
+/// catches a concurrent change between the read and this write.
+async fn update_alert_status(
+    tenant_id: &str,
+    sk: &str,
+    current_status: &str,
+    action: AlertAction,
+    actor: &str,
+    note: Option<String>,
+) -> Result<Alert, AppError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let (status, at_attribute, by_attribute) = match action {
+        AlertAction::Acknowledge => (STATUS_ACKNOWLEDGED, "acknowledgedAt", "acknowledgedBy"),
+        AlertAction::Resolve => (STATUS_RESOLVED, "resolvedAt", "resolvedBy"),
+    };
+
+    let mut update_expression = format!(
+        "SET #status = :status, {} = :now, {} = :actor",
+        at_attribute, by_attribute
+    );
+    if note.is_some() {
+        update_expression.push_str(", resolutionNote = :note");
+    }
+
+    let client = aws_clients::get_dynamodb_client().await;
+    let mut request = client
+        .update_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(tenant_id.to_string()))
+        .key("sk", AttributeValue::S(sk.to_string()))
+        .update_expression(update_expression)
+        .condition_expression("#status = :current")
+        .expression_attribute_names("#status", "status")
+        .expression_attribute_values(":status", AttributeValue::S(status.to_string()))
+        .expression_attribute_values(":current", AttributeValue::S(current_status.to_string()))
+        .expression_attribute_values(":now", AttributeValue::S(now))
+        .expression_attribute_values(":actor", AttributeValue::S(actor.to_string()))
+        .return_values(ReturnValue::AllNew);
+    if let Some(note) = note {
+        request = request.expression_attribute_values(":note", AttributeValue::S(note));
+    }
+
+    let result = request
+        .send()
+        .await
+        .map_err(|e| match e.as_service_error() {
+            Some(UpdateItemError::ConditionalCheckFailedException(_)) => AppError::Conflict(
+                "Alert was updated by someone else; reload and try again".to_string(),
+            ),
+            _ => AppError::AwsError(format!("Failed to update alert: {}", e)),
+        })?;
+
+    let item = result.attributes.ok_or_else(|| {
+        AppError::InternalError("Alert update returned no attributes".to_string())
+    })?;
+    from_item(item)
+        .map_err(|e| AppError::InternalError(format!("Failed to deserialize alert: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(json: serde_json::Value) -> UpdateAlertSettingsRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn alert(issue_number: i32, metric: &str, status: &str) -> Alert {
+        Alert {
+            alert_id: format!("{}-{}", issue_number, metric),
+            issue_number,
+            subject: None,
+            metric: metric.to_string(),
+            direction: "spike".to_string(),
+            value: 0.3,
+            baseline_mean: 0.0,
+            baseline_std_dev: 0.0,
+            z_score: 30.0,
+            threshold: 3.0,
+            baseline_issues: 10,
+            status: status.to_string(),
+            created_at: "2026-10-01T12:15:00Z".to_string(),
+            acknowledged_at: None,
+            acknowledged_by: None,
+            resolved_at: None,
+            resolved_by: None,
+            resolution_note: None,
+        }
+    }
+
+    #[test]
+    fn test_settings_default_fills_missing_fields() {
+        let item: HashMap<String, AttributeValue> =
+            HashMap::from([("emailNotifications".to_string(), AttributeValue::Bool(true))]);
+        let settings: AlertSettings = from_item(item).unwrap();
+
+        assert!(settings.enabled);
+        assert!(settings.email_notifications);
+        assert_eq!(settings.z_score_threshold, DEFAULT_Z_SCORE_THRESHOLD);
+        assert_eq!(settings.baseline_issue_count, DEFAULT_BASELINE_ISSUE_COUNT);
+    }
+
+    #[test]
+    fn test_apply_settings_update_merges_and_validates() {
+        let current = AlertSettings::default();
+
+        let updated = apply_settings_update(
+            &current,
+            &update(serde_json::json!({ "zScoreThreshold": 2.5, "emailNotifications": true })),
+        )
+        .unwrap();
+        assert_eq!(updated.z_score_threshold, 2.5);
+        assert!(updated.email_notifications);
+        assert!(updated.enabled);
+        assert_eq!(updated.baseline_issue_count, DEFAULT_BASELINE_ISSUE_COUNT);
+
+        for body in [
+            serde_json::json!({ "zScoreThreshold": 0.5 }),
+            serde_json::json!({ "zScoreThreshold": 11 }),
+            serde_json::json!({ "baselineIssueCount": 2 }),
+            serde_json::json!({ "baselineIssueCount": 51 }),
+        ] {
+            assert!(matches!(
+                apply_settings_update(&current, &update(body)),
+                Err(AppError::BadRequest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_alert_id() {
+        assert_eq!(
+            parse_alert_id("42-complaintRate").unwrap(),
+            (42, "complaintRate")
+        );
+        assert_eq!(alert_sk(42, "complaintRate"), "alert#00042#complaintRate");
+
+        for invalid in ["42", "0-openRate", "abc-openRate", "42-sends", "42-"] {
+            assert!(matches!(
+                parse_alert_id(invalid),
+                Err(AppError::BadRequest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_check_transition() {
+        assert!(check_transition(STATUS_OPEN, AlertAction::Acknowledge).is_ok());
+        assert!(check_transition(STATUS_OPEN, AlertAction::Resolve).is_ok());
+        assert!(check_transition(STATUS_ACKNOWLEDGED, AlertAction::Resolve).is_ok());
+        assert!(matches!(
+            check_transition(STATUS_ACKNOWLEDGED, AlertAction::Acknowledge),
+            Err(AppError::Conflict(_))
+        ));
+        assert!(matches!(
+            check_transition(STATUS_RESOLVED, AlertAction::Resolve),
+            Err(AppError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn test_count_alerts_by_status() {
+        let alerts = vec![
+            alert(42, "complaintRate", STATUS_OPEN),
+            alert(42, "bounceRate", STATUS_OPEN),
+            alert(41, "openRate", STATUS_ACKNOWLEDGED),
+            alert(38, "clickRate", STATUS_RESOLVED),
+        ];
+
+        assert_eq!(
+            count_alerts(&alerts),
+            AlertCounts {
+                open: 2,
+                acknowledged: 1,
+                resolved: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_normalize_note() {
+        assert_eq!(normalize_note(None).unwrap(), None);
+        assert_eq!(normalize_note(Some("   ")).unwrap(), None);
+        assert_eq!(
+            normalize_note(Some(" Bad list import ")).unwrap(),
+            Some("Bad list import".to_string())
+        );
+        assert!(normalize_note(Some(&"x".repeat(NOTE_MAX_LEN + 1))).is_err());
+    }
+}
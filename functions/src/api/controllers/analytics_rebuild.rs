@@ -0,0 +1,835 @@
+//! Bulk analytics rebuild (`POST /issues/analytics/rebuild`).
+//!
+//! `POST /issues/:id/analytics/rebuild` re-emits `ISSUE_PUBLISHED` for one
+//! issue. This endpoint does the same for every published issue in an
+//! issue-number range or a `publishedAt` window, as a tracked async job: the
+//! job record (`pk = tenant`, `sk = analytics-rebuild#<jobId>`) lists the
+//! selected issues with a per-issue status, and the rebuild-issue-analytics
+//! Lambda walks that list, publishing one event at a time with a pause in
+//! between so the aggregator isn't flooded. Issues already queued are
+//! skipped on every run, so a job that failed part-way (or ran out of Lambda
+//! time) picks up where it stopped when resumed.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{DateTime, Utc};
+use lambda_http::{Body, Error, Request, Response};
+use newsletter::admin::{auth, aws_clients, error::AppError, response};
+use serde::{Deserialize, Serialize};
+use serde_dynamo::from_item;
+use std::collections::HashMap;
+use std::env;
+
+// ── Constants ──────────────────────────────────────────────────────────
+
+const JOB_SK_PREFIX: &str = "analytics-rebuild#";
+
+/// Upper bound on issues per job. The worker publishes about one event per
+/// second, so this keeps a full job inside a single Lambda run.
+const MAX_ISSUES_PER_JOB: usize = 500;
+const JOB_TTL_DAYS: i64 = 30;
+/// A `running` job that hasn't reported progress for this long outlived its
+/// Lambda (15 minute maximum) and may be resumed.
+const STALE_RUNNING_MINUTES: i64 = 20;
+
+const JOB_STATUS_PENDING: &str = "pending";
+const JOB_STATUS_RUNNING: &str = "running";
+const JOB_STATUS_FAILED: &str = "failed";
+const JOB_STATUS_INTERRUPTED: &str = "interrupted";
+
+const ISSUE_STATUS_PENDING: &str = "pending";
+const ISSUE_STATUS_QUEUED: &str = "queued";
+const ISSUE_STATUS_FAILED: &str = "failed";
+
+fn job_sk(job_id: &str) -> String {
+    format!("{}{}", JOB_SK_PREFIX, job_id)
+}
+
+// ── Types ──────────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct StartRebuildRequest {
+    from_issue: Option<i32>,
+    to_issue: Option<i32>,
+    /// Date (YYYY-MM-DD) or RFC 3339 timestamp; inclusive.
+    published_from: Option<String>,
+    /// Date (whole day, UTC) or RFC 3339 timestamp; inclusive.
+    published_to: Option<String>,
+}
+
+/// Which published issues a job covers. Open ends are unbounded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum RebuildScope {
+    #[serde(rename_all = "camelCase")]
+    IssueRange {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from_issue: Option<i32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        to_issue: Option<i32>,
+    },
+    #[serde(rename_all = "camelCase")]
+    PublishedWindow {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        published_from: Option<DateTime<Utc>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        published_to: Option<DateTime<Utc>>,
+    },
+}
+
+/// A published issue as listed from GSI1.
+#[derive(Debug, Clone)]
+struct PublishedIssue {
+    issue_number: i32,
+    subject: String,
+    published_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RebuildIssue {
+    issue_number: i32,
+    subject: String,
+    published_at: String,
+    /// `pending`, `queued` (event published) or `failed`.
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    processed_at: Option<String>,
+}
+
+/// The job record. The worker updates `status`, the per-issue entries,
+/// `startedAt`/`finishedAt` and `updatedAt` in place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RebuildJobRecord {
+    pk: String,
+    sk: String,
+    job_id: String,
+    tenant_id: String,
+    /// Requesting user, stamped on the re-emitted events.
+    user_id: String,
+    /// `pending`, `running`, `completed`, `failed` (some issues failed) or
+    /// `interrupted` (the worker ran out of time).
+    status: String,
+    scope: RebuildScope,
+    issues: Vec<RebuildIssue>,
+    /// Worker runs requested: 1 at creation, +1 per resume.
+    attempts: i64,
+    created_at: String,
+    updated_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    started_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    finished_at: Option<String>,
+    ttl: i64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct RebuildProgress {
+    total: usize,
+    pending: usize,
+    queued: usize,
+    failed: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RebuildJobResponse {
+    job_id: String,
+    status: String,
+    scope: RebuildScope,
+    progress: RebuildProgress,
+    issues: Vec<RebuildIssue>,
+    attempts: i64,
+    created_at: String,
+    updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finished_at: Option<String>,
+}
+
+impl From<RebuildJobRecord> for RebuildJobResponse {
+    fn from(r: RebuildJobRecord) -> Self {
+        RebuildJobResponse {
+            job_id: r.job_id,
+            status: r.status,
+            scope: r.scope,
+            progress: progress(&r.issues),
+            issues: r.issues,
+            attempts: r.attempts,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+            started_at: r.started_at,
+            finished_at: r.finished_at,
+        }
+    }
+}
+
+// ── Public handlers ────────────────────────────────────────────────────
+
+/// POST /issues/analytics/rebuild
+pub async fn start_rebuild(event: Request) -> Result<Response<Body>, Error> {
+    match handle_start_rebuild(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+/// GET /issues/analytics/rebuild/:jobId
+pub async fn get_rebuild_job(
+    event: Request,
+    job_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_get_rebuild_job(event, job_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+/// POST /issues/analytics/rebuild/:jobId/resume
+pub async fn resume_rebuild(
+    event: Request,
+    job_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_resume_rebuild(event, job_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+// ── Internal handlers ──────────────────────────────────────────────────
+
+async fn handle_start_rebuild(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Unauthorized("Tenant access required".to_string()))?;
+
+    let request: StartRebuildRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+    let scope = parse_scope(&request)?;
+
+    let issues = select_issues(list_published_issues(&tenant_id).await?, &scope);
+    if issues.is_empty() {
+        return Err(AppError::BadRequest(
+            "No published issues match the requested range".to_string(),
+        ));
+    }
+    if issues.len() > MAX_ISSUES_PER_JOB {
+        return Err(AppError::BadRequest(format!(
+            "{} issues match; a rebuild covers at most {}. Narrow the range and run several jobs",
+            issues.len(),
+            MAX_ISSUES_PER_JOB
+        )));
+    }
+
+    let now = Utc::now();
+    let job_id = ulid::Ulid::new().to_string();
+    let record = RebuildJobRecord {
+        pk: tenant_id.clone(),
+        sk: job_sk(&job_id),
+        job_id: job_id.clone(),
+        tenant_id: tenant_id.clone(),
+        user_id: user_context.user_id.clone(),
+        status: JOB_STATUS_PENDING.to_string(),
+        scope,
+        issues,
+        attempts: 1,
+        created_at: now.to_rfc3339(),
+        updated_at: now.to_rfc3339(),
+        started_at: None,
+        finished_at: None,
+        ttl: (now + chrono::Duration::days(JOB_TTL_DAYS)).timestamp(),
+    };
+
+    put_job_record(&record, None).await?;
+    invoke_worker(&tenant_id, &job_id).await?;
+
+    tracing::info!(
+        tenant_id = %tenant_id,
+        job_id = %job_id,
+        issues = record.issues.len(),
+        "Analytics rebuild job queued"
+    );
+
+    response::format_response(202, RebuildJobResponse::from(record))
+}
+
+async fn handle_get_rebuild_job(
+    event: Request,
+    job_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Unauthorized("Tenant access required".to_string()))?;
+
+    let job_id = job_id.ok_or_else(|| AppError::BadRequest("Job ID is required".to_string()))?;
+    let record = get_job_record(&tenant_id, &job_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Rebuild job not found".to_string()))?;
+
+    response::format_response(200, RebuildJobResponse::from(record))
+}
+
+async fn handle_resume_rebuild(
+    event: Request,
+    job_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Unauthorized("Tenant access required".to_string()))?;
+
+    let job_id = job_id.ok_or_else(|| AppError::BadRequest("Job ID is required".to_string()))?;
+    let mut record = get_job_record(&tenant_id, &job_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Rebuild job not found".to_string()))?;
+
+    let now = Utc::now();
+    check_resumable(&record, now)?;
+
+    let expected_updated_at = record.updated_at.clone();
+    reset_failed_issues(&mut record.issues);
+    record.status = JOB_STATUS_PENDING.to_string();
+    record.attempts += 1;
+    record.finished_at = None;
+    record.updated_at = now.to_rfc3339();
+    record.ttl = (now + chrono::Duration::days(JOB_TTL_DAYS)).timestamp();
+
+    // Guard on updatedAt so a worker still reporting progress (or a second
+    // resume) can't be overwritten.
+    put_job_record(&record, Some(&expected_updated_at)).await?;
+    invoke_worker(&tenant_id, &job_id).await?;
+
+    response::format_response(202, RebuildJobResponse::from(record))
+}
+
+// ── Validation and selection ───────────────────────────────────────────
+
+fn parse_scope(request: &StartRebuildRequest) -> Result<RebuildScope, AppError> {
+    let has_range = request.from_issue.is_some() || request.to_issue.is_some();
+    let has_window = request.published_from.is_some() || request.published_to.is_some();
+
+    match (has_range, has_window) {
+        (true, true) => Err(AppError::BadRequest(
+            "Provide either fromIssue/toIssue or publishedFrom/publishedTo, not both".to_string(),
+        )),
+        (false, false) => Err(AppError::BadRequest(
+            "Provide an issue range (fromIssue/toIssue) or a publishedAt window (publishedFrom/publishedTo)"
+                .to_string(),
+        )),
+        (true, false) => {
+            if request.from_issue.is_some_and(|n| n < 1) || request.to_issue.is_some_and(|n| n < 1)
+            {
+                return Err(AppError::BadRequest(
+                    "fromIssue and toIssue must be >= 1".to_string(),
+                ));
+            }
+            if let (Some(from), Some(to)) = (request.from_issue, request.to_issue) {
+                if from > to {
+                    return Err(AppError::BadRequest(
+                        "fromIssue must not be greater than toIssue".to_string(),
+                    ));
+                }
+            }
+            Ok(RebuildScope::IssueRange {
+                from_issue: request.from_issue,
+                to_issue: request.to_issue,
+            })
+        }
+        (false, true) => {
+            let from = request
+                .published_from
+                .as_deref()
+                .map(|v| parse_date_bound(v, "publishedFrom", false))
+                .transpose()?;
+            let to = request
+                .published_to
+                .as_deref()
+                .map(|v| parse_date_bound(v, "publishedTo", true))
+                .transpose()?;
+            if let (Some(from), Some(to)) = (from, to) {
+                if from > to {
+                    return Err(AppError::BadRequest(
+                        "publishedFrom must not be after publishedTo".to_string(),
+                    ));
+                }
+            }
+            Ok(RebuildScope::PublishedWindow {
+                published_from: from,
+                published_to: to,
+            })
+        }
+    }
+}
+
+/// Accepts an RFC 3339 timestamp or a plain date. A plain upper bound
+/// covers the whole day (UTC).
+fn parse_date_bound(value: &str, field: &str, end_of_day: bool) -> Result<DateTime<Utc>, AppError> {
+    let value = value.trim();
+    if let Ok(instant) = DateTime::parse_from_rfc3339(value) {
+        return Ok(instant.with_timezone(&Utc));
+    }
+
+    let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        AppError::BadRequest(format!(
+            "{} must be a date (YYYY-MM-DD) or an RFC 3339 timestamp",
+            field
+        ))
+    })?;
+    let time = if end_of_day {
+        chrono::NaiveTime::from_hms_milli_opt(23, 59, 59, 999)
+    } else {
+        chrono::NaiveTime::from_hms_opt(0, 0, 0)
+    }
+    .expect("valid time of day");
+
+    Ok(date.and_time(time).and_utc())
+}
+
+/// Published issues in scope, oldest first, each starting `pending`. Issues
+/// without a parseable `publishedAt` never match a window.
+fn select_issues(issues: Vec<PublishedIssue>, scope: &RebuildScope) -> Vec<RebuildIssue> {
+    let mut selected: Vec<RebuildIssue> = issues
+        .into_iter()
+        .filter(|issue| match scope {
+            RebuildScope::IssueRange {
+                from_issue,
+                to_issue,
+            } => {
+                from_issue.is_none_or(|from| issue.issue_number >= from)
+                    && to_issue.is_none_or(|to| issue.issue_number <= to)
+            }
+            RebuildScope::PublishedWindow {
+                published_from,
+                published_to,
+            } => match DateTime::parse_from_rfc3339(&issue.published_at) {
+                Ok(published) => {
+                    let published = published.with_timezone(&Utc);
+                    published_from.is_none_or(|from| published >= from)
+                        && published_to.is_none_or(|to| published <= to)
+                }
+                Err(_) => false,
+            },
+        })
+        .map(|issue| RebuildIssue {
+            issue_number: issue.issue_number,
+            subject: issue.subject,
+            published_at: issue.published_at,
+            status: ISSUE_STATUS_PENDING.to_string(),
+            error: None,
+            processed_at: None,
+        })
+        .collect();
+
+    selected.sort_by_key(|issue| issue.issue_number);
+    selected
+}
+
+fn progress(issues: &[RebuildIssue]) -> RebuildProgress {
+    let count = |status: &str| issues.iter().filter(|i| i.status == status).count();
+    RebuildProgress {
+        total: issues.len(),
+        pending: count(ISSUE_STATUS_PENDING),
+        queued: count(ISSUE_STATUS_QUEUED),
+        failed: count(ISSUE_STATUS_FAILED),
+    }
+}
+
+/// Only jobs that stopped short can be resumed: `failed`, `interrupted`, or
+/// a `running`/`pending` job whose worker has gone quiet.
+fn check_resumable(record: &RebuildJobRecord, now: DateTime<Utc>) -> Result<(), AppError> {
+    let stale = DateTime::parse_from_rfc3339(&record.updated_at)
+        .map(|updated| {
+            now - updated.with_timezone(&Utc) > chrono::Duration::minutes(STALE_RUNNING_MINUTES)
+        })
+        .unwrap_or(true);
+
+    match record.status.as_str() {
+        JOB_STATUS_FAILED | JOB_STATUS_INTERRUPTED => Ok(()),
+        JOB_STATUS_RUNNING | JOB_STATUS_PENDING if stale => Ok(()),
+        JOB_STATUS_RUNNING | JOB_STATUS_PENDING => Err(AppError::Conflict(
+            "Rebuild job is still in progress".to_string(),
+        )),
+        _ => Err(AppError::Conflict(
+            "Rebuild job has already completed".to_string(),
+        )),
+    }
+}
+
+/// Puts failed issues back in the queue; queued issues are left alone.
+fn reset_failed_issues(issues: &mut [RebuildIssue]) {
+    for issue in issues
+        .iter_mut()
+        .filter(|i| i.status == ISSUE_STATUS_FAILED)
+    {
+        issue.status = ISSUE_STATUS_PENDING.to_string();
+        issue.error = None;
+        issue.processed_at = None;
+    }
+}
+
+// ── Storage ────────────────────────────────────────────────────────────
+
+fn table_name() -> Result<String, AppError> {
+    env::var("TABLE_NAME").map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))
+}
+
+async fn list_published_issues(tenant_id: &str) -> Result<Vec<PublishedIssue>, AppError> {
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let table_name = table_name()?;
+
+    let mut issues = Vec::new();
+    let mut exclusive_start_key = None;
+    loop {
+        let result = ddb_client
+            .query()
+            .table_name(&table_name)
+            .index_name("GSI1")
+            .key_condition_expression("GSI1PK = :gsi1pk")
+            .filter_expression("#status = :published")
+            .expression_attribute_names("#status", "status")
+            .expression_attribute_values(
+                ":gsi1pk",
+                AttributeValue::S(format!("{}#newsletter", tenant_id)),
+            )
+            .expression_attribute_values(":published", AttributeValue::S("published".to_string()))
+            .projection_expression("pk, subject, publishedAt, updatedAt")
+            .set_exclusive_start_key(exclusive_start_key)
+            .send()
+            .await?;
+
+        issues.extend(result.items().iter().filter_map(parse_published_issue));
+
+        exclusive_start_key = result.last_evaluated_key().cloned();
+        if exclusive_start_key.is_none() {
+            break;
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Mirrors the single-issue rebuild: an issue without `publishedAt` falls
+/// back to `updatedAt`.
+fn parse_published_issue(item: &HashMap<String, AttributeValue>) -> Option<PublishedIssue> {
+    let string_attr = |name: &str| item.get(name).and_then(|v| v.as_s().ok()).cloned();
+    Some(PublishedIssue {
+        issue_number: string_attr("pk")?.rsplit('#').next()?.parse().ok()?,
+        subject: string_attr("subject").unwrap_or_default(),
+        published_at: string_attr("publishedAt").or_else(|| string_attr("updatedAt"))?,
+    })
+}
+
+async fn get_job_record(
+    tenant_id: &str,
+    job_id: &str,
+) -> Result<Option<RebuildJobRecord>, AppError> {
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+
+    let result = ddb_client
+        .get_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(tenant_id.to_string()))
+        .key("sk", AttributeValue::S(job_sk(job_id)))
+        .consistent_read(true)
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to get rebuild job: {}", e)))?;
+
+    match result.item {
+        Some(item) => from_item(item).map(Some).map_err(|e| {
+            AppError::InternalError(format!("Failed to deserialize rebuild job: {}", e))
+        }),
+        None => Ok(None),
+    }
+}
+
+/// Writes a new job (`expected_updated_at = None`) or replaces one that
+/// hasn't changed since it was read.
+async fn put_job_record(
+    record: &RebuildJobRecord,
+    expected_updated_at: Option<&str>,
+) -> Result<(), AppError> {
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+
+    let item = serde_dynamo::to_item(record)
+        .map_err(|e| AppError::InternalError(format!("Failed to serialize rebuild job: {}", e)))?;
+
+    let mut request = ddb_client
+        .put_item()
+        .table_name(table_name()?)
+        .set_item(Some(item));
+    request = match expected_updated_at {
+        None => request.condition_expression("attribute_not_exists(pk)"),
+        Some(updated_at) => request
+            .condition_expression("updatedAt = :expected_updated_at")
+            .expression_attribute_values(
+                ":expected_updated_at",
+                AttributeValue::S(updated_at.to_string()),
+            ),
+    };
+
+    request.send().await.map_err(|e| {
+        if e.to_string().contains("ConditionalCheckFailed") {
+            AppError::Conflict("Rebuild job changed while updating; retry".to_string())
+        } else {
+            AppError::AwsError(format!("DynamoDB put failed: {}", e))
+        }
+    })?;
+
+    Ok(())
+}
+
+async fn invoke_worker(tenant_id: &str, job_id: &str) -> Result<(), AppError> {
+    let function_name = env::var("ANALYTICS_REBUILD_FUNCTION_NAME").map_err(|_| {
+        AppError::InternalError("ANALYTICS_REBUILD_FUNCTION_NAME not set".to_string())
+    })?;
+
+    let payload = serde_json::json!({
+        "tenantId": tenant_id,
+        "jobId": job_id
+    });
+
+    aws_clients::get_lambda_client()
+        .await
+        .invoke()
+        .function_name(&function_name)
+        .invocation_type(aws_sdk_lambda::types::InvocationType::Event)
+        .payload(aws_smithy_types::Blob::new(
+            serde_json::to_vec(&payload).map_err(|e| {
+                AppError::InternalError(format!("Failed to serialize payload: {}", e))
+            })?,
+        ))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Lambda invoke error: {}", e)))?;
+
+    Ok(())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn published(issue_number: i32, published_at: &str) -> PublishedIssue {
+        PublishedIssue {
+            issue_number,
+            subject: format!("Issue {}", issue_number),
+            published_at: published_at.to_string(),
+        }
+    }
+
+    fn sample_issues() -> Vec<PublishedIssue> {
+        vec![
+            published(12, "2025-03-10T09:00:00Z"),
+            published(10, "2025-02-24T09:00:00Z"),
+            published(11, "2025-03-03T09:00:00Z"),
+            published(9, "not-a-date"),
+        ]
+    }
+
+    fn record(status: &str, updated_at: &str) -> RebuildJobRecord {
+        RebuildJobRecord {
+            pk: "tenant-1".to_string(),
+            sk: job_sk("job-1"),
+            job_id: "job-1".to_string(),
+            tenant_id: "tenant-1".to_string(),
+            user_id: "user-1".to_string(),
+            status: status.to_string(),
+            scope: RebuildScope::IssueRange {
+                from_issue: Some(10),
+                to_issue: None,
+            },
+            issues: select_issues(
+                sample_issues(),
+                &RebuildScope::IssueRange {
+                    from_issue: Some(10),
+                    to_issue: None,
+                },
+            ),
+            attempts: 1,
+            created_at: "2025-03-11T08:00:00Z".to_string(),
+            updated_at: updated_at.to_string(),
+            started_at: None,
+            finished_at: None,
+            ttl: 0,
+        }
+    }
+
+    fn bad_request(result: Result<RebuildScope, AppError>) -> String {
+        match result {
+            Err(AppError::BadRequest(message)) => message,
+            other => panic!("expected BadRequest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_scope_requires_exactly_one_mode() {
+        let neither = StartRebuildRequest::default();
+        assert!(bad_request(parse_scope(&neither)).contains("issue range"));
+
+        let both = StartRebuildRequest {
+            from_issue: Some(1),
+            published_from: Some("2025-01-01".to_string()),
+            ..Default::default()
+        };
+        assert!(bad_request(parse_scope(&both)).contains("not both"));
+    }
+
+    #[test]
+    fn test_parse_scope_validates_range_and_window() {
+        let reversed = StartRebuildRequest {
+            from_issue: Some(5),
+            to_issue: Some(3),
+            ..Default::default()
+        };
+        assert!(parse_scope(&reversed).is_err());
+
+        let window = StartRebuildRequest {
+            published_from: Some("2025-03-01".to_string()),
+            published_to: Some("2025-03-03".to_string()),
+            ..Default::default()
+        };
+        match parse_scope(&window).unwrap() {
+            RebuildScope::PublishedWindow {
+                published_from,
+                published_to,
+            } => {
+                assert_eq!(
+                    published_from.unwrap().to_rfc3339(),
+                    "2025-03-01T00:00:00+00:00"
+                );
+                assert_eq!(
+                    published_to.unwrap().to_rfc3339(),
+                    "2025-03-03T23:59:59.999+00:00"
+                );
+            }
+            other => panic!("expected window, got {:?}", other),
+        }
+
+        let bad_date = StartRebuildRequest {
+            published_from: Some("March".to_string()),
+            ..Default::default()
+        };
+        assert!(bad_request(parse_scope(&bad_date)).contains("publishedFrom"));
+    }
+
+    #[test]
+    fn test_select_issues_by_range_sorts_oldest_first() {
+        let scope = RebuildScope::IssueRange {
+            from_issue: Some(10),
+            to_issue: Some(11),
+        };
+        let selected = select_issues(sample_issues(), &scope);
+        let numbers: Vec<i32> = selected.iter().map(|i| i.issue_number).collect();
+        assert_eq!(numbers, vec![10, 11]);
+        assert!(selected.iter().all(|i| i.status == ISSUE_STATUS_PENDING));
+    }
+
+    #[test]
+    fn test_select_issues_by_window_skips_unparseable_dates() {
+        let scope = RebuildScope::PublishedWindow {
+            published_from: Some(parse_date_bound("2025-03-01", "from", false).unwrap()),
+            published_to: None,
+        };
+        let numbers: Vec<i32> = select_issues(sample_issues(), &scope)
+            .iter()
+            .map(|i| i.issue_number)
+            .collect();
+        assert_eq!(numbers, vec![11, 12]);
+    }
+
+    #[test]
+    fn test_resume_resets_only_failed_issues() {
+        let mut job = record(JOB_STATUS_FAILED, "2025-03-11T08:05:00Z");
+        job.issues[0].status = ISSUE_STATUS_QUEUED.to_string();
+        job.issues[1].status = ISSUE_STATUS_FAILED.to_string();
+        job.issues[1].error = Some("throttled".to_string());
+
+        reset_failed_issues(&mut job.issues);
+        assert_eq!(job.issues[0].status, ISSUE_STATUS_QUEUED);
+        assert_eq!(job.issues[1].status, ISSUE_STATUS_PENDING);
+        assert_eq!(job.issues[1].error, None);
+        assert_eq!(
+            progress(&job.issues),
+            RebuildProgress {
+                total: 3,
+                pending: 2,
+                queued: 1,
+                failed: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_resumable() {
+        let now = DateTime::parse_from_rfc3339("2025-03-11T09:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert!(check_resumable(&record(JOB_STATUS_FAILED, "2025-03-11T08:59:00Z"), now).is_ok());
+        assert!(
+            check_resumable(&record(JOB_STATUS_INTERRUPTED, "2025-03-11T08:59:00Z"), now).is_ok()
+        );
+        assert!(check_resumable(&record(JOB_STATUS_RUNNING, "2025-03-11T08:59:00Z"), now).is_err());
+        assert!(check_resumable(&record(JOB_STATUS_RUNNING, "2025-03-11T08:00:00Z"), now).is_ok());
+        assert!(check_resumable(&record("completed", "2025-03-11T08:00:00Z"), now).is_err());
+    }
+
+    #[test]
+    fn test_job_record_round_trips_through_dynamo() {
+        let job = record(JOB_STATUS_PENDING, "2025-03-11T08:00:00Z");
+        let item: HashMap<String, AttributeValue> = serde_dynamo::to_item(&job).unwrap();
+        assert_eq!(
+            item.get("sk")
+                .and_then(|v| v.as_s().ok())
+                .map(String::as_str),
+            Some("analytics-rebuild#job-1")
+        );
+        let issues = item.get("issues").and_then(|v| v.as_l().ok()).unwrap();
+        let first = issues[0].as_m().unwrap();
+        assert_eq!(
+            first
+                .get("issueNumber")
+                .and_then(|v| v.as_n().ok())
+                .map(String::as_str),
+            Some("10")
+        );
+        assert_eq!(
+            first
+                .get("status")
+                .and_then(|v| v.as_s().ok())
+                .map(String::as_str),
+            Some("pending")
+        );
+
+        let back: RebuildJobRecord = from_item(item).unwrap();
+        assert_eq!(back.scope, job.scope);
+        assert_eq!(back.issues, job.issues);
+    }
+
+    #[test]
+    fn test_job_response_shape() {
+        let json = serde_json::to_value(RebuildJobResponse::from(record(
+            JOB_STATUS_PENDING,
+            "2025-03-11T08:00:00Z",
+        )))
+        .unwrap();
+        assert_eq!(json["jobId"], "job-1");
+        assert_eq!(json["scope"]["type"], "issueRange");
+        assert_eq!(json["scope"]["fromIssue"], 10);
+        assert!(json["scope"].get("toIssue").is_none());
+        assert_eq!(json["progress"]["total"], 3);
+        assert_eq!(json["issues"][0]["issueNumber"], 10);
+        assert!(json.get("pk").is_none());
+        assert!(json.get("userId").is_none());
+    }
+}
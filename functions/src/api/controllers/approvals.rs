@@ -0,0 +1,1058 @@
+//! Editorial approval for issues.
+//!
+//! A tenant can require sign-off before anything is sent. A draft is submitted
+//! for review (`in_review`), reviewers approve it until the policy's
+//! `requiredApprovals` distinct people have signed off (`approved`), and a
+//! rejection sends it back to `draft` with a comment. While the policy is
+//! enabled the publish workflow refuses to start for an issue that isn't
+//! approved, and changing what an approved (or in-review) issue says drops it
+//! back to `draft`.
+//!
+//! Approval state lives on the issue record in its own attributes
+//! (`approvalStatus`, `approvalRound`, ...) rather than in `status`, which the
+//! stage-issue state machine owns. Every submission, approval, rejection and
+//! reset is kept as an `approval#<ULID>` record under the issue.
+
+use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
+use lambda_http::{Body, Error, Request, Response};
+use newsletter::admin::{auth, auth::UserContext, aws_clients, error::AppError, response};
+use newsletter::senders::validation;
+use serde::{Deserialize, Serialize};
+use serde_dynamo::from_item;
+use std::collections::HashMap;
+use std::env;
+
+// ── Constants ──────────────────────────────────────────────────────────
+
+const POLICY_SK: &str = "approval-policy";
+pub(crate) const APPROVAL_SK_PREFIX: &str = "approval#";
+
+pub(crate) const APPROVAL_IN_REVIEW: &str = "in_review";
+pub(crate) const APPROVAL_APPROVED: &str = "approved";
+/// Reported for issues without an `approvalStatus` attribute.
+const APPROVAL_DRAFT: &str = "draft";
+
+/// Issue attributes that make up the approval state. Removing all of them puts
+/// the issue back to `draft`.
+pub(crate) const APPROVAL_STATE_ATTRIBUTES: [&str; 5] = [
+    "approvalStatus",
+    "approvalRound",
+    "approvalSubmittedBy",
+    "approvalSubmittedAt",
+    "approvalApprovers",
+];
+
+const ACTION_SUBMIT: &str = "submit";
+const ACTION_APPROVE: &str = "approve";
+const ACTION_REJECT: &str = "reject";
+const ACTION_RESET: &str = "reset";
+
+const MAX_APPROVERS: usize = 25;
+const MAX_REQUIRED_APPROVALS: u32 = 10;
+const COMMENT_MAX_LEN: usize = 2000;
+
+// ── Data types ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ApprovalPolicy {
+    enabled: bool,
+    #[serde(rename = "requiredApprovals")]
+    required_approvals: u32,
+    /// Lowercased emails allowed to approve. Empty means any teammate other
+    /// than the submitter.
+    #[serde(default)]
+    approvers: Vec<String>,
+    #[serde(default, rename = "updatedAt", skip_serializing_if = "Option::is_none")]
+    updated_at: Option<String>,
+    #[serde(default, rename = "updatedBy", skip_serializing_if = "Option::is_none")]
+    updated_by: Option<String>,
+}
+
+impl Default for ApprovalPolicy {
+    fn default() -> Self {
+        ApprovalPolicy {
+            enabled: false,
+            required_approvals: 1,
+            approvers: Vec::new(),
+            updated_at: None,
+            updated_by: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct UpdateApprovalPolicyRequest {
+    enabled: Option<bool>,
+    #[serde(rename = "requiredApprovals")]
+    required_approvals: Option<i64>,
+    approvers: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Default)]
+struct ApprovalActionRequest {
+    comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ApprovalActor {
+    #[serde(rename = "userId")]
+    user_id: String,
+    email: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+}
+
+impl ApprovalActor {
+    fn from_context(user_context: &UserContext) -> Self {
+        ApprovalActor {
+            user_id: user_context.user_id.clone(),
+            email: normalize_email(&user_context.email),
+            username: user_context.username.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ApprovalEntry {
+    #[serde(rename = "approvalId")]
+    approval_id: String,
+    action: String,
+    /// The submission this entry belongs to (the submit entry's id).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    round: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    actor: ApprovalActor,
+    #[serde(rename = "createdAt")]
+    created_at: String,
+}
+
+/// The approval attributes of an issue record.
+#[derive(Debug, Clone, Default)]
+struct IssueApprovalState {
+    status: String,
+    approval_status: Option<String>,
+    round: Option<String>,
+    submitted_by: Option<String>,
+    approvers: Vec<String>,
+}
+
+impl IssueApprovalState {
+    fn approval_status(&self) -> &str {
+        self.approval_status.as_deref().unwrap_or(APPROVAL_DRAFT)
+    }
+}
+
+#[derive(Serialize)]
+struct ApprovalProgress {
+    required: u32,
+    received: usize,
+    approvers: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct IssueApprovalResponse {
+    id: String,
+    #[serde(rename = "issueNumber")]
+    issue_number: i32,
+    #[serde(rename = "approvalStatus")]
+    approval_status: String,
+    approvals: ApprovalProgress,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entry: Option<ApprovalEntry>,
+}
+
+#[derive(Serialize)]
+struct IssueApprovalHistoryResponse {
+    id: String,
+    #[serde(rename = "issueNumber")]
+    issue_number: i32,
+    #[serde(rename = "approvalStatus")]
+    approval_status: String,
+    approvals: ApprovalProgress,
+    entries: Vec<ApprovalEntry>,
+}
+
+// ── Validation ─────────────────────────────────────────────────────────
+
+fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+fn normalize_approvers(approvers: &[String]) -> Result<Vec<String>, AppError> {
+    let mut normalized: Vec<String> = Vec::new();
+    for approver in approvers {
+        let email = normalize_email(approver);
+        validation::validate_email(&email)
+            .map_err(|_| AppError::BadRequest(format!("Invalid approver email '{}'", approver)))?;
+        if !normalized.contains(&email) {
+            normalized.push(email);
+        }
+    }
+    if normalized.len() > MAX_APPROVERS {
+        return Err(AppError::BadRequest(format!(
+            "approvers cannot have more than {} entries",
+            MAX_APPROVERS
+        )));
+    }
+    Ok(normalized)
+}
+
+/// Merges a PUT body into the current policy and validates the result.
+fn apply_policy_update(
+    current: &ApprovalPolicy,
+    body: &UpdateApprovalPolicyRequest,
+) -> Result<ApprovalPolicy, AppError> {
+    let approvers = match &body.approvers {
+        Some(approvers) => normalize_approvers(approvers)?,
+        None => current.approvers.clone(),
+    };
+
+    let required_approvals = match body.required_approvals {
+        Some(n) if n < 1 || n > i64::from(MAX_REQUIRED_APPROVALS) => {
+            return Err(AppError::BadRequest(format!(
+                "requiredApprovals must be between 1 and {}",
+                MAX_REQUIRED_APPROVALS
+            )));
+        }
+        Some(n) => n as u32,
+        None => current.required_approvals,
+    };
+
+    if !approvers.is_empty() && required_approvals as usize > approvers.len() {
+        return Err(AppError::BadRequest(
+            "requiredApprovals cannot exceed the number of approvers".to_string(),
+        ));
+    }
+
+    Ok(ApprovalPolicy {
+        enabled: body.enabled.unwrap_or(current.enabled),
+        required_approvals,
+        approvers,
+        updated_at: current.updated_at.clone(),
+        updated_by: current.updated_by.clone(),
+    })
+}
+
+fn normalize_comment(comment: Option<&str>, required: bool) -> Result<Option<String>, AppError> {
+    let comment = comment.map(str::trim).filter(|c| !c.is_empty());
+    match comment {
+        None if required => Err(AppError::BadRequest(
+            "A comment is required when rejecting an issue".to_string(),
+        )),
+        Some(c) if c.chars().count() > COMMENT_MAX_LEN => Err(AppError::BadRequest(format!(
+            "comment cannot be longer than {} characters",
+            COMMENT_MAX_LEN
+        ))),
+        other => Ok(other.map(str::to_string)),
+    }
+}
+
+/// Submitters can't review their own work, and when the policy names
+/// approvers only they can.
+fn check_reviewer(
+    policy: &ApprovalPolicy,
+    state: &IssueApprovalState,
+    email: &str,
+) -> Result<(), AppError> {
+    if state.submitted_by.as_deref() == Some(email) {
+        return Err(AppError::Forbidden(
+            "You cannot review an issue you submitted".to_string(),
+        ));
+    }
+    if !policy.approvers.is_empty() && !policy.approvers.iter().any(|a| a == email) {
+        return Err(AppError::Forbidden(
+            "Only configured approvers can review issues".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Why the publish workflow may not start for an issue, if anything. Resends
+/// of published issues aren't held back: they went out once already.
+fn schedule_block(
+    policy: &ApprovalPolicy,
+    status: &str,
+    approval_status: Option<&str>,
+) -> Option<&'static str> {
+    if !policy.enabled || status == "published" || approval_status == Some(APPROVAL_APPROVED) {
+        return None;
+    }
+    Some("Issue must be approved before it can be scheduled")
+}
+
+// ── Schedule gate (used by issues.rs) ──────────────────────────────────
+
+/// Refuses `action: schedule` on create while the tenant requires approval —
+/// a brand-new issue can't have been reviewed.
+pub(crate) async fn ensure_can_schedule_new_issue(tenant_id: &str) -> Result<(), AppError> {
+    if load_policy(tenant_id).await?.enabled {
+        return Err(AppError::Conflict(
+            "Approval is required before scheduling; save the issue as a draft and submit it for review"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Refuses to start the publish workflow for an issue that isn't approved
+/// while the tenant's approval policy is enabled.
+pub(crate) async fn ensure_issue_approved(
+    tenant_id: &str,
+    issue_number: i32,
+) -> Result<(), AppError> {
+    let policy = load_policy(tenant_id).await?;
+    if !policy.enabled {
+        return Ok(());
+    }
+    let state = load_issue_state(tenant_id, issue_number).await?;
+    match schedule_block(&policy, &state.status, state.approval_status.as_deref()) {
+        Some(message) => Err(AppError::Conflict(message.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// Records that an edit dropped the issue's approval. Best-effort; the update
+/// that caused it has already been saved.
+pub(crate) async fn record_approval_reset(
+    tenant_id: &str,
+    issue_number: i32,
+    user_id: &str,
+    email: &str,
+    username: Option<&str>,
+) {
+    let entry = ApprovalEntry {
+        approval_id: ulid::Ulid::new().to_string(),
+        action: ACTION_RESET.to_string(),
+        round: None,
+        comment: Some("Content changed after submission".to_string()),
+        actor: ApprovalActor {
+            user_id: user_id.to_string(),
+            email: normalize_email(email),
+            username: username.map(str::to_string),
+        },
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    if let Err(e) = put_entry(tenant_id, issue_number, &entry).await {
+        tracing::warn!(
+            tenant_id = %tenant_id,
+            issue_number,
+            error = ?e,
+            "Failed to record approval reset"
+        );
+    }
+}
+
+// ── Policy endpoints ───────────────────────────────────────────────────
+
+pub async fn get_approval_policy(event: Request) -> Result<Response<Body>, Error> {
+    match handle_get_approval_policy(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_get_approval_policy(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&user_context)?;
+
+    response::format_response(200, load_policy(&tenant_id).await?)
+}
+
+pub async fn update_approval_policy(event: Request) -> Result<Response<Body>, Error> {
+    match handle_update_approval_policy(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_update_approval_policy(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&user_context)?;
+
+    let body: UpdateApprovalPolicyRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+
+    let current = load_policy(&tenant_id).await?;
+    let mut policy = apply_policy_update(&current, &body)?;
+    policy.updated_at = Some(chrono::Utc::now().to_rfc3339());
+    policy.updated_by = Some(user_context.email.clone());
+
+    save_policy(&tenant_id, &policy).await?;
+
+    tracing::info!(
+        tenant_id = %tenant_id,
+        enabled = policy.enabled,
+        required_approvals = policy.required_approvals,
+        "Approval policy updated"
+    );
+
+    response::format_response(200, policy)
+}
+
+// ── Issue endpoints ────────────────────────────────────────────────────
+
+pub async fn submit_issue_for_review(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_submit_issue_for_review(event, issue_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_submit_issue_for_review(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let (user_context, tenant_id, issue_number) = require_reviewer_context(&event, issue_id)?;
+    let body = parse_action_body(&event)?;
+    let comment = normalize_comment(body.comment.as_deref(), false)?;
+
+    let state = load_issue_state(&tenant_id, issue_number).await?;
+    if !matches!(state.status.as_str(), "draft" | "failed") {
+        return Err(AppError::Conflict(format!(
+            "Cannot submit issue with status '{}' for review",
+            state.status
+        )));
+    }
+    if state.approval_status.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Issue is already {}",
+            state.approval_status()
+        )));
+    }
+
+    // The submit entry's id names the round its approvals belong to.
+    let round = ulid::Ulid::new().to_string();
+    let entry = ApprovalEntry {
+        approval_id: round.clone(),
+        action: ACTION_SUBMIT.to_string(),
+        round: Some(round.clone()),
+        comment,
+        actor: ApprovalActor::from_context(&user_context),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let client = aws_clients::get_dynamodb_client().await;
+    client
+        .update_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(issue_pk(&tenant_id, issue_number)))
+        .key("sk", AttributeValue::S("newsletter".to_string()))
+        .update_expression(
+            "SET approvalStatus = :in_review, approvalRound = :round, \
+             approvalSubmittedBy = :email, approvalSubmittedAt = :now \
+             REMOVE approvalApprovers",
+        )
+        .condition_expression("attribute_exists(pk) AND attribute_not_exists(approvalStatus)")
+        .expression_attribute_values(":in_review", AttributeValue::S(APPROVAL_IN_REVIEW.into()))
+        .expression_attribute_values(":round", AttributeValue::S(round))
+        .expression_attribute_values(":email", AttributeValue::S(entry.actor.email.clone()))
+        .expression_attribute_values(":now", AttributeValue::S(entry.created_at.clone()))
+        .send()
+        .await
+        .map_err(|e| conflict_or_aws(e, "Issue approval state changed; reload and try again"))?;
+
+    put_entry(&tenant_id, issue_number, &entry).await?;
+
+    let policy = load_policy(&tenant_id).await?;
+    response::format_response(
+        200,
+        IssueApprovalResponse {
+            id: issue_number.to_string(),
+            issue_number,
+            approval_status: APPROVAL_IN_REVIEW.to_string(),
+            approvals: progress(&policy, &[]),
+            entry: Some(entry),
+        },
+    )
+}
+
+pub async fn approve_issue(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_approve_issue(event, issue_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_approve_issue(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let (user_context, tenant_id, issue_number) = require_reviewer_context(&event, issue_id)?;
+    let body = parse_action_body(&event)?;
+    let comment = normalize_comment(body.comment.as_deref(), false)?;
+
+    let policy = load_policy(&tenant_id).await?;
+    let state = load_issue_state(&tenant_id, issue_number).await?;
+    if state.approval_status.as_deref() != Some(APPROVAL_IN_REVIEW) {
+        return Err(AppError::Conflict(format!(
+            "Issue is {}, not in review",
+            state.approval_status()
+        )));
+    }
+
+    let actor = ApprovalActor::from_context(&user_context);
+    check_reviewer(&policy, &state, &actor.email)?;
+    if state.approvers.contains(&actor.email) {
+        return Err(AppError::Conflict(
+            "You have already approved this issue".to_string(),
+        ));
+    }
+    let round = state.round.clone().unwrap_or_default();
+
+    let client = aws_clients::get_dynamodb_client().await;
+    let table_name = table_name()?;
+    let pk = issue_pk(&tenant_id, issue_number);
+
+    let result = client
+        .update_item()
+        .table_name(&table_name)
+        .key("pk", AttributeValue::S(pk.clone()))
+        .key("sk", AttributeValue::S("newsletter".to_string()))
+        .update_expression("ADD approvalApprovers :approver")
+        .condition_expression(
+            "approvalStatus = :in_review AND approvalRound = :round \
+             AND NOT contains(approvalApprovers, :email)",
+        )
+        .expression_attribute_values(":approver", AttributeValue::Ss(vec![actor.email.clone()]))
+        .expression_attribute_values(":email", AttributeValue::S(actor.email.clone()))
+        .expression_attribute_values(":in_review", AttributeValue::S(APPROVAL_IN_REVIEW.into()))
+        .expression_attribute_values(":round", AttributeValue::S(round.clone()))
+        .return_values(ReturnValue::AllNew)
+        .send()
+        .await
+        .map_err(|e| conflict_or_aws(e, "Issue approval state changed; reload and try again"))?;
+
+    let approvers = result
+        .attributes()
+        .map(|item| parse_issue_state(item).approvers)
+        .unwrap_or_default();
+
+    // Reading the set back (rather than counting before the ADD) means two
+    // concurrent final approvals can't both see one short of the threshold.
+    let approval_status = if approvers.len() >= policy.required_approvals as usize {
+        client
+            .update_item()
+            .table_name(&table_name)
+            .key("pk", AttributeValue::S(pk))
+            .key("sk", AttributeValue::S("newsletter".to_string()))
+            .update_expression("SET approvalStatus = :approved")
+            .condition_expression("approvalRound = :round")
+            .expression_attribute_values(":approved", AttributeValue::S(APPROVAL_APPROVED.into()))
+            .expression_attribute_values(":round", AttributeValue::S(round.clone()))
+            .send()
+            .await
+            .map_err(|e| {
+                conflict_or_aws(e, "Issue approval state changed; reload and try again")
+            })?;
+        APPROVAL_APPROVED
+    } else {
+        APPROVAL_IN_REVIEW
+    };
+
+    let entry = ApprovalEntry {
+        approval_id: ulid::Ulid::new().to_string(),
+        action: ACTION_APPROVE.to_string(),
+        round: Some(round),
+        comment,
+        actor,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    put_entry(&tenant_id, issue_number, &entry).await?;
+
+    response::format_response(
+        200,
+        IssueApprovalResponse {
+            id: issue_number.to_string(),
+            issue_number,
+            approval_status: approval_status.to_string(),
+            approvals: progress(&policy, &approvers),
+            entry: Some(entry),
+        },
+    )
+}
+
+pub async fn reject_issue(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_reject_issue(event, issue_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_reject_issue(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let (user_context, tenant_id, issue_number) = require_reviewer_context(&event, issue_id)?;
+    let body = parse_action_body(&event)?;
+    let comment = normalize_comment(body.comment.as_deref(), true)?;
+
+    let policy = load_policy(&tenant_id).await?;
+    let state = load_issue_state(&tenant_id, issue_number).await?;
+    if state.approval_status.is_none() {
+        return Err(AppError::Conflict(
+            "Issue has not been submitted for review".to_string(),
+        ));
+    }
+    // Once the send is queued the approval is spent; cancel it first.
+    if !matches!(state.status.as_str(), "draft" | "failed") {
+        return Err(AppError::Conflict(format!(
+            "Cannot reject issue with status '{}'",
+            state.status
+        )));
+    }
+
+    let actor = ApprovalActor::from_context(&user_context);
+    check_reviewer(&policy, &state, &actor.email)?;
+    let round = state.round.clone().unwrap_or_default();
+
+    let client = aws_clients::get_dynamodb_client().await;
+    client
+        .update_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(issue_pk(&tenant_id, issue_number)))
+        .key("sk", AttributeValue::S("newsletter".to_string()))
+        .update_expression(format!("REMOVE {}", APPROVAL_STATE_ATTRIBUTES.join(", ")))
+        .condition_expression("approvalRound = :round")
+        .expression_attribute_values(":round", AttributeValue::S(round.clone()))
+        .send()
+        .await
+        .map_err(|e| conflict_or_aws(e, "Issue approval state changed; reload and try again"))?;
+
+    let entry = ApprovalEntry {
+        approval_id: ulid::Ulid::new().to_string(),
+        action: ACTION_REJECT.to_string(),
+        round: Some(round),
+        comment,
+        actor,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    put_entry(&tenant_id, issue_number, &entry).await?;
+
+    response::format_response(
+        200,
+        IssueApprovalResponse {
+            id: issue_number.to_string(),
+            issue_number,
+            approval_status: APPROVAL_DRAFT.to_string(),
+            approvals: progress(&policy, &[]),
+            entry: Some(entry),
+        },
+    )
+}
+
+pub async fn list_issue_approvals(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_list_issue_approvals(event, issue_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_list_issue_approvals(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&user_context)?;
+    let issue_number = parse_issue_number(issue_id)?;
+
+    let policy = load_policy(&tenant_id).await?;
+    let state = load_issue_state(&tenant_id, issue_number).await?;
+
+    let client = aws_clients::get_dynamodb_client().await;
+    let table_name = table_name()?;
+    let mut entries = Vec::new();
+    let mut last_key = None;
+    loop {
+        let result = client
+            .query()
+            .table_name(&table_name)
+            .key_condition_expression("pk = :pk AND begins_with(sk, :prefix)")
+            .expression_attribute_values(
+                ":pk",
+                AttributeValue::S(issue_pk(&tenant_id, issue_number)),
+            )
+            .expression_attribute_values(
+                ":prefix",
+                AttributeValue::S(APPROVAL_SK_PREFIX.to_string()),
+            )
+            .set_exclusive_start_key(last_key)
+            .send()
+            .await
+            .map_err(|e| AppError::AwsError(format!("Failed to query approvals: {}", e)))?;
+
+        entries.extend(result.items().iter().filter_map(|item| {
+            from_item::<_, ApprovalEntry>(item.clone())
+                .map_err(|e| tracing::error!("Failed to deserialize approval entry: {}", e))
+                .ok()
+        }));
+
+        last_key = result.last_evaluated_key().cloned();
+        if last_key.is_none() {
+            break;
+        }
+    }
+
+    response::format_response(
+        200,
+        IssueApprovalHistoryResponse {
+            id: issue_number.to_string(),
+            issue_number,
+            approval_status: state.approval_status().to_string(),
+            approvals: progress(&policy, &state.approvers),
+            entries,
+        },
+    )
+}
+
+// ── Helpers ────────────────────────────────────────────────────────────
+
+fn require_tenant(user_context: &UserContext) -> Result<String, AppError> {
+    user_context
+        .tenant_id
+        .clone()
+        .ok_or_else(|| AppError::Unauthorized("Tenant access required".to_string()))
+}
+
+/// Reviews are attributed by email, so every review action needs one.
+fn require_reviewer_context(
+    event: &Request,
+    issue_id: Option<String>,
+) -> Result<(UserContext, String, i32), AppError> {
+    let user_context = auth::get_user_context(event)?;
+    let tenant_id = require_tenant(&user_context)?;
+    if user_context.email.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "Email is required to review issues".to_string(),
+        ));
+    }
+    let issue_number = parse_issue_number(issue_id)?;
+    Ok((user_context, tenant_id, issue_number))
+}
+
+fn parse_issue_number(issue_id: Option<String>) -> Result<i32, AppError> {
+    issue_id
+        .ok_or_else(|| AppError::BadRequest("Issue ID is required".to_string()))?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid issue ID format".to_string()))
+}
+
+/// An empty body is the same as `{}`.
+fn parse_action_body(event: &Request) -> Result<ApprovalActionRequest, AppError> {
+    if event.body().is_empty() {
+        return Ok(ApprovalActionRequest::default());
+    }
+    serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))
+}
+
+fn progress(policy: &ApprovalPolicy, approvers: &[String]) -> ApprovalProgress {
+    ApprovalProgress {
+        required: policy.required_approvals,
+        received: approvers.len(),
+        approvers: approvers.to_vec(),
+    }
+}
+
+fn issue_pk(tenant_id: &str, issue_number: i32) -> String {
+    format!("{}#{}", tenant_id, issue_number)
+}
+
+fn table_name() -> Result<String, AppError> {
+    env::var("TABLE_NAME").map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))
+}
+
+fn conflict_or_aws(
+    err: aws_sdk_dynamodb::error::SdkError<UpdateItemError>,
+    conflict_message: &str,
+) -> AppError {
+    match err.as_service_error() {
+        Some(UpdateItemError::ConditionalCheckFailedException(_)) => {
+            AppError::Conflict(conflict_message.to_string())
+        }
+        _ => AppError::AwsError(format!("Failed to update issue approval: {}", err)),
+    }
+}
+
+fn parse_issue_state(item: &HashMap<String, AttributeValue>) -> IssueApprovalState {
+    let text = |key: &str| {
+        item.get(key)
+            .and_then(|v| v.as_s().ok())
+            .map(|s| s.to_string())
+    };
+    let mut approvers = item
+        .get("approvalApprovers")
+        .and_then(|v| v.as_ss().ok())
+        .cloned()
+        .unwrap_or_default();
+    approvers.sort();
+
+    IssueApprovalState {
+        status: text("status").unwrap_or_default(),
+        approval_status: text("approvalStatus"),
+        round: text("approvalRound"),
+        submitted_by: text("approvalSubmittedBy"),
+        approvers,
+    }
+}
+
+async fn load_issue_state(
+    tenant_id: &str,
+    issue_number: i32,
+) -> Result<IssueApprovalState, AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let result = client
+        .get_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(issue_pk(tenant_id, issue_number)))
+        .key("sk", AttributeValue::S("newsletter".to_string()))
+        .projection_expression(
+            "#status, approvalStatus, approvalRound, approvalSubmittedBy, approvalApprovers",
+        )
+        .expression_attribute_names("#status", "status")
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to load issue: {}", e)))?;
+
+    result
+        .item()
+        .map(parse_issue_state)
+        .ok_or_else(|| AppError::NotFound("Issue not found".to_string()))
+}
+
+pub(crate) async fn load_policy(tenant_id: &str) -> Result<ApprovalPolicy, AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let result = client
+        .get_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(tenant_id.to_string()))
+        .key("sk", AttributeValue::S(POLICY_SK.to_string()))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to load approval policy: {}", e)))?;
+
+    match result.item {
+        Some(item) => from_item(item).map_err(|e| {
+            AppError::InternalError(format!("Failed to deserialize approval policy: {}", e))
+        }),
+        None => Ok(ApprovalPolicy::default()),
+    }
+}
+
+async fn save_policy(tenant_id: &str, policy: &ApprovalPolicy) -> Result<(), AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let mut item: HashMap<String, AttributeValue> = serde_dynamo::to_item(policy).map_err(|e| {
+        AppError::InternalError(format!("Failed to serialize approval policy: {}", e))
+    })?;
+    item.insert("pk".to_string(), AttributeValue::S(tenant_id.to_string()));
+    item.insert("sk".to_string(), AttributeValue::S(POLICY_SK.to_string()));
+
+    client
+        .put_item()
+        .table_name(table_name()?)
+        .set_item(Some(item))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to save approval policy: {}", e)))?;
+
+    Ok(())
+}
+
+async fn put_entry(
+    tenant_id: &str,
+    issue_number: i32,
+    entry: &ApprovalEntry,
+) -> Result<(), AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let mut item: HashMap<String, AttributeValue> = serde_dynamo::to_item(entry).map_err(|e| {
+        AppError::InternalError(format!("Failed to serialize approval entry: {}", e))
+    })?;
+    item.insert(
+        "pk".to_string(),
+        AttributeValue::S(issue_pk(tenant_id, issue_number)),
+    );
+    item.insert(
+        "sk".to_string(),
+        AttributeValue::S(format!("{}{}", APPROVAL_SK_PREFIX, entry.approval_id)),
+    );
+
+    client
+        .put_item()
+        .table_name(table_name()?)
+        .set_item(Some(item))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to record approval: {}", e)))?;
+
+    Ok(())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(enabled: bool, required: u32, approvers: &[&str]) -> ApprovalPolicy {
+        ApprovalPolicy {
+            enabled,
+            required_approvals: required,
+            approvers: approvers.iter().map(|a| a.to_string()).collect(),
+            ..ApprovalPolicy::default()
+        }
+    }
+
+    fn update(
+        enabled: Option<bool>,
+        required: Option<i64>,
+        approvers: Option<&[&str]>,
+    ) -> UpdateApprovalPolicyRequest {
+        UpdateApprovalPolicyRequest {
+            enabled,
+            required_approvals: required,
+            approvers: approvers.map(|list| list.iter().map(|a| a.to_string()).collect()),
+        }
+    }
+
+    fn in_review(submitted_by: &str) -> IssueApprovalState {
+        IssueApprovalState {
+            status: "draft".to_string(),
+            approval_status: Some(APPROVAL_IN_REVIEW.to_string()),
+            round: Some("01ROUND".to_string()),
+            submitted_by: Some(submitted_by.to_string()),
+            approvers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_policy_update_normalizes_and_merges() {
+        let current = policy(false, 1, &[]);
+        let updated = apply_policy_update(
+            &current,
+            &update(
+                Some(true),
+                Some(2),
+                Some(&[
+                    " Editor@Example.com",
+                    "editor@example.com",
+                    "chief@example.com",
+                ]),
+            ),
+        )
+        .unwrap();
+
+        assert!(updated.enabled);
+        assert_eq!(updated.required_approvals, 2);
+        assert_eq!(
+            updated.approvers,
+            vec!["editor@example.com", "chief@example.com"]
+        );
+
+        // Omitted fields keep their current values.
+        let toggled = apply_policy_update(&updated, &update(Some(false), None, None)).unwrap();
+        assert!(!toggled.enabled);
+        assert_eq!(toggled.required_approvals, 2);
+        assert_eq!(toggled.approvers.len(), 2);
+    }
+
+    #[test]
+    fn test_policy_update_rejects_invalid_values() {
+        let current = ApprovalPolicy::default();
+
+        assert!(apply_policy_update(&current, &update(None, Some(0), None)).is_err());
+        assert!(apply_policy_update(&current, &update(None, Some(11), None)).is_err());
+        assert!(
+            apply_policy_update(&current, &update(None, None, Some(&["not-an-email"]))).is_err()
+        );
+        // More approvals required than there are approvers.
+        assert!(apply_policy_update(
+            &current,
+            &update(None, Some(3), Some(&["a@example.com", "b@example.com"]))
+        )
+        .is_err());
+        // Without an approver list any teammate can approve.
+        assert!(apply_policy_update(&current, &update(None, Some(3), None)).is_ok());
+    }
+
+    #[test]
+    fn test_reject_requires_comment() {
+        assert!(normalize_comment(None, true).is_err());
+        assert!(normalize_comment(Some("   "), true).is_err());
+        assert_eq!(
+            normalize_comment(Some("  Needs a new intro "), true).unwrap(),
+            Some("Needs a new intro".to_string())
+        );
+        assert_eq!(normalize_comment(Some(" "), false).unwrap(), None);
+        assert!(normalize_comment(Some(&"x".repeat(COMMENT_MAX_LEN + 1)), false).is_err());
+    }
+
+    #[test]
+    fn test_check_reviewer() {
+        let state = in_review("author@example.com");
+
+        let open = policy(true, 1, &[]);
+        assert!(check_reviewer(&open, &state, "editor@example.com").is_ok());
+        assert!(matches!(
+            check_reviewer(&open, &state, "author@example.com"),
+            Err(AppError::Forbidden(_))
+        ));
+
+        let named = policy(true, 1, &["chief@example.com"]);
+        assert!(check_reviewer(&named, &state, "chief@example.com").is_ok());
+        assert!(matches!(
+            check_reviewer(&named, &state, "editor@example.com"),
+            Err(AppError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn test_schedule_block() {
+        let enabled = policy(true, 1, &[]);
+        let disabled = policy(false, 1, &[]);
+
+        assert!(schedule_block(&disabled, "draft", None).is_none());
+        assert!(schedule_block(&enabled, "draft", None).is_some());
+        assert!(schedule_block(&enabled, "draft", Some(APPROVAL_IN_REVIEW)).is_some());
+        assert!(schedule_block(&enabled, "draft", Some(APPROVAL_APPROVED)).is_none());
+        // Resending an already-published issue isn't held back.
+        assert!(schedule_block(&enabled, "published", None).is_none());
+    }
+
+    #[test]
+    fn test_parse_issue_state() {
+        let mut item = HashMap::new();
+        item.insert("status".to_string(), AttributeValue::S("draft".to_string()));
+        item.insert(
+            "approvalStatus".to_string(),
+            AttributeValue::S(APPROVAL_IN_REVIEW.to_string()),
+        );
+        item.insert(
+            "approvalApprovers".to_string(),
+            AttributeValue::Ss(vec![
+                "b@example.com".to_string(),
+                "a@example.com".to_string(),
+            ]),
+        );
+
+        let state = parse_issue_state(&item);
+        assert_eq!(state.approval_status(), APPROVAL_IN_REVIEW);
+        assert_eq!(state.approvers, vec!["a@example.com", "b@example.com"]);
+        assert!(state.round.is_none());
+
+        let draft = parse_issue_state(&HashMap::new());
+        assert_eq!(draft.approval_status(), APPROVAL_DRAFT);
+    }
+}
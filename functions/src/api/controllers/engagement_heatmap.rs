@@ -0,0 +1,420 @@
+//! Tenant engagement heatmap (`GET /subscribers/engagement-heatmap`).
+//!
+//! Every open and click is appended to the subscriber's rolling
+//! `recentActivity` list (utils/activity-timeline.mjs, newest 20 entries,
+//! each with a UTC `ts`). The per-subscriber `openHours` histogram only keeps
+//! the UTC hour, so the weekday × hour matrix is built from those timestamps
+//! instead, converted either into one zone for the whole tenant or into each
+//! subscriber's confirmed `timeZone`. Weekday 0 is Monday.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use chrono_tz::Tz;
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use newsletter::admin::{auth, error::AppError, response};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+use crate::controllers::segments;
+
+/// Slots returned as send-time candidates.
+const TOP_SLOT_COUNT: usize = 3;
+
+// ── Types ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeatmapMode {
+    /// Every event converted into a single zone.
+    Tenant,
+    /// Each subscriber's events converted into their own `timeZone`.
+    Subscriber,
+}
+
+impl HeatmapMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            HeatmapMode::Tenant => "tenant",
+            HeatmapMode::Subscriber => "subscriber",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ActivityKind {
+    Open,
+    Click,
+}
+
+struct HeatmapQuery {
+    mode: HeatmapMode,
+    time_zone: Tz,
+    segment_id: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Heatmap {
+    opens: [[i64; 24]; 7],
+    clicks: [[i64; 24]; 7],
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct HeatmapSlot {
+    weekday: u32,
+    hour: u32,
+    opens: i64,
+    clicks: i64,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct EngagementHeatmapResponse {
+    mode: &'static str,
+    /// Zone used in tenant mode, and for subscribers without a usable
+    /// `timeZone` in subscriber mode.
+    time_zone: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    segment_id: Option<String>,
+    /// `opens[weekday][hour]`, weekday 0 = Monday.
+    opens: [[i64; 24]; 7],
+    /// `clicks[weekday][hour]`, weekday 0 = Monday.
+    clicks: [[i64; 24]; 7],
+    top_slots: Vec<HeatmapSlot>,
+    subscribers_counted: i64,
+    /// Subscribers placed in the fallback zone in subscriber mode.
+    subscribers_without_time_zone: i64,
+    events_counted: i64,
+}
+
+// ── Public endpoint handlers ───────────────────────────────────────────
+
+/// GET /subscribers/engagement-heatmap
+pub async fn get_engagement_heatmap(event: Request) -> Result<Response<Body>, Error> {
+    match handle_get_engagement_heatmap(event).await {
+        Ok(resp) => Ok(resp),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+// ── Internal handlers ──────────────────────────────────────────────────
+
+async fn handle_get_engagement_heatmap(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .clone()
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
+
+    let query = parse_heatmap_query_params(&event, user_context.time_zone.as_deref())?;
+
+    let members = match &query.segment_id {
+        Some(segment_id) => {
+            let missing =
+                segments::find_missing_segments(&tenant_id, std::slice::from_ref(segment_id))
+                    .await?;
+            if !missing.is_empty() {
+                return Err(AppError::NotFound("Segment not found".to_string()));
+            }
+            Some(segments::list_segment_member_emails(&tenant_id, segment_id).await?)
+        }
+        None => None,
+    };
+
+    let mut heatmap = Heatmap::default();
+    let mut subscribers_counted: i64 = 0;
+    let mut subscribers_without_time_zone: i64 = 0;
+    let mut events_counted: i64 = 0;
+
+    segments::visit_subscribers(&tenant_id, "email, timeZone, recentActivity", |item| {
+        if !is_member(item, members.as_ref()) {
+            return;
+        }
+
+        let zone = match query.mode {
+            HeatmapMode::Tenant => query.time_zone,
+            HeatmapMode::Subscriber => match subscriber_time_zone(item) {
+                Some(zone) => zone,
+                None => {
+                    subscribers_without_time_zone += 1;
+                    query.time_zone
+                }
+            },
+        };
+
+        let activity = parse_recent_activity(item);
+        subscribers_counted += 1;
+        events_counted += activity.len() as i64;
+        for (at, kind) in activity {
+            heatmap.record(at, kind, zone);
+        }
+    })
+    .await?;
+
+    let top_slots = heatmap.top_slots(TOP_SLOT_COUNT);
+
+    response::format_response(
+        200,
+        EngagementHeatmapResponse {
+            mode: query.mode.as_str(),
+            time_zone: query.time_zone.name().to_string(),
+            segment_id: query.segment_id,
+            opens: heatmap.opens,
+            clicks: heatmap.clicks,
+            top_slots,
+            subscribers_counted,
+            subscribers_without_time_zone,
+            events_counted,
+        },
+    )
+}
+
+// ── Helpers ────────────────────────────────────────────────────────────
+
+/// Parse `mode` (`tenant` | `subscriber`, default `tenant`), `timeZone`
+/// (defaults to the caller's profile zone, then UTC) and `segmentId`.
+fn parse_heatmap_query_params(
+    event: &Request,
+    profile_time_zone: Option<&str>,
+) -> Result<HeatmapQuery, AppError> {
+    let query_params = event.query_string_parameters();
+
+    let mode = match query_params.first("mode") {
+        None | Some("tenant") => HeatmapMode::Tenant,
+        Some("subscriber") => HeatmapMode::Subscriber,
+        Some(_) => {
+            return Err(AppError::BadRequest(
+                "mode must be one of: tenant, subscriber".to_string(),
+            ))
+        }
+    };
+
+    let time_zone = match query_params.first("timeZone") {
+        Some(name) => name.parse::<Tz>().map_err(|_| {
+            AppError::BadRequest(
+                "timeZone must be an IANA timezone name (e.g. \"America/New_York\")".to_string(),
+            )
+        })?,
+        None => profile_time_zone
+            .and_then(|name| name.parse::<Tz>().ok())
+            .unwrap_or(Tz::UTC),
+    };
+
+    let segment_id = query_params
+        .first("segmentId")
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string);
+
+    Ok(HeatmapQuery {
+        mode,
+        time_zone,
+        segment_id,
+    })
+}
+
+fn is_member(item: &HashMap<String, AttributeValue>, members: Option<&HashSet<String>>) -> bool {
+    match members {
+        None => true,
+        Some(members) => item
+            .get("email")
+            .and_then(|v| v.as_s().ok())
+            .is_some_and(|email| members.contains(email)),
+    }
+}
+
+fn subscriber_time_zone(item: &HashMap<String, AttributeValue>) -> Option<Tz> {
+    item.get("timeZone")
+        .and_then(|v| v.as_s().ok())
+        .and_then(|name| name.parse::<Tz>().ok())
+}
+
+/// Opens and clicks from a subscriber's `recentActivity` list. Entries with
+/// an unknown type or unparseable `ts` are skipped.
+fn parse_recent_activity(
+    item: &HashMap<String, AttributeValue>,
+) -> Vec<(DateTime<Utc>, ActivityKind)> {
+    let Some(entries) = item.get("recentActivity").and_then(|v| v.as_l().ok()) else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let entry = entry.as_m().ok()?;
+            let kind = match entry.get("type").and_then(|v| v.as_s().ok())?.as_str() {
+                "open" => ActivityKind::Open,
+                "click" => ActivityKind::Click,
+                _ => return None,
+            };
+            let at = entry
+                .get("ts")
+                .and_then(|v| v.as_s().ok())
+                .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())?
+                .with_timezone(&Utc);
+            Some((at, kind))
+        })
+        .collect()
+}
+
+impl Heatmap {
+    fn record(&mut self, at: DateTime<Utc>, kind: ActivityKind, zone: Tz) {
+        let local = at.with_timezone(&zone);
+        let weekday = local.weekday().num_days_from_monday() as usize;
+        let hour = local.hour() as usize;
+        match kind {
+            ActivityKind::Open => self.opens[weekday][hour] += 1,
+            ActivityKind::Click => self.clicks[weekday][hour] += 1,
+        }
+    }
+
+    /// The `count` busiest slots by opens + clicks, busiest first. Empty
+    /// slots are never returned; ties go to the earlier weekday and hour.
+    fn top_slots(&self, count: usize) -> Vec<HeatmapSlot> {
+        let mut slots: Vec<HeatmapSlot> = (0..7)
+            .flat_map(|weekday| (0..24).map(move |hour| (weekday, hour)))
+            .map(|(weekday, hour)| HeatmapSlot {
+                weekday: weekday as u32,
+                hour: hour as u32,
+                opens: self.opens[weekday][hour],
+                clicks: self.clicks[weekday][hour],
+            })
+            .filter(|slot| slot.opens + slot.clicks > 0)
+            .collect();
+        // Stable sort keeps weekday/hour order among ties.
+        slots.sort_by_key(|slot| std::cmp::Reverse(slot.opens + slot.clicks));
+        slots.truncate(count);
+        slots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity_entry(kind: &str, ts: &str) -> AttributeValue {
+        AttributeValue::M(HashMap::from([
+            ("type".to_string(), AttributeValue::S(kind.to_string())),
+            ("issue".to_string(), AttributeValue::N("12".to_string())),
+            ("ts".to_string(), AttributeValue::S(ts.to_string())),
+        ]))
+    }
+
+    fn subscriber_item(
+        email: &str,
+        time_zone: Option<&str>,
+        activity: Vec<AttributeValue>,
+    ) -> HashMap<String, AttributeValue> {
+        let mut item = HashMap::from([
+            ("email".to_string(), AttributeValue::S(email.to_string())),
+            ("recentActivity".to_string(), AttributeValue::L(activity)),
+        ]);
+        if let Some(zone) = time_zone {
+            item.insert("timeZone".to_string(), AttributeValue::S(zone.to_string()));
+        }
+        item
+    }
+
+    fn utc(ts: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(ts)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_recent_activity_skips_bad_entries() {
+        let item = subscriber_item(
+            "reader@example.com",
+            None,
+            vec![
+                activity_entry("open", "2025-03-03T14:05:00.000Z"),
+                activity_entry("click", "2025-03-03T14:06:00.000Z"),
+                activity_entry("forward", "2025-03-03T14:07:00.000Z"),
+                activity_entry("open", "not a timestamp"),
+            ],
+        );
+
+        let activity = parse_recent_activity(&item);
+        assert_eq!(
+            activity,
+            vec![
+                (utc("2025-03-03T14:05:00Z"), ActivityKind::Open),
+                (utc("2025-03-03T14:06:00Z"), ActivityKind::Click),
+            ]
+        );
+        assert!(parse_recent_activity(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_record_converts_into_zone_across_weekday_boundary() {
+        let mut heatmap = Heatmap::default();
+        // Monday 02:30 UTC is Sunday 21:30 in New York (EST).
+        heatmap.record(
+            utc("2025-03-03T02:30:00Z"),
+            ActivityKind::Open,
+            chrono_tz::America::New_York,
+        );
+        heatmap.record(utc("2025-03-03T02:30:00Z"), ActivityKind::Click, Tz::UTC);
+
+        assert_eq!(heatmap.opens[6][21], 1);
+        assert_eq!(heatmap.clicks[0][2], 1);
+        assert_eq!(heatmap.opens.iter().flatten().sum::<i64>(), 1);
+    }
+
+    #[test]
+    fn test_record_follows_daylight_saving() {
+        let mut heatmap = Heatmap::default();
+        // 13:00 UTC is 08:00 in New York in winter and 09:00 in summer.
+        heatmap.record(
+            utc("2025-01-06T13:00:00Z"),
+            ActivityKind::Open,
+            chrono_tz::America::New_York,
+        );
+        heatmap.record(
+            utc("2025-07-07T13:00:00Z"),
+            ActivityKind::Open,
+            chrono_tz::America::New_York,
+        );
+
+        assert_eq!(heatmap.opens[0][8], 1);
+        assert_eq!(heatmap.opens[0][9], 1);
+    }
+
+    #[test]
+    fn test_top_slots_orders_by_total_and_skips_empty() {
+        let mut heatmap = Heatmap::default();
+        heatmap.opens[1][9] = 5;
+        heatmap.clicks[1][9] = 2;
+        heatmap.opens[3][14] = 4;
+        heatmap.opens[0][7] = 4;
+
+        let slots = heatmap.top_slots(3);
+        let positions: Vec<(u32, u32)> = slots.iter().map(|s| (s.weekday, s.hour)).collect();
+        assert_eq!(positions, vec![(1, 9), (0, 7), (3, 14)]);
+        assert_eq!(slots[0].clicks, 2);
+
+        assert!(Heatmap::default().top_slots(3).is_empty());
+    }
+
+    #[test]
+    fn test_subscriber_time_zone_rejects_unknown_zone() {
+        let known = subscriber_item("a@example.com", Some("Europe/Berlin"), vec![]);
+        let unknown = subscriber_item("b@example.com", Some("Mars/Olympus"), vec![]);
+
+        assert_eq!(
+            subscriber_time_zone(&known),
+            Some(chrono_tz::Europe::Berlin)
+        );
+        assert_eq!(subscriber_time_zone(&unknown), None);
+    }
+
+    #[test]
+    fn test_is_member_filters_by_segment() {
+        let item = subscriber_item("reader@example.com", None, vec![]);
+        let members = HashSet::from(["reader@example.com".to_string()]);
+        let others = HashSet::from(["someone@example.com".to_string()]);
+
+        assert!(is_member(&item, None));
+        assert!(is_member(&item, Some(&members)));
+        assert!(!is_member(&item, Some(&others)));
+    }
+}
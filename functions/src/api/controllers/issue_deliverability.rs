@@ -0,0 +1,472 @@
+//! Deliverability by mailbox provider (`GET /issues/:id/deliverability` and
+//! `GET /issues/deliverability/trends`).
+//!
+//! handle-email-status classifies every delivery, unique open, bounce and
+//! complaint by the recipient's mailbox provider (utils/mailbox-provider.mjs:
+//! a built-in consumer-domain table, then the domain's MX hosts) and bumps a
+//! `stats#provider#<provider>` counter record under the issue. The aggregate
+//! stats record hides a problem that starts at one provider; this breaks the
+//! same counters out per provider and flags the ones that stand out. Rates
+//! are percentages of deliveries, as on the issue metrics.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use newsletter::admin::{auth, aws_clients, error::AppError, response};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::controllers::issues;
+
+const PROVIDER_SK_PREFIX: &str = "stats#provider#";
+
+/// Provider ids (the `stats#provider#` suffixes written by
+/// handle-email-status) and their display labels, in response order.
+const PROVIDERS: [(&str, &str); 5] = [
+    ("gmail", "Gmail"),
+    ("outlook", "Outlook/Hotmail"),
+    ("yahoo", "Yahoo"),
+    ("icloud", "Apple iCloud"),
+    ("other", "Corporate/other"),
+];
+
+/// Mailbox providers' published bulk-sender limits: Gmail and Yahoo start
+/// filtering above a 0.3% complaint rate; SES reviews accounts above 5%
+/// bounces.
+const HIGH_BOUNCE_RATE: f64 = 5.0;
+const HIGH_COMPLAINT_RATE: f64 = 0.3;
+/// A provider opening at less than this share of everyone else's open rate
+/// is likely junking the issue.
+const OPEN_RATE_OUTLIER_RATIO: f64 = 0.5;
+/// Deliveries a provider needs before its rates are flagged.
+const MIN_DELIVERIES_FOR_FLAGS: i64 = 100;
+
+const DEFAULT_TREND_ISSUES: i32 = 10;
+const MAX_TREND_ISSUES: i32 = 50;
+
+// ── Types ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct ProviderCounts {
+    deliveries: i64,
+    opens: i64,
+    bounces: i64,
+    complaints: i64,
+}
+
+impl ProviderCounts {
+    fn add(&mut self, other: &ProviderCounts) {
+        self.deliveries += other.deliveries;
+        self.opens += other.opens;
+        self.bounces += other.bounces;
+        self.complaints += other.complaints;
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DeliverabilityRates {
+    deliveries: i64,
+    opens: i64,
+    bounces: i64,
+    complaints: i64,
+    open_rate: f64,
+    bounce_rate: f64,
+    complaint_rate: f64,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ProviderDeliverability {
+    provider: &'static str,
+    label: &'static str,
+    #[serde(flatten)]
+    rates: DeliverabilityRates,
+    /// Percentage of the issue's deliveries that went to this provider.
+    delivery_share: f64,
+    /// `highBounceRate`, `highComplaintRate` and/or `openRateOutlier`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    flags: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueDeliverabilityResponse {
+    id: String,
+    issue_number: i32,
+    totals: DeliverabilityRates,
+    providers: Vec<ProviderDeliverability>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeliverabilityTrendIssue {
+    id: String,
+    issue_number: i32,
+    providers: Vec<ProviderDeliverability>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeliverabilityTrendsResponse {
+    /// Newest first, like `/issues/trends`.
+    issues: Vec<DeliverabilityTrendIssue>,
+    /// Each provider's counters summed over the window.
+    aggregates: Vec<ProviderDeliverability>,
+}
+
+// ── Handlers ───────────────────────────────────────────────────────────
+
+pub async fn get_issue_deliverability(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_get_issue_deliverability(event, issue_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+pub async fn get_deliverability_trends(event: Request) -> Result<Response<Body>, Error> {
+    match handle_get_deliverability_trends(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_get_issue_deliverability(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Unauthorized("Tenant access required".to_string()))?;
+
+    let issue_id =
+        issue_id.ok_or_else(|| AppError::BadRequest("Issue ID is required".to_string()))?;
+    let issue = issues::get_issue_by_id(&tenant_id, &issue_id).await?;
+
+    let counts = query_provider_counts(&tenant_id, issue.issue_number).await?;
+    let mut totals = ProviderCounts::default();
+    for c in counts.values() {
+        totals.add(c);
+    }
+
+    response::format_response(
+        200,
+        IssueDeliverabilityResponse {
+            id: issue.issue_number.to_string(),
+            issue_number: issue.issue_number,
+            totals: rates(&totals),
+            providers: build_provider_breakdown(&counts),
+        },
+    )
+}
+
+async fn handle_get_deliverability_trends(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Unauthorized("Tenant access required".to_string()))?;
+
+    let issue_count = parse_issue_count(event.query_string_parameters().first("issueCount"))?;
+    let recent = issues::query_recent_issue_stats(&tenant_id, issue_count).await?;
+
+    let mut window: HashMap<String, ProviderCounts> = HashMap::new();
+    let mut trend_issues = Vec::with_capacity(recent.len());
+    for (issue_number, _) in recent {
+        let counts = query_provider_counts(&tenant_id, issue_number).await?;
+        for (provider, c) in &counts {
+            window.entry(provider.clone()).or_default().add(c);
+        }
+        trend_issues.push(DeliverabilityTrendIssue {
+            id: issue_number.to_string(),
+            issue_number,
+            providers: build_provider_breakdown(&counts),
+        });
+    }
+
+    response::format_response(
+        200,
+        DeliverabilityTrendsResponse {
+            issues: trend_issues,
+            aggregates: build_provider_breakdown(&window),
+        },
+    )
+}
+
+fn parse_issue_count(value: Option<&str>) -> Result<i32, AppError> {
+    match value {
+        None => Ok(DEFAULT_TREND_ISSUES),
+        Some(raw) => raw
+            .trim()
+            .parse::<i32>()
+            .ok()
+            .filter(|n| (1..=MAX_TREND_ISSUES).contains(n))
+            .ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "issueCount must be between 1 and {}",
+                    MAX_TREND_ISSUES
+                ))
+            }),
+    }
+}
+
+// ── Breakdown ──────────────────────────────────────────────────────────
+
+fn percent(numerator: i64, denominator: i64) -> f64 {
+    if denominator <= 0 {
+        return 0.0;
+    }
+    (numerator as f64 / denominator as f64 * 10000.0).round() / 100.0
+}
+
+fn rates(counts: &ProviderCounts) -> DeliverabilityRates {
+    DeliverabilityRates {
+        deliveries: counts.deliveries,
+        opens: counts.opens,
+        bounces: counts.bounces,
+        complaints: counts.complaints,
+        open_rate: percent(counts.opens, counts.deliveries),
+        bounce_rate: percent(counts.bounces, counts.deliveries),
+        complaint_rate: percent(counts.complaints, counts.deliveries),
+    }
+}
+
+/// One entry per known provider (zeros when it had no traffic), with its
+/// share of deliveries and any flags. Counters for an unrecognised provider
+/// id are folded into `other`.
+fn build_provider_breakdown(
+    counts: &HashMap<String, ProviderCounts>,
+) -> Vec<ProviderDeliverability> {
+    let mut by_provider: Vec<ProviderCounts> = vec![ProviderCounts::default(); PROVIDERS.len()];
+    for (provider, c) in counts {
+        let index = PROVIDERS
+            .iter()
+            .position(|(id, _)| id == provider)
+            .unwrap_or(PROVIDERS.len() - 1);
+        by_provider[index].add(c);
+    }
+
+    let mut totals = ProviderCounts::default();
+    for c in &by_provider {
+        totals.add(c);
+    }
+
+    PROVIDERS
+        .iter()
+        .zip(&by_provider)
+        .map(|((id, label), c)| {
+            let mut rest = totals;
+            rest.deliveries -= c.deliveries;
+            rest.opens -= c.opens;
+            rest.bounces -= c.bounces;
+            rest.complaints -= c.complaints;
+
+            ProviderDeliverability {
+                provider: id,
+                label,
+                rates: rates(c),
+                delivery_share: percent(c.deliveries, totals.deliveries),
+                flags: provider_flags(c, &rest),
+            }
+        })
+        .collect()
+}
+
+/// Flags a provider whose bounce or complaint rate crosses the providers'
+/// limits, or whose open rate trails everyone else's by a wide margin.
+/// Providers with too little traffic are never flagged.
+fn provider_flags(counts: &ProviderCounts, rest: &ProviderCounts) -> Vec<&'static str> {
+    let mut flags = Vec::new();
+    if counts.deliveries < MIN_DELIVERIES_FOR_FLAGS {
+        return flags;
+    }
+
+    let own = rates(counts);
+    if own.bounce_rate >= HIGH_BOUNCE_RATE {
+        flags.push("highBounceRate");
+    }
+    if own.complaint_rate >= HIGH_COMPLAINT_RATE {
+        flags.push("highComplaintRate");
+    }
+    if rest.deliveries >= MIN_DELIVERIES_FOR_FLAGS {
+        let rest_open_rate = percent(rest.opens, rest.deliveries);
+        if rest_open_rate > 0.0 && own.open_rate < rest_open_rate * OPEN_RATE_OUTLIER_RATIO {
+            flags.push("openRateOutlier");
+        }
+    }
+    flags
+}
+
+// ── Storage ────────────────────────────────────────────────────────────
+
+fn parse_provider_counts(
+    item: &HashMap<String, AttributeValue>,
+) -> Option<(String, ProviderCounts)> {
+    let provider = item
+        .get("sk")
+        .and_then(|v| v.as_s().ok())
+        .and_then(|sk| sk.strip_prefix(PROVIDER_SK_PREFIX))?
+        .to_string();
+    let number = |name: &str| {
+        item.get(name)
+            .and_then(|v| v.as_n().ok())
+            .and_then(|n| n.parse::<i64>().ok())
+            .unwrap_or(0)
+    };
+    Some((
+        provider,
+        ProviderCounts {
+            deliveries: number("deliveries"),
+            opens: number("opens"),
+            bounces: number("bounces"),
+            complaints: number("complaints"),
+        },
+    ))
+}
+
+async fn query_provider_counts(
+    tenant_id: &str,
+    issue_number: i32,
+) -> Result<HashMap<String, ProviderCounts>, AppError> {
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let table_name = std::env::var("TABLE_NAME")
+        .map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))?;
+
+    // At most one record per provider, so a single page always suffices.
+    let result = ddb_client
+        .query()
+        .table_name(&table_name)
+        .key_condition_expression("pk = :pk AND begins_with(sk, :sk_prefix)")
+        .expression_attribute_values(
+            ":pk",
+            AttributeValue::S(format!("{}#{}", tenant_id, issue_number)),
+        )
+        .expression_attribute_values(
+            ":sk_prefix",
+            AttributeValue::S(PROVIDER_SK_PREFIX.to_string()),
+        )
+        .send()
+        .await?;
+
+    Ok(result
+        .items()
+        .iter()
+        .filter_map(parse_provider_counts)
+        .collect())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(deliveries: i64, opens: i64, bounces: i64, complaints: i64) -> ProviderCounts {
+        ProviderCounts {
+            deliveries,
+            opens,
+            bounces,
+            complaints,
+        }
+    }
+
+    fn breakdown(entries: &[(&str, ProviderCounts)]) -> Vec<ProviderDeliverability> {
+        let map: HashMap<String, ProviderCounts> = entries
+            .iter()
+            .map(|(provider, c)| (provider.to_string(), *c))
+            .collect();
+        build_provider_breakdown(&map)
+    }
+
+    #[test]
+    fn test_breakdown_lists_every_provider_in_order() {
+        let providers = breakdown(&[("gmail", counts(600, 300, 6, 0))]);
+        let ids: Vec<&str> = providers.iter().map(|p| p.provider).collect();
+        assert_eq!(ids, vec!["gmail", "outlook", "yahoo", "icloud", "other"]);
+        assert_eq!(providers[0].rates.open_rate, 50.0);
+        assert_eq!(providers[0].rates.bounce_rate, 1.0);
+        assert_eq!(providers[0].delivery_share, 100.0);
+        assert_eq!(providers[1].rates.deliveries, 0);
+        assert_eq!(providers[1].rates.open_rate, 0.0);
+    }
+
+    #[test]
+    fn test_breakdown_folds_unknown_providers_into_other() {
+        let providers = breakdown(&[
+            ("other", counts(10, 1, 0, 0)),
+            ("fastmail", counts(5, 2, 0, 0)),
+        ]);
+        assert_eq!(providers[4].rates.deliveries, 15);
+        assert_eq!(providers[4].rates.opens, 3);
+    }
+
+    #[test]
+    fn test_flags_single_provider_problems() {
+        let providers = breakdown(&[
+            ("gmail", counts(1000, 450, 10, 1)),
+            ("outlook", counts(500, 40, 40, 2)),
+            ("yahoo", counts(200, 90, 2, 0)),
+        ]);
+        assert!(providers[0].flags.is_empty());
+        assert_eq!(
+            providers[1].flags,
+            vec!["highBounceRate", "highComplaintRate", "openRateOutlier"]
+        );
+        assert!(providers[2].flags.is_empty());
+        assert!((providers[0].delivery_share - 58.82).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_flags_need_enough_deliveries() {
+        let providers = breakdown(&[
+            ("gmail", counts(1000, 450, 10, 1)),
+            ("icloud", counts(50, 1, 10, 5)),
+        ]);
+        assert!(providers[3].flags.is_empty());
+    }
+
+    #[test]
+    fn test_parse_provider_counts() {
+        let item = HashMap::from([
+            ("pk".to_string(), AttributeValue::S("tenant#42".to_string())),
+            (
+                "sk".to_string(),
+                AttributeValue::S("stats#provider#yahoo".to_string()),
+            ),
+            (
+                "deliveries".to_string(),
+                AttributeValue::N("120".to_string()),
+            ),
+            ("bounces".to_string(), AttributeValue::N("3".to_string())),
+        ]);
+        let (provider, c) = parse_provider_counts(&item).unwrap();
+        assert_eq!(provider, "yahoo");
+        assert_eq!(c, counts(120, 0, 3, 0));
+
+        let stats = HashMap::from([("sk".to_string(), AttributeValue::S("stats".to_string()))]);
+        assert!(parse_provider_counts(&stats).is_none());
+    }
+
+    #[test]
+    fn test_parse_issue_count() {
+        assert_eq!(parse_issue_count(None).unwrap(), DEFAULT_TREND_ISSUES);
+        assert_eq!(parse_issue_count(Some("25")).unwrap(), 25);
+        assert!(parse_issue_count(Some("0")).is_err());
+        assert!(parse_issue_count(Some("51")).is_err());
+        assert!(parse_issue_count(Some("ten")).is_err());
+    }
+
+    #[test]
+    fn test_provider_serialization_flattens_rates() {
+        let providers = breakdown(&[("gmail", counts(200, 100, 0, 0))]);
+        let json = serde_json::to_value(&providers[0]).unwrap();
+        assert_eq!(json["provider"], "gmail");
+        assert_eq!(json["label"], "Gmail");
+        assert_eq!(json["openRate"], 50.0);
+        assert_eq!(json["deliveryShare"], 100.0);
+        assert!(json.get("flags").is_none());
+        assert!(json.get("rates").is_none());
+    }
+}
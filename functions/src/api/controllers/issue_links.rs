@@ -0,0 +1,528 @@
+//! Per-link click analytics for an issue (`GET /issues/:id/links`).
+//!
+//! update-link-tracking writes a `link#<hash(url)>` record under the issue
+//! (`pk = {tenant}#{n}`) for every tracked link, with its position in the
+//! issue and the LLM topic classification; the click handlers bump its
+//! `clicks_total`. Unique clicks and the email/web split come from the
+//! issue's `click#` events, which carry the subscriber hash and traffic
+//! source. Click events expire after 90 days, so for older issues totals
+//! still come from the link records while uniques and sources drop to zero.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use newsletter::admin::{auth, aws_clients, error::AppError, response};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+use crate::controllers::issues;
+
+const LINK_SK_PREFIX: &str = "link#";
+const CLICK_SK_PREFIX: &str = "click#";
+
+/// Subscriber hash recorded for anonymous (web) clicks.
+const UNKNOWN_SUBSCRIBER: &str = "unknown";
+
+const SORT_OPTIONS: [&str; 3] = ["clicks", "unique", "position"];
+
+// ── Types ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, PartialEq)]
+enum LinkSort {
+    Clicks,
+    Unique,
+    Position,
+}
+
+#[derive(Debug, PartialEq)]
+enum OutputFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Default)]
+struct LinkRecord {
+    url: String,
+    position: Option<i64>,
+    clicks_total: i64,
+    primary_topic: Option<String>,
+    secondary_topics: Vec<String>,
+}
+
+#[derive(Debug)]
+struct ClickEvent {
+    link_url: String,
+    subscriber_hash: Option<String>,
+    traffic_source: String,
+    link_position: Option<i64>,
+}
+
+#[derive(Serialize, Debug, Default, Clone, Copy, PartialEq)]
+struct LinkSourceClicks {
+    email: i64,
+    web: i64,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct LinkPerformance {
+    url: String,
+    /// 1-based ordinal of the link in the issue; absent for links that were
+    /// clicked but aren't in the issue content (e.g. template footer links).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<i64>,
+    clicks: i64,
+    unique_clicks: i64,
+    /// Percentage of all link clicks in the issue.
+    click_share: f64,
+    source: LinkSourceClicks,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    secondary_topics: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueLinksResponse {
+    id: String,
+    issue_number: i32,
+    total_clicks: i64,
+    /// Distinct subscribers who clicked any link.
+    unique_clickers: usize,
+    links: Vec<LinkPerformance>,
+}
+
+// ── Handler ────────────────────────────────────────────────────────────
+
+pub async fn list_issue_links(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_list_issue_links(event, issue_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_list_issue_links(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Unauthorized("Tenant access required".to_string()))?;
+
+    let issue_id =
+        issue_id.ok_or_else(|| AppError::BadRequest("Issue ID is required".to_string()))?;
+    let query_params = event.query_string_parameters();
+    let sort = parse_sort(query_params.first("sort"))?;
+    let format = parse_format(query_params.first("format"))?;
+
+    let issue = issues::get_issue_by_id(&tenant_id, &issue_id).await?;
+    let issue_pk = format!("{}#{}", tenant_id, issue.issue_number);
+
+    let link_items = query_issue_items(&issue_pk, LINK_SK_PREFIX, None).await?;
+    let click_items = query_issue_items(
+        &issue_pk,
+        CLICK_SK_PREFIX,
+        Some("linkUrl, subscriberEmailHash, trafficSource, linkPosition"),
+    )
+    .await?;
+
+    let link_records: Vec<LinkRecord> = link_items.iter().filter_map(parse_link_record).collect();
+    let click_events: Vec<ClickEvent> = click_items.iter().filter_map(parse_click_event).collect();
+
+    let mut links = aggregate_links(link_records, &click_events);
+    sort_links(&mut links, &sort);
+
+    if format == OutputFormat::Csv {
+        return response::format_csv_response(
+            200,
+            links_to_csv(&links),
+            &format!("issue-{}-links.csv", issue.issue_number),
+        );
+    }
+
+    response::format_response(
+        200,
+        IssueLinksResponse {
+            id: issue.issue_number.to_string(),
+            issue_number: issue.issue_number,
+            total_clicks: links.iter().map(|l| l.clicks).sum(),
+            unique_clickers: unique_clickers(&click_events),
+            links,
+        },
+    )
+}
+
+fn parse_sort(value: Option<&str>) -> Result<LinkSort, AppError> {
+    match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+        None | Some("") | Some("clicks") => Ok(LinkSort::Clicks),
+        Some("unique") => Ok(LinkSort::Unique),
+        Some("position") => Ok(LinkSort::Position),
+        Some(other) => Err(AppError::BadRequest(format!(
+            "Invalid sort '{}'. Must be one of: {}",
+            other,
+            SORT_OPTIONS.join(", ")
+        ))),
+    }
+}
+
+fn parse_format(value: Option<&str>) -> Result<OutputFormat, AppError> {
+    match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+        None | Some("") | Some("json") => Ok(OutputFormat::Json),
+        Some("csv") => Ok(OutputFormat::Csv),
+        Some(other) => Err(AppError::BadRequest(format!(
+            "Invalid format '{}'. Must be json or csv",
+            other
+        ))),
+    }
+}
+
+// ── Storage ────────────────────────────────────────────────────────────
+
+async fn query_issue_items(
+    issue_pk: &str,
+    sk_prefix: &str,
+    projection: Option<&str>,
+) -> Result<Vec<HashMap<String, AttributeValue>>, AppError> {
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let table_name = std::env::var("TABLE_NAME")
+        .map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))?;
+
+    let mut items = Vec::new();
+    let mut exclusive_start_key = None;
+    loop {
+        let result = ddb_client
+            .query()
+            .table_name(&table_name)
+            .key_condition_expression("pk = :pk AND begins_with(sk, :sk_prefix)")
+            .expression_attribute_values(":pk", AttributeValue::S(issue_pk.to_string()))
+            .expression_attribute_values(":sk_prefix", AttributeValue::S(sk_prefix.to_string()))
+            .set_projection_expression(projection.map(str::to_string))
+            .set_exclusive_start_key(exclusive_start_key)
+            .send()
+            .await?;
+
+        items.extend(result.items().iter().cloned());
+
+        exclusive_start_key = result.last_evaluated_key().cloned();
+        if exclusive_start_key.is_none() {
+            break;
+        }
+    }
+
+    Ok(items)
+}
+
+fn string_attr(item: &HashMap<String, AttributeValue>, name: &str) -> Option<String> {
+    item.get(name).and_then(|v| v.as_s().ok()).cloned()
+}
+
+fn number_attr(item: &HashMap<String, AttributeValue>, name: &str) -> Option<i64> {
+    item.get(name)
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<f64>().ok())
+        .map(|n| n as i64)
+}
+
+fn parse_link_record(item: &HashMap<String, AttributeValue>) -> Option<LinkRecord> {
+    Some(LinkRecord {
+        url: string_attr(item, "url")?,
+        position: number_attr(item, "position"),
+        clicks_total: number_attr(item, "clicks_total").unwrap_or(0),
+        primary_topic: string_attr(item, "primaryTopic"),
+        secondary_topics: item
+            .get("secondaryTopics")
+            .and_then(|v| v.as_l().ok())
+            .map(|topics| {
+                topics
+                    .iter()
+                    .filter_map(|t| t.as_s().ok().cloned())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+fn parse_click_event(item: &HashMap<String, AttributeValue>) -> Option<ClickEvent> {
+    Some(ClickEvent {
+        link_url: string_attr(item, "linkUrl")?,
+        subscriber_hash: string_attr(item, "subscriberEmailHash")
+            .filter(|hash| hash != UNKNOWN_SUBSCRIBER),
+        traffic_source: string_attr(item, "trafficSource").unwrap_or_else(|| "web".to_string()),
+        link_position: number_attr(item, "linkPosition"),
+    })
+}
+
+// ── Aggregation ────────────────────────────────────────────────────────
+
+/// One row per URL. Totals come from the link records (the running
+/// counters); a URL that only shows up in click events — a link record that
+/// was never created, or has expired — counts its events instead.
+fn aggregate_links(records: Vec<LinkRecord>, clicks: &[ClickEvent]) -> Vec<LinkPerformance> {
+    let mut by_url: HashMap<String, LinkRecord> = HashMap::new();
+    for record in records {
+        by_url.entry(record.url.clone()).or_insert(record);
+    }
+
+    let mut event_counts: HashMap<&str, i64> = HashMap::new();
+    let mut uniques: HashMap<&str, HashSet<&str>> = HashMap::new();
+    let mut sources: HashMap<&str, LinkSourceClicks> = HashMap::new();
+    for click in clicks {
+        let url = click.link_url.as_str();
+        *event_counts.entry(url).or_default() += 1;
+        if let Some(hash) = click.subscriber_hash.as_deref() {
+            uniques.entry(url).or_default().insert(hash);
+        }
+        let source = sources.entry(url).or_default();
+        if click.traffic_source == "email" {
+            source.email += 1;
+        } else {
+            source.web += 1;
+        }
+
+        let record = by_url
+            .entry(click.link_url.clone())
+            .or_insert_with(|| LinkRecord {
+                url: click.link_url.clone(),
+                ..LinkRecord::default()
+            });
+        if record.position.is_none() {
+            record.position = click.link_position;
+        }
+    }
+
+    let rows: Vec<(LinkRecord, i64)> = by_url
+        .into_values()
+        .map(|record| {
+            let events = event_counts.get(record.url.as_str()).copied().unwrap_or(0);
+            let clicks = record.clicks_total.max(events);
+            (record, clicks)
+        })
+        .collect();
+    let total_clicks: i64 = rows.iter().map(|(_, clicks)| clicks).sum();
+
+    rows.into_iter()
+        .map(|(record, clicks)| {
+            let url = record.url.as_str();
+            let click_share = if total_clicks > 0 {
+                ((clicks as f64 / total_clicks as f64) * 100.0 * 100.0).round() / 100.0
+            } else {
+                0.0
+            };
+            LinkPerformance {
+                position: record.position,
+                clicks,
+                unique_clicks: uniques.get(url).map_or(0, |set| set.len() as i64),
+                click_share,
+                source: sources.get(url).copied().unwrap_or_default(),
+                topic: record.primary_topic,
+                secondary_topics: record.secondary_topics,
+                url: record.url,
+            }
+        })
+        .collect()
+}
+
+fn unique_clickers(clicks: &[ClickEvent]) -> usize {
+    clicks
+        .iter()
+        .filter_map(|c| c.subscriber_hash.as_deref())
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// Best performers first; ties (and `position`) fall back to issue order,
+/// with links that have no position last.
+fn sort_links(links: &mut [LinkPerformance], sort: &LinkSort) {
+    let by_position = |a: &LinkPerformance, b: &LinkPerformance| {
+        a.position
+            .unwrap_or(i64::MAX)
+            .cmp(&b.position.unwrap_or(i64::MAX))
+            .then_with(|| a.url.cmp(&b.url))
+    };
+    links.sort_by(|a, b| match sort {
+        LinkSort::Clicks => b
+            .clicks
+            .cmp(&a.clicks)
+            .then_with(|| b.unique_clicks.cmp(&a.unique_clicks))
+            .then_with(|| by_position(a, b)),
+        LinkSort::Unique => b
+            .unique_clicks
+            .cmp(&a.unique_clicks)
+            .then_with(|| b.clicks.cmp(&a.clicks))
+            .then_with(|| by_position(a, b)),
+        LinkSort::Position => by_position(a, b),
+    });
+}
+
+// ── CSV ────────────────────────────────────────────────────────────────
+
+fn links_to_csv(links: &[LinkPerformance]) -> String {
+    let mut csv =
+        String::from("position,url,clicks,uniqueClicks,clickShare,emailClicks,webClicks,topic\n");
+    for link in links {
+        let fields = [
+            link.position.map(|p| p.to_string()).unwrap_or_default(),
+            csv_field(&link.url),
+            link.clicks.to_string(),
+            link.unique_clicks.to_string(),
+            link.click_share.to_string(),
+            link.source.email.to_string(),
+            link.source.web.to_string(),
+            csv_field(link.topic.as_deref().unwrap_or_default()),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// RFC 4180 quoting, plus a leading `'` on values a spreadsheet would
+/// otherwise evaluate as a formula.
+pub(crate) fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(url: &str, position: Option<i64>, clicks: i64, topic: Option<&str>) -> LinkRecord {
+        LinkRecord {
+            url: url.to_string(),
+            position,
+            clicks_total: clicks,
+            primary_topic: topic.map(str::to_string),
+            secondary_topics: Vec::new(),
+        }
+    }
+
+    fn click(url: &str, subscriber: Option<&str>, source: &str) -> ClickEvent {
+        ClickEvent {
+            link_url: url.to_string(),
+            subscriber_hash: subscriber.map(str::to_string),
+            traffic_source: source.to_string(),
+            link_position: None,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_links_counts_uniques_and_sources() {
+        let records = vec![
+            record("https://a.dev", Some(1), 3, Some("serverless")),
+            record("https://b.dev", Some(2), 1, None),
+            record("https://unclicked.dev", Some(3), 0, None),
+        ];
+        let clicks = vec![
+            click("https://a.dev", Some("h1"), "email"),
+            click("https://a.dev", Some("h1"), "email"),
+            click("https://a.dev", None, "web"),
+            click("https://b.dev", Some("h2"), "email"),
+        ];
+
+        let mut links = aggregate_links(records, &clicks);
+        sort_links(&mut links, &LinkSort::Clicks);
+
+        assert_eq!(links.len(), 3);
+        assert_eq!(links[0].url, "https://a.dev");
+        assert_eq!(links[0].clicks, 3);
+        assert_eq!(links[0].unique_clicks, 1);
+        assert_eq!(links[0].click_share, 75.0);
+        assert_eq!(links[0].source, LinkSourceClicks { email: 2, web: 1 });
+        assert_eq!(links[0].topic.as_deref(), Some("serverless"));
+        assert_eq!(links[2].url, "https://unclicked.dev");
+        assert_eq!(links[2].click_share, 0.0);
+        assert_eq!(unique_clickers(&clicks), 2);
+    }
+
+    #[test]
+    fn test_aggregate_links_includes_clicks_without_link_record() {
+        let mut footer = click("https://footer.dev", Some("h1"), "email");
+        footer.link_position = Some(9);
+        let links = aggregate_links(vec![record("https://a.dev", Some(1), 0, None)], &[footer]);
+
+        let footer = links
+            .iter()
+            .find(|l| l.url == "https://footer.dev")
+            .unwrap();
+        assert_eq!(footer.clicks, 1);
+        assert_eq!(footer.position, Some(9));
+        assert_eq!(footer.click_share, 100.0);
+    }
+
+    #[test]
+    fn test_sort_links() {
+        let records = vec![
+            record("https://c.dev", None, 5, None),
+            record("https://a.dev", Some(2), 5, None),
+            record("https://b.dev", Some(1), 2, None),
+        ];
+        let clicks = vec![
+            click("https://b.dev", Some("h1"), "email"),
+            click("https://b.dev", Some("h2"), "email"),
+        ];
+
+        let mut links = aggregate_links(records, &clicks);
+        let order =
+            |links: &[LinkPerformance]| links.iter().map(|l| l.url.clone()).collect::<Vec<_>>();
+
+        sort_links(&mut links, &LinkSort::Clicks);
+        assert_eq!(
+            order(&links),
+            vec!["https://a.dev", "https://c.dev", "https://b.dev"]
+        );
+        sort_links(&mut links, &LinkSort::Unique);
+        assert_eq!(
+            order(&links),
+            vec!["https://b.dev", "https://a.dev", "https://c.dev"]
+        );
+        sort_links(&mut links, &LinkSort::Position);
+        assert_eq!(
+            order(&links),
+            vec!["https://b.dev", "https://a.dev", "https://c.dev"]
+        );
+    }
+
+    #[test]
+    fn test_parse_query_options() {
+        assert_eq!(parse_sort(None).unwrap(), LinkSort::Clicks);
+        assert_eq!(parse_sort(Some("Position")).unwrap(), LinkSort::Position);
+        assert!(parse_sort(Some("random")).is_err());
+        assert_eq!(parse_format(None).unwrap(), OutputFormat::Json);
+        assert_eq!(parse_format(Some("csv")).unwrap(), OutputFormat::Csv);
+        assert!(parse_format(Some("xml")).is_err());
+    }
+
+    #[test]
+    fn test_links_to_csv_escapes_fields() {
+        let mut links = aggregate_links(
+            vec![
+                record("https://a.dev/?q=1,2", Some(1), 1, Some("=cmd")),
+                record("https://b.dev", None, 0, None),
+            ],
+            &[],
+        );
+        sort_links(&mut links, &LinkSort::Position);
+
+        assert_eq!(
+            links_to_csv(&links),
+            "position,url,clicks,uniqueClicks,clickShare,emailClicks,webClicks,topic\n\
+             1,\"https://a.dev/?q=1,2\",1,0,100,0,0,'=cmd\n\
+             ,https://b.dev,0,0,0,0,0,\n"
+        );
+    }
+}
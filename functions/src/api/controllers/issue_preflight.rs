@@ -0,0 +1,985 @@
+//! Pre-send preflight report for issues.
+//!
+//! `POST /issues/:id/preflight` renders the issue the way the publish workflow
+//! will and runs deterministic checks over the result: broken or duplicate
+//! links, images without `alt`, partials that rendered empty, a missing
+//! unsubscribe link, subject/preheader length, Gmail's clipping limit and the
+//! image-to-text ratio. `POST /issues/review` covers prose through the LLM;
+//! this covers the mechanical problems that break a send.
+//!
+//! Rendering follows publish-issue: `html` content is the master as-is, `json`
+//! content is the data for the selected template, and `markdown` content is
+//! rendered through the selected template (or the bundled default) with data
+//! shaped like parse-md-to-json's. The markdown conversion here is a close
+//! approximation (front matter, `### ` sections, links, images and
+//! paragraphs) rather than the full showdown pass, which is enough for every
+//! check below.
+
+use lambda_http::{Body, Error, Request, Response};
+use newsletter::admin::{auth, error::AppError, response};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use crate::controllers::issue_search::{decode_entities, strip_front_matter, strip_html};
+use crate::controllers::issues::{self, IssueRecord};
+use crate::controllers::{snippets, template_render, templates};
+
+// ── Constants ──────────────────────────────────────────────────────────
+
+/// The template publish-issue falls back to when an issue has none.
+const DEFAULT_TEMPLATE: &str = include_str!("../../../../templates/newsletter.hbs");
+
+/// Gmail clips messages whose HTML is larger than this, hiding everything
+/// after the cut — usually including the unsubscribe footer.
+const GMAIL_CLIP_BYTES: usize = 102 * 1024;
+/// Warn before the limit: per-recipient tokens and click-tracking redirects
+/// make the sent HTML larger than the rendered master.
+const GMAIL_CLIP_WARN_BYTES: usize = 92 * 1024;
+
+const SUBJECT_MAX_CHARS: usize = 100;
+const PREHEADER_MAX_CHARS: usize = 150;
+
+/// Below this much visible text per image an email reads as image-only to
+/// spam filters.
+const MIN_TEXT_CHARS_PER_IMAGE: usize = 200;
+
+/// Placeholder send-email-v2 fills with the recipient's unsubscribe link.
+const UNSUBSCRIBE_PLACEHOLDER: &str = "__UNSUBSCRIBE_URL__";
+const PERSONALIZATION_TOKENS: [&str; 3] = ["__EMAIL_HASH__", "__EMAIL__", UNSUBSCRIBE_PLACEHOLDER];
+
+/// Body shortcodes parse-md-to-json handles itself rather than through a
+/// snippet.
+const BUILT_IN_SHORTCODES: [&str; 3] = ["sponsor", "social", "robotVoice"];
+
+/// Longest list of offending items reported per check.
+const MAX_DETAILS: usize = 20;
+
+// ── Types ──────────────────────────────────────────────────────────────
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    Warning,
+    Error,
+}
+
+#[derive(Serialize, Debug)]
+struct PreflightCheck {
+    id: &'static str,
+    status: CheckStatus,
+    message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    details: Vec<String>,
+}
+
+impl PreflightCheck {
+    fn new(id: &'static str, status: CheckStatus, message: impl Into<String>) -> Self {
+        PreflightCheck {
+            id,
+            status,
+            message: message.into(),
+            details: Vec::new(),
+        }
+    }
+
+    fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details.into_iter().take(MAX_DETAILS).collect();
+        self
+    }
+}
+
+#[derive(Serialize)]
+struct PreflightReport {
+    id: String,
+    #[serde(rename = "issueNumber")]
+    issue_number: i32,
+    #[serde(rename = "contentType")]
+    content_type: String,
+    #[serde(rename = "templateId", skip_serializing_if = "Option::is_none")]
+    template_id: Option<String>,
+    /// True when no check is an error.
+    passed: bool,
+    errors: usize,
+    warnings: usize,
+    #[serde(rename = "htmlBytes")]
+    html_bytes: usize,
+    checks: Vec<PreflightCheck>,
+}
+
+/// The issue as it will be sent, plus what rendering had to paper over.
+#[derive(Debug, Default)]
+struct RenderedIssue {
+    html: String,
+    unresolved_partials: Vec<String>,
+    unresolved_shortcodes: Vec<String>,
+    preheader: Option<String>,
+}
+
+#[derive(Debug)]
+struct HtmlTag {
+    name: String,
+    attributes: HashMap<String, String>,
+}
+
+// ── Handler ────────────────────────────────────────────────────────────
+
+pub async fn preflight_issue(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_preflight_issue(event, issue_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_preflight_issue(
+    event: Request,
+    issue_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Unauthorized("Tenant access required".to_string()))?;
+
+    let issue_id =
+        issue_id.ok_or_else(|| AppError::BadRequest("Issue ID is required".to_string()))?;
+    let issue = issues::get_issue_by_id(&tenant_id, &issue_id).await?;
+    let content_type = issues::normalize_content_type(issue.content_type.as_deref());
+
+    let mut checks = Vec::new();
+    let rendered = match render_issue(&tenant_id, &issue, &content_type).await {
+        Ok(rendered) => {
+            checks.push(PreflightCheck::new(
+                "render",
+                CheckStatus::Pass,
+                "Issue rendered",
+            ));
+            Some(rendered)
+        }
+        // Authoring mistakes (bad Handlebars, bad JSON, missing template) are
+        // a failed check, not a failed request.
+        Err(AppError::BadRequest(message)) => {
+            checks.push(PreflightCheck::new("render", CheckStatus::Error, message));
+            None
+        }
+        Err(e) => return Err(e),
+    };
+
+    checks.push(check_subject(&issue.subject));
+    if let Some(rendered) = &rendered {
+        checks.extend(check_rendered(rendered));
+    }
+
+    let errors = count_status(&checks, CheckStatus::Error);
+    let warnings = count_status(&checks, CheckStatus::Warning);
+
+    response::format_response(
+        200,
+        PreflightReport {
+            id: issue.issue_number.to_string(),
+            issue_number: issue.issue_number,
+            content_type,
+            template_id: issue.template_id.clone(),
+            passed: errors == 0,
+            errors,
+            warnings,
+            html_bytes: rendered.as_ref().map_or(0, |r| r.html.len()),
+            checks,
+        },
+    )
+}
+
+fn count_status(checks: &[PreflightCheck], status: CheckStatus) -> usize {
+    checks.iter().filter(|c| c.status == status).count()
+}
+
+// ── Rendering ──────────────────────────────────────────────────────────
+
+async fn render_issue(
+    tenant_id: &str,
+    issue: &IssueRecord,
+    content_type: &str,
+) -> Result<RenderedIssue, AppError> {
+    let metadata_preheader = issue.metadata.as_ref().and_then(preheader_from);
+
+    if content_type == "html" {
+        return Ok(RenderedIssue {
+            html: issue.content.clone(),
+            preheader: metadata_preheader,
+            ..RenderedIssue::default()
+        });
+    }
+
+    let (data, unresolved_shortcodes) = if content_type == "json" {
+        (
+            json_issue_data(&issue.content, issue.issue_number)?,
+            Vec::new(),
+        )
+    } else {
+        let snippet_names = load_snippets(tenant_id)
+            .await?
+            .into_iter()
+            .map(|s| s.name)
+            .collect::<HashSet<_>>();
+        (
+            markdown_issue_data(&issue.content, issue.issue_number),
+            unresolved_shortcodes(&issue.content, &snippet_names),
+        )
+    };
+
+    let template = match issue.template_id.as_deref() {
+        Some(template_id) => {
+            match templates::get_template_content(tenant_id, template_id).await? {
+                Some(content) => content,
+                // publish-issue falls back to the default for markdown; json
+                // has nothing to fall back to.
+                None if content_type != "json" => DEFAULT_TEMPLATE.to_string(),
+                None => {
+                    return Err(AppError::BadRequest(format!(
+                        "Template '{}' not found",
+                        template_id
+                    )))
+                }
+            }
+        }
+        None if content_type == "json" => {
+            return Err(AppError::BadRequest(
+                "JSON issues need a template to render".to_string(),
+            ))
+        }
+        None => DEFAULT_TEMPLATE.to_string(),
+    };
+
+    let snippets = load_snippets(tenant_id).await?;
+    let html = template_render::render_template(&template, &data, &snippets)?;
+
+    Ok(RenderedIssue {
+        html,
+        unresolved_partials: template_render::unresolved_partials(&template, &snippets),
+        unresolved_shortcodes,
+        preheader: metadata_preheader.or_else(|| preheader_from(&data)),
+    })
+}
+
+async fn load_snippets(tenant_id: &str) -> Result<Vec<template_render::Snippet>, AppError> {
+    Ok(snippets::query_snippets_by_tenant(tenant_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+/// Mirrors parse-json-issue: the content is the template data and
+/// `metadata.number` is always the issue number.
+fn json_issue_data(content: &str, issue_number: i32) -> Result<Value, AppError> {
+    let mut data: Value = serde_json::from_str(content)
+        .map_err(|e| AppError::BadRequest(format!("Issue content is not valid JSON: {}", e)))?;
+    let Some(object) = data.as_object_mut() else {
+        return Err(AppError::BadRequest(
+            "Issue content must be a JSON object".to_string(),
+        ));
+    };
+    let metadata = object
+        .entry("metadata")
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !metadata.is_object() {
+        *metadata = Value::Object(Map::new());
+    }
+    metadata["number"] = json!(issue_number);
+    Ok(data)
+}
+
+/// Data shaped like parse-md-to-json's output: front matter becomes
+/// `metadata`, each `### ` heading a section, and the "Tip of the week" and
+/// "Last words" sections move to their own slots.
+fn markdown_issue_data(content: &str, issue_number: i32) -> Value {
+    let front_matter = parse_front_matter(content);
+    let body = strip_front_matter(content);
+
+    let mut sections = Vec::new();
+    let mut content_slots = Map::new();
+    for chunk in body.split("### ") {
+        let (header, text) = chunk.split_once('\n').unwrap_or(("", chunk));
+        let header = header.trim();
+        if header.is_empty() {
+            continue;
+        }
+        let html = markdown_to_html(text);
+        let lower = header.to_lowercase();
+        if lower.contains("tip of the week") {
+            content_slots.insert("tipOfTheWeek".to_string(), json!({ "text": html }));
+        } else if lower.contains("last words") {
+            content_slots.insert("lastWords".to_string(), json!(html));
+        } else {
+            sections.push(json!({ "header": header, "text": html }));
+        }
+    }
+    content_slots.insert("sections".to_string(), json!(sections));
+
+    let field = |key: &str| front_matter.get(key).cloned();
+    let mut metadata = json!({
+        "number": issue_number,
+        "url": format!("https://readysetcloud.io/newsletter/{}", issue_number),
+    });
+    for key in ["title", "description", "date", "preheader"] {
+        if let Some(value) = field(key) {
+            metadata[key] = json!(value);
+        }
+    }
+
+    json!({ "metadata": metadata, "content": content_slots })
+}
+
+/// Top-level `key: value` pairs of a `---` front matter block.
+fn parse_front_matter(content: &str) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+    let mut lines = content.trim_start().lines();
+    if lines.next().map(str::trim_end) != Some("---") {
+        return fields;
+    }
+    for line in lines {
+        if line.trim_end() == "---" {
+            break;
+        }
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            if !value.is_empty() {
+                fields.insert(key.trim().to_string(), value.to_string());
+            }
+        }
+    }
+    fields
+}
+
+fn markdown_image_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"!\[([^\]]*)\]\(\s*([^)\s]*)(?:\s+"[^"]*")?\s*\)"#)
+            .expect("Failed to compile markdown image regex")
+    })
+}
+
+fn markdown_link_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"\[([^\]]*)\]\(\s*([^)\s]*)(?:\s+"[^"]*")?\s*\)"#)
+            .expect("Failed to compile markdown link regex")
+    })
+}
+
+fn autolink_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"<(https?://[^>\s]+)>").expect("Failed to compile autolink regex")
+    })
+}
+
+/// Paragraphs, images and links; everything else (including raw HTML) passes
+/// through. Line breaks inside a paragraph become `<br>` like
+/// parse-md-to-json's.
+fn markdown_to_html(text: &str) -> String {
+    text.split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|paragraph| {
+            let html = paragraph.lines().collect::<Vec<_>>().join("<br>");
+            let html = markdown_image_regex().replace_all(&html, r#"<img src="$2" alt="$1">"#);
+            let html = markdown_link_regex().replace_all(&html, r#"<a href="$2">$1</a>"#);
+            let html = autolink_regex().replace_all(&html, r#"<a href="$1">$1</a>"#);
+            format!("<p>{}</p>", html)
+        })
+        .collect()
+}
+
+fn shortcode_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"\{\{<\s*([a-zA-Z][a-zA-Z0-9_-]*)").expect("Failed to compile shortcode regex")
+    })
+}
+
+/// Body shortcodes (`{{< name >}}`) with no snippet behind them.
+/// parse-md-to-json leaves these in the email as literal text.
+fn unresolved_shortcodes(content: &str, snippet_names: &HashSet<String>) -> Vec<String> {
+    let mut missing: Vec<String> = shortcode_regex()
+        .captures_iter(content)
+        .filter_map(|c| c.get(1).map(|m| m.as_str().to_string()))
+        .filter(|name| {
+            !BUILT_IN_SHORTCODES.contains(&name.as_str()) && !snippet_names.contains(name)
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    missing.sort();
+    missing
+}
+
+/// `preheader`, then `metadata.preheader`, then `description` /
+/// `metadata.description` (which the default template uses as its preheader).
+fn preheader_from(data: &Value) -> Option<String> {
+    let paths: [&[&str]; 4] = [
+        &["preheader"],
+        &["metadata", "preheader"],
+        &["description"],
+        &["metadata", "description"],
+    ];
+    paths.iter().find_map(|path| {
+        path.iter()
+            .try_fold(data, |value, key| value.get(key))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    })
+}
+
+// ── Checks ─────────────────────────────────────────────────────────────
+
+fn check_rendered(rendered: &RenderedIssue) -> Vec<PreflightCheck> {
+    let tags = parse_tags(&rendered.html);
+    let hrefs: Vec<&str> = tags
+        .iter()
+        .filter(|t| t.name == "a")
+        .filter_map(|t| t.attributes.get("href").map(String::as_str))
+        .collect();
+    let images: Vec<&HtmlTag> = tags.iter().filter(|t| t.name == "img").collect();
+
+    vec![
+        check_links(&hrefs),
+        check_duplicate_links(&hrefs),
+        check_image_alt(&images),
+        check_partials(rendered),
+        check_unsubscribe(&rendered.html, &hrefs),
+        check_preheader(rendered.preheader.as_deref()),
+        check_size(rendered.html.len()),
+        check_image_ratio(&rendered.html, &images),
+    ]
+}
+
+fn check_subject(subject: &str) -> PreflightCheck {
+    let length = subject.trim().chars().count();
+    if length == 0 {
+        PreflightCheck::new("subject", CheckStatus::Error, "Subject is empty")
+    } else if length > SUBJECT_MAX_CHARS {
+        PreflightCheck::new(
+            "subject",
+            CheckStatus::Warning,
+            format!(
+                "Subject is {} characters; most clients cut it off well before {}",
+                length, SUBJECT_MAX_CHARS
+            ),
+        )
+    } else {
+        PreflightCheck::new("subject", CheckStatus::Pass, "Subject length is fine")
+    }
+}
+
+fn check_preheader(preheader: Option<&str>) -> PreflightCheck {
+    let length = preheader.map_or(0, |p| p.trim().chars().count());
+    if length == 0 {
+        PreflightCheck::new(
+            "preheader",
+            CheckStatus::Warning,
+            "No preheader; inboxes will preview the first text in the email instead",
+        )
+    } else if length > PREHEADER_MAX_CHARS {
+        PreflightCheck::new(
+            "preheader",
+            CheckStatus::Warning,
+            format!(
+                "Preheader is {} characters; inboxes show at most about {}",
+                length, PREHEADER_MAX_CHARS
+            ),
+        )
+    } else {
+        PreflightCheck::new("preheader", CheckStatus::Pass, "Preheader length is fine")
+    }
+}
+
+fn check_links(hrefs: &[&str]) -> PreflightCheck {
+    let problems: Vec<String> = hrefs
+        .iter()
+        .filter_map(|href| link_problem(href).map(|problem| format!("{}: {}", href, problem)))
+        .collect();
+    if problems.is_empty() {
+        PreflightCheck::new(
+            "links",
+            CheckStatus::Pass,
+            format!("{} links look valid", hrefs.len()),
+        )
+    } else {
+        PreflightCheck::new(
+            "links",
+            CheckStatus::Error,
+            format!("{} malformed links", problems.len()),
+        )
+        .with_details(problems)
+    }
+}
+
+/// Why a link won't work in an email, if it won't.
+fn link_problem(href: &str) -> Option<&'static str> {
+    let href = decode_entities(href.trim());
+    if href.is_empty() || href == "#" {
+        return Some("empty link");
+    }
+    if href.contains("{{") || href.contains("}}") {
+        return Some("unrendered template expression");
+    }
+    if href.contains(char::is_whitespace) {
+        return Some("contains whitespace");
+    }
+
+    let probe = PERSONALIZATION_TOKENS
+        .iter()
+        .fold(href.clone(), |acc, token| acc.replace(token, "token"));
+    if href == UNSUBSCRIBE_PLACEHOLDER {
+        return None;
+    }
+    let lower = probe.to_ascii_lowercase();
+
+    if let Some(address) = lower.strip_prefix("mailto:") {
+        return (!address.contains('@')).then_some("mailto link without an address");
+    }
+    if lower.starts_with("tel:") {
+        return None;
+    }
+    let Some(rest) = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"))
+    else {
+        if lower.starts_with("javascript:") {
+            return Some("script links are stripped by mail clients");
+        }
+        if lower.contains(':') {
+            return Some("unsupported scheme");
+        }
+        return Some("relative URL; email links must be absolute");
+    };
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default();
+    let valid_host = host.contains('.')
+        && !host.starts_with('.')
+        && !host.ends_with('.')
+        && !host.contains("..");
+    (!valid_host).then_some("missing or invalid host")
+}
+
+/// Case-insensitive scheme and host, no fragment or trailing slash.
+fn normalize_link(href: &str) -> Option<String> {
+    let href = decode_entities(href.trim());
+    let (scheme, rest) = href.split_once("://")?;
+    let scheme = scheme.to_ascii_lowercase();
+    if scheme != "http" && scheme != "https" {
+        return None;
+    }
+    let rest = rest.split('#').next().unwrap_or_default();
+    let (host, path) = rest.split_at(rest.find(['/', '?']).unwrap_or(rest.len()));
+    let path = path.strip_suffix('/').unwrap_or(path);
+    Some(format!(
+        "{}://{}{}",
+        scheme,
+        host.to_ascii_lowercase(),
+        path
+    ))
+}
+
+fn check_duplicate_links(hrefs: &[&str]) -> PreflightCheck {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for href in hrefs {
+        let Some(link) = normalize_link(href) else {
+            continue;
+        };
+        if PERSONALIZATION_TOKENS.iter().any(|t| link.contains(t)) {
+            continue;
+        }
+        match counts.iter_mut().find(|(existing, _)| *existing == link) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((link, 1)),
+        }
+    }
+
+    let duplicates: Vec<String> = counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(link, count)| format!("{} ({} times)", link, count))
+        .collect();
+    if duplicates.is_empty() {
+        PreflightCheck::new("duplicate-links", CheckStatus::Pass, "No duplicate links")
+    } else {
+        PreflightCheck::new(
+            "duplicate-links",
+            CheckStatus::Warning,
+            format!(
+                "{} links appear more than once, which splits their click counts",
+                duplicates.len()
+            ),
+        )
+        .with_details(duplicates)
+    }
+}
+
+fn check_image_alt(images: &[&HtmlTag]) -> PreflightCheck {
+    let missing: Vec<String> = images
+        .iter()
+        .filter(|img| !img.attributes.contains_key("alt") && !is_tracking_pixel(img))
+        .map(|img| img.attributes.get("src").cloned().unwrap_or_default())
+        .collect();
+    if missing.is_empty() {
+        PreflightCheck::new("image-alt", CheckStatus::Pass, "Every image has alt text")
+    } else {
+        PreflightCheck::new(
+            "image-alt",
+            CheckStatus::Warning,
+            format!(
+                "{} images have no alt attribute; they show as blank boxes when images are blocked",
+                missing.len()
+            ),
+        )
+        .with_details(missing)
+    }
+}
+
+fn check_partials(rendered: &RenderedIssue) -> PreflightCheck {
+    let mut details: Vec<String> = rendered
+        .unresolved_partials
+        .iter()
+        .map(|name| format!("{{{{> {} }}}} has no snippet and rendered empty", name))
+        .collect();
+    details.extend(rendered.unresolved_shortcodes.iter().map(|name| {
+        format!(
+            "{{{{< {} >}}}} has no snippet and is sent as literal text",
+            name
+        )
+    }));
+    if details.is_empty() {
+        PreflightCheck::new("partials", CheckStatus::Pass, "Every partial resolved")
+    } else {
+        PreflightCheck::new(
+            "partials",
+            CheckStatus::Error,
+            format!("{} partials or shortcodes did not resolve", details.len()),
+        )
+        .with_details(details)
+    }
+}
+
+fn check_unsubscribe(html: &str, hrefs: &[&str]) -> PreflightCheck {
+    let has_link = html.contains(UNSUBSCRIBE_PLACEHOLDER)
+        || hrefs
+            .iter()
+            .any(|href| href.to_ascii_lowercase().contains("unsubscribe"));
+    if has_link {
+        PreflightCheck::new("unsubscribe", CheckStatus::Pass, "Unsubscribe link present")
+    } else {
+        PreflightCheck::new(
+            "unsubscribe",
+            CheckStatus::Error,
+            format!(
+                "No unsubscribe link; add an unsubscribe link or the {} placeholder",
+                UNSUBSCRIBE_PLACEHOLDER
+            ),
+        )
+    }
+}
+
+fn check_size(bytes: usize) -> PreflightCheck {
+    let kb = bytes as f64 / 1024.0;
+    if bytes > GMAIL_CLIP_BYTES {
+        PreflightCheck::new(
+            "size",
+            CheckStatus::Error,
+            format!(
+                "HTML is {:.1}KB; Gmail clips anything over 102KB, hiding the rest of the email",
+                kb
+            ),
+        )
+    } else if bytes > GMAIL_CLIP_WARN_BYTES {
+        PreflightCheck::new(
+            "size",
+            CheckStatus::Warning,
+            format!(
+                "HTML is {:.1}KB, close to Gmail's 102KB clipping limit once links are tracked",
+                kb
+            ),
+        )
+    } else {
+        PreflightCheck::new("size", CheckStatus::Pass, format!("HTML is {:.1}KB", kb))
+    }
+}
+
+fn check_image_ratio(html: &str, images: &[&HtmlTag]) -> PreflightCheck {
+    let image_count = images.iter().filter(|img| !is_tracking_pixel(img)).count();
+    let text_chars = strip_html(html)
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .count();
+
+    if image_count > 0 && text_chars / image_count < MIN_TEXT_CHARS_PER_IMAGE {
+        PreflightCheck::new(
+            "image-ratio",
+            CheckStatus::Warning,
+            format!(
+                "{} images for {} characters of text; image-heavy emails are more likely to be filtered as spam",
+                image_count, text_chars
+            ),
+        )
+    } else {
+        PreflightCheck::new(
+            "image-ratio",
+            CheckStatus::Pass,
+            format!(
+                "{} images for {} characters of text",
+                image_count, text_chars
+            ),
+        )
+    }
+}
+
+/// 1x1 open-tracking images don't count as content.
+fn is_tracking_pixel(img: &HtmlTag) -> bool {
+    ["width", "height"]
+        .iter()
+        .any(|dim| img.attributes.get(*dim).map(|v| v.trim()) == Some("1"))
+}
+
+// ── HTML scanning ──────────────────────────────────────────────────────
+
+fn tag_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?is)<(a|img)\b([^>]*)>").expect("Failed to compile tag regex"))
+}
+
+fn attribute_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"([a-zA-Z_:][-a-zA-Z0-9_:.]*)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'=<>`]+)))?"#,
+        )
+        .expect("Failed to compile attribute regex")
+    })
+}
+
+/// `<a>` and `<img>` tags with their attributes (names lowercased; a bare
+/// attribute has an empty value).
+fn parse_tags(html: &str) -> Vec<HtmlTag> {
+    tag_regex()
+        .captures_iter(html)
+        .map(|tag| {
+            let attributes = attribute_regex()
+                .captures_iter(&tag[2])
+                .map(|attr| {
+                    let value = attr
+                        .get(2)
+                        .or_else(|| attr.get(3))
+                        .or_else(|| attr.get(4))
+                        .map_or("", |m| m.as_str());
+                    (attr[1].to_ascii_lowercase(), value.to_string())
+                })
+                .collect();
+            HtmlTag {
+                name: tag[1].to_ascii_lowercase(),
+                attributes,
+            }
+        })
+        .collect()
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statuses(rendered: &RenderedIssue) -> HashMap<&'static str, CheckStatus> {
+        check_rendered(rendered)
+            .into_iter()
+            .map(|c| (c.id, c.status))
+            .collect()
+    }
+
+    fn rendered(html: &str) -> RenderedIssue {
+        RenderedIssue {
+            html: html.to_string(),
+            preheader: Some("This week in serverless".to_string()),
+            ..RenderedIssue::default()
+        }
+    }
+
+    #[test]
+    fn test_link_problems() {
+        assert_eq!(link_problem("https://example.com/post?x=1"), None);
+        assert_eq!(link_problem("mailto:hi@example.com"), None);
+        assert_eq!(link_problem("__UNSUBSCRIBE_URL__"), None);
+        assert_eq!(
+            link_problem("https://example.com/unsubscribe?email=__EMAIL_HASH__"),
+            None
+        );
+        assert_eq!(link_problem("https://example.com/a?b=1&amp;c=2"), None);
+
+        assert_eq!(link_problem(""), Some("empty link"));
+        assert_eq!(link_problem("#"), Some("empty link"));
+        assert_eq!(
+            link_problem("https://{{ url }}"),
+            Some("unrendered template expression")
+        );
+        assert_eq!(
+            link_problem("https://example.com/my post"),
+            Some("contains whitespace")
+        );
+        assert_eq!(link_problem("https://"), Some("missing or invalid host"));
+        assert_eq!(
+            link_problem("http://localhost"),
+            Some("missing or invalid host")
+        );
+        assert_eq!(
+            link_problem("/blog/post"),
+            Some("relative URL; email links must be absolute")
+        );
+        assert_eq!(
+            link_problem("mailto:"),
+            Some("mailto link without an address")
+        );
+    }
+
+    #[test]
+    fn test_duplicate_links_ignore_case_slash_and_fragment() {
+        let check = check_duplicate_links(&[
+            "https://Example.com/post/",
+            "https://example.com/post#top",
+            "https://example.com/other",
+            "https://example.com/unsubscribe?e=__EMAIL_HASH__",
+            "https://example.com/unsubscribe?e=__EMAIL_HASH__",
+        ]);
+        assert_eq!(check.status, CheckStatus::Warning);
+        assert_eq!(check.details, vec!["https://example.com/post (2 times)"]);
+    }
+
+    #[test]
+    fn test_clean_email_passes() {
+        let html = format!(
+            "<p>{}</p><a href=\"https://example.com/a\">A</a><img src=\"https://example.com/i.png\" alt=\"Logo\"><a href='{}'>Unsubscribe</a>",
+            "Plenty of words here. ".repeat(20),
+            UNSUBSCRIBE_PLACEHOLDER
+        );
+        let result = statuses(&rendered(&html));
+        assert!(
+            result.values().all(|s| *s == CheckStatus::Pass),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_missing_unsubscribe_alt_and_image_heavy() {
+        let html = "<p>Hi</p><img src=\"a.png\"><img src=\"b.png\" alt=\"\"><img src=\"p.gif\" width=\"1\" height=\"1\">";
+        let result = statuses(&rendered(html));
+        assert_eq!(result["unsubscribe"], CheckStatus::Error);
+        assert_eq!(result["image-alt"], CheckStatus::Warning);
+        assert_eq!(result["image-ratio"], CheckStatus::Warning);
+
+        let alt = check_image_alt(&parse_tags(html).iter().collect::<Vec<_>>());
+        // alt="" marks a decorative image; the 1x1 pixel is ignored.
+        assert_eq!(alt.details, vec!["a.png"]);
+    }
+
+    #[test]
+    fn test_size_limits() {
+        assert_eq!(check_size(50 * 1024).status, CheckStatus::Pass);
+        assert_eq!(check_size(95 * 1024).status, CheckStatus::Warning);
+        assert_eq!(check_size(GMAIL_CLIP_BYTES + 1).status, CheckStatus::Error);
+    }
+
+    #[test]
+    fn test_subject_and_preheader() {
+        assert_eq!(check_subject("  ").status, CheckStatus::Error);
+        assert_eq!(check_subject("Weekly notes").status, CheckStatus::Pass);
+        assert_eq!(check_subject(&"x".repeat(101)).status, CheckStatus::Warning);
+
+        assert_eq!(check_preheader(None).status, CheckStatus::Warning);
+        assert_eq!(check_preheader(Some("Short")).status, CheckStatus::Pass);
+        assert_eq!(
+            check_preheader(Some(&"x".repeat(151))).status,
+            CheckStatus::Warning
+        );
+    }
+
+    #[test]
+    fn test_unresolved_partials_and_shortcodes_fail() {
+        let issue = RenderedIssue {
+            unresolved_partials: vec!["footer".to_string()],
+            unresolved_shortcodes: vec!["callout".to_string()],
+            ..rendered("<a href=\"https://example.com/unsubscribe\">Unsubscribe</a>")
+        };
+        let check = check_partials(&issue);
+        assert_eq!(check.status, CheckStatus::Error);
+        assert_eq!(
+            check.details,
+            vec![
+                "{{> footer }} has no snippet and rendered empty",
+                "{{< callout >}} has no snippet and is sent as literal text"
+            ]
+        );
+
+        let snippets: HashSet<String> = ["known".to_string()].into_iter().collect();
+        assert_eq!(
+            unresolved_shortcodes(
+                "{{< known >}} {{< sponsor >}} {{< callout text=\"x\" >}}",
+                &snippets
+            ),
+            vec!["callout"]
+        );
+    }
+
+    #[test]
+    fn test_markdown_issue_data() {
+        let content = "---\ntitle: \"Issue 42\"\ndescription: A look at queues\ntags:\n  - aws\n---\n### Queues\nRead [the post](https://example.com/q) and ![diagram](https://example.com/d.png).\n\nSecond paragraph\n### Last words\nBye\n";
+        let data = markdown_issue_data(content, 42);
+
+        assert_eq!(data["metadata"]["title"], "Issue 42");
+        assert_eq!(data["metadata"]["number"], 42);
+        assert_eq!(preheader_from(&data).as_deref(), Some("A look at queues"));
+        assert_eq!(data["content"]["sections"][0]["header"], "Queues");
+        assert_eq!(
+            data["content"]["sections"][0]["text"],
+            "<p>Read <a href=\"https://example.com/q\">the post</a> and <img src=\"https://example.com/d.png\" alt=\"diagram\">.</p><p>Second paragraph</p>"
+        );
+        assert_eq!(data["content"]["lastWords"], "<p>Bye</p>");
+        assert_eq!(data["content"]["sections"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_json_issue_data_sets_number() {
+        let data = json_issue_data(r#"{"metadata":{"title":"T"},"preheader":"P"}"#, 7).unwrap();
+        assert_eq!(data["metadata"]["number"], 7);
+        assert_eq!(preheader_from(&data).as_deref(), Some("P"));
+
+        assert!(json_issue_data("[1,2]", 7).is_err());
+        assert!(json_issue_data("not json", 7).is_err());
+    }
+
+    #[test]
+    fn test_default_template_passes_its_own_checks() {
+        let data = markdown_issue_data(
+            "---\ntitle: T\ndescription: D\n---\n### Section\nSee [docs](https://example.com/docs).\n",
+            3,
+        );
+        let html = template_render::render_template(DEFAULT_TEMPLATE, &data, &[]).unwrap();
+        let result = statuses(&RenderedIssue {
+            html,
+            preheader: preheader_from(&data),
+            ..RenderedIssue::default()
+        });
+        assert_eq!(result["unsubscribe"], CheckStatus::Pass);
+        assert_eq!(result["links"], CheckStatus::Pass);
+        assert_eq!(result["size"], CheckStatus::Pass);
+    }
+}
@@ -0,0 +1,452 @@
+//! Sponsorship revenue attributed to issues.
+//!
+//! Sponsorship entries (`sponsorship#<sponsorId>#<date>#<id>` under the
+//! tenant) name the issue they ran in via `issueId`, which is the issue's
+//! composite key (`<tenant>#<n>`). Booked and fulfilled entries count as the
+//! issue's revenue; drafts and cancellations don't. Sponsored clicks come from
+//! the entry's `clickCache` when present, otherwise from `clicks_total` on the
+//! issue's link records for `sponsorLinkIds`.
+//!
+//! Effective CPM is revenue per 1,000 deliveries and effective CPC is revenue
+//! per sponsored click; revenue per click uses every click in the issue.
+
+use aws_sdk_dynamodb::types::{AttributeValue, KeysAndAttributes};
+use newsletter::admin::{aws_clients, error::AppError};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::controllers::sponsors::{SponsorshipEntry, SPONSORSHIP_SK_PREFIX};
+
+/// Sponsorship statuses that count towards an issue's revenue.
+const REVENUE_STATUSES: [&str; 2] = ["booked", "fulfilled"];
+
+// ── Types ──────────────────────────────────────────────────────────────
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueSponsorship {
+    sponsorship_id: String,
+    sponsor_id: String,
+    status: String,
+    placement_type: String,
+    amount_charged: f64,
+    sponsored_clicks: i64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueSponsorshipRevenue {
+    pub revenue: f64,
+    pub sponsored_clicks: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revenue_per_delivery: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revenue_per_click: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_cpm: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_cpc: Option<f64>,
+    sponsorships: Vec<IssueSponsorship>,
+}
+
+impl IssueSponsorshipRevenue {
+    pub fn has_sponsorships(&self) -> bool {
+        !self.sponsorships.is_empty()
+    }
+}
+
+/// Revenue totals across a set of issues, with the same ratios as a single
+/// issue computed over the summed counters.
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RevenueTotals {
+    total_revenue: f64,
+    sponsored_issues: i64,
+    total_delivered: i64,
+    total_clicks: i64,
+    total_sponsored_clicks: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revenue_per_delivery: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revenue_per_click: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    effective_cpm: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    effective_cpc: Option<f64>,
+}
+
+// ── Loading ────────────────────────────────────────────────────────────
+
+/// Every revenue-counting sponsorship entry of the tenant, grouped by issue
+/// number. Entries that fail to parse or don't name an issue are skipped.
+pub(crate) async fn load_sponsorships_by_issue(
+    tenant_id: &str,
+) -> Result<HashMap<i32, Vec<SponsorshipEntry>>, AppError> {
+    let mut by_issue: HashMap<i32, Vec<SponsorshipEntry>> = HashMap::new();
+    for entry in query_revenue_sponsorships(tenant_id, None).await? {
+        if let Some(issue_number) = sponsorship_issue_number(&entry.issue_id) {
+            by_issue.entry(issue_number).or_default().push(entry);
+        }
+    }
+    Ok(by_issue)
+}
+
+/// The revenue-counting sponsorship entries that ran in one issue. The query
+/// filters on `issueId`, so other issues' entries never leave DynamoDB.
+pub(crate) async fn load_issue_sponsorships(
+    tenant_id: &str,
+    issue_number: i32,
+) -> Result<Vec<SponsorshipEntry>, AppError> {
+    let entries = query_revenue_sponsorships(tenant_id, Some(issue_number)).await?;
+    Ok(entries
+        .into_iter()
+        .filter(|entry| sponsorship_issue_number(&entry.issue_id) == Some(issue_number))
+        .collect())
+}
+
+/// Booked and fulfilled sponsorship entries of the tenant, narrowed to those
+/// naming `issue_number` (in either `issueId` form) when one is given.
+async fn query_revenue_sponsorships(
+    tenant_id: &str,
+    issue_number: Option<i32>,
+) -> Result<Vec<SponsorshipEntry>, AppError> {
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let table_name = get_table_name()?;
+    let mut entries = Vec::new();
+    let mut exclusive_start_key = None;
+
+    loop {
+        let mut request = ddb_client
+            .query()
+            .table_name(&table_name)
+            .key_condition_expression("pk = :pk AND begins_with(sk, :sk_prefix)")
+            .expression_attribute_values(":pk", AttributeValue::S(tenant_id.to_string()))
+            .expression_attribute_values(
+                ":sk_prefix",
+                AttributeValue::S(SPONSORSHIP_SK_PREFIX.to_string()),
+            )
+            .set_exclusive_start_key(exclusive_start_key.take());
+        if let Some(issue_number) = issue_number {
+            request = request
+                .filter_expression("issueId IN (:issue_key, :issue_number)")
+                .expression_attribute_values(
+                    ":issue_key",
+                    AttributeValue::S(format!("{}#{}", tenant_id, issue_number)),
+                )
+                .expression_attribute_values(
+                    ":issue_number",
+                    AttributeValue::S(issue_number.to_string()),
+                );
+        }
+        let result = request.send().await?;
+
+        for item in result.items() {
+            let Ok(entry) = serde_dynamo::from_item::<_, SponsorshipEntry>(item.clone()) else {
+                continue;
+            };
+            if REVENUE_STATUSES.contains(&entry.status.as_str()) {
+                entries.push(entry);
+            }
+        }
+
+        match result.last_evaluated_key() {
+            Some(key) if !key.is_empty() => exclusive_start_key = Some(key.clone()),
+            _ => break,
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Revenue for one issue from its sponsorship entries and engagement
+/// counters. Sponsored clicks are read from each entry's cache or link records.
+pub(crate) async fn build_issue_revenue(
+    tenant_id: &str,
+    issue_number: i32,
+    entries: &[SponsorshipEntry],
+    deliveries: i64,
+    clicks: i64,
+) -> Result<IssueSponsorshipRevenue, AppError> {
+    let mut sponsorships = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let sponsored_clicks = match &entry.click_cache {
+            Some(cache) => cache.total_clicks as i64,
+            None => link_clicks(tenant_id, issue_number, &entry.sponsor_link_ids).await?,
+        };
+        sponsorships.push(IssueSponsorship {
+            sponsorship_id: entry.sponsorship_id.clone(),
+            sponsor_id: entry.sponsor_id.clone(),
+            status: entry.status.clone(),
+            placement_type: entry.placement_type.clone(),
+            amount_charged: entry.amount_charged,
+            sponsored_clicks,
+        });
+    }
+
+    Ok(summarize_issue_revenue(sponsorships, deliveries, clicks))
+}
+
+/// Sum of `clicks_total` across an issue's link records (`link#<id>`).
+async fn link_clicks(
+    tenant_id: &str,
+    issue_number: i32,
+    link_ids: &[String],
+) -> Result<i64, AppError> {
+    if link_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let table_name = get_table_name()?;
+    let pk = format!("{}#{}", tenant_id, issue_number);
+
+    let mut keys_and_attributes =
+        KeysAndAttributes::builder().projection_expression("clicks_total");
+    for link_id in link_ids {
+        keys_and_attributes = keys_and_attributes.keys(HashMap::from([
+            ("pk".to_string(), AttributeValue::S(pk.clone())),
+            (
+                "sk".to_string(),
+                AttributeValue::S(format!("link#{}", link_id)),
+            ),
+        ]));
+    }
+    let mut pending = Some(keys_and_attributes.build().map_err(|e| {
+        AppError::InternalError(format!("Failed to build KeysAndAttributes: {}", e))
+    })?);
+
+    let mut total = 0;
+    while let Some(request) = pending.take() {
+        let result = ddb_client
+            .batch_get_item()
+            .request_items(&table_name, request)
+            .send()
+            .await
+            .map_err(|e| AppError::AwsError(format!("DynamoDB BatchGetItem error: {}", e)))?;
+
+        if let Some(items) = result.responses().and_then(|r| r.get(&table_name)) {
+            total += items
+                .iter()
+                .filter_map(|item| item.get("clicks_total"))
+                .filter_map(|v| v.as_n().ok())
+                .filter_map(|n| n.parse::<i64>().ok())
+                .sum::<i64>();
+        }
+
+        pending = result
+            .unprocessed_keys()
+            .and_then(|keys| keys.get(&table_name))
+            .filter(|keys| !keys.keys().is_empty())
+            .cloned();
+    }
+
+    Ok(total)
+}
+
+fn get_table_name() -> Result<String, AppError> {
+    std::env::var("TABLE_NAME")
+        .map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))
+}
+
+// ── Calculations ───────────────────────────────────────────────────────
+
+/// Issue number from a sponsorship's `issueId`: the composite key
+/// (`<tenant>#<n>`) or a bare number.
+fn sponsorship_issue_number(issue_id: &str) -> Option<i32> {
+    issue_id
+        .rsplit('#')
+        .next()
+        .and_then(|n| n.trim().parse::<i32>().ok())
+        .filter(|n| *n > 0)
+}
+
+fn ratio(numerator: f64, denominator: i64, scale: f64, decimals: i32) -> Option<f64> {
+    if denominator <= 0 {
+        return None;
+    }
+    let factor = 10f64.powi(decimals);
+    Some((numerator * scale / denominator as f64 * factor).round() / factor)
+}
+
+fn round_money(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+fn summarize_issue_revenue(
+    sponsorships: Vec<IssueSponsorship>,
+    deliveries: i64,
+    clicks: i64,
+) -> IssueSponsorshipRevenue {
+    let revenue: f64 = sponsorships.iter().map(|s| s.amount_charged).sum();
+    let sponsored_clicks: i64 = sponsorships.iter().map(|s| s.sponsored_clicks).sum();
+
+    IssueSponsorshipRevenue {
+        revenue: round_money(revenue),
+        sponsored_clicks,
+        revenue_per_delivery: ratio(revenue, deliveries, 1.0, 4),
+        revenue_per_click: ratio(revenue, clicks, 1.0, 4),
+        effective_cpm: ratio(revenue, deliveries, 1000.0, 2),
+        effective_cpc: ratio(revenue, sponsored_clicks, 1.0, 2),
+        sponsorships,
+    }
+}
+
+/// Totals over `(revenue, deliveries, clicks)` per issue.
+pub(crate) fn summarize_revenue_totals<'a>(
+    issues: impl IntoIterator<Item = (&'a IssueSponsorshipRevenue, i64, i64)>,
+) -> RevenueTotals {
+    let mut total_revenue = 0.0;
+    let mut sponsored_issues = 0;
+    let mut total_delivered = 0;
+    let mut total_clicks = 0;
+    let mut total_sponsored_clicks = 0;
+
+    for (revenue, deliveries, clicks) in issues {
+        total_revenue += revenue.revenue;
+        if revenue.has_sponsorships() {
+            sponsored_issues += 1;
+        }
+        total_delivered += deliveries;
+        total_clicks += clicks;
+        total_sponsored_clicks += revenue.sponsored_clicks;
+    }
+
+    RevenueTotals {
+        total_revenue: round_money(total_revenue),
+        sponsored_issues,
+        total_delivered,
+        total_clicks,
+        total_sponsored_clicks,
+        revenue_per_delivery: ratio(total_revenue, total_delivered, 1.0, 4),
+        revenue_per_click: ratio(total_revenue, total_clicks, 1.0, 4),
+        effective_cpm: ratio(total_revenue, total_delivered, 1000.0, 2),
+        effective_cpc: ratio(total_revenue, total_sponsored_clicks, 1.0, 2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sponsorship(id: &str, amount_charged: f64, sponsored_clicks: i64) -> IssueSponsorship {
+        IssueSponsorship {
+            sponsorship_id: id.to_string(),
+            sponsor_id: "sponsor-1".to_string(),
+            status: "fulfilled".to_string(),
+            placement_type: "primary".to_string(),
+            amount_charged,
+            sponsored_clicks,
+        }
+    }
+
+    #[test]
+    fn test_sponsorship_issue_number_accepts_composite_and_bare_ids() {
+        assert_eq!(sponsorship_issue_number("tenant-1#42"), Some(42));
+        assert_eq!(sponsorship_issue_number("42"), Some(42));
+        assert_eq!(sponsorship_issue_number("tenant-1#draft"), None);
+        assert_eq!(sponsorship_issue_number("tenant-1#0"), None);
+        assert_eq!(sponsorship_issue_number(""), None);
+    }
+
+    #[test]
+    fn test_summarize_issue_revenue_computes_unit_economics() {
+        let revenue = summarize_issue_revenue(
+            vec![
+                sponsorship("s-1", 400.0, 150),
+                sponsorship("s-2", 100.0, 50),
+            ],
+            10_000,
+            800,
+        );
+
+        assert_eq!(revenue.revenue, 500.0);
+        assert_eq!(revenue.sponsored_clicks, 200);
+        assert_eq!(revenue.revenue_per_delivery, Some(0.05));
+        assert_eq!(revenue.revenue_per_click, Some(0.625));
+        assert_eq!(revenue.effective_cpm, Some(50.0));
+        assert_eq!(revenue.effective_cpc, Some(2.5));
+        assert!(revenue.has_sponsorships());
+    }
+
+    #[test]
+    fn test_summarize_issue_revenue_omits_ratios_without_denominators() {
+        let revenue = summarize_issue_revenue(vec![sponsorship("s-1", 250.0, 0)], 0, 0);
+
+        let json = serde_json::to_value(&revenue).unwrap();
+        assert_eq!(json["revenue"], json!(250.0));
+        assert!(json.get("revenuePerDelivery").is_none());
+        assert!(json.get("effectiveCpm").is_none());
+        assert!(json.get("effectiveCpc").is_none());
+        assert_eq!(json["sponsorships"][0]["amountCharged"], json!(250.0));
+    }
+
+    #[test]
+    fn test_summarize_revenue_totals_uses_summed_counters() {
+        let sponsored = summarize_issue_revenue(vec![sponsorship("s-1", 300.0, 60)], 5_000, 400);
+        let unsponsored = summarize_issue_revenue(Vec::new(), 5_000, 200);
+
+        let totals =
+            summarize_revenue_totals([(&sponsored, 5_000, 400), (&unsponsored, 5_000, 200)]);
+
+        assert_eq!(totals.total_revenue, 300.0);
+        assert_eq!(totals.sponsored_issues, 1);
+        assert_eq!(totals.total_delivered, 10_000);
+        assert_eq!(totals.total_clicks, 600);
+        assert_eq!(totals.effective_cpm, Some(30.0));
+        assert_eq!(totals.revenue_per_click, Some(0.5));
+        assert_eq!(totals.effective_cpc, Some(5.0));
+    }
+
+    #[test]
+    fn test_sponsorship_entry_parses_from_dynamo_item() {
+        let item: HashMap<String, AttributeValue> = HashMap::from([
+            (
+                "sponsorshipId".to_string(),
+                AttributeValue::S("s-1".to_string()),
+            ),
+            (
+                "sponsorId".to_string(),
+                AttributeValue::S("sp-1".to_string()),
+            ),
+            (
+                "issueId".to_string(),
+                AttributeValue::S("tenant-1#7".to_string()),
+            ),
+            (
+                "issueTitle".to_string(),
+                AttributeValue::S("Issue 7".to_string()),
+            ),
+            (
+                "sponsorshipDate".to_string(),
+                AttributeValue::S("2025-03-01".to_string()),
+            ),
+            (
+                "amountCharged".to_string(),
+                AttributeValue::N("450.5".to_string()),
+            ),
+            (
+                "status".to_string(),
+                AttributeValue::S("fulfilled".to_string()),
+            ),
+            (
+                "placementType".to_string(),
+                AttributeValue::S("primary".to_string()),
+            ),
+            ("sponsorLinkIds".to_string(), AttributeValue::L(vec![])),
+            (
+                "createdAt".to_string(),
+                AttributeValue::S("2025-02-01T00:00:00Z".to_string()),
+            ),
+            (
+                "updatedAt".to_string(),
+                AttributeValue::S("2025-02-01T00:00:00Z".to_string()),
+            ),
+        ]);
+
+        let entry: SponsorshipEntry = serde_dynamo::from_item(item).unwrap();
+        assert_eq!(entry.amount_charged, 450.5);
+        assert_eq!(sponsorship_issue_number(&entry.issue_id), Some(7));
+        assert!(entry.click_cache.is_none());
+    }
+}
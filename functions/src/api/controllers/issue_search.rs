@@ -0,0 +1,1271 @@
+//! Full-text search across a tenant's issues (`GET /issues/search`).
+//!
+//! Each issue has a search document next to it (`pk = {tenant}#{n}`,
+//! `sk = "search"`) holding its content reduced to plain text — markdown
+//! syntax, HTML tags and JSON structure stripped. The issue endpoints rewrite
+//! it on create/update and remove it on delete. Search lists the tenant's
+//! issues from GSI1, applies the status/date filters, loads their documents
+//! and ranks them in-process with BM25 (subject matches weighted higher).
+//! A document that is missing or older than its issue (issues written outside
+//! the API, or created before search existed) is rebuilt on the spot, so the
+//! index heals itself without a backfill job.
+
+use aws_sdk_dynamodb::types::{AttributeValue, KeysAndAttributes, PutRequest, WriteRequest};
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use newsletter::admin::{auth, aws_clients, error::AppError, response};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Sort key of an issue's search document; deleted with the issue.
+pub(crate) const SEARCH_SK: &str = "search";
+
+/// Bump when text extraction changes so existing documents are rebuilt.
+const SEARCH_INDEX_VERSION: i64 = 1;
+
+/// Documents are capped well under DynamoDB's 400 KB item limit.
+const MAX_INDEXED_TEXT_BYTES: usize = 100_000;
+
+const MAX_QUERY_LEN: usize = 200;
+const MAX_QUERY_TERMS: usize = 10;
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 50;
+const MAX_BATCH_GET: usize = 100;
+const MAX_BATCH_WRITE: usize = 25;
+
+const SEARCH_STATUSES: [&str; 5] = ["draft", "scheduled", "in progress", "published", "failed"];
+
+// BM25 parameters (the usual defaults).
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+/// A subject occurrence counts as this many body occurrences.
+const SUBJECT_BOOST: f64 = 3.0;
+
+/// A query term that is only a prefix of a word ("lambda" in "lambdas")
+/// counts for half an exact match. Shorter terms match exactly only.
+const PREFIX_MATCH_WEIGHT: f64 = 0.5;
+const MIN_PREFIX_LEN: usize = 3;
+
+/// Snippet window, in words, and how many words of context precede the
+/// first match in it.
+const SNIPPET_WORDS: usize = 30;
+const SNIPPET_LEAD_WORDS: usize = 8;
+
+const STOP_WORDS: [&str; 20] = [
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it", "of", "on",
+    "or", "that", "the", "to", "with",
+];
+
+// ── Request/Response types ─────────────────────────────────────────────
+
+#[derive(Debug)]
+struct SearchIssuesQuery {
+    q: String,
+    terms: Vec<String>,
+    status: Option<String>,
+    from: Option<chrono::DateTime<chrono::Utc>>,
+    to: Option<chrono::DateTime<chrono::Utc>>,
+    limit: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchIssuesResponse {
+    query: String,
+    /// Matching issues before `limit` is applied.
+    total: usize,
+    results: Vec<SearchResult>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResult {
+    id: String,
+    issue_number: i32,
+    subject: String,
+    status: String,
+    created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    published_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_at: Option<String>,
+    score: f64,
+    /// Subject, HTML-escaped, with matched words wrapped in `<mark>`.
+    subject_highlight: String,
+    /// Best-matching passage of the content, escaped and marked the same way.
+    snippet: String,
+}
+
+/// The GSI1 view of an issue: enough to filter and to spot stale documents.
+#[derive(Debug, Clone)]
+struct IssueSummary {
+    issue_number: i32,
+    subject: String,
+    status: String,
+    created_at: String,
+    published_at: Option<String>,
+    scheduled_at: Option<String>,
+    updated_at: String,
+}
+
+#[derive(Debug, Clone)]
+struct SearchDocument {
+    text: String,
+    source_updated_at: String,
+    version: i64,
+}
+
+// ── Handler ────────────────────────────────────────────────────────────
+
+pub async fn search_issues(event: Request) -> Result<Response<Body>, Error> {
+    match handle_search_issues(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_search_issues(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Unauthorized("Tenant access required".to_string()))?;
+
+    let params = event.query_string_parameters();
+    let query = parse_search_query(
+        params.first("q"),
+        params.first("status"),
+        params.first("from"),
+        params.first("to"),
+        params.first("limit"),
+    )?;
+
+    let issues: Vec<IssueSummary> = list_issue_summaries(&tenant_id)
+        .await?
+        .into_iter()
+        .filter(|issue| matches_filters(issue, &query))
+        .collect();
+
+    let issue_numbers: Vec<i32> = issues.iter().map(|issue| issue.issue_number).collect();
+    let mut documents = load_search_documents(&tenant_id, &issue_numbers).await?;
+
+    let stale: Vec<i32> = issues
+        .iter()
+        .filter(|issue| {
+            documents.get(&issue.issue_number).is_none_or(|doc| {
+                doc.version != SEARCH_INDEX_VERSION || doc.source_updated_at != issue.updated_at
+            })
+        })
+        .map(|issue| issue.issue_number)
+        .collect();
+
+    if !stale.is_empty() {
+        tracing::info!(
+            tenant_id = %tenant_id,
+            count = stale.len(),
+            "Rebuilding stale issue search documents"
+        );
+        let rebuilt = rebuild_search_documents(&tenant_id, &stale).await?;
+        documents.extend(rebuilt);
+    }
+
+    let indexed: Vec<(&IssueSummary, &SearchDocument)> = issues
+        .iter()
+        .filter_map(|issue| documents.get(&issue.issue_number).map(|doc| (issue, doc)))
+        .collect();
+
+    let fields: Vec<(&str, &str)> = indexed
+        .iter()
+        .map(|(issue, doc)| (issue.subject.as_str(), doc.text.as_str()))
+        .collect();
+    let ranked = rank_documents(&fields, &query.terms);
+
+    let results = ranked
+        .iter()
+        .take(query.limit)
+        .map(|&(index, score)| {
+            let (issue, doc) = indexed[index];
+            SearchResult {
+                id: issue.issue_number.to_string(),
+                issue_number: issue.issue_number,
+                subject: issue.subject.clone(),
+                status: issue.status.clone(),
+                created_at: issue.created_at.clone(),
+                published_at: issue.published_at.clone(),
+                scheduled_at: issue.scheduled_at.clone(),
+                score: (score * 1000.0).round() / 1000.0,
+                subject_highlight: highlight(&issue.subject, &query.terms),
+                snippet: build_snippet(&doc.text, &query.terms),
+            }
+        })
+        .collect();
+
+    response::format_response(
+        200,
+        SearchIssuesResponse {
+            query: query.q,
+            total: ranked.len(),
+            results,
+        },
+    )
+}
+
+fn parse_search_query(
+    q: Option<&str>,
+    status: Option<&str>,
+    from: Option<&str>,
+    to: Option<&str>,
+    limit: Option<&str>,
+) -> Result<SearchIssuesQuery, AppError> {
+    let q = q.map(str::trim).unwrap_or_default();
+    if q.is_empty() {
+        return Err(AppError::BadRequest(
+            "Query parameter q is required".to_string(),
+        ));
+    }
+    if q.chars().count() > MAX_QUERY_LEN {
+        return Err(AppError::BadRequest(format!(
+            "q must be at most {} characters",
+            MAX_QUERY_LEN
+        )));
+    }
+
+    let terms = query_terms(q);
+    if terms.is_empty() {
+        return Err(AppError::BadRequest(
+            "q must contain at least one word".to_string(),
+        ));
+    }
+
+    let status = status.map(str::trim).filter(|s| !s.is_empty());
+    if let Some(status) = status {
+        if !SEARCH_STATUSES.contains(&status) {
+            return Err(AppError::BadRequest(format!(
+                "Invalid status. Must be one of: {}",
+                SEARCH_STATUSES.join(", ")
+            )));
+        }
+    }
+
+    let from = from
+        .map(|value| parse_date_bound(value, "from", false))
+        .transpose()?;
+    let to = to
+        .map(|value| parse_date_bound(value, "to", true))
+        .transpose()?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(AppError::BadRequest(
+                "from must not be after to".to_string(),
+            ));
+        }
+    }
+
+    let limit = match limit {
+        None => DEFAULT_SEARCH_LIMIT,
+        Some(value) => value
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|limit| (1..=MAX_SEARCH_LIMIT).contains(limit))
+            .ok_or_else(|| {
+                AppError::BadRequest(format!("Limit must be between 1 and {}", MAX_SEARCH_LIMIT))
+            })?,
+    };
+
+    Ok(SearchIssuesQuery {
+        q: q.to_string(),
+        terms,
+        status: status.map(str::to_string),
+        from,
+        to,
+        limit,
+    })
+}
+
+/// Accepts an RFC 3339 timestamp or a plain date. A plain `to` date covers
+/// the whole day (UTC).
+fn parse_date_bound(
+    value: &str,
+    field: &str,
+    end_of_day: bool,
+) -> Result<chrono::DateTime<chrono::Utc>, AppError> {
+    let value = value.trim();
+    if let Ok(instant) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(instant.with_timezone(&chrono::Utc));
+    }
+
+    let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        AppError::BadRequest(format!(
+            "{} must be a date (YYYY-MM-DD) or an RFC 3339 timestamp",
+            field
+        ))
+    })?;
+    let time = if end_of_day {
+        chrono::NaiveTime::from_hms_milli_opt(23, 59, 59, 999)
+    } else {
+        chrono::NaiveTime::from_hms_opt(0, 0, 0)
+    }
+    .expect("valid time of day");
+
+    Ok(date.and_time(time).and_utc())
+}
+
+/// Filters on status and on the issue's date: when it was published, else
+/// when it is scheduled, else when it was created.
+fn matches_filters(issue: &IssueSummary, query: &SearchIssuesQuery) -> bool {
+    if query.status.as_deref().is_some_and(|s| s != issue.status) {
+        return false;
+    }
+    if query.from.is_none() && query.to.is_none() {
+        return true;
+    }
+
+    let date = issue
+        .published_at
+        .as_deref()
+        .or(issue.scheduled_at.as_deref())
+        .unwrap_or(&issue.created_at);
+    let Ok(date) = chrono::DateTime::parse_from_rfc3339(date) else {
+        return false;
+    };
+    let date = date.with_timezone(&chrono::Utc);
+
+    query.from.is_none_or(|from| date >= from) && query.to.is_none_or(|to| date <= to)
+}
+
+// ── Index maintenance (called from the issue endpoints) ────────────────
+
+/// Writes (or rewrites) the search document for an issue. Best-effort: a
+/// failure is logged and the document is rebuilt by the next search that
+/// finds it stale.
+pub(crate) async fn index_issue(
+    tenant_id: &str,
+    issue_number: i32,
+    content: &str,
+    content_type: Option<&str>,
+    updated_at: &str,
+) {
+    let doc = SearchDocument {
+        text: extract_search_text(content, content_type.unwrap_or("markdown")),
+        source_updated_at: updated_at.to_string(),
+        version: SEARCH_INDEX_VERSION,
+    };
+
+    if let Err(e) = put_search_documents(tenant_id, &[(issue_number, doc)]).await {
+        tracing::warn!(
+            tenant_id = %tenant_id,
+            issue_number = issue_number,
+            error = ?e,
+            "Failed to index issue for search"
+        );
+    }
+}
+
+fn get_table_name() -> Result<String, AppError> {
+    std::env::var("TABLE_NAME")
+        .map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))
+}
+
+fn issue_key(tenant_id: &str, issue_number: i32, sk: &str) -> HashMap<String, AttributeValue> {
+    HashMap::from([
+        (
+            "pk".to_string(),
+            AttributeValue::S(format!("{}#{}", tenant_id, issue_number)),
+        ),
+        ("sk".to_string(), AttributeValue::S(sk.to_string())),
+    ])
+}
+
+fn issue_number_from_pk(item: &HashMap<String, AttributeValue>) -> Option<i32> {
+    item.get("pk")
+        .and_then(|v| v.as_s().ok())
+        .and_then(|pk| pk.rsplit('#').next())
+        .and_then(|n| n.parse::<i32>().ok())
+}
+
+fn string_attr(item: &HashMap<String, AttributeValue>, name: &str) -> Option<String> {
+    item.get(name).and_then(|v| v.as_s().ok()).cloned()
+}
+
+async fn list_issue_summaries(tenant_id: &str) -> Result<Vec<IssueSummary>, AppError> {
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let table_name = get_table_name()?;
+
+    let mut issues = Vec::new();
+    let mut exclusive_start_key = None;
+    loop {
+        let result = ddb_client
+            .query()
+            .table_name(&table_name)
+            .index_name("GSI1")
+            .key_condition_expression("GSI1PK = :gsi1pk")
+            .expression_attribute_values(
+                ":gsi1pk",
+                AttributeValue::S(format!("{}#newsletter", tenant_id)),
+            )
+            .projection_expression(
+                "pk, subject, #status, createdAt, publishedAt, scheduledAt, updatedAt",
+            )
+            .expression_attribute_names("#status", "status")
+            .set_exclusive_start_key(exclusive_start_key)
+            .send()
+            .await?;
+
+        issues.extend(result.items().iter().filter_map(|item| {
+            Some(IssueSummary {
+                issue_number: issue_number_from_pk(item)?,
+                subject: string_attr(item, "subject").unwrap_or_default(),
+                status: string_attr(item, "status")?,
+                created_at: string_attr(item, "createdAt")?,
+                published_at: string_attr(item, "publishedAt"),
+                scheduled_at: string_attr(item, "scheduledAt"),
+                updated_at: string_attr(item, "updatedAt").unwrap_or_default(),
+            })
+        }));
+
+        exclusive_start_key = result.last_evaluated_key().cloned();
+        if exclusive_start_key.is_none() {
+            break;
+        }
+    }
+
+    Ok(issues)
+}
+
+/// BatchGetItem over `(tenant, issue number, sk)` keys, following
+/// UnprocessedKeys until everything has been read.
+async fn batch_get_issue_items(
+    tenant_id: &str,
+    issue_numbers: &[i32],
+    sk: &str,
+    projection: &str,
+) -> Result<Vec<HashMap<String, AttributeValue>>, AppError> {
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let table_name = get_table_name()?;
+    let mut items = Vec::new();
+
+    for chunk in issue_numbers.chunks(MAX_BATCH_GET) {
+        let keys = chunk
+            .iter()
+            .map(|issue_number| issue_key(tenant_id, *issue_number, sk))
+            .collect();
+
+        let mut pending = Some(
+            KeysAndAttributes::builder()
+                .set_keys(Some(keys))
+                .projection_expression(projection)
+                .build()
+                .map_err(|e| {
+                    AppError::InternalError(format!("Failed to build KeysAndAttributes: {}", e))
+                })?,
+        );
+
+        while let Some(keys_and_attrs) = pending.take() {
+            let result = ddb_client
+                .batch_get_item()
+                .request_items(&table_name, keys_and_attrs)
+                .send()
+                .await
+                .map_err(|e| AppError::AwsError(format!("DynamoDB BatchGetItem error: {}", e)))?;
+
+            if let Some(found) = result.responses().and_then(|r| r.get(&table_name)) {
+                items.extend(found.iter().cloned());
+            }
+
+            pending = result
+                .unprocessed_keys()
+                .and_then(|keys| keys.get(&table_name))
+                .filter(|keys| !keys.keys().is_empty())
+                .cloned();
+        }
+    }
+
+    Ok(items)
+}
+
+async fn load_search_documents(
+    tenant_id: &str,
+    issue_numbers: &[i32],
+) -> Result<HashMap<i32, SearchDocument>, AppError> {
+    let items = batch_get_issue_items(
+        tenant_id,
+        issue_numbers,
+        SEARCH_SK,
+        "pk, searchText, sourceUpdatedAt, indexVersion",
+    )
+    .await?;
+
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            let doc = SearchDocument {
+                text: string_attr(item, "searchText").unwrap_or_default(),
+                source_updated_at: string_attr(item, "sourceUpdatedAt").unwrap_or_default(),
+                version: item
+                    .get("indexVersion")
+                    .and_then(|v| v.as_n().ok())
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(0),
+            };
+            Some((issue_number_from_pk(item)?, doc))
+        })
+        .collect())
+}
+
+/// Reads the issues' content and rewrites their search documents. Writing
+/// is best-effort; the rebuilt documents are returned either way.
+async fn rebuild_search_documents(
+    tenant_id: &str,
+    issue_numbers: &[i32],
+) -> Result<HashMap<i32, SearchDocument>, AppError> {
+    let items = batch_get_issue_items(
+        tenant_id,
+        issue_numbers,
+        "newsletter",
+        "pk, content, contentType, updatedAt",
+    )
+    .await?;
+
+    let rebuilt: Vec<(i32, SearchDocument)> = items
+        .iter()
+        .filter_map(|item| {
+            let content = string_attr(item, "content").unwrap_or_default();
+            let content_type =
+                string_attr(item, "contentType").unwrap_or_else(|| "markdown".to_string());
+            let doc = SearchDocument {
+                text: extract_search_text(&content, &content_type),
+                source_updated_at: string_attr(item, "updatedAt").unwrap_or_default(),
+                version: SEARCH_INDEX_VERSION,
+            };
+            Some((issue_number_from_pk(item)?, doc))
+        })
+        .collect();
+
+    if let Err(e) = put_search_documents(tenant_id, &rebuilt).await {
+        tracing::warn!(
+            tenant_id = %tenant_id,
+            error = ?e,
+            "Failed to store rebuilt search documents"
+        );
+    }
+
+    Ok(rebuilt.into_iter().collect())
+}
+
+async fn put_search_documents(
+    tenant_id: &str,
+    documents: &[(i32, SearchDocument)],
+) -> Result<(), AppError> {
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let table_name = get_table_name()?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    for chunk in documents.chunks(MAX_BATCH_WRITE) {
+        let mut requests = Vec::with_capacity(chunk.len());
+        for (issue_number, doc) in chunk {
+            let mut item = issue_key(tenant_id, *issue_number, SEARCH_SK);
+            item.insert(
+                "searchText".to_string(),
+                AttributeValue::S(doc.text.clone()),
+            );
+            item.insert(
+                "sourceUpdatedAt".to_string(),
+                AttributeValue::S(doc.source_updated_at.clone()),
+            );
+            item.insert(
+                "indexVersion".to_string(),
+                AttributeValue::N(doc.version.to_string()),
+            );
+            item.insert("indexedAt".to_string(), AttributeValue::S(now.clone()));
+
+            let put = PutRequest::builder()
+                .set_item(Some(item))
+                .build()
+                .map_err(|e| {
+                    AppError::InternalError(format!("Failed to build PutRequest: {}", e))
+                })?;
+            requests.push(WriteRequest::builder().put_request(put).build());
+        }
+
+        let mut pending = requests;
+        let mut retries = 0;
+        while !pending.is_empty() {
+            let result = ddb_client
+                .batch_write_item()
+                .request_items(&table_name, pending)
+                .send()
+                .await
+                .map_err(|e| AppError::AwsError(format!("DynamoDB BatchWriteItem error: {}", e)))?;
+
+            pending = result
+                .unprocessed_items()
+                .and_then(|items| items.get(&table_name))
+                .cloned()
+                .unwrap_or_default();
+
+            retries += 1;
+            if !pending.is_empty() && retries > 3 {
+                return Err(AppError::AwsError(format!(
+                    "{} search documents left unprocessed",
+                    pending.len()
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// ── Text extraction ────────────────────────────────────────────────────
+
+/// Reduces issue content to searchable plain text with collapsed whitespace.
+/// Markdown syntax and front matter are dropped, HTML is reduced to its text,
+/// and JSON content contributes its string values (not its keys).
+pub(crate) fn extract_search_text(content: &str, content_type: &str) -> String {
+    let raw = match content_type {
+        "html" => strip_html(content),
+        "json" => match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(value) => {
+                let mut parts = Vec::new();
+                collect_json_text(&value, &mut parts);
+                parts.join("\n")
+            }
+            Err(_) => strip_markdown(content),
+        },
+        _ => strip_markdown(content),
+    };
+
+    let mut text = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.len() > MAX_INDEXED_TEXT_BYTES {
+        let mut end = MAX_INDEXED_TEXT_BYTES;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+    text
+}
+
+fn collect_json_text(value: &serde_json::Value, parts: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => {
+            let s = s.trim();
+            if !s.is_empty() && !is_url(s) {
+                parts.push(strip_markdown(s));
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                collect_json_text(value, parts);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for value in map.values() {
+                collect_json_text(value, parts);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_url(s: &str) -> bool {
+    s.starts_with("http://") || s.starts_with("https://") || s.starts_with("mailto:")
+}
+
+fn strip_markdown(content: &str) -> String {
+    let body = strip_front_matter(content);
+    let mut lines = Vec::new();
+
+    for line in body.lines() {
+        let line = line.trim();
+        if line.starts_with("```") || line.starts_with("~~~") || is_reference_definition(line) {
+            continue;
+        }
+
+        let line = line.trim_start_matches(['#', '>']).trim_start();
+        let line = strip_list_marker(line);
+        lines.push(strip_inline_markdown(line));
+    }
+
+    strip_html(&lines.join("\n"))
+}
+
+pub(crate) fn strip_front_matter(content: &str) -> &str {
+    let trimmed = content.trim_start();
+    let Some(rest) = trimmed.strip_prefix("---") else {
+        return content;
+    };
+    if !rest.starts_with(['\n', '\r']) {
+        return content;
+    }
+    match rest.find("\n---") {
+        Some(end) => {
+            let after = &rest[end + 4..];
+            after.split_once('\n').map_or("", |(_, body)| body)
+        }
+        None => content,
+    }
+}
+
+/// `[1]: https://example.com` lines carry no prose.
+fn is_reference_definition(line: &str) -> bool {
+    line.starts_with('[')
+        && line
+            .split_once("]:")
+            .is_some_and(|(label, _)| !label.contains(']'))
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            return rest;
+        }
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(rest) = line[digits..].strip_prefix(". ") {
+            return rest;
+        }
+    }
+    line
+}
+
+/// Keeps link and image text, drops their URLs, emphasis markers and bare
+/// URLs.
+fn strip_inline_markdown(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(open) = rest.find('[') {
+        let (before, after) = rest.split_at(open);
+        out.push_str(before.strip_suffix('!').unwrap_or(before));
+
+        let Some(close) = after.find("](") else {
+            out.push_str(after);
+            rest = "";
+            break;
+        };
+        let label = &after[1..close];
+        let target = &after[close + 2..];
+        match target.find(')') {
+            Some(end) => {
+                out.push_str(label);
+                rest = &target[end + 1..];
+            }
+            None => {
+                out.push_str(after);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out.split(' ')
+        .filter(|word| !is_url(word))
+        .map(|word| word.replace(['*', '`'], "").replace("~~", ""))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) fn strip_html(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(open) = rest.find('<') {
+        out.push_str(&rest[..open]);
+        let tag = &rest[open..];
+
+        // A `<` that doesn't open a tag ("a < b") is text.
+        if !tag[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!') {
+            out.push('<');
+            rest = &tag[1..];
+            continue;
+        }
+
+        if let Some(comment) = tag.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let Some(close) = tag.find('>') else {
+            out.push_str(tag);
+            rest = "";
+            break;
+        };
+
+        let name = tag[1..close]
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        rest = &tag[close + 1..];
+
+        // Script and style bodies are not text.
+        if name == "script" || name == "style" {
+            let end_tag = format!("</{}", name);
+            rest = rest
+                .to_ascii_lowercase()
+                .find(&end_tag)
+                .and_then(|end| rest[end..].find('>').map(|gt| &rest[end + gt + 1..]))
+                .unwrap_or("");
+        }
+
+        // Tags separate words ("<p>a</p><p>b</p>" is "a b", not "ab").
+        out.push(' ');
+    }
+    out.push_str(rest);
+
+    decode_entities(&out)
+}
+
+pub(crate) fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let candidate = &rest[amp..];
+        let decoded = candidate
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&candidate[1..end]).map(|c| (c, end)));
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &candidate[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &candidate[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = name.strip_prefix('#')?;
+            let value = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(value)
+        }
+    }
+}
+
+// ── Ranking ────────────────────────────────────────────────────────────
+
+/// Lowercased words with their byte ranges in `text`.
+fn tokenize(text: &str) -> Vec<(usize, usize, String)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                tokens.push((s, i, text[s..i].to_lowercase()));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push((s, text.len(), text[s..].to_lowercase()));
+    }
+
+    tokens
+}
+
+/// Distinct query words, without stop words unless the query is nothing but.
+fn query_terms(q: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for (_, _, word) in tokenize(q) {
+        if !terms.contains(&word) {
+            terms.push(word);
+        }
+    }
+
+    let content_terms: Vec<String> = terms
+        .iter()
+        .filter(|term| !STOP_WORDS.contains(&term.as_str()))
+        .cloned()
+        .collect();
+    let mut terms = if content_terms.is_empty() {
+        terms
+    } else {
+        content_terms
+    };
+    terms.truncate(MAX_QUERY_TERMS);
+    terms
+}
+
+/// How well a document word matches a query term: 1 for the same word,
+/// [`PREFIX_MATCH_WEIGHT`] when the term is a prefix of it, else 0.
+fn term_match(term: &str, word: &str) -> f64 {
+    if word == term {
+        1.0
+    } else if term.len() >= MIN_PREFIX_LEN && word.starts_with(term) {
+        PREFIX_MATCH_WEIGHT
+    } else {
+        0.0
+    }
+}
+
+/// Weighted occurrences of each term in a word-count map.
+fn term_frequencies(counts: &HashMap<String, usize>, terms: &[String]) -> Vec<f64> {
+    terms
+        .iter()
+        .map(|term| {
+            counts
+                .iter()
+                .map(|(word, count)| term_match(term, word) * *count as f64)
+                .sum()
+        })
+        .collect()
+}
+
+fn word_counts(text: &str) -> (HashMap<String, usize>, usize) {
+    let tokens = tokenize(text);
+    let total = tokens.len();
+    let mut counts = HashMap::new();
+    for (_, _, word) in tokens {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    (counts, total)
+}
+
+/// BM25 over `(subject, text)` documents, with subject occurrences boosted
+/// and the score scaled by the share of query terms the document contains.
+/// Returns `(document index, score)` for matching documents, best first
+/// (ties go to the most recent issue, i.e. the later index).
+fn rank_documents(docs: &[(&str, &str)], terms: &[String]) -> Vec<(usize, f64)> {
+    if docs.is_empty() || terms.is_empty() {
+        return Vec::new();
+    }
+
+    let stats: Vec<(Vec<f64>, f64)> = docs
+        .iter()
+        .map(|(subject, text)| {
+            let (subject_counts, subject_len) = word_counts(subject);
+            let (text_counts, text_len) = word_counts(text);
+            let subject_tf = term_frequencies(&subject_counts, terms);
+            let text_tf = term_frequencies(&text_counts, terms);
+            let tf = subject_tf
+                .iter()
+                .zip(&text_tf)
+                .map(|(s, t)| s * SUBJECT_BOOST + t)
+                .collect();
+            (tf, subject_len as f64 * SUBJECT_BOOST + text_len as f64)
+        })
+        .collect();
+
+    let n = docs.len() as f64;
+    let avg_len = (stats.iter().map(|(_, len)| len).sum::<f64>() / n).max(1.0);
+    let idf: Vec<f64> = (0..terms.len())
+        .map(|i| {
+            let df = stats.iter().filter(|(tf, _)| tf[i] > 0.0).count() as f64;
+            (1.0 + (n - df + 0.5) / (df + 0.5)).ln()
+        })
+        .collect();
+
+    let mut ranked: Vec<(usize, f64)> = stats
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (tf, len))| {
+            let mut score = 0.0;
+            let mut matched = 0;
+            for (i, freq) in tf.iter().enumerate() {
+                if *freq > 0.0 {
+                    matched += 1;
+                    let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * len / avg_len);
+                    score += idf[i] * freq * (BM25_K1 + 1.0) / (freq + norm);
+                }
+            }
+            (matched > 0).then(|| (index, score * matched as f64 / terms.len() as f64))
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.0.cmp(&a.0)));
+    ranked
+}
+
+// ── Highlighting ───────────────────────────────────────────────────────
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn matches_any(word: &str, terms: &[String]) -> bool {
+    terms.iter().any(|term| term_match(term, word) > 0.0)
+}
+
+/// Escapes `text` and wraps every word matching a query term in `<mark>`.
+fn highlight(text: &str, terms: &[String]) -> String {
+    let mut out = String::with_capacity(text.len() + 16);
+    let mut last = 0;
+    for (start, end, word) in tokenize(text) {
+        if matches_any(&word, terms) {
+            out.push_str(&escape_html(&text[last..start]));
+            out.push_str("<mark>");
+            out.push_str(&escape_html(&text[start..end]));
+            out.push_str("</mark>");
+            last = end;
+        }
+    }
+    out.push_str(&escape_html(&text[last..]));
+    out
+}
+
+/// The [`SNIPPET_WORDS`]-word window covering the most distinct query terms,
+/// highlighted, with an ellipsis where it cuts the text. Falls back to the
+/// opening words when only the subject matched.
+fn build_snippet(text: &str, terms: &[String]) -> String {
+    let tokens = tokenize(text);
+    if tokens.is_empty() {
+        return String::new();
+    }
+
+    let matched_term = |word: &str| terms.iter().position(|term| term_match(term, word) > 0.0);
+
+    let mut best_start = 0;
+    let mut best_score = (0, 0);
+    for (i, (_, _, word)) in tokens.iter().enumerate() {
+        if matched_term(word).is_none() {
+            continue;
+        }
+        let start = i.saturating_sub(SNIPPET_LEAD_WORDS);
+        let window = &tokens[start..(start + SNIPPET_WORDS).min(tokens.len())];
+        let hits: Vec<usize> = window
+            .iter()
+            .filter_map(|(_, _, word)| matched_term(word))
+            .collect();
+        let distinct = hits.iter().collect::<HashSet<_>>().len();
+        if (distinct, hits.len()) > best_score {
+            best_score = (distinct, hits.len());
+            best_start = start;
+        }
+    }
+
+    let best_end = (best_start + SNIPPET_WORDS).min(tokens.len());
+    let from = if best_start == 0 {
+        0
+    } else {
+        tokens[best_start].0
+    };
+    let to = if best_end == tokens.len() {
+        text.len()
+    } else {
+        tokens[best_end - 1].1
+    };
+
+    let mut snippet = String::new();
+    if from > 0 {
+        snippet.push('…');
+    }
+    snippet.push_str(&highlight(&text[from..to], terms));
+    if to < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(q: &str) -> Vec<String> {
+        query_terms(q)
+    }
+
+    #[test]
+    fn test_extract_search_text_markdown() {
+        let content = "---\ntitle: Weekly\ntags: [a]\n---\n# Hello **world**\n\n\
+            - Read [the guide](https://example.com/guide) today\n\
+            ![diagram](https://img.example.com/x.png)\n\
+            ```js\nconst x = 1;\n```\n\
+            [1]: https://example.com\n\
+            See https://example.com for more &amp; <b>bold</b> text";
+
+        assert_eq!(
+            extract_search_text(content, "markdown"),
+            "Hello world Read the guide today diagram const x = 1; See for more & bold text"
+        );
+    }
+
+    #[test]
+    fn test_extract_search_text_html() {
+        let content = "<html><head><style>p { color: red; }</style>\
+            <script>var a = '<p>';</script></head>\
+            <body><!-- hidden --><p>Step&nbsp;one</p><p>Step&#39;s two</p></body></html>";
+
+        assert_eq!(extract_search_text(content, "html"), "Step one Step's two");
+    }
+
+    #[test]
+    fn test_extract_search_text_json_uses_string_values() {
+        let content = serde_json::json!({
+            "title": "Serverless *news*",
+            "sections": [
+                { "heading": "EventBridge", "url": "https://example.com", "count": 3 }
+            ]
+        })
+        .to_string();
+
+        let text = extract_search_text(&content, "json");
+        assert!(text.contains("Serverless news"));
+        assert!(text.contains("EventBridge"));
+        assert!(!text.contains("https"));
+        assert!(!text.contains("heading"));
+    }
+
+    #[test]
+    fn test_extract_search_text_caps_length_on_char_boundary() {
+        let content = "é".repeat(MAX_INDEXED_TEXT_BYTES);
+        let text = extract_search_text(&content, "markdown");
+        assert!(text.len() <= MAX_INDEXED_TEXT_BYTES);
+        assert!(text.chars().all(|c| c == 'é'));
+    }
+
+    #[test]
+    fn test_query_terms_drops_stop_words_and_duplicates() {
+        assert_eq!(
+            terms("The Step Functions and step"),
+            vec!["step", "functions"]
+        );
+        // A query of only stop words still searches for them.
+        assert_eq!(terms("to be or"), vec!["to", "be", "or"]);
+        assert!(terms("  ?! ").is_empty());
+    }
+
+    #[test]
+    fn test_rank_documents_prefers_subject_and_coverage() {
+        let docs = [
+            ("Weekly digest", "A short note about DynamoDB streams."),
+            (
+                "DynamoDB deep dive",
+                "Partition keys and streams explained.",
+            ),
+            ("Serverless roundup", "Nothing relevant here."),
+        ];
+
+        let ranked = rank_documents(&docs, &terms("dynamodb streams"));
+        let order: Vec<usize> = ranked.iter().map(|(index, _)| *index).collect();
+        assert_eq!(order, vec![1, 0]);
+
+        let ranked = rank_documents(&docs, &terms("dynamodb roundup"));
+        assert_eq!(ranked.len(), 3);
+        assert!(ranked.iter().all(|(_, score)| *score > 0.0));
+    }
+
+    #[test]
+    fn test_rank_documents_prefix_matches_count_less() {
+        let docs = [("Lambdas", "lambdas everywhere"), ("Lambda", "one lambda")];
+        let ranked = rank_documents(&docs, &terms("lambda"));
+
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].0, 1);
+        assert!(rank_documents(&docs, &terms("la")).is_empty());
+    }
+
+    #[test]
+    fn test_highlight_escapes_and_marks() {
+        assert_eq!(
+            highlight("Use <Step> Functions & steps", &terms("step")),
+            "Use &lt;<mark>Step</mark>&gt; Functions &amp; <mark>steps</mark>"
+        );
+    }
+
+    #[test]
+    fn test_build_snippet_windows_around_matches() {
+        let words: Vec<String> = (0..100).map(|i| format!("w{}", i)).collect();
+        let mut text_words = words.clone();
+        text_words[60] = "EventBridge".to_string();
+        let text = text_words.join(" ");
+
+        let snippet = build_snippet(&text, &terms("eventbridge"));
+        assert!(snippet.starts_with("…w52 "));
+        assert!(snippet.contains("<mark>EventBridge</mark>"));
+        assert!(snippet.ends_with(" w81…"));
+
+        // Subject-only match: the opening words, unmarked.
+        let snippet = build_snippet(&text, &terms("nothing"));
+        assert!(snippet.starts_with("w0 w1"));
+        assert!(snippet.ends_with("w29…"));
+        assert!(!snippet.contains("<mark>"));
+    }
+
+    #[test]
+    fn test_parse_search_query_validation() {
+        let query = parse_search_query(Some(" step functions "), None, None, None, None).unwrap();
+        assert_eq!(query.q, "step functions");
+        assert_eq!(query.limit, DEFAULT_SEARCH_LIMIT);
+
+        assert!(parse_search_query(None, None, None, None, None).is_err());
+        assert!(parse_search_query(Some("  "), None, None, None, None).is_err());
+        assert!(parse_search_query(Some("!!"), None, None, None, None).is_err());
+        assert!(parse_search_query(Some("x"), Some("archived"), None, None, None).is_err());
+        assert!(parse_search_query(Some("x"), None, None, None, Some("0")).is_err());
+        assert!(parse_search_query(Some("x"), None, None, None, Some("51")).is_err());
+        assert!(parse_search_query(Some("x"), None, Some("yesterday"), None, None).is_err());
+        assert!(parse_search_query(
+            Some("x"),
+            None,
+            Some("2026-03-02"),
+            Some("2026-03-01"),
+            None
+        )
+        .is_err());
+        assert!(parse_search_query(Some("x"), Some("in progress"), None, None, None).is_ok());
+    }
+
+    #[test]
+    fn test_matches_filters_uses_issue_date() {
+        let issue = IssueSummary {
+            issue_number: 7,
+            subject: "s".to_string(),
+            status: "published".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            published_at: Some("2026-03-01T09:00:00Z".to_string()),
+            scheduled_at: None,
+            updated_at: "2026-03-01T09:00:00Z".to_string(),
+        };
+
+        let query = |status: Option<&str>, from: Option<&str>, to: Option<&str>| {
+            parse_search_query(Some("x"), status, from, to, None).unwrap()
+        };
+
+        assert!(matches_filters(&issue, &query(None, None, None)));
+        assert!(matches_filters(
+            &issue,
+            &query(Some("published"), None, None)
+        ));
+        assert!(!matches_filters(&issue, &query(Some("draft"), None, None)));
+        // A plain `to` date includes the whole day.
+        assert!(matches_filters(
+            &issue,
+            &query(None, Some("2026-03-01"), Some("2026-03-01"))
+        ));
+        // Published date wins over createdAt.
+        assert!(!matches_filters(
+            &issue,
+            &query(None, None, Some("2026-02-01T00:00:00Z"))
+        ));
+    }
+}
@@ -1,15 +1,31 @@
+pub mod ab_stats;
+pub mod alerts;
+pub mod analytics_rebuild;
 pub mod api_keys;
+pub mod approvals;
 pub mod brand;
 pub mod churn;
 pub mod domain;
+pub mod engagement_heatmap;
+pub mod issue_deliverability;
+pub mod issue_links;
+pub mod issue_preflight;
+pub mod issue_revenue;
+pub mod issue_search;
 pub mod issues;
 pub mod pricing;
 pub mod profile;
+pub mod recurring_schedules;
 pub mod reports;
 pub mod segments;
 pub mod senders;
 pub mod snippets;
 pub mod sponsors;
+pub mod subscriber_dsar;
+pub mod subscriber_fields;
+pub mod subscriber_import;
+pub mod subscriber_tags;
 pub mod subscribers;
+pub mod suppressions;
 pub mod template_render;
 pub mod templates;
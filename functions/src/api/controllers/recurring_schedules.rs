@@ -0,0 +1,1500 @@
+//! Recurring issue schedules.
+//!
+//! A recurring schedule materializes a new draft on every occurrence of an
+//! RRULE (e.g. `FREQ=WEEKLY;BYDAY=MO;BYHOUR=8`) evaluated in the schedule's
+//! timezone. The RRULE is translated into an EventBridge Scheduler `cron()`
+//! expression and each occurrence fires a `RECURRING_ISSUE_OCCURRENCE` event
+//! onto the default bus, the same putEvents plumbing the draft TTL schedule
+//! uses. The materialize-recurring-issue Lambda consumes the event, loads the
+//! content source, and creates (and optionally schedules) the draft, or skips
+//! the occurrence when no content is ready.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_scheduler::types::{FlexibleTimeWindow, FlexibleTimeWindowMode, ScheduleState, Target};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc, Weekday};
+use lambda_http::{Body, Error, Request, Response};
+use newsletter::admin::{auth, aws_clients, error::AppError, response};
+use serde::{Deserialize, Serialize};
+use serde_dynamo::from_item;
+use std::collections::HashMap;
+use std::env;
+
+use crate::controllers::issues;
+
+// ── Constants ──────────────────────────────────────────────────────────
+
+const SCHEDULE_SK_PREFIX: &str = "recurring-schedule#";
+const SCHEDULER_GROUP: &str = "newsletter";
+const OCCURRENCE_DETAIL_TYPE: &str = "RECURRING_ISSUE_OCCURRENCE";
+
+const NAME_MAX_LEN: usize = 100;
+const SUBJECT_MAX_LEN: usize = 200;
+/// Longest gap allowed between an occurrence and the auto-scheduled send.
+const MAX_PUBLISH_DELAY_MINUTES: i64 = 7 * 24 * 60;
+
+const CONTENT_SOURCE_ISSUE: &str = "issue";
+const CONTENT_SOURCE_GITHUB: &str = "github";
+/// Placeholder replaced with the occurrence's local date (YYYY-MM-DD) in
+/// GitHub paths and subject templates.
+const DATE_PLACEHOLDER: &str = "{date}";
+
+// ── Key patterns ───────────────────────────────────────────────────────
+
+fn schedule_sk(schedule_id: &str) -> String {
+    format!("{}{}", SCHEDULE_SK_PREFIX, schedule_id)
+}
+
+/// Builds the EventBridge Scheduler name (<= 64 chars, `[0-9a-zA-Z-_.]`).
+/// Unlike the one-time draft TTL schedules this name is stable for the life of
+/// the recurring schedule so updates and deletes can address it.
+fn scheduler_name(tenant_id: &str, schedule_id: &str) -> String {
+    let tenant_prefix: String = tenant_id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .take(16)
+        .collect();
+    format!("recurring-{}-{}", tenant_prefix, schedule_id)
+}
+
+// ── Recurrence rules ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ByDay {
+    /// `Some(n)` for "nth weekday of the month" (1..=5, or -1 for last).
+    ordinal: Option<i32>,
+    weekday: Weekday,
+}
+
+/// The subset of RFC 5545 recurrence rules that maps onto an EventBridge
+/// Scheduler cron expression: DAILY/WEEKLY/MONTHLY with BYDAY, BYMONTHDAY,
+/// BYHOUR, BYMINUTE and UNTIL. There is no DTSTART, so the time of day must
+/// be given explicitly with BYHOUR.
+#[derive(Debug, Clone, PartialEq)]
+struct Recurrence {
+    freq: Frequency,
+    by_day: Vec<ByDay>,
+    by_month_day: Vec<i32>,
+    by_hour: Vec<u32>,
+    by_minute: Vec<u32>,
+    until: Option<DateTime<Utc>>,
+}
+
+const WEEKDAY_CODES: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    WEEKDAY_CODES
+        .iter()
+        .find(|(_, day)| *day == weekday)
+        .map(|(code, _)| *code)
+        .unwrap_or("MO")
+}
+
+/// Cron day-of-week names and numbers (SUN = 1 ... SAT = 7).
+fn cron_weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MON",
+        Weekday::Tue => "TUE",
+        Weekday::Wed => "WED",
+        Weekday::Thu => "THU",
+        Weekday::Fri => "FRI",
+        Weekday::Sat => "SAT",
+        Weekday::Sun => "SUN",
+    }
+}
+
+fn cron_weekday_number(weekday: Weekday) -> u32 {
+    weekday.number_from_sunday()
+}
+
+fn parse_number_list(key: &str, value: &str, min: i64, max: i64) -> Result<Vec<i64>, AppError> {
+    value
+        .split(',')
+        .map(|part| {
+            part.trim()
+                .parse::<i64>()
+                .ok()
+                .filter(|n| (min..=max).contains(n))
+                .ok_or_else(|| {
+                    AppError::BadRequest(format!(
+                        "RRULE {} values must be between {} and {}",
+                        key, min, max
+                    ))
+                })
+        })
+        .collect()
+}
+
+fn parse_by_day(value: &str) -> Result<Vec<ByDay>, AppError> {
+    value
+        .split(',')
+        .map(|part| {
+            let part = part.trim();
+            // The weekday code is the last two characters; find where they
+            // start by char so a non-ASCII value can't split inside one.
+            let split = part.char_indices().rev().nth(1).map_or(0, |(i, _)| i);
+            let (ordinal, code) = part.split_at(split);
+            let weekday = WEEKDAY_CODES
+                .iter()
+                .find(|(c, _)| *c == code)
+                .map(|(_, day)| *day)
+                .ok_or_else(|| {
+                    AppError::BadRequest(format!("RRULE BYDAY value \"{}\" is not valid", part))
+                })?;
+            let ordinal = if ordinal.is_empty() {
+                None
+            } else {
+                let n = ordinal
+                    .trim_start_matches('+')
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|n| (1..=5).contains(n) || *n == -1)
+                    .ok_or_else(|| {
+                        AppError::BadRequest(
+                            "RRULE BYDAY ordinals must be 1 to 5, or -1 for the last weekday"
+                                .to_string(),
+                        )
+                    })?;
+                Some(n)
+            };
+            Ok(ByDay { ordinal, weekday })
+        })
+        .collect()
+}
+
+fn parse_until(value: &str) -> Result<DateTime<Utc>, AppError> {
+    let value = value.trim();
+    if let Ok(dt) = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S") {
+        return Ok(dt.and_utc());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+        if let Some(dt) = date.and_hms_opt(23, 59, 59) {
+            return Ok(dt.and_utc());
+        }
+    }
+    Err(AppError::BadRequest(
+        "RRULE UNTIL must be YYYYMMDD or YYYYMMDDTHHMMSSZ".to_string(),
+    ))
+}
+
+fn parse_rrule(input: &str) -> Result<Recurrence, AppError> {
+    let input = input.trim();
+    let input = input
+        .strip_prefix("RRULE:")
+        .or_else(|| input.strip_prefix("rrule:"))
+        .unwrap_or(input);
+    if input.is_empty() {
+        return Err(AppError::BadRequest("rrule is required".to_string()));
+    }
+
+    let mut freq = None;
+    let mut by_day = Vec::new();
+    let mut by_month_day = Vec::new();
+    let mut by_hour = Vec::new();
+    let mut by_minute = Vec::new();
+    let mut until = None;
+    let mut seen: Vec<String> = Vec::new();
+
+    for part in input.split(';').filter(|p| !p.trim().is_empty()) {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| AppError::BadRequest(format!("RRULE part \"{}\" is not valid", part)))?;
+        let key = key.trim().to_ascii_uppercase();
+        let value = value.trim().to_ascii_uppercase();
+        if seen.contains(&key) {
+            return Err(AppError::BadRequest(format!(
+                "RRULE part {} is repeated",
+                key
+            )));
+        }
+        seen.push(key.clone());
+
+        match key.as_str() {
+            "FREQ" => {
+                freq = Some(match value.as_str() {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    _ => {
+                        return Err(AppError::BadRequest(
+                            "RRULE FREQ must be DAILY, WEEKLY or MONTHLY".to_string(),
+                        ))
+                    }
+                })
+            }
+            // Scheduler cron expressions cannot skip periods, so only the
+            // default interval is supported.
+            "INTERVAL" => {
+                if value != "1" {
+                    return Err(AppError::BadRequest(
+                        "RRULE INTERVAL other than 1 is not supported".to_string(),
+                    ));
+                }
+            }
+            "BYDAY" => by_day = parse_by_day(&value)?,
+            "BYMONTHDAY" => {
+                by_month_day = parse_number_list(&key, &value, -1, 31)?
+                    .into_iter()
+                    .map(|n| n as i32)
+                    .collect();
+                if by_month_day.contains(&0) {
+                    return Err(AppError::BadRequest(
+                        "RRULE BYMONTHDAY values must be 1 to 31, or -1 for the last day"
+                            .to_string(),
+                    ));
+                }
+            }
+            "BYHOUR" => {
+                by_hour = parse_number_list(&key, &value, 0, 23)?
+                    .into_iter()
+                    .map(|n| n as u32)
+                    .collect()
+            }
+            "BYMINUTE" => {
+                by_minute = parse_number_list(&key, &value, 0, 59)?
+                    .into_iter()
+                    .map(|n| n as u32)
+                    .collect()
+            }
+            "UNTIL" => until = Some(parse_until(&value)?),
+            _ => {
+                return Err(AppError::BadRequest(format!(
+                    "RRULE part {} is not supported",
+                    key
+                )))
+            }
+        }
+    }
+
+    let freq = freq.ok_or_else(|| AppError::BadRequest("RRULE FREQ is required".to_string()))?;
+
+    if by_hour.is_empty() {
+        return Err(AppError::BadRequest(
+            "RRULE BYHOUR is required to set the time of day".to_string(),
+        ));
+    }
+    if by_minute.is_empty() {
+        by_minute.push(0);
+    }
+    by_hour.sort_unstable();
+    by_hour.dedup();
+    by_minute.sort_unstable();
+    by_minute.dedup();
+    by_month_day.sort_unstable();
+    by_month_day.dedup();
+
+    let has_ordinal = by_day.iter().any(|d| d.ordinal.is_some());
+    match freq {
+        Frequency::Daily | Frequency::Weekly => {
+            if !by_month_day.is_empty() {
+                return Err(AppError::BadRequest(
+                    "RRULE BYMONTHDAY is only supported with FREQ=MONTHLY".to_string(),
+                ));
+            }
+            if has_ordinal {
+                return Err(AppError::BadRequest(
+                    "RRULE BYDAY ordinals are only supported with FREQ=MONTHLY".to_string(),
+                ));
+            }
+            if freq == Frequency::Weekly && by_day.is_empty() {
+                return Err(AppError::BadRequest(
+                    "RRULE BYDAY is required with FREQ=WEEKLY".to_string(),
+                ));
+            }
+        }
+        Frequency::Monthly => {
+            if by_day.is_empty() == by_month_day.is_empty() {
+                return Err(AppError::BadRequest(
+                    "FREQ=MONTHLY requires exactly one of BYDAY or BYMONTHDAY".to_string(),
+                ));
+            }
+            if has_ordinal && by_day.len() > 1 {
+                return Err(AppError::BadRequest(
+                    "RRULE BYDAY supports a single ordinal weekday (e.g. 1MO or -1FR)".to_string(),
+                ));
+            }
+            if by_month_day.contains(&-1) && by_month_day.len() > 1 {
+                return Err(AppError::BadRequest(
+                    "RRULE BYMONTHDAY=-1 cannot be combined with other days".to_string(),
+                ));
+            }
+        }
+    }
+
+    Ok(Recurrence {
+        freq,
+        by_day,
+        by_month_day,
+        by_hour,
+        by_minute,
+        until,
+    })
+}
+
+fn join_numbers<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl Recurrence {
+    /// Canonical RRULE string persisted on the schedule.
+    fn to_rrule(&self) -> String {
+        let mut parts = vec![format!(
+            "FREQ={}",
+            match self.freq {
+                Frequency::Daily => "DAILY",
+                Frequency::Weekly => "WEEKLY",
+                Frequency::Monthly => "MONTHLY",
+            }
+        )];
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|d| match d.ordinal {
+                    Some(n) => format!("{}{}", n, weekday_code(d.weekday)),
+                    None => weekday_code(d.weekday).to_string(),
+                })
+                .collect();
+            parts.push(format!("BYDAY={}", days.join(",")));
+        }
+        if !self.by_month_day.is_empty() {
+            parts.push(format!("BYMONTHDAY={}", join_numbers(&self.by_month_day)));
+        }
+        parts.push(format!("BYHOUR={}", join_numbers(&self.by_hour)));
+        parts.push(format!("BYMINUTE={}", join_numbers(&self.by_minute)));
+        if let Some(until) = self.until {
+            parts.push(format!("UNTIL={}", until.format("%Y%m%dT%H%M%SZ")));
+        }
+        parts.join(";")
+    }
+
+    /// EventBridge Scheduler expression: `cron(min hour dom month dow year)`.
+    /// Exactly one of day-of-month and day-of-week must be `?`.
+    fn cron_expression(&self) -> String {
+        let (dom, dow) = if !self.by_month_day.is_empty() {
+            let dom = if self.by_month_day == [-1] {
+                "L".to_string()
+            } else {
+                join_numbers(&self.by_month_day)
+            };
+            (dom, "?".to_string())
+        } else if let Some(day) = self.by_day.iter().find(|d| d.ordinal.is_some()) {
+            let number = cron_weekday_number(day.weekday);
+            let dow = match day.ordinal {
+                Some(-1) => format!("{}L", number),
+                Some(n) => format!("{}#{}", number, n),
+                None => unreachable!(),
+            };
+            ("?".to_string(), dow)
+        } else if !self.by_day.is_empty() {
+            let days: Vec<&str> = self
+                .by_day
+                .iter()
+                .map(|d| cron_weekday_name(d.weekday))
+                .collect();
+            ("?".to_string(), days.join(","))
+        } else {
+            ("*".to_string(), "?".to_string())
+        };
+
+        format!(
+            "cron({} {} {} * {} *)",
+            join_numbers(&self.by_minute),
+            join_numbers(&self.by_hour),
+            dom,
+            dow
+        )
+    }
+}
+
+// ── Data types ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ContentSource {
+    #[serde(rename = "type")]
+    source_type: String,
+    /// `issue` source: the issue whose content is copied each occurrence.
+    #[serde(default, rename = "issueId", skip_serializing_if = "Option::is_none")]
+    issue_id: Option<String>,
+    /// `github` source: repository path (with a `{date}` placeholder) in the
+    /// tenant's connected repo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecurringScheduleRecord {
+    pk: String,
+    sk: String,
+    #[serde(rename = "scheduleId")]
+    schedule_id: String,
+    #[serde(rename = "tenantId")]
+    tenant_id: String,
+    name: String,
+    rrule: String,
+    timezone: String,
+    #[serde(rename = "scheduleExpression")]
+    schedule_expression: String,
+    #[serde(
+        default,
+        rename = "templateId",
+        skip_serializing_if = "Option::is_none"
+    )]
+    template_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    subject: Option<String>,
+    #[serde(rename = "contentSource")]
+    content_source: ContentSource,
+    #[serde(rename = "autoSchedule")]
+    auto_schedule: bool,
+    #[serde(rename = "publishDelayMinutes")]
+    publish_delay_minutes: i64,
+    enabled: bool,
+    #[serde(rename = "schedulerName")]
+    scheduler_name: String,
+    /// Written by the materialize-recurring-issue Lambda after each
+    /// occurrence (`{ occurrence, status, issueNumber?, reason? }`).
+    #[serde(default, rename = "lastRun", skip_serializing_if = "Option::is_none")]
+    last_run: Option<serde_json::Value>,
+    /// Local occurrence key (`YYYY-MM-DDTHH:MM`) claimed by the Lambda so a
+    /// redelivered scheduler event cannot create a second draft.
+    #[serde(
+        default,
+        rename = "lastOccurrence",
+        skip_serializing_if = "Option::is_none"
+    )]
+    last_occurrence: Option<String>,
+    #[serde(rename = "createdAt")]
+    created_at: String,
+    #[serde(rename = "updatedAt")]
+    updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+struct RecurringScheduleDetail {
+    #[serde(rename = "scheduleId")]
+    schedule_id: String,
+    name: String,
+    rrule: String,
+    timezone: String,
+    #[serde(rename = "scheduleExpression")]
+    schedule_expression: String,
+    #[serde(rename = "templateId", skip_serializing_if = "Option::is_none")]
+    template_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subject: Option<String>,
+    #[serde(rename = "contentSource")]
+    content_source: ContentSource,
+    #[serde(rename = "autoSchedule")]
+    auto_schedule: bool,
+    #[serde(rename = "publishDelayMinutes")]
+    publish_delay_minutes: i64,
+    enabled: bool,
+    #[serde(rename = "lastRun", skip_serializing_if = "Option::is_none")]
+    last_run: Option<serde_json::Value>,
+    #[serde(rename = "createdAt")]
+    created_at: String,
+    #[serde(rename = "updatedAt")]
+    updated_at: String,
+}
+
+impl From<RecurringScheduleRecord> for RecurringScheduleDetail {
+    fn from(r: RecurringScheduleRecord) -> Self {
+        RecurringScheduleDetail {
+            schedule_id: r.schedule_id,
+            name: r.name,
+            rrule: r.rrule,
+            timezone: r.timezone,
+            schedule_expression: r.schedule_expression,
+            template_id: r.template_id,
+            subject: r.subject,
+            content_source: r.content_source,
+            auto_schedule: r.auto_schedule,
+            publish_delay_minutes: r.publish_delay_minutes,
+            enabled: r.enabled,
+            last_run: r.last_run,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ListRecurringSchedulesResponse {
+    schedules: Vec<RecurringScheduleDetail>,
+    total: usize,
+}
+
+#[derive(Deserialize)]
+struct CreateRecurringScheduleRequest {
+    name: Option<String>,
+    rrule: Option<String>,
+    timezone: Option<String>,
+    #[serde(rename = "templateId")]
+    template_id: Option<String>,
+    subject: Option<String>,
+    #[serde(rename = "contentSource")]
+    content_source: Option<ContentSource>,
+    #[serde(rename = "autoSchedule")]
+    auto_schedule: Option<bool>,
+    #[serde(rename = "publishDelayMinutes")]
+    publish_delay_minutes: Option<i64>,
+    enabled: Option<bool>,
+}
+
+/// All fields optional; an empty `templateId` or `subject` clears it.
+#[derive(Deserialize)]
+struct UpdateRecurringScheduleRequest {
+    name: Option<String>,
+    rrule: Option<String>,
+    timezone: Option<String>,
+    #[serde(rename = "templateId")]
+    template_id: Option<String>,
+    subject: Option<String>,
+    #[serde(rename = "contentSource")]
+    content_source: Option<ContentSource>,
+    #[serde(rename = "autoSchedule")]
+    auto_schedule: Option<bool>,
+    #[serde(rename = "publishDelayMinutes")]
+    publish_delay_minutes: Option<i64>,
+    enabled: Option<bool>,
+}
+
+// ── Validation ─────────────────────────────────────────────────────────
+
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::BadRequest("name is required".to_string()));
+    }
+    if name.chars().count() > NAME_MAX_LEN {
+        return Err(AppError::BadRequest(format!(
+            "name must be at most {} characters",
+            NAME_MAX_LEN
+        )));
+    }
+    Ok(name.to_string())
+}
+
+fn validate_timezone(timezone: &str) -> Result<String, AppError> {
+    let timezone = timezone.trim();
+    if !issues::is_plausible_iana_time_zone(timezone) {
+        return Err(AppError::BadRequest(
+            "timezone must be an IANA timezone name (e.g. \"America/New_York\")".to_string(),
+        ));
+    }
+    Ok(timezone.to_string())
+}
+
+fn validate_subject(subject: &str) -> Result<Option<String>, AppError> {
+    let subject = subject.trim();
+    if subject.chars().count() > SUBJECT_MAX_LEN {
+        return Err(AppError::BadRequest(format!(
+            "subject must be at most {} characters",
+            SUBJECT_MAX_LEN
+        )));
+    }
+    Ok((!subject.is_empty()).then(|| subject.to_string()))
+}
+
+fn validate_publish_delay(minutes: i64) -> Result<i64, AppError> {
+    if !(0..=MAX_PUBLISH_DELAY_MINUTES).contains(&minutes) {
+        return Err(AppError::BadRequest(format!(
+            "publishDelayMinutes must be between 0 and {}",
+            MAX_PUBLISH_DELAY_MINUTES
+        )));
+    }
+    Ok(minutes)
+}
+
+/// Shape-checks a content source and returns its canonical form. Existence of
+/// a referenced issue is checked separately since it needs DynamoDB.
+fn normalize_content_source(source: &ContentSource) -> Result<ContentSource, AppError> {
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+
+    match source.source_type.as_str() {
+        CONTENT_SOURCE_ISSUE => {
+            let issue_id = non_empty(&source.issue_id).ok_or_else(|| {
+                AppError::BadRequest("contentSource.issueId is required".to_string())
+            })?;
+            Ok(ContentSource {
+                source_type: CONTENT_SOURCE_ISSUE.to_string(),
+                issue_id: Some(issue_id),
+                path: None,
+                branch: None,
+            })
+        }
+        CONTENT_SOURCE_GITHUB => {
+            let path = non_empty(&source.path).ok_or_else(|| {
+                AppError::BadRequest("contentSource.path is required".to_string())
+            })?;
+            if !path.contains(DATE_PLACEHOLDER) {
+                return Err(AppError::BadRequest(format!(
+                    "contentSource.path must contain a {} placeholder",
+                    DATE_PLACEHOLDER
+                )));
+            }
+            Ok(ContentSource {
+                source_type: CONTENT_SOURCE_GITHUB.to_string(),
+                issue_id: None,
+                path: Some(path.trim_start_matches('/').to_string()),
+                branch: non_empty(&source.branch),
+            })
+        }
+        _ => Err(AppError::BadRequest(
+            "contentSource.type must be \"issue\" or \"github\"".to_string(),
+        )),
+    }
+}
+
+async fn validate_references(
+    tenant_id: &str,
+    template_id: Option<&str>,
+    content_source: &ContentSource,
+) -> Result<(), AppError> {
+    if let Some(template_id) = template_id {
+        issues::validate_template_exists(tenant_id, template_id).await?;
+    }
+    if let Some(issue_id) = content_source.issue_id.as_deref() {
+        issues::get_issue_by_id(tenant_id, issue_id)
+            .await
+            .map_err(|e| match e {
+                AppError::NotFound(_) => {
+                    AppError::BadRequest(format!("contentSource issue {} not found", issue_id))
+                }
+                other => other,
+            })?;
+    }
+    Ok(())
+}
+
+// ── Handlers ───────────────────────────────────────────────────────────
+
+pub async fn list_recurring_schedules(event: Request) -> Result<Response<Body>, Error> {
+    match handle_list_recurring_schedules(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_list_recurring_schedules(event: Request) -> Result<Response<Body>, AppError> {
+    let tenant_id = require_tenant(&event)?;
+
+    let client = aws_clients::get_dynamodb_client().await;
+    let table_name = table_name()?;
+
+    let mut schedules = Vec::new();
+    let mut last_key = None;
+    loop {
+        let result = client
+            .query()
+            .table_name(&table_name)
+            .key_condition_expression("pk = :pk AND begins_with(sk, :prefix)")
+            .expression_attribute_values(":pk", AttributeValue::S(tenant_id.clone()))
+            .expression_attribute_values(
+                ":prefix",
+                AttributeValue::S(SCHEDULE_SK_PREFIX.to_string()),
+            )
+            .set_exclusive_start_key(last_key)
+            .send()
+            .await
+            .map_err(|e| AppError::AwsError(format!("Failed to query schedules: {}", e)))?;
+
+        schedules.extend(result.items().iter().filter_map(|item| {
+            from_item::<_, RecurringScheduleRecord>(item.clone())
+                .map_err(|e| tracing::error!("Failed to deserialize recurring schedule: {}", e))
+                .ok()
+                .map(RecurringScheduleDetail::from)
+        }));
+
+        last_key = result.last_evaluated_key().cloned();
+        if last_key.is_none() {
+            break;
+        }
+    }
+
+    response::format_response(
+        200,
+        ListRecurringSchedulesResponse {
+            total: schedules.len(),
+            schedules,
+        },
+    )
+}
+
+pub async fn create_recurring_schedule(event: Request) -> Result<Response<Body>, Error> {
+    match handle_create_recurring_schedule(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_create_recurring_schedule(event: Request) -> Result<Response<Body>, AppError> {
+    let tenant_id = require_tenant(&event)?;
+
+    let body: CreateRecurringScheduleRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+
+    let name = validate_name(body.name.as_deref().unwrap_or_default())?;
+    let recurrence = parse_rrule(body.rrule.as_deref().unwrap_or_default())?;
+    let timezone = validate_timezone(body.timezone.as_deref().unwrap_or_default())?;
+    let content_source = normalize_content_source(
+        body.content_source
+            .as_ref()
+            .ok_or_else(|| AppError::BadRequest("contentSource is required".to_string()))?,
+    )?;
+    let template_id = body
+        .template_id
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+    let subject = match body.subject.as_deref() {
+        Some(subject) => validate_subject(subject)?,
+        None => None,
+    };
+    let publish_delay_minutes = validate_publish_delay(body.publish_delay_minutes.unwrap_or(0))?;
+
+    validate_references(&tenant_id, template_id.as_deref(), &content_source).await?;
+
+    let schedule_id = ulid::Ulid::new().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    let record = RecurringScheduleRecord {
+        pk: tenant_id.clone(),
+        sk: schedule_sk(&schedule_id),
+        scheduler_name: scheduler_name(&tenant_id, &schedule_id),
+        schedule_id,
+        tenant_id,
+        name,
+        rrule: recurrence.to_rrule(),
+        timezone,
+        schedule_expression: recurrence.cron_expression(),
+        template_id,
+        subject,
+        content_source,
+        auto_schedule: body.auto_schedule.unwrap_or(false),
+        publish_delay_minutes,
+        enabled: body.enabled.unwrap_or(true),
+        last_run: None,
+        last_occurrence: None,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    put_schedule_record(&record).await?;
+
+    // Roll the record back if the scheduler rejects the definition so a
+    // schedule never exists in the table without its trigger.
+    if let Err(e) = upsert_scheduler(&record, recurrence.until, true).await {
+        if let Err(cleanup) = delete_schedule_record(&record.tenant_id, &record.schedule_id).await {
+            tracing::error!(
+                schedule_id = %record.schedule_id,
+                error = %cleanup,
+                "Failed to roll back recurring schedule record"
+            );
+        }
+        return Err(e);
+    }
+
+    response::format_response(201, RecurringScheduleDetail::from(record))
+}
+
+pub async fn get_recurring_schedule(
+    event: Request,
+    schedule_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_get_recurring_schedule(event, schedule_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_get_recurring_schedule(
+    event: Request,
+    schedule_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let tenant_id = require_tenant(&event)?;
+    let schedule_id =
+        schedule_id.ok_or_else(|| AppError::BadRequest("Schedule ID is required".to_string()))?;
+
+    let record = get_schedule_record(&tenant_id, &schedule_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Recurring schedule not found".to_string()))?;
+
+    response::format_response(200, RecurringScheduleDetail::from(record))
+}
+
+pub async fn update_recurring_schedule(
+    event: Request,
+    schedule_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_update_recurring_schedule(event, schedule_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_update_recurring_schedule(
+    event: Request,
+    schedule_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let tenant_id = require_tenant(&event)?;
+    let schedule_id =
+        schedule_id.ok_or_else(|| AppError::BadRequest("Schedule ID is required".to_string()))?;
+
+    let body: UpdateRecurringScheduleRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+
+    let mut record = get_schedule_record(&tenant_id, &schedule_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Recurring schedule not found".to_string()))?;
+
+    if let Some(name) = body.name.as_deref() {
+        record.name = validate_name(name)?;
+    }
+    let recurrence = parse_rrule(body.rrule.as_deref().unwrap_or(&record.rrule))?;
+    record.rrule = recurrence.to_rrule();
+    record.schedule_expression = recurrence.cron_expression();
+    if let Some(timezone) = body.timezone.as_deref() {
+        record.timezone = validate_timezone(timezone)?;
+    }
+    if let Some(template_id) = body.template_id {
+        let template_id = template_id.trim().to_string();
+        record.template_id = (!template_id.is_empty()).then_some(template_id);
+    }
+    if let Some(subject) = body.subject.as_deref() {
+        record.subject = validate_subject(subject)?;
+    }
+    if let Some(content_source) = body.content_source.as_ref() {
+        record.content_source = normalize_content_source(content_source)?;
+    }
+    if let Some(auto_schedule) = body.auto_schedule {
+        record.auto_schedule = auto_schedule;
+    }
+    if let Some(minutes) = body.publish_delay_minutes {
+        record.publish_delay_minutes = validate_publish_delay(minutes)?;
+    }
+    if let Some(enabled) = body.enabled {
+        record.enabled = enabled;
+    }
+
+    validate_references(
+        &tenant_id,
+        record.template_id.as_deref(),
+        &record.content_source,
+    )
+    .await?;
+
+    record.updated_at = chrono::Utc::now().to_rfc3339();
+
+    upsert_scheduler(&record, recurrence.until, false).await?;
+    let record = update_schedule_record(&record).await?;
+
+    response::format_response(200, RecurringScheduleDetail::from(record))
+}
+
+pub async fn delete_recurring_schedule(
+    event: Request,
+    schedule_id: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_delete_recurring_schedule(event, schedule_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_delete_recurring_schedule(
+    event: Request,
+    schedule_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let tenant_id = require_tenant(&event)?;
+    let schedule_id =
+        schedule_id.ok_or_else(|| AppError::BadRequest("Schedule ID is required".to_string()))?;
+
+    let record = get_schedule_record(&tenant_id, &schedule_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Recurring schedule not found".to_string()))?;
+
+    // Remove the trigger first: a dangling record is harmless, a dangling
+    // trigger keeps firing.
+    delete_scheduler(&record.scheduler_name).await?;
+    delete_schedule_record(&tenant_id, &schedule_id).await?;
+
+    response::format_response(204, ())
+}
+
+// ── Scheduler helpers ──────────────────────────────────────────────────
+
+/// Creates or replaces the EventBridge schedule backing a recurring schedule.
+/// Occurrences are evaluated in the record's timezone (so DST shifts follow
+/// local wall-clock time) and fire a `RECURRING_ISSUE_OCCURRENCE` event.
+async fn upsert_scheduler(
+    record: &RecurringScheduleRecord,
+    until: Option<DateTime<Utc>>,
+    create: bool,
+) -> Result<(), AppError> {
+    let scheduler = aws_clients::get_scheduler_client().await;
+    let role_arn = env::var("SCHEDULER_ROLE_ARN")
+        .map_err(|_| AppError::InternalError("SCHEDULER_ROLE_ARN not set".to_string()))?;
+
+    let detail = serde_json::json!({
+        "tenantId": record.tenant_id,
+        "scheduleId": record.schedule_id
+    });
+    let input = serde_json::json!({
+        "Entries": [{
+            "Source": "newsletter-service",
+            "DetailType": OCCURRENCE_DETAIL_TYPE,
+            "Detail": detail.to_string(),
+            "EventBusName": "default"
+        }]
+    });
+
+    let window = FlexibleTimeWindow::builder()
+        .mode(FlexibleTimeWindowMode::Off)
+        .build()
+        .map_err(|e| AppError::InternalError(format!("Failed to build time window: {}", e)))?;
+    let target = Target::builder()
+        .arn("arn:aws:scheduler:::aws-sdk:eventbridge:putEvents")
+        .role_arn(&role_arn)
+        .input(input.to_string())
+        .build()
+        .map_err(|e| AppError::InternalError(format!("Failed to build target: {}", e)))?;
+    let state = if record.enabled {
+        ScheduleState::Enabled
+    } else {
+        ScheduleState::Disabled
+    };
+    let end_date =
+        until.map(|until| aws_sdk_scheduler::primitives::DateTime::from_secs(until.timestamp()));
+
+    let result = if create {
+        scheduler
+            .create_schedule()
+            .name(&record.scheduler_name)
+            .group_name(SCHEDULER_GROUP)
+            .schedule_expression(&record.schedule_expression)
+            .schedule_expression_timezone(&record.timezone)
+            .set_end_date(end_date)
+            .state(state)
+            .flexible_time_window(window)
+            .target(target)
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    } else {
+        scheduler
+            .update_schedule()
+            .name(&record.scheduler_name)
+            .group_name(SCHEDULER_GROUP)
+            .schedule_expression(&record.schedule_expression)
+            .schedule_expression_timezone(&record.timezone)
+            .set_end_date(end_date)
+            .state(state)
+            .flexible_time_window(window)
+            .target(target)
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    };
+
+    result.map_err(|e| AppError::AwsError(format!("Failed to save recurring schedule: {}", e)))
+}
+
+async fn delete_scheduler(name: &str) -> Result<(), AppError> {
+    let scheduler = aws_clients::get_scheduler_client().await;
+
+    match scheduler
+        .delete_schedule()
+        .name(name)
+        .group_name(SCHEDULER_GROUP)
+        .send()
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            let service_error = e.into_service_error();
+            if service_error.is_resource_not_found_exception() {
+                Ok(())
+            } else {
+                Err(AppError::AwsError(format!(
+                    "Failed to delete recurring schedule: {}",
+                    service_error
+                )))
+            }
+        }
+    }
+}
+
+// ── Persistence helpers ────────────────────────────────────────────────
+
+fn require_tenant(event: &Request) -> Result<String, AppError> {
+    let user_context = auth::get_user_context(event)?;
+    user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Unauthorized("Tenant access required".to_string()))
+}
+
+fn table_name() -> Result<String, AppError> {
+    env::var("TABLE_NAME")
+        .map_err(|_| AppError::InternalError("TABLE_NAME not configured".to_string()))
+}
+
+async fn get_schedule_record(
+    tenant_id: &str,
+    schedule_id: &str,
+) -> Result<Option<RecurringScheduleRecord>, AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let table_name = table_name()?;
+
+    let result = client
+        .get_item()
+        .table_name(table_name)
+        .key("pk", AttributeValue::S(tenant_id.to_string()))
+        .key("sk", AttributeValue::S(schedule_sk(schedule_id)))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to get recurring schedule: {}", e)))?;
+
+    match result.item {
+        Some(item) => {
+            let record: RecurringScheduleRecord = from_item(item).map_err(|e| {
+                AppError::InternalError(format!("Failed to deserialize recurring schedule: {}", e))
+            })?;
+            Ok(Some(record))
+        }
+        None => Ok(None),
+    }
+}
+
+async fn put_schedule_record(record: &RecurringScheduleRecord) -> Result<(), AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let table_name = table_name()?;
+
+    let item = serde_dynamo::to_item(record).map_err(|e| {
+        AppError::InternalError(format!("Failed to serialize recurring schedule: {}", e))
+    })?;
+
+    client
+        .put_item()
+        .table_name(table_name)
+        .set_item(Some(item))
+        .condition_expression("attribute_not_exists(pk) AND attribute_not_exists(sk)")
+        .send()
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("ConditionalCheckFailed") {
+                AppError::Conflict("Recurring schedule already exists".to_string())
+            } else {
+                AppError::AwsError(format!("DynamoDB put failed: {}", e))
+            }
+        })?;
+
+    Ok(())
+}
+
+/// An `UpdateItem` expression with its attribute names and values.
+struct ScheduleUpdate {
+    expression: String,
+    names: HashMap<String, String>,
+    values: HashMap<String, AttributeValue>,
+}
+
+/// The update for an edited schedule. Only the user-editable attributes are
+/// written, so the `lastRun` and `lastOccurrence` the materialize Lambda
+/// records in the meantime are kept.
+fn schedule_update(record: &RecurringScheduleRecord) -> Result<ScheduleUpdate, AppError> {
+    let content_source = serde_dynamo::to_attribute_value(&record.content_source).map_err(|e| {
+        AppError::InternalError(format!("Failed to serialize content source: {}", e))
+    })?;
+
+    let mut set = vec![
+        ("name", AttributeValue::S(record.name.clone())),
+        ("rrule", AttributeValue::S(record.rrule.clone())),
+        ("timezone", AttributeValue::S(record.timezone.clone())),
+        (
+            "scheduleExpression",
+            AttributeValue::S(record.schedule_expression.clone()),
+        ),
+        ("contentSource", content_source),
+        ("autoSchedule", AttributeValue::Bool(record.auto_schedule)),
+        (
+            "publishDelayMinutes",
+            AttributeValue::N(record.publish_delay_minutes.to_string()),
+        ),
+        ("enabled", AttributeValue::Bool(record.enabled)),
+        ("updatedAt", AttributeValue::S(record.updated_at.clone())),
+    ];
+    let mut remove = Vec::new();
+    for (attribute, value) in [
+        ("templateId", &record.template_id),
+        ("subject", &record.subject),
+    ] {
+        match value {
+            Some(value) => set.push((attribute, AttributeValue::S(value.clone()))),
+            None => remove.push(attribute),
+        }
+    }
+
+    let mut expression = format!(
+        "SET {}",
+        set.iter()
+            .map(|(attribute, _)| format!("#{0} = :{0}", attribute))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if !remove.is_empty() {
+        expression.push_str(&format!(
+            " REMOVE {}",
+            remove
+                .iter()
+                .map(|attribute| format!("#{}", attribute))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    let names = set
+        .iter()
+        .map(|(attribute, _)| *attribute)
+        .chain(remove.iter().copied())
+        .map(|attribute| (format!("#{}", attribute), attribute.to_string()))
+        .collect();
+    let values = set
+        .into_iter()
+        .map(|(attribute, value)| (format!(":{}", attribute), value))
+        .collect();
+
+    Ok(ScheduleUpdate {
+        expression,
+        names,
+        values,
+    })
+}
+
+/// Saves an edit to an existing schedule and returns the stored record.
+async fn update_schedule_record(
+    record: &RecurringScheduleRecord,
+) -> Result<RecurringScheduleRecord, AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let table_name = table_name()?;
+    let update = schedule_update(record)?;
+
+    let result = client
+        .update_item()
+        .table_name(table_name)
+        .key("pk", AttributeValue::S(record.pk.clone()))
+        .key("sk", AttributeValue::S(record.sk.clone()))
+        .update_expression(update.expression)
+        .condition_expression("attribute_exists(pk) AND attribute_exists(sk)")
+        .set_expression_attribute_names(Some(update.names))
+        .set_expression_attribute_values(Some(update.values))
+        .return_values(aws_sdk_dynamodb::types::ReturnValue::AllNew)
+        .send()
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("ConditionalCheckFailed") {
+                AppError::NotFound("Recurring schedule not found".to_string())
+            } else {
+                AppError::AwsError(format!("DynamoDB update failed: {}", e))
+            }
+        })?;
+
+    let item = result.attributes.ok_or_else(|| {
+        AppError::InternalError("Recurring schedule update returned no attributes".to_string())
+    })?;
+    from_item(item).map_err(|e| {
+        AppError::InternalError(format!("Failed to deserialize recurring schedule: {}", e))
+    })
+}
+
+async fn delete_schedule_record(tenant_id: &str, schedule_id: &str) -> Result<(), AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let table_name = table_name()?;
+
+    client
+        .delete_item()
+        .table_name(table_name)
+        .key("pk", AttributeValue::S(tenant_id.to_string()))
+        .key("sk", AttributeValue::S(schedule_sk(schedule_id)))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("DynamoDB delete failed: {}", e)))?;
+
+    Ok(())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bad_request_message(result: Result<Recurrence, AppError>) -> String {
+        match result {
+            Err(AppError::BadRequest(message)) => message,
+            Err(other) => panic!("expected BadRequest, got {:?}", other),
+            Ok(rule) => panic!("expected an error, got {:?}", rule),
+        }
+    }
+
+    #[test]
+    fn test_weekly_rule_to_cron() {
+        let rule = parse_rrule("FREQ=WEEKLY;BYDAY=MO;BYHOUR=8").unwrap();
+        assert_eq!(rule.cron_expression(), "cron(0 8 ? * MON *)");
+        assert_eq!(rule.to_rrule(), "FREQ=WEEKLY;BYDAY=MO;BYHOUR=8;BYMINUTE=0");
+    }
+
+    #[test]
+    fn test_rule_is_case_insensitive_and_accepts_prefix() {
+        let rule = parse_rrule("RRULE:freq=weekly;byday=fr,mo;byhour=17;byminute=30").unwrap();
+        assert_eq!(rule.cron_expression(), "cron(30 17 ? * FRI,MON *)");
+    }
+
+    #[test]
+    fn test_daily_rule_to_cron() {
+        let rule = parse_rrule("FREQ=DAILY;BYHOUR=6,18;BYMINUTE=15").unwrap();
+        assert_eq!(rule.cron_expression(), "cron(15 6,18 * * ? *)");
+
+        let weekdays = parse_rrule("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=7").unwrap();
+        assert_eq!(
+            weekdays.cron_expression(),
+            "cron(0 7 ? * MON,TUE,WED,THU,FRI *)"
+        );
+    }
+
+    #[test]
+    fn test_monthly_rules_to_cron() {
+        let nth = parse_rrule("FREQ=MONTHLY;BYDAY=2TU;BYHOUR=9").unwrap();
+        assert_eq!(nth.cron_expression(), "cron(0 9 ? * 3#2 *)");
+        assert_eq!(nth.to_rrule(), "FREQ=MONTHLY;BYDAY=2TU;BYHOUR=9;BYMINUTE=0");
+
+        let last = parse_rrule("FREQ=MONTHLY;BYDAY=-1FR;BYHOUR=9").unwrap();
+        assert_eq!(last.cron_expression(), "cron(0 9 ? * 6L *)");
+
+        let days = parse_rrule("FREQ=MONTHLY;BYMONTHDAY=15,1;BYHOUR=10").unwrap();
+        assert_eq!(days.cron_expression(), "cron(0 10 1,15 * ? *)");
+
+        let last_day = parse_rrule("FREQ=MONTHLY;BYMONTHDAY=-1;BYHOUR=10").unwrap();
+        assert_eq!(last_day.cron_expression(), "cron(0 10 L * ? *)");
+    }
+
+    #[test]
+    fn test_until_is_parsed_and_kept() {
+        let rule = parse_rrule("FREQ=WEEKLY;BYDAY=MO;BYHOUR=8;UNTIL=20261231").unwrap();
+        assert_eq!(
+            rule.until.unwrap().to_rfc3339(),
+            "2026-12-31T23:59:59+00:00"
+        );
+        assert!(rule.to_rrule().ends_with("UNTIL=20261231T235959Z"));
+
+        let rule = parse_rrule("FREQ=DAILY;BYHOUR=8;UNTIL=20270101T120000Z").unwrap();
+        assert_eq!(
+            rule.until.unwrap().to_rfc3339(),
+            "2027-01-01T12:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_rejects_rules_cron_cannot_express() {
+        assert!(
+            bad_request_message(parse_rrule("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO;BYHOUR=8"))
+                .contains("INTERVAL")
+        );
+        assert!(bad_request_message(parse_rrule("FREQ=YEARLY;BYHOUR=8")).contains("FREQ"));
+        assert!(
+            bad_request_message(parse_rrule("FREQ=WEEKLY;BYDAY=MO;BYHOUR=8;COUNT=3"))
+                .contains("COUNT")
+        );
+        assert!(bad_request_message(parse_rrule("BYDAY=MO;BYHOUR=8")).contains("FREQ"));
+        assert!(bad_request_message(parse_rrule("")).contains("required"));
+    }
+
+    #[test]
+    fn test_rejects_incomplete_or_conflicting_rules() {
+        assert!(bad_request_message(parse_rrule("FREQ=WEEKLY;BYDAY=MO")).contains("BYHOUR"));
+        assert!(bad_request_message(parse_rrule("FREQ=WEEKLY;BYHOUR=8")).contains("BYDAY"));
+        assert!(
+            bad_request_message(parse_rrule("FREQ=WEEKLY;BYDAY=1MO;BYHOUR=8")).contains("ordinals")
+        );
+        assert!(
+            bad_request_message(parse_rrule("FREQ=MONTHLY;BYDAY=MO;BYMONTHDAY=1;BYHOUR=8"))
+                .contains("exactly one")
+        );
+        assert!(
+            bad_request_message(parse_rrule("FREQ=MONTHLY;BYDAY=1MO,3MO;BYHOUR=8"))
+                .contains("single ordinal")
+        );
+        assert!(
+            bad_request_message(parse_rrule("FREQ=MONTHLY;BYMONTHDAY=-1,15;BYHOUR=8"))
+                .contains("-1")
+        );
+        assert!(bad_request_message(parse_rrule("FREQ=DAILY;BYHOUR=24")).contains("BYHOUR"));
+        assert!(
+            bad_request_message(parse_rrule("FREQ=WEEKLY;BYDAY=XX;BYHOUR=8")).contains("BYDAY")
+        );
+        assert!(
+            bad_request_message(parse_rrule("FREQ=WEEKLY;BYDAY=XÖY;BYHOUR=8")).contains("BYDAY")
+        );
+        assert!(
+            bad_request_message(parse_rrule("FREQ=DAILY;FREQ=WEEKLY;BYHOUR=8"))
+                .contains("repeated")
+        );
+    }
+
+    #[test]
+    fn test_normalize_content_source() {
+        let issue = normalize_content_source(&ContentSource {
+            source_type: "issue".to_string(),
+            issue_id: Some(" 42 ".to_string()),
+            path: Some("ignored".to_string()),
+            branch: None,
+        })
+        .unwrap();
+        assert_eq!(issue.issue_id.as_deref(), Some("42"));
+        assert!(issue.path.is_none());
+
+        let github = normalize_content_source(&ContentSource {
+            source_type: "github".to_string(),
+            issue_id: None,
+            path: Some("/content/newsletter/{date}.md".to_string()),
+            branch: Some(" ".to_string()),
+        })
+        .unwrap();
+        assert_eq!(github.path.as_deref(), Some("content/newsletter/{date}.md"));
+        assert!(github.branch.is_none());
+
+        let missing_placeholder = normalize_content_source(&ContentSource {
+            source_type: "github".to_string(),
+            issue_id: None,
+            path: Some("content/latest.md".to_string()),
+            branch: None,
+        });
+        assert!(matches!(missing_placeholder, Err(AppError::BadRequest(_))));
+
+        let unknown = normalize_content_source(&ContentSource {
+            source_type: "rss".to_string(),
+            issue_id: None,
+            path: None,
+            branch: None,
+        });
+        assert!(matches!(unknown, Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn test_validate_fields() {
+        assert_eq!(validate_name("  Weekly digest ").unwrap(), "Weekly digest");
+        assert!(validate_name("   ").is_err());
+        assert!(validate_timezone("America/New_York").is_ok());
+        assert!(validate_timezone("Eastern").is_err());
+        assert_eq!(validate_subject("  ").unwrap(), None);
+        assert!(validate_publish_delay(MAX_PUBLISH_DELAY_MINUTES).is_ok());
+        assert!(validate_publish_delay(-1).is_err());
+    }
+
+    #[test]
+    fn test_scheduler_name_fits_scheduler_limits() {
+        let name = scheduler_name(
+            "tenant#with spaces-and-a-long-id",
+            "01J9ZQ4Y3R8V6WQ2K5T7N1M0XC",
+        );
+        assert!(name.len() <= 64);
+        assert!(name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')));
+        assert!(name.ends_with("01J9ZQ4Y3R8V6WQ2K5T7N1M0XC"));
+    }
+
+    fn sample_record() -> RecurringScheduleRecord {
+        RecurringScheduleRecord {
+            pk: "tenant-1".to_string(),
+            sk: schedule_sk("01ABC"),
+            schedule_id: "01ABC".to_string(),
+            tenant_id: "tenant-1".to_string(),
+            name: "Weekly".to_string(),
+            rrule: "FREQ=WEEKLY;BYDAY=MO;BYHOUR=8;BYMINUTE=0".to_string(),
+            timezone: "Europe/London".to_string(),
+            schedule_expression: "cron(0 8 ? * MON *)".to_string(),
+            template_id: None,
+            subject: Some("Digest for {date}".to_string()),
+            content_source: ContentSource {
+                source_type: "issue".to_string(),
+                issue_id: Some("42".to_string()),
+                path: None,
+                branch: None,
+            },
+            auto_schedule: true,
+            publish_delay_minutes: 60,
+            enabled: true,
+            scheduler_name: scheduler_name("tenant-1", "01ABC"),
+            last_run: None,
+            last_occurrence: None,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_record_round_trips_through_dynamo() {
+        let record = sample_record();
+
+        let item: std::collections::HashMap<String, AttributeValue> =
+            serde_dynamo::to_item(&record).unwrap();
+        assert_eq!(
+            item.get("sk")
+                .and_then(|v| v.as_s().ok())
+                .map(String::as_str),
+            Some("recurring-schedule#01ABC")
+        );
+        let parsed: RecurringScheduleRecord = from_item(item).unwrap();
+        assert_eq!(parsed.content_source, record.content_source);
+
+        let json = serde_json::to_value(RecurringScheduleDetail::from(parsed)).unwrap();
+        assert_eq!(json["contentSource"]["type"], "issue");
+        assert_eq!(json["autoSchedule"], true);
+        assert!(json.get("pk").is_none());
+    }
+
+    #[test]
+    fn test_schedule_update_leaves_run_state_alone() {
+        let update = schedule_update(&sample_record()).unwrap();
+
+        assert!(update.expression.starts_with("SET #name = :name, "));
+        assert!(update.expression.contains("#subject = :subject"));
+        assert!(update.expression.ends_with(" REMOVE #templateId"));
+        assert_eq!(
+            update.values.get(":publishDelayMinutes"),
+            Some(&AttributeValue::N("60".to_string()))
+        );
+        for attribute in ["lastRun", "lastOccurrence", "createdAt", "schedulerName"] {
+            assert!(!update.expression.contains(attribute));
+            assert!(!update.names.values().any(|name| name == attribute));
+        }
+    }
+}
@@ -7,9 +7,12 @@ use chrono::Utc;
 use lambda_http::{Body, Error, Request, RequestExt};
 use newsletter::admin::{auth, aws_clients, error::AppError, response};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 
+use super::{subscriber_fields, subscribers};
+
 // ── Constants ──────────────────────────────────────────────────────────
 
 const SEGMENT_NAME_MAX_LEN: usize = 100;
@@ -88,11 +91,13 @@ struct InterestScoreEntry {
     last_scored_at: String,
 }
 
-/// Per-subscriber engagement + interest data fetched via BatchGetItem.
+/// Per-subscriber engagement, interest and custom field data fetched via
+/// BatchGetItem.
 type SubscriberData = (
     Option<i64>,
     Option<i64>,
     Option<HashMap<String, InterestScoreEntry>>,
+    BTreeMap<String, Value>,
 );
 
 #[derive(Serialize)]
@@ -106,6 +111,8 @@ struct MemberResponse {
     added_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     interest_scores: Option<HashMap<String, InterestScoreEntry>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    custom_fields: BTreeMap<String, Value>,
 }
 
 #[derive(Serialize)]
@@ -1279,8 +1286,12 @@ async fn handle_list_members(
         .and_then(|n| n.parse::<i64>().ok())
         .unwrap_or(0);
 
-    // 2. Parse query params: pageSize and nextToken
+    // 2. Parse query params: pageSize, nextToken and custom field filters.
+    // Filters apply to the page read from DynamoDB, so a filtered page can
+    // hold fewer than pageSize members while nextToken is still set.
     let query_params = event.query_string_parameters();
+    let fields = subscriber_fields::load_fields(&tenant_id).await?;
+    let filters = subscriber_fields::parse_field_filters(query_params.iter(), &fields)?;
 
     let page_size: i32 = query_params
         .first("pageSize")
@@ -1373,7 +1384,9 @@ async fn handle_list_members(
 
         let keys_and_attrs = KeysAndAttributes::builder()
             .set_keys(Some(keys))
-            .projection_expression("email, lastEngagedIssue, engagementCount, interestScores")
+            .projection_expression(
+                "email, lastEngagedIssue, engagementCount, interestScores, customFields",
+            )
             .build()
             .map_err(|e| {
                 AppError::InternalError(format!("Failed to build KeysAndAttributes: {}", e))
@@ -1400,9 +1413,19 @@ async fn handle_list_members(
                                 .and_then(|v| v.as_n().ok())
                                 .and_then(|n| n.parse::<i64>().ok());
                             let interest_scores = parse_interest_scores(item);
+                            let custom_fields =
+                                subscriber_fields::effective_custom_fields(&fields, item);
+                            if !subscriber_fields::matches_field_filters(&custom_fields, &filters) {
+                                continue;
+                            }
                             subscriber_map.insert(
                                 email.clone(),
-                                (last_engaged, engagement_count, interest_scores),
+                                (
+                                    last_engaged,
+                                    engagement_count,
+                                    interest_scores,
+                                    custom_fields,
+                                ),
                             );
                         }
                     }
@@ -1411,21 +1434,24 @@ async fn handle_list_members(
         }
     }
 
-    // 6. Build response, omitting members whose subscriber record no longer exists (Req 10.9)
-    let members: Vec<MemberResponse> = member_data
-        .into_iter()
-        .filter_map(|(email, added_at)| {
-            subscriber_map
-                .remove(&email)
-                .map(|(last_engaged, eng_count, scores)| MemberResponse {
-                    email,
-                    last_engaged_issue: last_engaged,
-                    engagement_count: eng_count,
-                    added_at,
-                    interest_scores: Some(scores.unwrap_or_default()),
-                })
-        })
-        .collect();
+    // 6. Build response, omitting members whose subscriber record no longer
+    // exists (Req 10.9) or doesn't pass the field filters
+    let members: Vec<MemberResponse> =
+        member_data
+            .into_iter()
+            .filter_map(|(email, added_at)| {
+                subscriber_map.remove(&email).map(
+                    |(last_engaged, eng_count, scores, custom_fields)| MemberResponse {
+                        email,
+                        last_engaged_issue: last_engaged,
+                        engagement_count: eng_count,
+                        added_at,
+                        interest_scores: Some(scores.unwrap_or_default()),
+                        custom_fields,
+                    },
+                )
+            })
+            .collect();
 
     // 7. Encode nextToken from LastEvaluatedKey
     let next_token = query_result.last_evaluated_key().map(|lek| {
@@ -1734,6 +1760,156 @@ async fn query_all_member_export_entries(
     Ok(entries)
 }
 
+// ── Audience lookups (issue targeting) ─────────────────────────────────
+
+/// Returns the ids in `segment_ids` that have no segment record for the
+/// tenant, in input order.
+pub(crate) async fn find_missing_segments(
+    tenant_id: &str,
+    segment_ids: &[String],
+) -> Result<Vec<String>, AppError> {
+    let table_name = get_subscribers_table_name()?;
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let mut found: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+    for chunk in segment_ids.chunks(MAX_BATCH_SIZE) {
+        let keys: Vec<HashMap<String, AttributeValue>> = chunk
+            .iter()
+            .map(|segment_id| {
+                let mut key = HashMap::new();
+                key.insert(
+                    "tenantId".to_string(),
+                    AttributeValue::S(tenant_id.to_string()),
+                );
+                key.insert(
+                    "email".to_string(),
+                    AttributeValue::S(format!("SEGMENT#{}", segment_id)),
+                );
+                key
+            })
+            .collect();
+
+        let mut pending = Some(
+            KeysAndAttributes::builder()
+                .set_keys(Some(keys))
+                .projection_expression("segmentId")
+                .build()
+                .map_err(|e| {
+                    AppError::InternalError(format!("Failed to build KeysAndAttributes: {}", e))
+                })?,
+        );
+
+        while let Some(keys_and_attrs) = pending.take() {
+            let result = ddb_client
+                .batch_get_item()
+                .request_items(&table_name, keys_and_attrs)
+                .send()
+                .await
+                .map_err(|e| AppError::AwsError(format!("DynamoDB BatchGetItem error: {}", e)))?;
+
+            if let Some(items) = result.responses().and_then(|r| r.get(&table_name)) {
+                for item in items {
+                    if let Some(segment_id) = item.get("segmentId").and_then(|v| v.as_s().ok()) {
+                        found.insert(segment_id.clone());
+                    }
+                }
+            }
+
+            pending = result
+                .unprocessed_keys()
+                .and_then(|keys| keys.get(&table_name))
+                .filter(|keys| !keys.keys().is_empty())
+                .cloned();
+        }
+    }
+
+    Ok(segment_ids
+        .iter()
+        .filter(|segment_id| !found.contains(*segment_id))
+        .cloned()
+        .collect())
+}
+
+/// Emails of every member of a segment.
+pub(crate) async fn list_segment_member_emails(
+    tenant_id: &str,
+    segment_id: &str,
+) -> Result<std::collections::HashSet<String>, AppError> {
+    let table_name = get_subscribers_table_name()?;
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let member_prefix = format!("SEGMENT#{}#MEMBER#", segment_id);
+    let mut emails = std::collections::HashSet::new();
+    let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+
+    loop {
+        let result = ddb_client
+            .query()
+            .table_name(&table_name)
+            .key_condition_expression("tenantId = :pk AND begins_with(email, :sk_prefix)")
+            .expression_attribute_values(":pk", AttributeValue::S(tenant_id.to_string()))
+            .expression_attribute_values(":sk_prefix", AttributeValue::S(member_prefix.clone()))
+            .projection_expression("subscriberEmail")
+            .set_exclusive_start_key(last_key.take())
+            .send()
+            .await
+            .map_err(|e| AppError::AwsError(format!("DynamoDB Query error: {}", e)))?;
+
+        for item in result.items() {
+            if let Some(email) = item.get("subscriberEmail").and_then(|v| v.as_s().ok()) {
+                emails.insert(email.clone());
+            }
+        }
+
+        match result.last_evaluated_key() {
+            Some(key) if !key.is_empty() => last_key = Some(key.clone()),
+            _ => break,
+        }
+    }
+
+    Ok(emails)
+}
+
+/// Calls `visit` with every subscriber record of the tenant (segment records
+/// sharing the partition are skipped), reading only `projection`.
+pub(crate) async fn visit_subscribers<F>(
+    tenant_id: &str,
+    projection: &str,
+    mut visit: F,
+) -> Result<(), AppError>
+where
+    F: FnMut(&HashMap<String, AttributeValue>),
+{
+    let table_name = get_subscribers_table_name()?;
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+
+    loop {
+        let result = ddb_client
+            .query()
+            .table_name(&table_name)
+            .key_condition_expression("tenantId = :pk")
+            .expression_attribute_values(":pk", AttributeValue::S(tenant_id.to_string()))
+            .projection_expression(projection)
+            .set_exclusive_start_key(last_key.take())
+            .send()
+            .await
+            .map_err(|e| AppError::AwsError(format!("DynamoDB Query error: {}", e)))?;
+
+        for item in result.items() {
+            if subscribers::is_subscriber_record(item) {
+                visit(item);
+            }
+        }
+
+        match result.last_evaluated_key() {
+            Some(key) if !key.is_empty() => last_key = Some(key.clone()),
+            _ => break,
+        }
+    }
+
+    Ok(())
+}
+
 // ── Helper functions ───────────────────────────────────────────────────
 
 fn parse_segment_item(
@@ -2662,6 +2838,7 @@ mod tests {
             engagement_count: Some(12),
             added_at: "2025-01-15T10:00:00Z".to_string(),
             interest_scores: None,
+            custom_fields: BTreeMap::new(),
         };
         let json = serde_json::to_value(&resp).unwrap();
         assert_eq!(json["email"], "user@example.com");
@@ -2678,6 +2855,7 @@ mod tests {
             engagement_count: None,
             added_at: "2025-01-15T10:00:00Z".to_string(),
             interest_scores: None,
+            custom_fields: BTreeMap::new(),
         };
         let json = serde_json::to_value(&resp).unwrap();
         assert_eq!(json["email"], "user@example.com");
@@ -2695,6 +2873,7 @@ mod tests {
                 engagement_count: Some(12),
                 added_at: "2025-01-15T10:00:00Z".to_string(),
                 interest_scores: None,
+                custom_fields: BTreeMap::new(),
             }],
             next_token: Some("abc123".to_string()),
             total_count: 42,
@@ -2727,6 +2906,7 @@ mod tests {
                 engagement_count: None,
                 added_at: "2025-01-15T10:00:00Z".to_string(),
                 interest_scores: None,
+                custom_fields: BTreeMap::new(),
             }],
             next_token: None,
             total_count: 1,
@@ -2859,6 +3039,7 @@ mod tests {
                         engagement_count: *eng_count,
                         added_at,
                         interest_scores: None,
+                        custom_fields: BTreeMap::new(),
                     })
             })
             .collect();
@@ -3912,6 +4093,7 @@ mod tests {
                     engagement_count: eng_count,
                     added_at: "2025-01-15T10:00:00Z".to_string(),
                     interest_scores: Some(parsed.unwrap_or_default()),
+                    custom_fields: BTreeMap::new(),
                 };
 
                 // 3. interest_scores is Some(empty map), not None
@@ -0,0 +1,439 @@
+//! Data subject access and erasure requests (`/subscribers/:email/dsar`).
+//!
+//! A request is a tracked async job (`pk = tenant`,
+//! `sk = subscriber-dsar#<jobId>`) worked by the process-subscriber-dsar
+//! Lambda, because it has to read every issue's engagement events.
+//!
+//! - `access` gathers everything stored about the address into a JSON bundle
+//!   on S3: the subscriber record (recent activity, interest scores, bot
+//!   flags, custom fields, tags), segment memberships, per-issue opens,
+//!   clicks, bounces and complaints, and departure records.
+//!   `GET /subscribers/dsar/:jobId` hands back a short-lived download link.
+//! - `erasure` deletes the same records, takes the deleted events back out of
+//!   the issue stats counters, pseudonymizes what has to stay (departure
+//!   records keep cohort retention whole; bounced-address lists on issue
+//!   stats are redacted) and appends a hash-chained completion receipt
+//!   (`sk = dsar-receipt#<sequence>`), so a removed or edited receipt breaks
+//!   the chain.
+//!
+//! The address itself is dropped from the job record once the worker is done;
+//! only its hash stays.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_s3::presigning::PresigningConfig;
+use chrono::Utc;
+use lambda_http::{Body, Error, Request, Response};
+use newsletter::admin::{auth, aws_clients, error::AppError, response};
+use percent_encoding::percent_decode_str;
+use serde::{Deserialize, Serialize};
+use serde_dynamo::from_item;
+use std::collections::BTreeMap;
+use std::env;
+use std::time::Duration;
+
+use super::subscribers;
+
+// ── Constants ──────────────────────────────────────────────────────────
+
+const JOB_SK_PREFIX: &str = "subscriber-dsar#";
+/// How long a bundle download link stays valid.
+const DOWNLOAD_URL_EXPIRES_SECS: u64 = 900;
+const JOB_TTL_DAYS: i64 = 30;
+
+const JOB_STATUS_PENDING: &str = "pending";
+const JOB_STATUS_COMPLETED: &str = "completed";
+
+fn job_sk(job_id: &str) -> String {
+    format!("{}{}", JOB_SK_PREFIX, job_id)
+}
+
+// ── Types ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum DsarMode {
+    Access,
+    Erasure,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct CreateDsarRequest {
+    mode: Option<DsarMode>,
+}
+
+/// Erasure receipt as recorded on the job. The full receipt (with the
+/// per-record counts it hashes over) is the `dsar-receipt#<sequence>` record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DsarReceipt {
+    sequence: i64,
+    receipt_hash: String,
+    previous_hash: String,
+    completed_at: String,
+}
+
+/// The job record. The worker updates `status`, `summary`, `bundleKey`,
+/// `receipt`, `startedAt`/`finishedAt` and `updatedAt` in place, and removes
+/// `email` when it finishes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DsarJobRecord {
+    pk: String,
+    sk: String,
+    job_id: String,
+    tenant_id: String,
+    user_id: String,
+    mode: DsarMode,
+    /// `pending`, `running`, `completed` or `failed`.
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    email_hash: String,
+    /// Records found (access) or erased (erasure), by kind.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    summary: Option<BTreeMap<String, i64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bundle_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    receipt: Option<DsarReceipt>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    failure_reason: Option<String>,
+    created_at: String,
+    updated_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    started_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    finished_at: Option<String>,
+    ttl: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DsarJobResponse {
+    job_id: String,
+    mode: DsarMode,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    email_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<BTreeMap<String, i64>>,
+    /// Presigned link to the access bundle, once it is ready.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    download_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_in: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receipt: Option<DsarReceipt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure_reason: Option<String>,
+    created_at: String,
+    updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finished_at: Option<String>,
+}
+
+impl From<DsarJobRecord> for DsarJobResponse {
+    fn from(r: DsarJobRecord) -> Self {
+        DsarJobResponse {
+            job_id: r.job_id,
+            mode: r.mode,
+            status: r.status,
+            email: r.email,
+            email_hash: r.email_hash,
+            summary: r.summary,
+            download_url: None,
+            expires_in: None,
+            receipt: r.receipt,
+            failure_reason: r.failure_reason,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+            started_at: r.started_at,
+            finished_at: r.finished_at,
+        }
+    }
+}
+
+// ── Public handlers ────────────────────────────────────────────────────
+
+/// POST /subscribers/:email/dsar
+pub async fn create_request(
+    event: Request,
+    email: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_create_request(event, email).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+/// GET /subscribers/dsar/:jobId
+pub async fn get_request(event: Request, job_id: Option<String>) -> Result<Response<Body>, Error> {
+    match handle_get_request(event, job_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+// ── Internal handlers ──────────────────────────────────────────────────
+
+/// Requests are accepted whether or not the address is still subscribed:
+/// engagement events and departure records outlive the subscriber row.
+async fn handle_create_request(
+    event: Request,
+    email: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
+
+    let email = email.ok_or_else(|| AppError::BadRequest("Email is required".to_string()))?;
+    let decoded_email = percent_decode_str(&email)
+        .decode_utf8()
+        .map_err(|e| AppError::BadRequest(format!("Invalid email encoding: {}", e)))?;
+    let email = subscribers::subscriber_key(&decoded_email)?;
+
+    let request: CreateDsarRequest = if event.body().is_empty() {
+        CreateDsarRequest::default()
+    } else {
+        serde_json::from_slice(event.body())
+            .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?
+    };
+    let mode = request.mode.unwrap_or(DsarMode::Access);
+
+    let now = Utc::now();
+    let job_id = ulid::Ulid::new().to_string();
+    let record = DsarJobRecord {
+        pk: tenant_id.clone(),
+        sk: job_sk(&job_id),
+        job_id: job_id.clone(),
+        tenant_id: tenant_id.clone(),
+        user_id: user_context.user_id.clone(),
+        mode,
+        status: JOB_STATUS_PENDING.to_string(),
+        email_hash: subscribers::hash_email(&email),
+        email: Some(email),
+        summary: None,
+        bundle_key: None,
+        receipt: None,
+        failure_reason: None,
+        created_at: now.to_rfc3339(),
+        updated_at: now.to_rfc3339(),
+        started_at: None,
+        finished_at: None,
+        ttl: (now + chrono::Duration::days(JOB_TTL_DAYS)).timestamp(),
+    };
+
+    put_job_record(&record).await?;
+    invoke_worker(&tenant_id, &job_id).await?;
+
+    tracing::info!(
+        tenant_id = %tenant_id,
+        job_id = %job_id,
+        mode = ?mode,
+        "Data subject request queued"
+    );
+
+    response::format_response(202, DsarJobResponse::from(record))
+}
+
+async fn handle_get_request(
+    event: Request,
+    job_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
+
+    let job_id = job_id.ok_or_else(|| AppError::BadRequest("Job ID is required".to_string()))?;
+    let record = get_job_record(&tenant_id, &job_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Data subject request not found".to_string()))?;
+
+    let bundle_key = downloadable_bundle(&record).map(str::to_string);
+    let mut body = DsarJobResponse::from(record);
+    if let Some(key) = bundle_key {
+        body.download_url = Some(presign_download(&key).await?);
+        body.expires_in = Some(DOWNLOAD_URL_EXPIRES_SECS);
+    }
+
+    response::format_response(200, body)
+}
+
+/// The bundle to link to: only a finished access request has one.
+fn downloadable_bundle(record: &DsarJobRecord) -> Option<&str> {
+    if record.mode == DsarMode::Access && record.status == JOB_STATUS_COMPLETED {
+        record.bundle_key.as_deref()
+    } else {
+        None
+    }
+}
+
+// ── Storage ────────────────────────────────────────────────────────────
+
+fn table_name() -> Result<String, AppError> {
+    env::var("TABLE_NAME").map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))
+}
+
+fn bucket_name() -> Result<String, AppError> {
+    env::var("BUCKET").map_err(|_| AppError::InternalError("BUCKET not set".to_string()))
+}
+
+async fn presign_download(key: &str) -> Result<String, AppError> {
+    let presigned = aws_clients::get_s3_client()
+        .await
+        .get_object()
+        .bucket(bucket_name()?)
+        .key(key)
+        .response_content_disposition("attachment; filename=\"subscriber-data.json\"")
+        .presigned(
+            PresigningConfig::expires_in(Duration::from_secs(DOWNLOAD_URL_EXPIRES_SECS))
+                .map_err(|e| AppError::InternalError(format!("Presign config error: {}", e)))?,
+        )
+        .await
+        .map_err(|e| AppError::InternalError(format!("Presign failed: {}", e)))?;
+
+    Ok(presigned.uri().to_string())
+}
+
+async fn get_job_record(tenant_id: &str, job_id: &str) -> Result<Option<DsarJobRecord>, AppError> {
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+
+    let result = ddb_client
+        .get_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(tenant_id.to_string()))
+        .key("sk", AttributeValue::S(job_sk(job_id)))
+        .consistent_read(true)
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to get data subject request: {}", e)))?;
+
+    match result.item {
+        Some(item) => from_item(item).map(Some).map_err(|e| {
+            AppError::InternalError(format!("Failed to deserialize data subject request: {}", e))
+        }),
+        None => Ok(None),
+    }
+}
+
+async fn put_job_record(record: &DsarJobRecord) -> Result<(), AppError> {
+    let item = serde_dynamo::to_item(record).map_err(|e| {
+        AppError::InternalError(format!("Failed to serialize data subject request: {}", e))
+    })?;
+
+    aws_clients::get_dynamodb_client()
+        .await
+        .put_item()
+        .table_name(table_name()?)
+        .set_item(Some(item))
+        .condition_expression("attribute_not_exists(pk)")
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("DynamoDB put failed: {}", e)))?;
+
+    Ok(())
+}
+
+async fn invoke_worker(tenant_id: &str, job_id: &str) -> Result<(), AppError> {
+    let function_name = env::var("SUBSCRIBER_DSAR_FUNCTION_NAME").map_err(|_| {
+        AppError::InternalError("SUBSCRIBER_DSAR_FUNCTION_NAME not set".to_string())
+    })?;
+
+    let payload = serde_json::json!({
+        "tenantId": tenant_id,
+        "jobId": job_id
+    });
+
+    aws_clients::get_lambda_client()
+        .await
+        .invoke()
+        .function_name(&function_name)
+        .invocation_type(aws_sdk_lambda::types::InvocationType::Event)
+        .payload(aws_smithy_types::Blob::new(
+            serde_json::to_vec(&payload).map_err(|e| {
+                AppError::InternalError(format!("Failed to serialize payload: {}", e))
+            })?,
+        ))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Lambda invoke error: {}", e)))?;
+
+    Ok(())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(mode: DsarMode, status: &str) -> DsarJobRecord {
+        DsarJobRecord {
+            pk: "tenant-a".to_string(),
+            sk: job_sk("01JOB"),
+            job_id: "01JOB".to_string(),
+            tenant_id: "tenant-a".to_string(),
+            user_id: "user-1".to_string(),
+            mode,
+            status: status.to_string(),
+            email: Some("reader@example.com".to_string()),
+            email_hash: subscribers::hash_email("reader@example.com"),
+            summary: None,
+            bundle_key: Some("subscriber-dsar/tenant-a/01JOB.json".to_string()),
+            receipt: None,
+            failure_reason: None,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+            started_at: None,
+            finished_at: None,
+            ttl: 0,
+        }
+    }
+
+    #[test]
+    fn test_create_request_mode_defaults_and_rejects_unknown() {
+        let request: CreateDsarRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(request.mode.unwrap_or(DsarMode::Access), DsarMode::Access);
+
+        let request: CreateDsarRequest = serde_json::from_str(r#"{"mode":"erasure"}"#).unwrap();
+        assert_eq!(request.mode, Some(DsarMode::Erasure));
+
+        assert!(serde_json::from_str::<CreateDsarRequest>(r#"{"mode":"delete"}"#).is_err());
+        assert!(serde_json::from_str::<CreateDsarRequest>(r#"{"erase":true}"#).is_err());
+    }
+
+    #[test]
+    fn test_job_record_round_trips_through_dynamo() {
+        let mut original = record(DsarMode::Erasure, JOB_STATUS_COMPLETED);
+        original.email = None;
+        original.summary = Some(BTreeMap::from([("events".to_string(), 4)]));
+        original.receipt = Some(DsarReceipt {
+            sequence: 3,
+            receipt_hash: "abc".to_string(),
+            previous_hash: "def".to_string(),
+            completed_at: "2026-01-01T00:01:00Z".to_string(),
+        });
+
+        let item: std::collections::HashMap<String, AttributeValue> =
+            serde_dynamo::to_item(&original).unwrap();
+        assert_eq!(item.get("mode").unwrap().as_s().unwrap(), "erasure");
+        assert!(!item.contains_key("email"));
+
+        let parsed: DsarJobRecord = from_item(item).unwrap();
+        assert_eq!(parsed.receipt, original.receipt);
+        assert_eq!(parsed.summary, original.summary);
+    }
+
+    #[test]
+    fn test_downloadable_bundle_only_for_completed_access() {
+        assert!(downloadable_bundle(&record(DsarMode::Access, JOB_STATUS_COMPLETED)).is_some());
+        assert!(downloadable_bundle(&record(DsarMode::Access, JOB_STATUS_PENDING)).is_none());
+        assert!(downloadable_bundle(&record(DsarMode::Erasure, JOB_STATUS_COMPLETED)).is_none());
+    }
+}
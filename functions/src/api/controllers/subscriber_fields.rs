@@ -0,0 +1,1013 @@
+//! Tenant-defined custom subscriber fields.
+//!
+//! A tenant describes extra subscriber attributes (company, role, plan, ...)
+//! in a single `subscriber-fields` record. Values live on the subscriber row in
+//! a `customFields` map keyed by field key and are validated against these
+//! definitions on every API write and import. A subscriber without a stored
+//! value reads as the field's default, both here and when an issue is rendered.
+//!
+//! The value rules are mirrored by functions/utils/subscriber-fields.mjs for
+//! imports and the send path. Keep the two in sync.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use lambda_http::{Body, Error, Request, Response};
+use newsletter::admin::{auth, aws_clients, error::AppError, response};
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_dynamo::from_item;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::sync::OnceLock;
+
+// ── Constants ──────────────────────────────────────────────────────────
+
+const SCHEMA_SK: &str = "subscriber-fields";
+
+/// Subscriber attribute holding the custom field values.
+pub(crate) const CUSTOM_FIELDS_ATTRIBUTE: &str = "customFields";
+
+/// Built-in subscriber attributes. They share the `subscriber.*` template
+/// namespace and the import column mapping with custom fields.
+const RESERVED_KEYS: [&str; 3] = ["email", "firstName", "lastName"];
+
+const MAX_FIELDS: usize = 25;
+const LABEL_MAX_LEN: usize = 100;
+const MAX_ENUM_OPTIONS: usize = 50;
+const OPTION_MAX_LEN: usize = 100;
+const STRING_VALUE_MAX_LEN: usize = 500;
+
+/// Query parameter prefix for list filters: `field.<key>[.<op>]=<value>`.
+const FILTER_PARAM_PREFIX: &str = "field.";
+
+static FIELD_KEY_RE: OnceLock<Regex> = OnceLock::new();
+
+/// Keys are used as `{{subscriber.<key>}}` in templates, so they must be a
+/// plain Handlebars identifier.
+fn field_key_regex() -> &'static Regex {
+    FIELD_KEY_RE.get_or_init(|| {
+        Regex::new(r"^[a-zA-Z][a-zA-Z0-9_]{0,39}$").expect("Failed to compile field key regex")
+    })
+}
+
+// ── Data types ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum FieldType {
+    String,
+    Number,
+    /// A calendar date, stored as `YYYY-MM-DD`.
+    Date,
+    Boolean,
+    /// One of the field's `options`.
+    Enum,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SubscriberField {
+    pub(crate) key: String,
+    label: String,
+    #[serde(rename = "type")]
+    pub(crate) field_type: FieldType,
+    /// Every subscriber must have a value (stored or default).
+    #[serde(default)]
+    pub(crate) required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) default: Option<Value>,
+    /// Allowed values of an `enum` field.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    options: Vec<String>,
+    created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct FieldSchemaRecord {
+    fields: Vec<SubscriberField>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_by: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FieldListResponse {
+    fields: Vec<SubscriberField>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateFieldRequest {
+    key: String,
+    label: Option<String>,
+    #[serde(rename = "type")]
+    field_type: FieldType,
+    #[serde(default)]
+    required: bool,
+    #[serde(default)]
+    default: Option<Value>,
+    #[serde(default)]
+    options: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct UpdateFieldRequest {
+    label: Option<String>,
+    #[serde(rename = "type")]
+    field_type: Option<FieldType>,
+    required: Option<bool>,
+    /// `null` clears the default; omitted leaves it alone.
+    #[serde(default, deserialize_with = "present")]
+    default: Option<Value>,
+    options: Option<Vec<String>>,
+}
+
+/// Distinguishes an explicit `null` (`Some(Value::Null)`) from an omitted key.
+fn present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Exists,
+}
+
+/// One `field.<key>[.<op>]=<value>` list filter, with the value already
+/// normalised for the field's type.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FieldFilter {
+    key: String,
+    op: FilterOp,
+    value: Value,
+}
+
+// ── Field definition rules ─────────────────────────────────────────────
+
+fn validate_key(key: &str, existing: &[SubscriberField]) -> Result<(), AppError> {
+    if !field_key_regex().is_match(key) {
+        return Err(AppError::BadRequest(
+            "Field key must start with a letter and contain only letters, digits and underscores (max 40 characters)"
+                .to_string(),
+        ));
+    }
+    if RESERVED_KEYS
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(key))
+    {
+        return Err(AppError::BadRequest(format!(
+            "'{}' is a built-in subscriber field",
+            key
+        )));
+    }
+    // Keys are matched case-insensitively against CSV headers.
+    if existing.iter().any(|f| f.key.eq_ignore_ascii_case(key)) {
+        return Err(AppError::Conflict(format!(
+            "A field with key '{}' already exists",
+            key
+        )));
+    }
+    Ok(())
+}
+
+fn clean_label(label: &str) -> Result<String, AppError> {
+    let label = label.trim();
+    if label.is_empty() || label.chars().count() > LABEL_MAX_LEN {
+        return Err(AppError::BadRequest(format!(
+            "label must be 1-{} characters",
+            LABEL_MAX_LEN
+        )));
+    }
+    Ok(label.to_string())
+}
+
+fn clean_options(field_type: FieldType, options: Vec<String>) -> Result<Vec<String>, AppError> {
+    if field_type != FieldType::Enum {
+        if !options.is_empty() {
+            return Err(AppError::BadRequest(
+                "options are only allowed on enum fields".to_string(),
+            ));
+        }
+        return Ok(options);
+    }
+    if options.is_empty() || options.len() > MAX_ENUM_OPTIONS {
+        return Err(AppError::BadRequest(format!(
+            "An enum field needs 1-{} options",
+            MAX_ENUM_OPTIONS
+        )));
+    }
+
+    let mut cleaned: Vec<String> = Vec::with_capacity(options.len());
+    for option in options {
+        let option = option.trim().to_string();
+        if option.is_empty() || option.chars().count() > OPTION_MAX_LEN {
+            return Err(AppError::BadRequest(format!(
+                "Options must be 1-{} characters",
+                OPTION_MAX_LEN
+            )));
+        }
+        if cleaned.iter().any(|o| o.eq_ignore_ascii_case(&option)) {
+            return Err(AppError::BadRequest(format!(
+                "Option '{}' is listed more than once",
+                option
+            )));
+        }
+        cleaned.push(option);
+    }
+    Ok(cleaned)
+}
+
+/// Validates the field's default against its own type and options, storing
+/// the normalised value. A `null` default is dropped.
+fn apply_default(field: &mut SubscriberField, default: Option<Value>) -> Result<(), AppError> {
+    field.default = match default {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(
+            normalize_value(field, &value)
+                .map_err(|e| AppError::BadRequest(format!("default: {}", e)))?,
+        ),
+    };
+    Ok(())
+}
+
+fn build_field(
+    body: CreateFieldRequest,
+    existing: &[SubscriberField],
+    now: &str,
+) -> Result<SubscriberField, AppError> {
+    if existing.len() >= MAX_FIELDS {
+        return Err(AppError::BadRequest(format!(
+            "A tenant can define at most {} subscriber fields",
+            MAX_FIELDS
+        )));
+    }
+    let key = body.key.trim().to_string();
+    validate_key(&key, existing)?;
+
+    let mut field = SubscriberField {
+        label: clean_label(body.label.as_deref().unwrap_or(&key))?,
+        key,
+        field_type: body.field_type,
+        required: body.required,
+        default: None,
+        options: clean_options(body.field_type, body.options)?,
+        created_at: now.to_string(),
+        updated_at: None,
+    };
+    apply_default(&mut field, body.default)?;
+    Ok(field)
+}
+
+fn apply_field_update(
+    current: &SubscriberField,
+    body: UpdateFieldRequest,
+    now: &str,
+) -> Result<SubscriberField, AppError> {
+    if body.field_type.is_some_and(|t| t != current.field_type) {
+        return Err(AppError::BadRequest(
+            "A field's type can't be changed; delete it and create a new one".to_string(),
+        ));
+    }
+
+    let mut field = current.clone();
+    if let Some(label) = body.label {
+        field.label = clean_label(&label)?;
+    }
+    if let Some(required) = body.required {
+        field.required = required;
+    }
+    if let Some(options) = body.options {
+        field.options = clean_options(field.field_type, options)?;
+    }
+    // Re-check the default whenever the options change.
+    let default = body.default.or_else(|| field.default.clone());
+    apply_default(&mut field, default)?;
+    field.updated_at = Some(now.to_string());
+    Ok(field)
+}
+
+// ── Value rules ────────────────────────────────────────────────────────
+
+/// A value that reads as "not set": `null` or a blank string.
+fn is_blank(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// The stored form of `value` for this field, or why it doesn't fit.
+fn normalize_value(field: &SubscriberField, value: &Value) -> Result<Value, String> {
+    match (field.field_type, value) {
+        (FieldType::String, Value::String(s)) => {
+            let s = s.trim();
+            if s.chars().count() > STRING_VALUE_MAX_LEN {
+                return Err(format!(
+                    "must be at most {} characters",
+                    STRING_VALUE_MAX_LEN
+                ));
+            }
+            Ok(Value::String(s.to_string()))
+        }
+        (FieldType::Number, Value::Number(n)) if n.as_f64().is_some_and(f64::is_finite) => {
+            Ok(value.clone())
+        }
+        (FieldType::Date, Value::String(s)) => {
+            chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+                .map(|d| Value::String(d.format("%Y-%m-%d").to_string()))
+                .map_err(|_| "must be a date in YYYY-MM-DD format".to_string())
+        }
+        (FieldType::Boolean, Value::Bool(_)) => Ok(value.clone()),
+        (FieldType::Enum, Value::String(s)) => field
+            .options
+            .iter()
+            .find(|option| option.eq_ignore_ascii_case(s.trim()))
+            .map(|option| Value::String(option.clone()))
+            .ok_or_else(|| format!("must be one of: {}", field.options.join(", "))),
+        (FieldType::String | FieldType::Enum, _) => Err("must be a string".to_string()),
+        (FieldType::Number, _) => Err("must be a number".to_string()),
+        (FieldType::Date, _) => Err("must be a date string (YYYY-MM-DD)".to_string()),
+        (FieldType::Boolean, _) => Err("must be true or false".to_string()),
+    }
+}
+
+/// Applies `input` on top of the subscriber's `current` values and checks the
+/// result against the schema. `null` or a blank string clears a value. Stored
+/// values for fields that no longer exist are dropped. Fails on unknown keys,
+/// values that don't fit their field, and required fields left without a
+/// value or default.
+pub(crate) fn resolve_custom_fields(
+    fields: &[SubscriberField],
+    current: &BTreeMap<String, Value>,
+    input: &Map<String, Value>,
+) -> Result<BTreeMap<String, Value>, AppError> {
+    let mut values: BTreeMap<String, Value> = current
+        .iter()
+        .filter(|(key, _)| fields.iter().any(|f| &f.key == *key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+
+    for (key, value) in input {
+        let field = fields
+            .iter()
+            .find(|f| &f.key == key)
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown subscriber field '{}'", key)))?;
+        if is_blank(value) {
+            values.remove(key);
+            continue;
+        }
+        let normalized = normalize_value(field, value)
+            .map_err(|e| AppError::BadRequest(format!("customFields.{} {}", key, e)))?;
+        values.insert(key.clone(), normalized);
+    }
+
+    if let Some(missing) = fields
+        .iter()
+        .find(|f| f.required && f.default.is_none() && !values.contains_key(&f.key))
+    {
+        return Err(AppError::BadRequest(format!(
+            "customFields.{} is required",
+            missing.key
+        )));
+    }
+    Ok(values)
+}
+
+// ── Subscriber record helpers ──────────────────────────────────────────
+
+fn attribute_to_value(attribute: &AttributeValue) -> Option<Value> {
+    match attribute {
+        AttributeValue::S(s) => Some(Value::String(s.clone())),
+        AttributeValue::Bool(b) => Some(Value::Bool(*b)),
+        AttributeValue::N(n) => n.parse::<i64>().ok().map(Value::from).or_else(|| {
+            n.parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number)
+        }),
+        _ => None,
+    }
+}
+
+fn value_to_attribute(value: &Value) -> Option<AttributeValue> {
+    match value {
+        Value::String(s) => Some(AttributeValue::S(s.clone())),
+        Value::Bool(b) => Some(AttributeValue::Bool(*b)),
+        Value::Number(n) => Some(AttributeValue::N(n.to_string())),
+        _ => None,
+    }
+}
+
+/// The values stored on a subscriber row, as written.
+pub(crate) fn stored_custom_fields(
+    item: &HashMap<String, AttributeValue>,
+) -> BTreeMap<String, Value> {
+    item.get(CUSTOM_FIELDS_ATTRIBUTE)
+        .and_then(|v| v.as_m().ok())
+        .map(|map| {
+            map.iter()
+                .filter_map(|(key, attribute)| {
+                    attribute_to_value(attribute).map(|value| (key.clone(), value))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The subscriber's value for every defined field that has one, falling back
+/// to the field's default.
+pub(crate) fn effective_custom_fields(
+    fields: &[SubscriberField],
+    item: &HashMap<String, AttributeValue>,
+) -> BTreeMap<String, Value> {
+    let stored = stored_custom_fields(item);
+    fields
+        .iter()
+        .filter_map(|field| {
+            stored
+                .get(&field.key)
+                .or(field.default.as_ref())
+                .map(|value| (field.key.clone(), value.clone()))
+        })
+        .collect()
+}
+
+/// The `customFields` map attribute for a set of resolved values.
+pub(crate) fn custom_fields_attribute(values: &BTreeMap<String, Value>) -> AttributeValue {
+    AttributeValue::M(
+        values
+            .iter()
+            .filter_map(|(key, value)| value_to_attribute(value).map(|a| (key.clone(), a)))
+            .collect(),
+    )
+}
+
+// ── List filters ───────────────────────────────────────────────────────
+
+fn parse_filter_op(op: &str) -> Option<FilterOp> {
+    match op {
+        "eq" => Some(FilterOp::Eq),
+        "ne" => Some(FilterOp::Ne),
+        "gt" => Some(FilterOp::Gt),
+        "gte" => Some(FilterOp::Gte),
+        "lt" => Some(FilterOp::Lt),
+        "lte" => Some(FilterOp::Lte),
+        "exists" => Some(FilterOp::Exists),
+        _ => None,
+    }
+}
+
+/// Parses the `field.<key>[.<op>]=<value>` query parameters. `op` is one of
+/// `eq` (the default), `ne`, `gt`, `gte`, `lt`, `lte` or `exists`; range
+/// operators only apply to number and date fields. Other parameters are
+/// ignored.
+pub(crate) fn parse_field_filters<'a>(
+    params: impl IntoIterator<Item = (&'a str, &'a str)>,
+    fields: &[SubscriberField],
+) -> Result<Vec<FieldFilter>, AppError> {
+    let mut filters = Vec::new();
+    for (name, raw) in params {
+        let Some(spec) = name.strip_prefix(FILTER_PARAM_PREFIX) else {
+            continue;
+        };
+        let (key, op) = match spec.split_once('.') {
+            Some((key, op)) => (
+                key,
+                parse_filter_op(op).ok_or_else(|| {
+                    AppError::BadRequest(format!("Unknown filter operator '{}' in {}", op, name))
+                })?,
+            ),
+            None => (spec, FilterOp::Eq),
+        };
+        let field = fields.iter().find(|f| f.key == key).ok_or_else(|| {
+            AppError::BadRequest(format!("Unknown subscriber field '{}' in {}", key, name))
+        })?;
+
+        let value = match op {
+            FilterOp::Exists => match raw {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                _ => {
+                    return Err(AppError::BadRequest(format!(
+                        "{} must be true or false",
+                        name
+                    )))
+                }
+            },
+            FilterOp::Gt | FilterOp::Gte | FilterOp::Lt | FilterOp::Lte
+                if !matches!(field.field_type, FieldType::Number | FieldType::Date) =>
+            {
+                return Err(AppError::BadRequest(format!(
+                    "{} only applies to number and date fields",
+                    name
+                )));
+            }
+            _ => parse_filter_value(field, raw)
+                .map_err(|e| AppError::BadRequest(format!("{} {}", name, e)))?,
+        };
+        filters.push(FieldFilter {
+            key: key.to_string(),
+            op,
+            value,
+        });
+    }
+    Ok(filters)
+}
+
+fn parse_filter_value(field: &SubscriberField, raw: &str) -> Result<Value, String> {
+    let value = match field.field_type {
+        FieldType::Number => raw
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| "must be a number".to_string())?,
+        FieldType::Boolean => match raw {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => return Err("must be true or false".to_string()),
+        },
+        // Strings match case-insensitively, so keep the raw text.
+        FieldType::String => return Ok(Value::String(raw.trim().to_string())),
+        FieldType::Date | FieldType::Enum => Value::String(raw.to_string()),
+    };
+    normalize_value(field, &value)
+}
+
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::String(a), Value::String(b)) => a.eq_ignore_ascii_case(b),
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => a == b,
+    }
+}
+
+fn compare_values(a: &Value, b: &Value) -> Option<std::cmp::Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        // Dates are stored as YYYY-MM-DD, which sorts chronologically.
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// Whether a subscriber's (effective) values pass every filter. A missing
+/// value only passes `ne` and `exists=false`.
+pub(crate) fn matches_field_filters(
+    values: &BTreeMap<String, Value>,
+    filters: &[FieldFilter],
+) -> bool {
+    use std::cmp::Ordering;
+
+    filters.iter().all(|filter| {
+        let actual = values.get(&filter.key);
+        if filter.op == FilterOp::Exists {
+            return actual.is_some() == (filter.value == Value::Bool(true));
+        }
+        let Some(actual) = actual else {
+            return filter.op == FilterOp::Ne;
+        };
+        let ordering = compare_values(actual, &filter.value);
+        match filter.op {
+            FilterOp::Eq => values_equal(actual, &filter.value),
+            FilterOp::Ne => !values_equal(actual, &filter.value),
+            FilterOp::Gt => ordering == Some(Ordering::Greater),
+            FilterOp::Gte => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+            FilterOp::Lt => ordering == Some(Ordering::Less),
+            FilterOp::Lte => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            FilterOp::Exists => unreachable!(),
+        }
+    })
+}
+
+// ── Endpoints ──────────────────────────────────────────────────────────
+
+/// GET /subscriber-fields
+pub async fn list_fields(event: Request) -> Result<Response<Body>, Error> {
+    match handle_list_fields(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_list_fields(event: Request) -> Result<Response<Body>, AppError> {
+    let tenant_id = require_tenant(&event)?;
+    let fields = load_fields(&tenant_id).await?;
+    response::format_response(200, FieldListResponse { fields })
+}
+
+/// POST /subscriber-fields
+pub async fn create_field(event: Request) -> Result<Response<Body>, Error> {
+    match handle_create_field(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_create_field(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&event)?;
+
+    let body: CreateFieldRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+
+    let mut record = load_record(&tenant_id).await?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let field = build_field(body, &record.fields, &now)?;
+    record.fields.push(field.clone());
+    save_record(&tenant_id, &mut record, &user_context.email, &now).await?;
+
+    tracing::info!(tenant_id = %tenant_id, key = %field.key, "Subscriber field created");
+    response::format_response(201, field)
+}
+
+/// PUT /subscriber-fields/:key
+pub async fn update_field(event: Request, key: Option<String>) -> Result<Response<Body>, Error> {
+    match handle_update_field(event, key).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_update_field(
+    event: Request,
+    key: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&event)?;
+    let key = key.ok_or_else(|| AppError::BadRequest("Field key is required".to_string()))?;
+
+    let body: UpdateFieldRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+
+    let mut record = load_record(&tenant_id).await?;
+    let position = record
+        .fields
+        .iter()
+        .position(|f| f.key == key)
+        .ok_or_else(|| AppError::NotFound("Subscriber field not found".to_string()))?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let field = apply_field_update(&record.fields[position], body, &now)?;
+    record.fields[position] = field.clone();
+    save_record(&tenant_id, &mut record, &user_context.email, &now).await?;
+
+    tracing::info!(tenant_id = %tenant_id, key = %field.key, "Subscriber field updated");
+    response::format_response(200, field)
+}
+
+/// DELETE /subscriber-fields/:key — stored values stay on subscriber rows but
+/// are no longer returned, filtered on or rendered.
+pub async fn delete_field(event: Request, key: Option<String>) -> Result<Response<Body>, Error> {
+    match handle_delete_field(event, key).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_delete_field(
+    event: Request,
+    key: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&event)?;
+    let key = key.ok_or_else(|| AppError::BadRequest("Field key is required".to_string()))?;
+
+    let mut record = load_record(&tenant_id).await?;
+    let before = record.fields.len();
+    record.fields.retain(|f| f.key != key);
+    if record.fields.len() == before {
+        return Err(AppError::NotFound("Subscriber field not found".to_string()));
+    }
+    let now = chrono::Utc::now().to_rfc3339();
+    save_record(&tenant_id, &mut record, &user_context.email, &now).await?;
+
+    tracing::info!(tenant_id = %tenant_id, key = %key, "Subscriber field deleted");
+    response::format_response(200, json!({ "message": "Subscriber field deleted" }))
+}
+
+// ── Persistence ────────────────────────────────────────────────────────
+
+fn require_tenant(event: &Request) -> Result<String, AppError> {
+    let user_context = auth::get_user_context(event)?;
+    user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))
+}
+
+fn table_name() -> Result<String, AppError> {
+    env::var("TABLE_NAME").map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))
+}
+
+async fn load_record(tenant_id: &str) -> Result<FieldSchemaRecord, AppError> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let result = client
+        .get_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(tenant_id.to_string()))
+        .key("sk", AttributeValue::S(SCHEMA_SK.to_string()))
+        .consistent_read(true)
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to load subscriber fields: {}", e)))?;
+
+    match result.item {
+        Some(item) => from_item(item).map_err(|e| {
+            AppError::InternalError(format!("Failed to deserialize subscriber fields: {}", e))
+        }),
+        None => Ok(FieldSchemaRecord::default()),
+    }
+}
+
+/// The tenant's field definitions, in creation order. Empty when none exist.
+pub(crate) async fn load_fields(tenant_id: &str) -> Result<Vec<SubscriberField>, AppError> {
+    Ok(load_record(tenant_id).await?.fields)
+}
+
+async fn save_record(
+    tenant_id: &str,
+    record: &mut FieldSchemaRecord,
+    updated_by: &str,
+    now: &str,
+) -> Result<(), AppError> {
+    record.updated_at = Some(now.to_string());
+    record.updated_by = Some(updated_by.to_string());
+
+    let client = aws_clients::get_dynamodb_client().await;
+    let mut item: HashMap<String, AttributeValue> =
+        serde_dynamo::to_item(&*record).map_err(|e| {
+            AppError::InternalError(format!("Failed to serialize subscriber fields: {}", e))
+        })?;
+    item.insert("pk".to_string(), AttributeValue::S(tenant_id.to_string()));
+    item.insert("sk".to_string(), AttributeValue::S(SCHEMA_SK.to_string()));
+
+    client
+        .put_item()
+        .table_name(table_name()?)
+        .set_item(Some(item))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to save subscriber fields: {}", e)))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(key: &str, field_type: FieldType) -> SubscriberField {
+        SubscriberField {
+            key: key.to_string(),
+            label: key.to_string(),
+            field_type,
+            required: false,
+            default: None,
+            options: if field_type == FieldType::Enum {
+                vec!["Free".to_string(), "Pro".to_string()]
+            } else {
+                Vec::new()
+            },
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: None,
+        }
+    }
+
+    fn schema() -> Vec<SubscriberField> {
+        vec![
+            field("company", FieldType::String),
+            field("seats", FieldType::Number),
+            field("renewsOn", FieldType::Date),
+            field("beta", FieldType::Boolean),
+            SubscriberField {
+                required: true,
+                default: Some(json!("Free")),
+                ..field("plan", FieldType::Enum)
+            },
+        ]
+    }
+
+    fn create(value: Value) -> CreateFieldRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn input(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_build_field_validates_definition() {
+        let now = "2026-01-01T00:00:00Z";
+        let built = build_field(
+            create(json!({ "key": "plan", "type": "enum", "options": [" Free ", "Pro"], "default": "pro" })),
+            &[],
+            now,
+        )
+        .unwrap();
+        assert_eq!(built.label, "plan");
+        assert_eq!(built.options, vec!["Free", "Pro"]);
+        assert_eq!(built.default, Some(json!("Pro")));
+
+        let existing = schema();
+        for body in [
+            json!({ "key": "1st", "type": "string" }),
+            json!({ "key": "first-name", "type": "string" }),
+            json!({ "key": "Email", "type": "string" }),
+            json!({ "key": "role", "type": "string", "options": ["a"] }),
+            json!({ "key": "tier", "type": "enum", "options": [] }),
+            json!({ "key": "tier", "type": "enum", "options": ["a", "A"] }),
+            json!({ "key": "seatsMax", "type": "number", "default": "ten" }),
+        ] {
+            assert!(
+                matches!(
+                    build_field(create(body.clone()), &existing, now),
+                    Err(AppError::BadRequest(_))
+                ),
+                "{}",
+                body
+            );
+        }
+        assert!(matches!(
+            build_field(
+                create(json!({ "key": "Company", "type": "string" })),
+                &existing,
+                now
+            ),
+            Err(AppError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn test_apply_field_update_keeps_type_and_rechecks_default() {
+        let plan = &schema()[4];
+        let now = "2026-02-01T00:00:00Z";
+
+        let update: UpdateFieldRequest =
+            serde_json::from_value(json!({ "label": "Plan", "default": null })).unwrap();
+        let updated = apply_field_update(plan, update, now).unwrap();
+        assert_eq!(updated.label, "Plan");
+        assert_eq!(updated.default, None);
+        assert_eq!(updated.updated_at.as_deref(), Some(now));
+
+        let untouched = apply_field_update(plan, UpdateFieldRequest::default(), now).unwrap();
+        assert_eq!(untouched.default, Some(json!("Free")));
+
+        let drops_default: UpdateFieldRequest =
+            serde_json::from_value(json!({ "options": ["Pro", "Team"] })).unwrap();
+        assert!(apply_field_update(plan, drops_default, now).is_err());
+
+        let retype: UpdateFieldRequest =
+            serde_json::from_value(json!({ "type": "string" })).unwrap();
+        assert!(apply_field_update(plan, retype, now).is_err());
+    }
+
+    #[test]
+    fn test_resolve_custom_fields_normalises_and_merges() {
+        let current = BTreeMap::from([
+            ("company".to_string(), json!("Acme")),
+            ("retired".to_string(), json!("gone")),
+        ]);
+        let values = resolve_custom_fields(
+            &schema(),
+            &current,
+            &input(json!({
+                "seats": 12,
+                "renewsOn": " 2026-03-01 ",
+                "beta": false,
+                "plan": "pro",
+                "company": null
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(
+            values,
+            BTreeMap::from([
+                ("beta".to_string(), json!(false)),
+                ("plan".to_string(), json!("Pro")),
+                ("renewsOn".to_string(), json!("2026-03-01")),
+                ("seats".to_string(), json!(12)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_resolve_custom_fields_rejects_bad_values() {
+        let empty = BTreeMap::new();
+        for body in [
+            json!({ "phone": "555" }),
+            json!({ "seats": "12" }),
+            json!({ "renewsOn": "03/01/2026" }),
+            json!({ "beta": "yes" }),
+            json!({ "plan": "Enterprise" }),
+            json!({ "company": "x".repeat(STRING_VALUE_MAX_LEN + 1) }),
+        ] {
+            assert!(
+                resolve_custom_fields(&schema(), &empty, &input(body.clone())).is_err(),
+                "{}",
+                body
+            );
+        }
+
+        let mut fields = schema();
+        fields[0].required = true;
+        assert!(resolve_custom_fields(&fields, &empty, &Map::new()).is_err());
+        // `plan` is required too, but its default covers it.
+        assert!(
+            resolve_custom_fields(&fields, &empty, &input(json!({ "company": "Acme" }))).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_effective_custom_fields_round_trip_with_defaults() {
+        let values = BTreeMap::from([
+            ("company".to_string(), json!("Acme")),
+            ("seats".to_string(), json!(2.5)),
+            ("beta".to_string(), json!(true)),
+        ]);
+        let mut item = HashMap::from([(
+            CUSTOM_FIELDS_ATTRIBUTE.to_string(),
+            custom_fields_attribute(&values),
+        )]);
+        if let Some(AttributeValue::M(map)) = item.get_mut(CUSTOM_FIELDS_ATTRIBUTE) {
+            map.insert("retired".to_string(), AttributeValue::S("gone".to_string()));
+        }
+
+        let mut expected = values.clone();
+        expected.insert("plan".to_string(), json!("Free"));
+        assert_eq!(effective_custom_fields(&schema(), &item), expected);
+        assert_eq!(stored_custom_fields(&item).len(), 4);
+        assert!(effective_custom_fields(&[], &item).is_empty());
+    }
+
+    #[test]
+    fn test_parse_and_match_field_filters() {
+        let fields = schema();
+        let filters = parse_field_filters(
+            [
+                ("field.plan", "pro"),
+                ("field.seats.gte", "10"),
+                ("field.renewsOn.lt", "2026-06-01"),
+                ("field.company.exists", "true"),
+                ("limit", "50"),
+            ],
+            &fields,
+        )
+        .unwrap();
+        assert_eq!(filters.len(), 4);
+
+        let subscriber = BTreeMap::from([
+            ("company".to_string(), json!("Acme")),
+            ("plan".to_string(), json!("Pro")),
+            ("seats".to_string(), json!(12)),
+            ("renewsOn".to_string(), json!("2026-03-01")),
+        ]);
+        assert!(matches_field_filters(&subscriber, &filters));
+
+        let mut small = subscriber.clone();
+        small.insert("seats".to_string(), json!(3));
+        assert!(!matches_field_filters(&small, &filters));
+
+        let ne = parse_field_filters([("field.company.ne", "ACME")], &fields).unwrap();
+        assert!(!matches_field_filters(&subscriber, &ne));
+        assert!(matches_field_filters(&BTreeMap::new(), &ne));
+
+        for params in [
+            [("field.phone", "1")],
+            [("field.company.gt", "a")],
+            [("field.seats.between", "1")],
+            [("field.seats", "many")],
+            [("field.plan", "Enterprise")],
+            [("field.beta.exists", "yes")],
+        ] {
+            assert!(
+                parse_field_filters(params, &fields).is_err(),
+                "{:?}",
+                params
+            );
+        }
+    }
+
+    #[test]
+    fn test_schema_record_round_trips_through_dynamo() {
+        let record = FieldSchemaRecord {
+            fields: schema(),
+            updated_at: Some("2026-01-01T00:00:00Z".to_string()),
+            updated_by: Some("owner@example.com".to_string()),
+        };
+        let item: HashMap<String, AttributeValue> = serde_dynamo::to_item(&record).unwrap();
+        let parsed: FieldSchemaRecord = from_item(item).unwrap();
+        assert_eq!(parsed.fields, record.fields);
+    }
+}
@@ -0,0 +1,637 @@
+//! CSV subscriber import (`/subscribers/import`).
+//!
+//! An import is a tracked async job (`pk = tenant`,
+//! `sk = subscriber-import#<jobId>`). `POST /subscribers/import` creates the
+//! job and hands back a presigned S3 PUT URL for the CSV; once the upload is
+//! done, `POST /subscribers/import/:jobId/start` checks the object is there
+//! and invokes the process-subscriber-import Lambda. The worker applies the
+//! column mapping, validates and lowercases each address, drops duplicates
+//! within the file, skips addresses that are already subscribed or that
+//! unsubscribed/bounced/complained before, validates custom field columns
+//! against the tenant's `subscriber_fields` definitions, and records every
+//! skipped row with its reason on the job for `GET /subscribers/import/:jobId`.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_s3::operation::head_object::HeadObjectError;
+use aws_sdk_s3::presigning::PresigningConfig;
+use chrono::Utc;
+use lambda_http::{Body, Error, Request, Response};
+use newsletter::admin::{auth, aws_clients, error::AppError, response};
+use serde::{Deserialize, Serialize};
+use serde_dynamo::from_item;
+use std::collections::BTreeMap;
+use std::env;
+use std::time::Duration;
+
+use super::subscriber_fields::{self, SubscriberField};
+
+// ── Constants ──────────────────────────────────────────────────────────
+
+const JOB_SK_PREFIX: &str = "subscriber-import#";
+const UPLOAD_KEY_PREFIX: &str = "subscriber-imports/";
+
+const UPLOAD_URL_EXPIRES_SECS: u64 = 900;
+/// Keeps a full file inside a single worker run.
+const MAX_UPLOAD_BYTES: i64 = 10 * 1024 * 1024;
+const JOB_TTL_DAYS: i64 = 30;
+
+/// Built-in subscriber fields a CSV column can be mapped onto. Custom field
+/// keys can be mapped too.
+const MAPPABLE_FIELDS: [&str; 3] = ["email", "firstName", "lastName"];
+
+const JOB_STATUS_AWAITING_UPLOAD: &str = "awaiting-upload";
+const JOB_STATUS_PENDING: &str = "pending";
+
+fn job_sk(job_id: &str) -> String {
+    format!("{}{}", JOB_SK_PREFIX, job_id)
+}
+
+fn upload_key(tenant_id: &str, job_id: &str) -> String {
+    format!("{}{}/{}.csv", UPLOAD_KEY_PREFIX, tenant_id, job_id)
+}
+
+// ── Types ──────────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct CreateImportRequest {
+    /// Subscriber field -> CSV header. Unmapped fields are matched by
+    /// common header names (`Email Address`, `first_name`, ...); custom
+    /// fields by their key or label.
+    #[serde(default)]
+    column_mapping: Option<BTreeMap<String, String>>,
+}
+
+/// Counts the worker writes when the job finishes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportSummary {
+    total_rows: i64,
+    imported: i64,
+    already_subscribed: i64,
+    duplicates: i64,
+    suppressed: i64,
+    invalid: i64,
+    failed: i64,
+}
+
+/// A row that was not imported. `row` is the 1-based record number in the
+/// file, header included.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportRowError {
+    row: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    /// `missing-email`, `invalid-email`, `invalid-field`, `duplicate`,
+    /// `already-subscribed`, `suppressed` or `write-failed`.
+    reason: String,
+    /// The custom field an `invalid-field` row failed on, and why.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    field: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+/// The job record. The worker updates `status`, `summary`, `errors`,
+/// `startedAt`/`finishedAt` and `updatedAt` in place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportJobRecord {
+    pk: String,
+    sk: String,
+    job_id: String,
+    tenant_id: String,
+    user_id: String,
+    /// `awaiting-upload`, `pending`, `running`, `completed` or `failed`.
+    status: String,
+    upload_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    column_mapping: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    summary: Option<ImportSummary>,
+    #[serde(default)]
+    errors: Vec<ImportRowError>,
+    /// Set when more rows were skipped than the record keeps.
+    #[serde(default)]
+    errors_truncated: bool,
+    /// Why a `failed` job stopped (unreadable file, no email column, ...).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    failure_reason: Option<String>,
+    created_at: String,
+    updated_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    started_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    finished_at: Option<String>,
+    ttl: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportJobResponse {
+    job_id: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column_mapping: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<ImportSummary>,
+    errors: Vec<ImportRowError>,
+    errors_truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure_reason: Option<String>,
+    created_at: String,
+    updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finished_at: Option<String>,
+}
+
+impl From<ImportJobRecord> for ImportJobResponse {
+    fn from(r: ImportJobRecord) -> Self {
+        ImportJobResponse {
+            job_id: r.job_id,
+            status: r.status,
+            column_mapping: r.column_mapping,
+            summary: r.summary,
+            errors: r.errors,
+            errors_truncated: r.errors_truncated,
+            failure_reason: r.failure_reason,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+            started_at: r.started_at,
+            finished_at: r.finished_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateImportResponse {
+    job_id: String,
+    status: String,
+    upload_url: String,
+    expires_in: u64,
+}
+
+// ── Public handlers ────────────────────────────────────────────────────
+
+/// POST /subscribers/import
+pub async fn create_import(event: Request) -> Result<Response<Body>, Error> {
+    match handle_create_import(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+/// POST /subscribers/import/:jobId/start
+pub async fn start_import(event: Request, job_id: Option<String>) -> Result<Response<Body>, Error> {
+    match handle_start_import(event, job_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+/// GET /subscribers/import/:jobId
+pub async fn get_import(event: Request, job_id: Option<String>) -> Result<Response<Body>, Error> {
+    match handle_get_import(event, job_id).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+// ── Internal handlers ──────────────────────────────────────────────────
+
+async fn handle_create_import(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
+
+    let request: CreateImportRequest = if event.body().is_empty() {
+        CreateImportRequest::default()
+    } else {
+        serde_json::from_slice(event.body())
+            .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?
+    };
+    let column_mapping = match request.column_mapping {
+        Some(mapping) => {
+            let fields = subscriber_fields::load_fields(&tenant_id).await?;
+            Some(validate_column_mapping(mapping, &fields)?)
+        }
+        None => None,
+    };
+
+    let now = Utc::now();
+    let job_id = ulid::Ulid::new().to_string();
+    let record = ImportJobRecord {
+        pk: tenant_id.clone(),
+        sk: job_sk(&job_id),
+        job_id: job_id.clone(),
+        tenant_id: tenant_id.clone(),
+        user_id: user_context.user_id.clone(),
+        status: JOB_STATUS_AWAITING_UPLOAD.to_string(),
+        upload_key: upload_key(&tenant_id, &job_id),
+        column_mapping,
+        summary: None,
+        errors: Vec::new(),
+        errors_truncated: false,
+        failure_reason: None,
+        created_at: now.to_rfc3339(),
+        updated_at: now.to_rfc3339(),
+        started_at: None,
+        finished_at: None,
+        ttl: (now + chrono::Duration::days(JOB_TTL_DAYS)).timestamp(),
+    };
+
+    let upload_url = presign_upload(&record.upload_key).await?;
+    put_job_record(&record).await?;
+
+    response::format_response(
+        201,
+        CreateImportResponse {
+            job_id,
+            status: record.status,
+            upload_url,
+            expires_in: UPLOAD_URL_EXPIRES_SECS,
+        },
+    )
+}
+
+async fn handle_start_import(
+    event: Request,
+    job_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
+
+    let job_id = job_id.ok_or_else(|| AppError::BadRequest("Job ID is required".to_string()))?;
+    let mut record = get_job_record(&tenant_id, &job_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Import job not found".to_string()))?;
+
+    if record.status != JOB_STATUS_AWAITING_UPLOAD {
+        return Err(AppError::Conflict(format!(
+            "Import job has already been started (status: {})",
+            record.status
+        )));
+    }
+
+    let size = uploaded_size(&record.upload_key).await?;
+    check_upload_size(size)?;
+
+    let now = Utc::now();
+    mark_pending(&tenant_id, &job_id, &now.to_rfc3339()).await?;
+    invoke_worker(&tenant_id, &job_id).await?;
+
+    tracing::info!(
+        tenant_id = %tenant_id,
+        job_id = %job_id,
+        bytes = size,
+        "Subscriber import job queued"
+    );
+
+    record.status = JOB_STATUS_PENDING.to_string();
+    record.updated_at = now.to_rfc3339();
+    response::format_response(202, ImportJobResponse::from(record))
+}
+
+async fn handle_get_import(
+    event: Request,
+    job_id: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
+
+    let job_id = job_id.ok_or_else(|| AppError::BadRequest("Job ID is required".to_string()))?;
+    let record = get_job_record(&tenant_id, &job_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Import job not found".to_string()))?;
+
+    response::format_response(200, ImportJobResponse::from(record))
+}
+
+// ── Validation ─────────────────────────────────────────────────────────
+
+/// Mapped fields must be built-in or one of the tenant's custom fields and
+/// point at a non-blank header; two fields can't share a column. Headers are
+/// trimmed; the worker matches them case-insensitively.
+fn validate_column_mapping(
+    mapping: BTreeMap<String, String>,
+    custom_fields: &[SubscriberField],
+) -> Result<BTreeMap<String, String>, AppError> {
+    let mut validated = BTreeMap::new();
+    for (field, header) in mapping {
+        if !MAPPABLE_FIELDS.contains(&field.as_str())
+            && !custom_fields.iter().any(|f| f.key == field)
+        {
+            return Err(AppError::BadRequest(format!(
+                "Unknown columnMapping field '{}'. Must be one of {} or a custom field key",
+                field,
+                MAPPABLE_FIELDS.join(", ")
+            )));
+        }
+        let header = header.trim().to_string();
+        if header.is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "columnMapping.{} must name a CSV column",
+                field
+            )));
+        }
+        if validated
+            .values()
+            .any(|existing: &String| existing.eq_ignore_ascii_case(&header))
+        {
+            return Err(AppError::BadRequest(format!(
+                "Column '{}' is mapped to more than one field",
+                header
+            )));
+        }
+        validated.insert(field, header);
+    }
+    Ok(validated)
+}
+
+fn check_upload_size(size: i64) -> Result<(), AppError> {
+    if size <= 0 {
+        return Err(AppError::BadRequest(
+            "The uploaded file is empty".to_string(),
+        ));
+    }
+    if size > MAX_UPLOAD_BYTES {
+        return Err(AppError::BadRequest(format!(
+            "The uploaded file is larger than {} MB; split it into several imports",
+            MAX_UPLOAD_BYTES / (1024 * 1024)
+        )));
+    }
+    Ok(())
+}
+
+// ── Storage ────────────────────────────────────────────────────────────
+
+fn table_name() -> Result<String, AppError> {
+    env::var("TABLE_NAME").map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))
+}
+
+fn bucket_name() -> Result<String, AppError> {
+    env::var("BUCKET").map_err(|_| AppError::InternalError("BUCKET not set".to_string()))
+}
+
+async fn presign_upload(key: &str) -> Result<String, AppError> {
+    let presigned = aws_clients::get_s3_client()
+        .await
+        .put_object()
+        .bucket(bucket_name()?)
+        .key(key)
+        .content_type("text/csv")
+        .presigned(
+            PresigningConfig::expires_in(Duration::from_secs(UPLOAD_URL_EXPIRES_SECS))
+                .map_err(|e| AppError::InternalError(format!("Presign config error: {}", e)))?,
+        )
+        .await
+        .map_err(|e| AppError::InternalError(format!("Presign failed: {}", e)))?;
+
+    Ok(presigned.uri().to_string())
+}
+
+async fn uploaded_size(key: &str) -> Result<i64, AppError> {
+    let result = aws_clients::get_s3_client()
+        .await
+        .head_object()
+        .bucket(bucket_name()?)
+        .key(key)
+        .send()
+        .await;
+
+    match result {
+        Ok(head) => Ok(head.content_length().unwrap_or(0)),
+        Err(err) => {
+            let is_not_found = err
+                .as_service_error()
+                .map(|service_error| matches!(service_error, HeadObjectError::NotFound(_)))
+                .unwrap_or(false);
+            if is_not_found {
+                Err(AppError::BadRequest(
+                    "No file has been uploaded for this import yet".to_string(),
+                ))
+            } else {
+                Err(AppError::AwsError(format!("S3 head failed: {}", err)))
+            }
+        }
+    }
+}
+
+async fn get_job_record(
+    tenant_id: &str,
+    job_id: &str,
+) -> Result<Option<ImportJobRecord>, AppError> {
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+
+    let result = ddb_client
+        .get_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(tenant_id.to_string()))
+        .key("sk", AttributeValue::S(job_sk(job_id)))
+        .consistent_read(true)
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to get import job: {}", e)))?;
+
+    match result.item {
+        Some(item) => from_item(item).map(Some).map_err(|e| {
+            AppError::InternalError(format!("Failed to deserialize import job: {}", e))
+        }),
+        None => Ok(None),
+    }
+}
+
+async fn put_job_record(record: &ImportJobRecord) -> Result<(), AppError> {
+    let item = serde_dynamo::to_item(record)
+        .map_err(|e| AppError::InternalError(format!("Failed to serialize import job: {}", e)))?;
+
+    aws_clients::get_dynamodb_client()
+        .await
+        .put_item()
+        .table_name(table_name()?)
+        .set_item(Some(item))
+        .condition_expression("attribute_not_exists(pk)")
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("DynamoDB put failed: {}", e)))?;
+
+    Ok(())
+}
+
+/// Moves the job out of `awaiting-upload`, so a second start is a conflict
+/// rather than a second worker run.
+async fn mark_pending(tenant_id: &str, job_id: &str, now: &str) -> Result<(), AppError> {
+    aws_clients::get_dynamodb_client()
+        .await
+        .update_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(tenant_id.to_string()))
+        .key("sk", AttributeValue::S(job_sk(job_id)))
+        .update_expression("SET #status = :pending, updatedAt = :now")
+        .condition_expression("#status = :awaiting")
+        .expression_attribute_names("#status", "status")
+        .expression_attribute_values(
+            ":pending",
+            AttributeValue::S(JOB_STATUS_PENDING.to_string()),
+        )
+        .expression_attribute_values(
+            ":awaiting",
+            AttributeValue::S(JOB_STATUS_AWAITING_UPLOAD.to_string()),
+        )
+        .expression_attribute_values(":now", AttributeValue::S(now.to_string()))
+        .send()
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("ConditionalCheckFailed") {
+                AppError::Conflict("Import job has already been started".to_string())
+            } else {
+                AppError::AwsError(format!("DynamoDB update failed: {}", e))
+            }
+        })?;
+
+    Ok(())
+}
+
+async fn invoke_worker(tenant_id: &str, job_id: &str) -> Result<(), AppError> {
+    let function_name = env::var("SUBSCRIBER_IMPORT_FUNCTION_NAME").map_err(|_| {
+        AppError::InternalError("SUBSCRIBER_IMPORT_FUNCTION_NAME not set".to_string())
+    })?;
+
+    let payload = serde_json::json!({
+        "tenantId": tenant_id,
+        "jobId": job_id
+    });
+
+    aws_clients::get_lambda_client()
+        .await
+        .invoke()
+        .function_name(&function_name)
+        .invocation_type(aws_sdk_lambda::types::InvocationType::Event)
+        .payload(aws_smithy_types::Blob::new(
+            serde_json::to_vec(&payload).map_err(|e| {
+                AppError::InternalError(format!("Failed to serialize payload: {}", e))
+            })?,
+        ))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Lambda invoke error: {}", e)))?;
+
+    Ok(())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(field, header)| (field.to_string(), header.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_column_mapping_trims_headers() {
+        let validated = validate_column_mapping(
+            mapping(&[("email", " Email Address "), ("firstName", "First")]),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            validated.get("email").map(String::as_str),
+            Some("Email Address")
+        );
+        assert_eq!(
+            validated.get("firstName").map(String::as_str),
+            Some("First")
+        );
+    }
+
+    #[test]
+    fn test_validate_column_mapping_rejects_bad_entries() {
+        assert!(validate_column_mapping(mapping(&[("phone", "Phone")]), &[]).is_err());
+        assert!(validate_column_mapping(mapping(&[("email", "  ")]), &[]).is_err());
+        assert!(validate_column_mapping(
+            mapping(&[("firstName", "Name"), ("lastName", "name")]),
+            &[]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate_column_mapping_accepts_custom_fields() {
+        let fields: Vec<SubscriberField> = serde_json::from_value(serde_json::json!([
+            { "key": "company", "label": "Company", "type": "string", "createdAt": "2026-01-01T00:00:00Z" }
+        ]))
+        .unwrap();
+        let validated =
+            validate_column_mapping(mapping(&[("company", "Organisation")]), &fields).unwrap();
+        assert_eq!(
+            validated.get("company").map(String::as_str),
+            Some("Organisation")
+        );
+        assert!(validate_column_mapping(mapping(&[("Company", "Org")]), &fields).is_err());
+    }
+
+    #[test]
+    fn test_check_upload_size_bounds() {
+        assert!(check_upload_size(0).is_err());
+        assert!(check_upload_size(1024).is_ok());
+        assert!(check_upload_size(MAX_UPLOAD_BYTES + 1).is_err());
+    }
+
+    #[test]
+    fn test_job_record_reads_worker_fields() {
+        let item: std::collections::HashMap<String, AttributeValue> =
+            serde_dynamo::to_item(serde_json::json!({
+                "pk": "tenant-1",
+                "sk": "subscriber-import#01JOB",
+                "jobId": "01JOB",
+                "tenantId": "tenant-1",
+                "userId": "user-1",
+                "status": "completed",
+                "uploadKey": "subscriber-imports/tenant-1/01JOB.csv",
+                "summary": {
+                    "totalRows": 4, "imported": 1, "alreadySubscribed": 0,
+                    "duplicates": 1, "suppressed": 0, "invalid": 2, "failed": 0
+                },
+                "errors": [
+                    { "row": 3, "email": "a@example.com", "reason": "duplicate" },
+                    { "row": 4, "reason": "missing-email" },
+                    { "row": 5, "email": "b@example.com", "reason": "invalid-field",
+                      "field": "plan", "message": "must be one of: Free, Pro" }
+                ],
+                "createdAt": "2025-03-01T00:00:00Z",
+                "updatedAt": "2025-03-01T00:01:00Z",
+                "ttl": 0
+            }))
+            .unwrap();
+
+        let response = ImportJobResponse::from(from_item::<_, ImportJobRecord>(item).unwrap());
+        assert_eq!(response.summary.unwrap().imported, 1);
+        assert_eq!(response.errors.len(), 3);
+        assert_eq!(response.errors[1].email, None);
+        assert_eq!(response.errors[2].field.as_deref(), Some("plan"));
+        assert!(!response.errors_truncated);
+    }
+
+    #[test]
+    fn test_upload_key_is_scoped_to_tenant_and_job() {
+        assert_eq!(
+            upload_key("tenant-1", "01JOB"),
+            "subscriber-imports/tenant-1/01JOB.csv"
+        );
+        assert_eq!(job_sk("01JOB"), "subscriber-import#01JOB");
+    }
+}
@@ -0,0 +1,846 @@
+//! Subscriber tags.
+//!
+//! Tags are lightweight labels ("conference-2026", "beta-tester") kept as a
+//! `tags` string set on the subscriber row, so adding or removing one is a
+//! single `ADD` / `DELETE` update rather than a `SEGMENT#...#MEMBER#` record.
+//! Tags can be changed on one subscriber or in bulk, either for a list of
+//! addresses or for every subscriber matching a tag/custom field filter.
+//!
+//! Every subscriber whose tags actually change gets a `Subscriber Tags
+//! Changed` EventBridge event so automations can react to it.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use lambda_http::{Body, Error, Request, Response};
+use newsletter::admin::{auth, aws_clients, error::AppError, response};
+use percent_encoding::percent_decode_str;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::env;
+use std::sync::OnceLock;
+use tokio::task::JoinSet;
+
+use super::subscriber_fields;
+use super::subscribers;
+
+// ── Constants ──────────────────────────────────────────────────────────
+
+/// Subscriber attribute holding the tag string set.
+pub(crate) const TAGS_ATTRIBUTE: &str = "tags";
+
+/// Query parameter for list filters; repeat it to require several tags.
+const TAG_PARAM: &str = "tag";
+
+const MAX_TAGS_PER_REQUEST: usize = 20;
+const MAX_BULK_EMAILS: usize = 1000;
+/// Keeps a filtered bulk change inside the API Gateway timeout.
+const MAX_BULK_SUBSCRIBERS: usize = 10_000;
+const WRITE_CONCURRENCY: usize = 25;
+/// PutEvents takes at most 10 entries per call.
+const EVENTS_PER_CALL: usize = 10;
+
+static TAG_RE: OnceLock<Regex> = OnceLock::new();
+
+fn tag_regex() -> &'static Regex {
+    TAG_RE.get_or_init(|| {
+        Regex::new(r"^[a-z0-9][a-z0-9_-]{0,49}$").expect("Failed to compile tag regex")
+    })
+}
+
+// ── Types ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TagAction {
+    Add,
+    Remove,
+}
+
+#[derive(Deserialize)]
+struct TagsRequest {
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkTagRequest {
+    action: TagAction,
+    tags: Vec<String>,
+    emails: Option<Vec<String>>,
+    filter: Option<BulkTagFilter>,
+}
+
+/// Same rules as the `GET /subscribers` filters: every tag must be present
+/// and every `fields` entry is a `field.<key>[.<op>]` filter without the
+/// `field.` prefix. An empty filter matches every subscriber.
+#[derive(Deserialize, Default)]
+struct BulkTagFilter {
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    fields: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SubscriberTagsResponse {
+    email: String,
+    tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkTagResponse {
+    action: TagAction,
+    tags: Vec<String>,
+    /// Subscribers the request targeted (listed addresses that exist, or
+    /// filter matches).
+    matched: usize,
+    /// Subscribers whose tags changed.
+    updated: usize,
+    /// Listed addresses that aren't subscribers.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    not_found: Vec<String>,
+    failed: usize,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct TagCount {
+    tag: String,
+    count: i64,
+}
+
+#[derive(Serialize)]
+struct TagCountsResponse {
+    tags: Vec<TagCount>,
+}
+
+/// The tags one subscriber gained or lost, and the set it ended up with.
+#[derive(Debug, Clone, PartialEq)]
+struct TagChange {
+    email: String,
+    changed: Vec<String>,
+    tags: BTreeSet<String>,
+}
+
+#[derive(Default)]
+struct BulkOutcome {
+    changes: Vec<TagChange>,
+    not_found: Vec<String>,
+    failed: usize,
+}
+
+// ── Tag rules ──────────────────────────────────────────────────────────
+
+/// Trimmed and lowercased, so `Beta-Tester` and `beta-tester` are one tag.
+fn normalize_tag(raw: &str) -> Result<String, AppError> {
+    let tag = raw.trim().to_lowercase();
+    if !tag_regex().is_match(&tag) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid tag '{}': use up to 50 lowercase letters, digits, hyphens and underscores",
+            raw.trim()
+        )));
+    }
+    Ok(tag)
+}
+
+/// Normalised, de-duplicated and sorted request tags.
+fn normalize_tags(raw: &[String]) -> Result<Vec<String>, AppError> {
+    if raw.is_empty() {
+        return Err(AppError::BadRequest("Provide at least one tag".to_string()));
+    }
+    let tags = raw
+        .iter()
+        .map(|tag| normalize_tag(tag))
+        .collect::<Result<BTreeSet<_>, _>>()?;
+    if tags.len() > MAX_TAGS_PER_REQUEST {
+        return Err(AppError::BadRequest(format!(
+            "A request can change at most {} tags",
+            MAX_TAGS_PER_REQUEST
+        )));
+    }
+    Ok(tags.into_iter().collect())
+}
+
+/// The subscriber's tags, empty when it has none.
+pub(crate) fn stored_tags(item: &HashMap<String, AttributeValue>) -> BTreeSet<String> {
+    item.get(TAGS_ATTRIBUTE)
+        .and_then(|v| v.as_ss().ok())
+        .map(|tags| tags.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Parses the repeatable `tag=<tag>` list filter. Other parameters are
+/// ignored.
+pub(crate) fn parse_tag_filters<'a>(
+    params: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Result<Vec<String>, AppError> {
+    params
+        .into_iter()
+        .filter(|(name, _)| *name == TAG_PARAM)
+        .map(|(_, value)| normalize_tag(value))
+        .collect()
+}
+
+/// Whether the subscriber carries every tag in `required`.
+pub(crate) fn has_tags(tags: &BTreeSet<String>, required: &[String]) -> bool {
+    required.iter().all(|tag| tags.contains(tag))
+}
+
+/// The request tags that `action` actually changes on a subscriber holding
+/// `current`, and the resulting set.
+fn diff_tags(
+    action: TagAction,
+    current: &BTreeSet<String>,
+    tags: &[String],
+) -> (Vec<String>, BTreeSet<String>) {
+    let changed: Vec<String> = tags
+        .iter()
+        .filter(|tag| (action == TagAction::Add) != current.contains(*tag))
+        .cloned()
+        .collect();
+    let mut result = current.clone();
+    for tag in &changed {
+        match action {
+            TagAction::Add => result.insert(tag.clone()),
+            TagAction::Remove => result.remove(tag),
+        };
+    }
+    (changed, result)
+}
+
+/// Tag counts sorted by count (descending), then name.
+fn count_tags<'a>(tag_sets: impl IntoIterator<Item = &'a BTreeSet<String>>) -> Vec<TagCount> {
+    let mut counts: BTreeMap<&str, i64> = BTreeMap::new();
+    for tags in tag_sets {
+        for tag in tags {
+            *counts.entry(tag).or_default() += 1;
+        }
+    }
+    let mut result: Vec<TagCount> = counts
+        .into_iter()
+        .map(|(tag, count)| TagCount {
+            tag: tag.to_string(),
+            count,
+        })
+        .collect();
+    result.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+    result
+}
+
+fn build_event_detail(
+    tenant_id: &str,
+    user_id: &str,
+    action: TagAction,
+    change: &TagChange,
+    source: &str,
+) -> serde_json::Value {
+    let (added, removed) = match action {
+        TagAction::Add => (change.changed.clone(), Vec::new()),
+        TagAction::Remove => (Vec::new(), change.changed.clone()),
+    };
+    serde_json::json!({
+        "tenantId": tenant_id,
+        "userId": user_id,
+        "type": "SUBSCRIBER_TAGS_CHANGED",
+        "data": {
+            "email": change.email,
+            "added": added,
+            "removed": removed,
+            "tags": change.tags,
+            "source": source
+        }
+    })
+}
+
+// ── Endpoints ──────────────────────────────────────────────────────────
+
+/// GET /subscribers/tags — every tag in use with its subscriber count
+pub async fn get_tag_counts(event: Request) -> Result<Response<Body>, Error> {
+    match handle_get_tag_counts(event).await {
+        Ok(resp) => Ok(resp),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_get_tag_counts(event: Request) -> Result<Response<Body>, AppError> {
+    let tenant_id = require_tenant(&event)?;
+    let items = query_tagged_subscribers(&tenant_id, false).await?;
+    let tag_sets: Vec<BTreeSet<String>> = items.iter().map(stored_tags).collect();
+    response::format_response(
+        200,
+        TagCountsResponse {
+            tags: count_tags(&tag_sets),
+        },
+    )
+}
+
+/// POST /subscribers/:email/tags — add tags to one subscriber
+pub async fn add_tags(event: Request, email: Option<String>) -> Result<Response<Body>, Error> {
+    match handle_subscriber_tags(event, email, TagAction::Add).await {
+        Ok(resp) => Ok(resp),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+/// DELETE /subscribers/:email/tags — remove tags from one subscriber
+pub async fn remove_tags(event: Request, email: Option<String>) -> Result<Response<Body>, Error> {
+    match handle_subscriber_tags(event, email, TagAction::Remove).await {
+        Ok(resp) => Ok(resp),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_subscriber_tags(
+    event: Request,
+    email: Option<String>,
+    action: TagAction,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
+
+    let email = email.ok_or_else(|| AppError::BadRequest("Email is required".to_string()))?;
+    let decoded_email = percent_decode_str(&email)
+        .decode_utf8()
+        .map_err(|e| AppError::BadRequest(format!("Invalid email encoding: {}", e)))?;
+    let email = subscribers::subscriber_key(&decoded_email)?;
+
+    let body: TagsRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+    let tags = normalize_tags(&body.tags)?;
+
+    let change = apply_tags(
+        subscribers_table_name()?,
+        tenant_id.clone(),
+        email.clone(),
+        action,
+        tags,
+    )
+    .await?
+    .ok_or_else(|| AppError::NotFound("Subscriber not found".to_string()))?;
+
+    if !change.changed.is_empty() {
+        publish_tag_events(
+            &tenant_id,
+            &user_context.user_id,
+            action,
+            std::slice::from_ref(&change),
+            "subscriber",
+        )
+        .await;
+    }
+
+    response::format_response(
+        200,
+        SubscriberTagsResponse {
+            email,
+            tags: change.tags.into_iter().collect(),
+        },
+    )
+}
+
+/// POST /subscribers/tags/bulk — add or remove tags for a list of addresses
+/// or for every subscriber matching a filter
+pub async fn bulk_update_tags(event: Request) -> Result<Response<Body>, Error> {
+    match handle_bulk_update_tags(event).await {
+        Ok(resp) => Ok(resp),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_bulk_update_tags(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
+
+    let body: BulkTagRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+    let tags = normalize_tags(&body.tags)?;
+
+    let (matched, outcome) = match (body.emails, body.filter) {
+        (Some(emails), None) => {
+            let emails = normalize_emails(&emails)?;
+            let outcome = apply_tags_to_all(&tenant_id, emails.clone(), body.action, &tags).await?;
+            (emails.len() - outcome.not_found.len(), outcome)
+        }
+        (None, Some(filter)) => {
+            let fields = subscriber_fields::load_fields(&tenant_id).await?;
+            let params: Vec<(String, &str)> = filter
+                .fields
+                .iter()
+                .map(|(spec, value)| (format!("field.{}", spec), value.as_str()))
+                .collect();
+            let field_filters = subscriber_fields::parse_field_filters(
+                params.iter().map(|(name, value)| (name.as_str(), *value)),
+                &fields,
+            )?;
+            let tag_filters = filter
+                .tags
+                .iter()
+                .map(|tag| normalize_tag(tag))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut matched = 0;
+            let mut targets = Vec::new();
+            for item in query_tagged_subscribers(&tenant_id, true).await? {
+                let current = stored_tags(&item);
+                let values = subscriber_fields::effective_custom_fields(&fields, &item);
+                if !has_tags(&current, &tag_filters)
+                    || !subscriber_fields::matches_field_filters(&values, &field_filters)
+                {
+                    continue;
+                }
+                matched += 1;
+                // Subscribers the change wouldn't touch aren't written.
+                if !diff_tags(body.action, &current, &tags).0.is_empty() {
+                    if let Some(email) = item.get("email").and_then(|v| v.as_s().ok()) {
+                        targets.push(email.clone());
+                    }
+                }
+            }
+            if targets.len() > MAX_BULK_SUBSCRIBERS {
+                return Err(AppError::BadRequest(format!(
+                    "The filter would change {} subscribers; a bulk change takes at most {}. Narrow the filter",
+                    targets.len(),
+                    MAX_BULK_SUBSCRIBERS
+                )));
+            }
+            (
+                matched,
+                apply_tags_to_all(&tenant_id, targets, body.action, &tags).await?,
+            )
+        }
+        _ => {
+            return Err(AppError::BadRequest(
+                "Provide either emails or filter".to_string(),
+            ))
+        }
+    };
+
+    let changes: Vec<TagChange> = outcome
+        .changes
+        .into_iter()
+        .filter(|change| !change.changed.is_empty())
+        .collect();
+    publish_tag_events(
+        &tenant_id,
+        &user_context.user_id,
+        body.action,
+        &changes,
+        "bulk",
+    )
+    .await;
+
+    tracing::info!(
+        tenant_id = %tenant_id,
+        action = ?body.action,
+        matched,
+        updated = changes.len(),
+        failed = outcome.failed,
+        "Bulk subscriber tag change complete"
+    );
+
+    response::format_response(
+        200,
+        BulkTagResponse {
+            action: body.action,
+            tags,
+            matched,
+            updated: changes.len(),
+            not_found: outcome.not_found,
+            failed: outcome.failed,
+        },
+    )
+}
+
+// ── Helpers ────────────────────────────────────────────────────────────
+
+fn require_tenant(event: &Request) -> Result<String, AppError> {
+    let user_context = auth::get_user_context(event)?;
+    user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))
+}
+
+fn subscribers_table_name() -> Result<String, AppError> {
+    env::var("SUBSCRIBERS_TABLE_NAME")
+        .map_err(|_| AppError::InternalError("SUBSCRIBERS_TABLE_NAME not set".to_string()))
+}
+
+/// Subscriber keys for the listed addresses, de-duplicated in request order.
+fn normalize_emails(raw: &[String]) -> Result<Vec<String>, AppError> {
+    if raw.is_empty() {
+        return Err(AppError::BadRequest(
+            "Provide at least one email".to_string(),
+        ));
+    }
+    if raw.len() > MAX_BULK_EMAILS {
+        return Err(AppError::BadRequest(format!(
+            "A bulk change takes at most {} emails",
+            MAX_BULK_EMAILS
+        )));
+    }
+    let mut seen = BTreeSet::new();
+    let mut emails = Vec::new();
+    for value in raw {
+        let email = subscribers::subscriber_key(value).map_err(|_| {
+            AppError::BadRequest(format!("Invalid email address: {}", value.trim()))
+        })?;
+        if seen.insert(email.clone()) {
+            emails.push(email);
+        }
+    }
+    Ok(emails)
+}
+
+/// Subscriber rows for the tenant with their tags (and custom fields when
+/// `with_fields` is set).
+async fn query_tagged_subscribers(
+    tenant_id: &str,
+    with_fields: bool,
+) -> Result<Vec<HashMap<String, AttributeValue>>, AppError> {
+    let table_name = subscribers_table_name()?;
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let projection = if with_fields {
+        format!(
+            "email, #tags, {}",
+            subscriber_fields::CUSTOM_FIELDS_ATTRIBUTE
+        )
+    } else {
+        "email, #tags".to_string()
+    };
+
+    let mut items = Vec::new();
+    let mut exclusive_start_key = None;
+    loop {
+        let result = ddb_client
+            .query()
+            .table_name(&table_name)
+            .key_condition_expression("tenantId = :tid")
+            .expression_attribute_values(":tid", AttributeValue::S(tenant_id.to_string()))
+            .projection_expression(&projection)
+            .expression_attribute_names("#tags", TAGS_ATTRIBUTE)
+            .set_exclusive_start_key(exclusive_start_key)
+            .send()
+            .await?;
+
+        items.extend(
+            result
+                .items()
+                .iter()
+                .filter(|item| subscribers::is_subscriber_record(item))
+                .cloned(),
+        );
+
+        match result.last_evaluated_key() {
+            Some(key) if !key.is_empty() => exclusive_start_key = Some(key.clone()),
+            _ => break,
+        }
+    }
+    Ok(items)
+}
+
+/// Adds or removes `tags` on one subscriber. `Ok(None)` when the subscriber
+/// doesn't exist.
+async fn apply_tags(
+    table_name: String,
+    tenant_id: String,
+    email: String,
+    action: TagAction,
+    tags: Vec<String>,
+) -> Result<Option<TagChange>, AppError> {
+    let operation = match action {
+        TagAction::Add => "ADD",
+        TagAction::Remove => "DELETE",
+    };
+    let result = aws_clients::get_dynamodb_client()
+        .await
+        .update_item()
+        .table_name(table_name)
+        .key("tenantId", AttributeValue::S(tenant_id))
+        .key("email", AttributeValue::S(email.clone()))
+        .update_expression(format!(
+            "{} #tags :tags SET updatedAt = :updatedAt",
+            operation
+        ))
+        .condition_expression("attribute_exists(email)")
+        .expression_attribute_names("#tags", TAGS_ATTRIBUTE)
+        .expression_attribute_values(":tags", AttributeValue::Ss(tags.clone()))
+        .expression_attribute_values(
+            ":updatedAt",
+            AttributeValue::S(
+                chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            ),
+        )
+        .return_values(aws_sdk_dynamodb::types::ReturnValue::UpdatedOld)
+        .send()
+        .await;
+
+    let output = match result {
+        Ok(output) => output,
+        Err(e)
+            if DisplayErrorContext(&e)
+                .to_string()
+                .contains("ConditionalCheckFailed") =>
+        {
+            return Ok(None)
+        }
+        Err(e) => {
+            return Err(AppError::AwsError(format!(
+                "DynamoDB UpdateItem failed: {}",
+                DisplayErrorContext(&e)
+            )))
+        }
+    };
+
+    let previous = output.attributes().map(stored_tags).unwrap_or_default();
+    let (changed, tags) = diff_tags(action, &previous, &tags);
+    Ok(Some(TagChange {
+        email,
+        changed,
+        tags,
+    }))
+}
+
+async fn apply_tags_to_all(
+    tenant_id: &str,
+    emails: Vec<String>,
+    action: TagAction,
+    tags: &[String],
+) -> Result<BulkOutcome, AppError> {
+    let table_name = subscribers_table_name()?;
+    let mut outcome = BulkOutcome::default();
+
+    for batch in emails.chunks(WRITE_CONCURRENCY) {
+        let mut join_set = JoinSet::new();
+        for email in batch.iter().cloned() {
+            let (table_name, tenant_id, tags) =
+                (table_name.clone(), tenant_id.to_string(), tags.to_vec());
+            join_set.spawn(async move {
+                let result = apply_tags(table_name, tenant_id, email.clone(), action, tags).await;
+                (email, result)
+            });
+        }
+
+        while let Some(joined) = join_set.join_next().await {
+            match joined {
+                Ok((_, Ok(Some(change)))) => outcome.changes.push(change),
+                Ok((email, Ok(None))) => outcome.not_found.push(email),
+                Ok((email, Err(e))) => {
+                    tracing::warn!(error = ?e, tenant_id = %tenant_id, email = %email, "Failed to update subscriber tags");
+                    outcome.failed += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, tenant_id = %tenant_id, "Subscriber tag task failed");
+                    outcome.failed += 1;
+                }
+            }
+        }
+    }
+
+    outcome.not_found.sort();
+    Ok(outcome)
+}
+
+/// Publish one `Subscriber Tags Changed` event per changed subscriber.
+/// Best-effort: the tags are already written, so failures are only logged.
+async fn publish_tag_events(
+    tenant_id: &str,
+    user_id: &str,
+    action: TagAction,
+    changes: &[TagChange],
+    source: &str,
+) {
+    let client = aws_clients::get_eventbridge_client().await;
+    let chunks: Vec<Vec<String>> = changes
+        .chunks(EVENTS_PER_CALL)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|change| {
+                    build_event_detail(tenant_id, user_id, action, change, source).to_string()
+                })
+                .collect()
+        })
+        .collect();
+
+    for batch in chunks.chunks(WRITE_CONCURRENCY) {
+        let mut join_set = JoinSet::new();
+        for details in batch.iter().cloned() {
+            join_set.spawn(async move {
+                let entries = details
+                    .into_iter()
+                    .map(|detail| {
+                        aws_sdk_eventbridge::types::PutEventsRequestEntry::builder()
+                            .source("newsletter-service")
+                            .detail_type("Subscriber Tags Changed")
+                            .detail(detail)
+                            .build()
+                    })
+                    .collect();
+                client.put_events().set_entries(Some(entries)).send().await
+            });
+        }
+
+        while let Some(joined) = join_set.join_next().await {
+            match joined {
+                Ok(Ok(output)) if output.failed_entry_count() > 0 => {
+                    tracing::warn!(
+                        tenant_id = %tenant_id,
+                        failed = output.failed_entry_count(),
+                        "EventBridge rejected Subscriber Tags Changed events"
+                    );
+                }
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => {
+                    tracing::warn!(error = %e, tenant_id = %tenant_id, "Failed to publish Subscriber Tags Changed events");
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, tenant_id = %tenant_id, "Subscriber tag event task failed");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(tags: &[&str]) -> BTreeSet<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    fn strings(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_tags_lowercases_dedupes_and_sorts() {
+        let tags = normalize_tags(&strings(&[
+            " Beta-Tester ",
+            "conference-2026",
+            "beta-tester",
+        ]))
+        .unwrap();
+        assert_eq!(tags, strings(&["beta-tester", "conference-2026"]));
+    }
+
+    #[test]
+    fn test_normalize_tags_rejects_invalid_and_empty() {
+        assert!(normalize_tags(&[]).is_err());
+        assert!(normalize_tags(&strings(&["has space"])).is_err());
+        assert!(normalize_tags(&strings(&["-leading"])).is_err());
+        assert!(normalize_tags(&strings(&[&"a".repeat(51)])).is_err());
+        let too_many: Vec<String> = (0..=MAX_TAGS_PER_REQUEST)
+            .map(|i| format!("t{}", i))
+            .collect();
+        assert!(normalize_tags(&too_many).is_err());
+    }
+
+    #[test]
+    fn test_diff_tags_reports_only_real_changes() {
+        let current = set(&["beta-tester", "vip"]);
+
+        let (added, tags) = diff_tags(TagAction::Add, &current, &strings(&["beta-tester", "new"]));
+        assert_eq!(added, strings(&["new"]));
+        assert_eq!(tags, set(&["beta-tester", "new", "vip"]));
+
+        let (removed, tags) = diff_tags(TagAction::Remove, &current, &strings(&["vip", "missing"]));
+        assert_eq!(removed, strings(&["vip"]));
+        assert_eq!(tags, set(&["beta-tester"]));
+
+        let (none, _) = diff_tags(TagAction::Remove, &BTreeSet::new(), &strings(&["vip"]));
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_parse_tag_filters_and_has_tags() {
+        let filters = parse_tag_filters(vec![
+            ("tag", "VIP"),
+            ("field.plan", "Pro"),
+            ("tag", "beta-tester"),
+        ])
+        .unwrap();
+        assert_eq!(filters, strings(&["vip", "beta-tester"]));
+
+        assert!(has_tags(&set(&["beta-tester", "vip", "x"]), &filters));
+        assert!(!has_tags(&set(&["vip"]), &filters));
+        assert!(has_tags(&BTreeSet::new(), &[]));
+        assert!(parse_tag_filters(vec![("tag", "not valid")]).is_err());
+    }
+
+    #[test]
+    fn test_stored_tags_reads_string_set() {
+        let item = HashMap::from([
+            (
+                "email".to_string(),
+                AttributeValue::S("a@example.com".to_string()),
+            ),
+            (
+                TAGS_ATTRIBUTE.to_string(),
+                AttributeValue::Ss(strings(&["vip", "beta-tester"])),
+            ),
+        ]);
+        assert_eq!(stored_tags(&item), set(&["beta-tester", "vip"]));
+        assert!(stored_tags(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_count_tags_sorts_by_count_then_name() {
+        let sets = [
+            set(&["vip", "beta"]),
+            set(&["beta"]),
+            set(&["alpha", "vip"]),
+        ];
+        assert_eq!(
+            count_tags(&sets),
+            vec![
+                TagCount {
+                    tag: "beta".to_string(),
+                    count: 2
+                },
+                TagCount {
+                    tag: "vip".to_string(),
+                    count: 2
+                },
+                TagCount {
+                    tag: "alpha".to_string(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_emails_dedupes_and_validates() {
+        let emails = normalize_emails(&strings(&[
+            "A@Example.com",
+            "a@example.com",
+            "b@example.com",
+        ]))
+        .unwrap();
+        assert_eq!(emails, strings(&["a@example.com", "b@example.com"]));
+        assert!(normalize_emails(&[]).is_err());
+        assert!(normalize_emails(&strings(&["not-an-email"])).is_err());
+    }
+
+    #[test]
+    fn test_build_event_detail_shape() {
+        let change = TagChange {
+            email: "a@example.com".to_string(),
+            changed: strings(&["vip"]),
+            tags: set(&["beta", "vip"]),
+        };
+        let detail = build_event_detail("t1", "u1", TagAction::Add, &change, "bulk");
+        assert_eq!(detail["type"], "SUBSCRIBER_TAGS_CHANGED");
+        assert_eq!(detail["data"]["added"], serde_json::json!(["vip"]));
+        assert_eq!(detail["data"]["removed"], serde_json::json!([]));
+        assert_eq!(detail["data"]["tags"], serde_json::json!(["beta", "vip"]));
+        assert_eq!(detail["data"]["source"], "bulk");
+    }
+}
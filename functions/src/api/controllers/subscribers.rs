@@ -1,13 +1,21 @@
-use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::types::{AttributeValue, KeysAndAttributes};
 use aws_smithy_types::error::display::DisplayErrorContext;
 use lambda_http::{Body, Error, Request, RequestExt, Response};
+use newsletter::admin::suppression::{self, SuppressionEntry, SuppressionKind, SuppressionReason};
 use newsletter::admin::{auth, aws_clients, error::AppError, response};
+use newsletter::senders::validation;
 use percent_encoding::percent_decode_str;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 
+use super::issues::{decode_pagination_token, encode_pagination_token};
+use super::subscriber_fields::{self, FieldFilter, SubscriberField};
+use super::subscriber_tags;
+
 // ── Response types ─────────────────────────────────────────────────────
 
 #[derive(Serialize)]
@@ -80,6 +88,64 @@ struct SubscriberTrendSummary {
     points_returned: i64,
 }
 
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct SubscriberCohortsResponse {
+    cohorts: Vec<SignupCohort>,
+    issues: Vec<CohortIssue>,
+    /// Subscribers and departures skipped because `addedAt` is missing or unparseable.
+    excluded_subscribers: i64,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct CohortIssue {
+    issue_number: i64,
+    published_at: String,
+}
+
+/// Subscribers who signed up in one calendar month, current and departed.
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct SignupCohort {
+    /// Signup month as `YYYY-MM` (UTC).
+    cohort_month: String,
+    size: i64,
+    still_subscribed: i64,
+    average_engagement_count: f64,
+    /// One cell per issue published after the cohort month ended, oldest first.
+    retention: Vec<CohortRetentionCell>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct CohortRetentionCell {
+    issue_number: i64,
+    /// 1 for the first issue published after the signup month, 2 for the next, etc.
+    issues_since_signup: i64,
+    subscribed: i64,
+    subscribed_percentage: f64,
+    /// Members whose `lastEngagedIssue` is at or after this issue, i.e. still
+    /// engaging as of it.
+    engaged: i64,
+    engaged_percentage: f64,
+}
+
+/// A current or departed subscriber placed into a signup cohort.
+#[derive(Debug, Clone)]
+struct CohortMember {
+    signed_up_at: chrono::DateTime<chrono::Utc>,
+    departed_at: Option<chrono::DateTime<chrono::Utc>>,
+    last_engaged_issue: Option<i64>,
+    engagement_count: i64,
+}
+
+#[derive(Debug, Clone)]
+struct PublishedIssue {
+    issue_number: i64,
+    published_at: chrono::DateTime<chrono::Utc>,
+}
+
 /// Engagement cohort classification for a single subscriber.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum EngagementCohort {
@@ -92,6 +158,95 @@ struct SubscriberTrendsQuery {
     issue_count: i32,
 }
 
+struct SubscriberCohortsQuery {
+    issue_count: i32,
+    months: usize,
+}
+
+/// Orders `GET /subscribers` can return. `Email` is the table's key order
+/// and pages straight off DynamoDB; the others sort the tenant's list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SubscriberSort {
+    Email,
+    AddedAt,
+    LastEngagedIssue,
+    EngagementCount,
+}
+
+impl SubscriberSort {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "email" => Some(Self::Email),
+            "addedAt" => Some(Self::AddedAt),
+            "lastEngagedIssue" => Some(Self::LastEngagedIssue),
+            "engagementCount" => Some(Self::EngagementCount),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::AddedAt => "addedAt",
+            Self::LastEngagedIssue => "lastEngagedIssue",
+            Self::EngagementCount => "engagementCount",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue {
+    Text(String),
+    Number(i64),
+}
+
+/// A subscriber's place in a sorted listing; also the keyset cursor.
+#[derive(Debug, Clone, PartialEq)]
+struct SortEntry {
+    value: Option<SortValue>,
+    email: String,
+}
+
+/// `GET /subscribers` paging, ordering and filters.
+#[derive(Debug, PartialEq)]
+struct SubscriberListQuery {
+    limit: i32,
+    cursor: Option<SortEntry>,
+    sort_by: SubscriberSort,
+    descending: bool,
+    email_prefix: Option<String>,
+    suspected_bot: Option<bool>,
+    time_zone: Option<String>,
+    interest_topic: Option<String>,
+    min_interest_score: f64,
+    engaged_since_issue: Option<i64>,
+    field_filters: Vec<FieldFilter>,
+    tags: Vec<String>,
+}
+
+/// Sort-key prefix of the departure records written when a subscriber is removed.
+const DEPARTURE_SK_PREFIX: &str = "subscriber-departure#";
+/// Departure reason for an admin removal.
+const MANUAL_REMOVAL_REASON: &str = "manual-removal";
+
+const MAX_NAME_LEN: usize = 100;
+
+const DEFAULT_LIST_LIMIT: i32 = 50;
+const MAX_LIST_LIMIT: i32 = 200;
+/// DynamoDB page size while filtering in memory.
+const FILTERED_READ_PAGE_SIZE: i32 = 500;
+/// Rows a keyed page reads before returning what it has, so a selective
+/// filter can't walk a whole large tenant in one request.
+const MAX_ROWS_READ_PER_PAGE: usize = 5_000;
+/// Subscribers-table index on `tenantId` + `addedAt`, which serves the
+/// `addedAt` list order.
+const ADDED_AT_INDEX: &str = "AddedAtIndex";
+/// What a sorted listing reads for every subscriber to filter and order the
+/// list. The page itself is then fetched in full.
+const SORTED_LIST_PROJECTION: &str = "email, addedAt, lastEngagedIssue, engagementCount, \
+     timeZone, interestScores, honeypotTriggered, disposableDomain, suspiciousUserAgent, \
+     fastSubmission, suspiciousEmailPattern, customFields, #tags";
+
 fn default_issue_count() -> i32 {
     10
 }
@@ -100,7 +255,9 @@ fn default_issue_count() -> i32 {
 #[serde(rename_all = "camelCase")]
 struct SubscriberListResponse {
     subscribers: Vec<SubscriberListItem>,
-    total: i64,
+    /// Present when more subscribers may match; pass it back as `nextToken`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_token: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -129,6 +286,11 @@ struct SubscriberListItem {
     suspected_bot: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     bot_flags: Option<BotFlags>,
+    /// Tenant-defined fields (see `subscriber_fields`), defaults applied.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    custom_fields: BTreeMap<String, Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -184,6 +346,40 @@ struct SubscriberDetailResponse {
     recent_activity: Vec<ActivityEntry>,
     /// Total opens counted into the open-hour histogram. Zero when unseen.
     open_hour_total: i64,
+    /// Tenant-defined fields (see `subscriber_fields`), defaults applied.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    custom_fields: BTreeMap<String, Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+// ── Request types ──────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateSubscriberRequest {
+    email: String,
+    first_name: Option<String>,
+    last_name: Option<String>,
+    custom_fields: Option<Map<String, Value>>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct UpdateSubscriberRequest {
+    first_name: Option<String>,
+    last_name: Option<String>,
+    /// Merged into the stored values; `null` clears a field.
+    custom_fields: Option<Map<String, Value>>,
+}
+
+/// UpdateItem expression for a subscriber update, with its `:placeholder`
+/// values. `:updatedAt` is always referenced and bound by the caller, as is
+/// `:customFields` when the expression sets it.
+#[derive(Debug, PartialEq)]
+struct SubscriberUpdate {
+    expression: String,
+    values: Vec<(String, String)>,
 }
 
 // ── Public endpoint handlers ───────────────────────────────────────────
@@ -209,7 +405,7 @@ pub async fn list_subscribers(event: Request) -> Result<Response<Body>, Error> {
     }
 }
 
-/// GET /subscribers (no type param) — returns full subscriber list
+/// GET /subscribers (no type param) — one page of the subscriber list
 pub async fn get_subscriber_list(event: Request) -> Result<Response<Body>, Error> {
     match handle_get_subscriber_list(event).await {
         Ok(resp) => Ok(resp),
@@ -241,6 +437,14 @@ pub async fn get_subscriber_trends(event: Request) -> Result<Response<Body>, Err
     }
 }
 
+/// GET /subscribers/cohorts
+pub async fn get_subscriber_cohorts(event: Request) -> Result<Response<Body>, Error> {
+    match handle_get_subscriber_cohorts(event).await {
+        Ok(resp) => Ok(resp),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
 // ── Internal handlers ──────────────────────────────────────────────────
 
 async fn handle_get_audience_health(event: Request) -> Result<Response<Body>, AppError> {
@@ -307,6 +511,48 @@ async fn handle_get_subscriber_trends(event: Request) -> Result<Response<Body>,
     response::format_response(200, SubscriberTrendsResponse { points, summary })
 }
 
+async fn handle_get_subscriber_cohorts(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
+
+    let query = parse_subscriber_cohorts_query_params(&event)?;
+    let subscribers_table = get_subscribers_table_name()?;
+    let newsletter_table = get_newsletter_table_name()?;
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+
+    let (members, excluded_subscribers) = query_cohort_members(
+        ddb_client,
+        &subscribers_table,
+        &newsletter_table,
+        &tenant_id,
+    )
+    .await?;
+    let mut issues =
+        query_published_issues(ddb_client, &newsletter_table, &tenant_id, query.issue_count)
+            .await?;
+    issues.sort_by_key(|issue| issue.issue_number);
+
+    let cohorts = build_signup_cohorts(&members, &issues, query.months);
+    let issues = issues
+        .iter()
+        .map(|issue| CohortIssue {
+            issue_number: issue.issue_number,
+            published_at: issue.published_at.to_rfc3339(),
+        })
+        .collect();
+
+    response::format_response(
+        200,
+        SubscriberCohortsResponse {
+            cohorts,
+            issues,
+            excluded_subscribers,
+        },
+    )
+}
+
 async fn handle_get_sunset_candidates(event: Request) -> Result<Response<Body>, AppError> {
     let user_context = auth::get_user_context(&event)?;
     let tenant_id = user_context
@@ -357,13 +603,31 @@ async fn handle_get_subscriber_list(event: Request) -> Result<Response<Body>, Ap
         .tenant_id
         .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
 
+    let fields = subscriber_fields::load_fields(&tenant_id).await?;
+    let query_params = event.query_string_parameters();
+    let query = parse_subscriber_list_query(query_params.iter(), &fields)?;
+
     let subscribers_table = get_subscribers_table_name()?;
     let ddb_client = aws_clients::get_dynamodb_client().await;
 
-    let subscribers = query_all_subscribers(ddb_client, &subscribers_table, &tenant_id).await?;
-    let total = subscribers.len() as i64;
+    let page = match query.sort_by {
+        SubscriberSort::Email | SubscriberSort::AddedAt => {
+            query_subscriber_page(ddb_client, &subscribers_table, &tenant_id, &query, &fields)
+                .await?
+        }
+        SubscriberSort::LastEngagedIssue | SubscriberSort::EngagementCount => {
+            query_sorted_subscriber_page(
+                ddb_client,
+                &subscribers_table,
+                &tenant_id,
+                &query,
+                &fields,
+            )
+            .await?
+        }
+    };
 
-    response::format_response(200, SubscriberListResponse { subscribers, total })
+    response::format_response(200, page)
 }
 
 /// GET /subscribers/:email — full subscriber detail
@@ -396,6 +660,8 @@ async fn handle_get_subscriber(
     let subscribers_table = get_subscribers_table_name()?;
     let ddb_client = aws_clients::get_dynamodb_client().await;
 
+    let fields = subscriber_fields::load_fields(&tenant_id).await?;
+
     let result = ddb_client
         .get_item()
         .table_name(&subscribers_table)
@@ -409,17 +675,21 @@ async fn handle_get_subscriber(
         // end user still only ever sees the obscured "Something went wrong" that
         // format_error_response returns for AwsError.
         .map_err(|e| {
-            AppError::AwsError(format!("DynamoDB GetItem failed: {}", DisplayErrorContext(&e)))
+            AppError::AwsError(format!(
+                "DynamoDB GetItem failed: {}",
+                DisplayErrorContext(&e)
+            ))
         })?;
 
     let item = result
         .item()
         .ok_or_else(|| AppError::NotFound("Subscriber not found".to_string()))?;
 
-    response::format_response(200, parse_subscriber_detail(item))
+    response::format_response(200, parse_subscriber_detail(item, &fields))
 }
 
-/// DELETE /subscribers/:email — remove a subscriber
+/// DELETE /subscribers/:email — remove a subscriber. With `?suppress=true`
+/// the address is also put on the suppression list so it can't be re-added.
 pub async fn delete_subscriber(
     event: Request,
     email: Option<String>,
@@ -445,6 +715,15 @@ async fn handle_delete_subscriber(
         .decode_utf8()
         .map_err(|e| AppError::BadRequest(format!("Invalid email encoding: {}", e)))?
         .to_lowercase();
+    let suppress = match event.query_string_parameters().first("suppress") {
+        None | Some("false") => false,
+        Some("true") => true,
+        Some(_) => {
+            return Err(AppError::BadRequest(
+                "suppress must be true or false".to_string(),
+            ))
+        }
+    };
 
     let subscribers_table = get_subscribers_table_name()?;
     let newsletter_table = get_newsletter_table_name()?;
@@ -465,6 +744,24 @@ async fn handle_delete_subscriber(
         return Err(AppError::NotFound("Subscriber not found".to_string()));
     }
 
+    if let Some(old_item) = delete_result.attributes() {
+        if let Err(e) = record_subscriber_departure(
+            ddb_client,
+            &newsletter_table,
+            &tenant_id,
+            &decoded_email,
+            old_item,
+        )
+        .await
+        {
+            tracing::warn!(
+                error = ?e,
+                tenant_id = %tenant_id,
+                "Failed to record subscriber departure"
+            );
+        }
+    }
+
     // Decrement subscriber count
     ddb_client
         .update_item()
@@ -479,6 +776,19 @@ async fn handle_delete_subscriber(
         .await
         .map_err(|e| AppError::AwsError(format!("Failed to decrement subscriber count: {}", e)))?;
 
+    if suppress {
+        let entry = SuppressionEntry {
+            entry: decoded_email.clone(),
+            kind: SuppressionKind::Email,
+            reason: SuppressionReason::Manual,
+            source: "subscriber-delete".to_string(),
+            note: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            created_by: Some(user_context.email.clone()),
+        };
+        suppression::add_entry(ddb_client, &newsletter_table, &tenant_id, &entry).await?;
+    }
+
     // Increment manualRemovals counter on the most recently published issue (fire-and-forget)
     match get_most_recent_published_issue(ddb_client, &newsletter_table, &tenant_id).await {
         Ok(Some(issue_pk)) => {
@@ -512,6 +822,266 @@ async fn handle_delete_subscriber(
     response::format_response(200, serde_json::json!({ "message": "Subscriber removed" }))
 }
 
+/// POST /subscribers — add a single subscriber from the dashboard
+pub async fn create_subscriber(event: Request) -> Result<Response<Body>, Error> {
+    match handle_create_subscriber(event).await {
+        Ok(resp) => Ok(resp),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_create_subscriber(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
+
+    let body: CreateSubscriberRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+    let email = subscriber_key(&body.email)?;
+    let first_name = clean_name(body.first_name, "firstName")?;
+    let last_name = clean_name(body.last_name, "lastName")?;
+    let fields = subscriber_fields::load_fields(&tenant_id).await?;
+    let custom_fields = subscriber_fields::resolve_custom_fields(
+        &fields,
+        &BTreeMap::new(),
+        &body.custom_fields.unwrap_or_default(),
+    )?;
+
+    let subscribers_table = get_subscribers_table_name()?;
+    let newsletter_table = get_newsletter_table_name()?;
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+
+    if let Some(entry) =
+        suppression::find_entry(ddb_client, &newsletter_table, &tenant_id, &email).await?
+    {
+        return Err(AppError::Conflict(format!(
+            "{} is on the suppression list and can't be added",
+            entry.entry
+        )));
+    }
+
+    let now = chrono::Utc::now();
+    let added_at = now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let mut item = HashMap::from([
+        ("tenantId".to_string(), AttributeValue::S(tenant_id.clone())),
+        ("email".to_string(), AttributeValue::S(email.clone())),
+        ("addedAt".to_string(), AttributeValue::S(added_at.clone())),
+        (
+            "source".to_string(),
+            AttributeValue::S("manual".to_string()),
+        ),
+    ]);
+    if let Some(first_name) = &first_name {
+        item.insert(
+            "firstName".to_string(),
+            AttributeValue::S(first_name.clone()),
+        );
+    }
+    if let Some(last_name) = &last_name {
+        item.insert("lastName".to_string(), AttributeValue::S(last_name.clone()));
+    }
+    if !custom_fields.is_empty() {
+        item.insert(
+            subscriber_fields::CUSTOM_FIELDS_ATTRIBUTE.to_string(),
+            subscriber_fields::custom_fields_attribute(&custom_fields),
+        );
+    }
+
+    ddb_client
+        .put_item()
+        .table_name(&subscribers_table)
+        .set_item(Some(item.clone()))
+        .condition_expression("attribute_not_exists(email)")
+        .send()
+        .await
+        .map_err(|e| {
+            if DisplayErrorContext(&e)
+                .to_string()
+                .contains("ConditionalCheckFailed")
+            {
+                AppError::Conflict("Subscriber already exists".to_string())
+            } else {
+                AppError::AwsError(format!(
+                    "DynamoDB PutItem failed: {}",
+                    DisplayErrorContext(&e)
+                ))
+            }
+        })?;
+
+    ddb_client
+        .update_item()
+        .table_name(&newsletter_table)
+        .key("pk", AttributeValue::S(tenant_id.clone()))
+        .key("sk", AttributeValue::S("tenant".to_string()))
+        .update_expression("SET subscribers = if_not_exists(subscribers, :zero) + :inc")
+        .expression_attribute_values(":inc", AttributeValue::N("1".to_string()))
+        .expression_attribute_values(":zero", AttributeValue::N("0".to_string()))
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("Failed to increment subscriber count: {}", e)))?;
+
+    // The signup event record feeds subscriber trends; attribution and the
+    // SUBSCRIBER_ADDED event match add-subscriber.mjs. All best-effort.
+    if let Err(e) = record_subscriber_added(
+        ddb_client,
+        &newsletter_table,
+        &tenant_id,
+        &email,
+        &added_at,
+        now.timestamp_millis(),
+    )
+    .await
+    {
+        tracing::warn!(error = ?e, tenant_id = %tenant_id, "Failed to write subscriber event record");
+    }
+
+    match get_most_recent_published_issue(ddb_client, &newsletter_table, &tenant_id).await {
+        Ok(Some(issue_pk)) => {
+            if let Err(e) =
+                increment_issue_counter(ddb_client, &newsletter_table, &issue_pk, "subscribes")
+                    .await
+            {
+                tracing::warn!(
+                    error = ?e,
+                    tenant_id = %tenant_id,
+                    issue_pk = %issue_pk,
+                    "Failed to increment subscribes counter"
+                );
+            }
+        }
+        Ok(None) => {}
+        Err(e) => {
+            tracing::warn!(
+                error = ?e,
+                tenant_id = %tenant_id,
+                "Failed to look up most recent published issue for subscribes attribution"
+            );
+        }
+    }
+
+    if let Err(e) = publish_subscriber_added(
+        &tenant_id,
+        &user_context.user_id,
+        &email,
+        first_name.as_deref(),
+        last_name.as_deref(),
+        &added_at,
+    )
+    .await
+    {
+        tracing::warn!(error = ?e, tenant_id = %tenant_id, "Failed to publish Subscriber Added event");
+    }
+
+    response::format_response(201, parse_subscriber_detail(&item, &fields))
+}
+
+/// PUT /subscribers/:email — update a subscriber's name and custom fields. An
+/// empty string (or `null` custom field) clears the value; omitted fields are
+/// left alone.
+pub async fn update_subscriber(
+    event: Request,
+    email: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_update_subscriber(event, email).await {
+        Ok(resp) => Ok(resp),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_update_subscriber(
+    event: Request,
+    email: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))?;
+
+    let email = email.ok_or_else(|| AppError::BadRequest("Email is required".to_string()))?;
+    let decoded_email = percent_decode_str(&email)
+        .decode_utf8()
+        .map_err(|e| AppError::BadRequest(format!("Invalid email encoding: {}", e)))?;
+    let email = subscriber_key(&decoded_email)?;
+
+    let body: UpdateSubscriberRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+
+    let subscribers_table = get_subscribers_table_name()?;
+    let ddb_client = aws_clients::get_dynamodb_client().await;
+    let fields = subscriber_fields::load_fields(&tenant_id).await?;
+
+    // Custom fields are validated against the whole resulting set (required
+    // fields can't be cleared), so start from what is stored.
+    let custom_fields = match &body.custom_fields {
+        Some(input) => {
+            let current = ddb_client
+                .get_item()
+                .table_name(&subscribers_table)
+                .key("tenantId", AttributeValue::S(tenant_id.clone()))
+                .key("email", AttributeValue::S(email.clone()))
+                .send()
+                .await
+                .map_err(|e| {
+                    AppError::AwsError(format!(
+                        "DynamoDB GetItem failed: {}",
+                        DisplayErrorContext(&e)
+                    ))
+                })?
+                .item
+                .ok_or_else(|| AppError::NotFound("Subscriber not found".to_string()))?;
+            Some(subscriber_fields::resolve_custom_fields(
+                &fields,
+                &subscriber_fields::stored_custom_fields(&current),
+                input,
+            )?)
+        }
+        None => None,
+    };
+    let update = build_subscriber_update(&body, custom_fields.as_ref())?;
+
+    let mut request = ddb_client
+        .update_item()
+        .table_name(&subscribers_table)
+        .key("tenantId", AttributeValue::S(tenant_id))
+        .key("email", AttributeValue::S(email))
+        .update_expression(&update.expression)
+        .condition_expression("attribute_exists(email)")
+        .expression_attribute_values(
+            ":updatedAt",
+            AttributeValue::S(
+                chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            ),
+        )
+        .return_values(aws_sdk_dynamodb::types::ReturnValue::AllNew);
+    for (placeholder, value) in update.values {
+        request = request.expression_attribute_values(placeholder, AttributeValue::S(value));
+    }
+    if let Some(custom_fields) = custom_fields.as_ref().filter(|values| !values.is_empty()) {
+        request = request.expression_attribute_values(
+            ":customFields",
+            subscriber_fields::custom_fields_attribute(custom_fields),
+        );
+    }
+
+    let result = request.send().await.map_err(|e| {
+        if DisplayErrorContext(&e)
+            .to_string()
+            .contains("ConditionalCheckFailed")
+        {
+            AppError::NotFound("Subscriber not found".to_string())
+        } else {
+            AppError::AwsError(format!(
+                "DynamoDB UpdateItem failed: {}",
+                DisplayErrorContext(&e)
+            ))
+        }
+    })?;
+
+    let item = result.attributes().cloned().unwrap_or_default();
+    response::format_response(200, parse_subscriber_detail(&item, &fields))
+}
+
 // ── Helper functions ───────────────────────────────────────────────────
 
 /// Build the GSI1PK value used for issue attribution lookups.
@@ -601,29 +1171,231 @@ async fn increment_issue_counter(
     Ok(())
 }
 
-fn get_subscribers_table_name() -> Result<String, AppError> {
-    env::var("SUBSCRIBERS_TABLE_NAME")
-        .map_err(|_| AppError::InternalError("SUBSCRIBERS_TABLE_NAME not set".to_string()))
-}
+/// Write the departure record cohort retention reads once a subscriber row is
+/// gone. Mirrors `recordSubscriberDeparture` in utils/subscriber.mjs.
+async fn record_subscriber_departure(
+    ddb_client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    tenant_id: &str,
+    email: &str,
+    old_item: &HashMap<String, AttributeValue>,
+) -> Result<(), AppError> {
+    let departed_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let email_hash = hash_email(email);
 
-fn get_newsletter_table_name() -> Result<String, AppError> {
-    env::var("TABLE_NAME").map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))
+    let mut put = ddb_client
+        .put_item()
+        .table_name(table_name)
+        .item("pk", AttributeValue::S(tenant_id.to_string()))
+        .item(
+            "sk",
+            AttributeValue::S(format!(
+                "{}{}#{}",
+                DEPARTURE_SK_PREFIX, departed_at, email_hash
+            )),
+        )
+        .item("emailHash", AttributeValue::S(email_hash))
+        .item(
+            "reason",
+            AttributeValue::S(MANUAL_REMOVAL_REASON.to_string()),
+        )
+        .item("departedAt", AttributeValue::S(departed_at));
+
+    for key in ["addedAt", "lastEngagedIssue", "engagementCount"] {
+        if let Some(value) = old_item.get(key) {
+            put = put.item(key, value.clone());
+        }
+    }
+
+    put.send().await?;
+    Ok(())
 }
 
-fn parse_subscriber_trends_query_params(
-    event: &Request,
-) -> Result<SubscriberTrendsQuery, AppError> {
-    let query_params = event.query_string_parameters();
-    let issue_count = query_params
-        .first("issueCount")
-        .map(|s| {
-            s.parse::<i32>()
-                .map_err(|_| AppError::BadRequest("issueCount must be a valid integer".to_string()))
-        })
-        .transpose()?
-        .unwrap_or_else(default_issue_count);
+/// Write the `subscriber#` event record subscriber trends count signups from.
+/// Mirrors `createSubscriberEventRecord` in add-subscriber.mjs.
+async fn record_subscriber_added(
+    ddb_client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    tenant_id: &str,
+    email: &str,
+    added_at: &str,
+    timestamp_millis: i64,
+) -> Result<(), AppError> {
+    let ttl = chrono::Utc::now().timestamp() + 90 * 24 * 60 * 60;
 
-    if !(1..=50).contains(&issue_count) {
+    ddb_client
+        .put_item()
+        .table_name(table_name)
+        .item("pk", AttributeValue::S(tenant_id.to_string()))
+        .item(
+            "sk",
+            AttributeValue::S(format!("subscriber#{}#{}", timestamp_millis, email)),
+        )
+        .item("GSI1PK", AttributeValue::S(tenant_id.to_string()))
+        .item(
+            "GSI1SK",
+            AttributeValue::S(format!("subscriber#{}", timestamp_millis)),
+        )
+        .item("email", AttributeValue::S(email.to_string()))
+        .item("addedAt", AttributeValue::S(added_at.to_string()))
+        .item("ttl", AttributeValue::N(ttl.to_string()))
+        .send()
+        .await?;
+
+    Ok(())
+}
+
+/// Publish `Subscriber Added` in the shape `publishSubscriberEvent` uses, so
+/// the welcome-email flow treats dashboard adds like form signups.
+async fn publish_subscriber_added(
+    tenant_id: &str,
+    user_id: &str,
+    email: &str,
+    first_name: Option<&str>,
+    last_name: Option<&str>,
+    added_at: &str,
+) -> Result<(), AppError> {
+    let detail = serde_json::json!({
+        "tenantId": tenant_id,
+        "userId": user_id,
+        "type": "SUBSCRIBER_ADDED",
+        "data": {
+            "email": email,
+            "firstName": first_name,
+            "lastName": last_name,
+            "addedAt": added_at
+        }
+    });
+
+    let output = aws_clients::get_eventbridge_client()
+        .await
+        .put_events()
+        .entries(
+            aws_sdk_eventbridge::types::PutEventsRequestEntry::builder()
+                .source("newsletter-service")
+                .detail_type("Subscriber Added")
+                .detail(detail.to_string())
+                .build(),
+        )
+        .send()
+        .await
+        .map_err(|e| AppError::AwsError(format!("EventBridge PutEvents failed: {}", e)))?;
+
+    if output.failed_entry_count() > 0 {
+        return Err(AppError::AwsError(
+            "EventBridge rejected the Subscriber Added event".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Normalise an address into the Subscribers table sort key. Keys are
+/// lowercased and may not contain `#`, so a subscriber row can never collide
+/// with the segments feature's `SEGMENT#...` / `...#MEMBER#...` overloads
+/// and always passes `is_subscriber_record`.
+pub(crate) fn subscriber_key(raw: &str) -> Result<String, AppError> {
+    let email = raw.trim().to_lowercase();
+    if email.is_empty() {
+        return Err(AppError::BadRequest("Email is required".to_string()));
+    }
+    validation::validate_email(&email)
+        .map_err(|_| AppError::BadRequest("Invalid email address format".to_string()))?;
+    if email.contains('#') {
+        return Err(AppError::BadRequest(
+            "Email addresses containing '#' are not supported".to_string(),
+        ));
+    }
+    Ok(email)
+}
+
+/// Trimmed name, `None` when blank.
+fn clean_name(value: Option<String>, field: &str) -> Result<Option<String>, AppError> {
+    let Some(value) = value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    else {
+        return Ok(None);
+    };
+    if value.chars().count() > MAX_NAME_LEN {
+        return Err(AppError::BadRequest(format!(
+            "{} must be at most {} characters",
+            field, MAX_NAME_LEN
+        )));
+    }
+    Ok(Some(value))
+}
+
+/// `custom_fields` is the subscriber's full resolved set when the request
+/// touched custom fields; an empty set removes the attribute.
+fn build_subscriber_update(
+    body: &UpdateSubscriberRequest,
+    custom_fields: Option<&BTreeMap<String, Value>>,
+) -> Result<SubscriberUpdate, AppError> {
+    if body.first_name.is_none() && body.last_name.is_none() && custom_fields.is_none() {
+        return Err(AppError::BadRequest(
+            "Provide firstName, lastName and/or customFields to update".to_string(),
+        ));
+    }
+
+    let mut sets = vec!["updatedAt = :updatedAt".to_string()];
+    let mut removes = Vec::new();
+    let mut values = Vec::new();
+    for (attribute, field, value) in [
+        ("firstName", "firstName", &body.first_name),
+        ("lastName", "lastName", &body.last_name),
+    ] {
+        if value.is_none() {
+            continue;
+        }
+        match clean_name(value.clone(), field)? {
+            Some(name) => {
+                sets.push(format!("{} = :{}", attribute, attribute));
+                values.push((format!(":{}", attribute), name));
+            }
+            None => removes.push(attribute.to_string()),
+        }
+    }
+    match custom_fields {
+        Some(values) if values.is_empty() => {
+            removes.push(subscriber_fields::CUSTOM_FIELDS_ATTRIBUTE.to_string())
+        }
+        Some(_) => sets.push(format!(
+            "{} = :customFields",
+            subscriber_fields::CUSTOM_FIELDS_ATTRIBUTE
+        )),
+        None => {}
+    }
+
+    let mut expression = format!("SET {}", sets.join(", "));
+    if !removes.is_empty() {
+        expression.push_str(&format!(" REMOVE {}", removes.join(", ")));
+    }
+    Ok(SubscriberUpdate { expression, values })
+}
+
+fn get_subscribers_table_name() -> Result<String, AppError> {
+    env::var("SUBSCRIBERS_TABLE_NAME")
+        .map_err(|_| AppError::InternalError("SUBSCRIBERS_TABLE_NAME not set".to_string()))
+}
+
+fn get_newsletter_table_name() -> Result<String, AppError> {
+    env::var("TABLE_NAME").map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))
+}
+
+fn parse_subscriber_trends_query_params(
+    event: &Request,
+) -> Result<SubscriberTrendsQuery, AppError> {
+    let query_params = event.query_string_parameters();
+    let issue_count = query_params
+        .first("issueCount")
+        .map(|s| {
+            s.parse::<i32>()
+                .map_err(|_| AppError::BadRequest("issueCount must be a valid integer".to_string()))
+        })
+        .transpose()?
+        .unwrap_or_else(default_issue_count);
+
+    if !(1..=50).contains(&issue_count) {
         return Err(AppError::BadRequest(
             "issueCount must be between 1 and 50".to_string(),
         ));
@@ -632,7 +1404,195 @@ fn parse_subscriber_trends_query_params(
     Ok(SubscriberTrendsQuery { issue_count })
 }
 
-fn hash_email(email: &str) -> String {
+fn parse_subscriber_cohorts_query_params(
+    event: &Request,
+) -> Result<SubscriberCohortsQuery, AppError> {
+    let query_params = event.query_string_parameters();
+    let issue_count = query_params
+        .first("issueCount")
+        .map(|s| {
+            s.parse::<i32>()
+                .map_err(|_| AppError::BadRequest("issueCount must be a valid integer".to_string()))
+        })
+        .transpose()?
+        .unwrap_or(12);
+    let months = query_params
+        .first("months")
+        .map(|s| {
+            s.parse::<usize>()
+                .map_err(|_| AppError::BadRequest("months must be a valid integer".to_string()))
+        })
+        .transpose()?
+        .unwrap_or(12);
+
+    if !(1..=50).contains(&issue_count) {
+        return Err(AppError::BadRequest(
+            "issueCount must be between 1 and 50".to_string(),
+        ));
+    }
+    if !(1..=24).contains(&months) {
+        return Err(AppError::BadRequest(
+            "months must be between 1 and 24".to_string(),
+        ));
+    }
+
+    Ok(SubscriberCohortsQuery {
+        issue_count,
+        months,
+    })
+}
+
+/// Parses the `GET /subscribers` query string. `sortOrder` defaults to
+/// ascending for `email` and descending (newest, most engaged first) for the
+/// other sorts.
+fn parse_subscriber_list_query<'a>(
+    params: impl IntoIterator<Item = (&'a str, &'a str)>,
+    fields: &[SubscriberField],
+) -> Result<SubscriberListQuery, AppError> {
+    let params: Vec<(&str, &str)> = params.into_iter().collect();
+    let first = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+
+    let limit = first("limit")
+        .map(|s| {
+            s.parse::<i32>()
+                .map_err(|_| AppError::BadRequest("limit must be a valid integer".to_string()))
+        })
+        .transpose()?
+        .unwrap_or(DEFAULT_LIST_LIMIT);
+    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+        return Err(AppError::BadRequest(format!(
+            "limit must be between 1 and {}",
+            MAX_LIST_LIMIT
+        )));
+    }
+
+    let sort_by = match first("sortBy") {
+        Some(value) => SubscriberSort::parse(value).ok_or_else(|| {
+            AppError::BadRequest(
+                "sortBy must be one of email, addedAt, lastEngagedIssue, engagementCount"
+                    .to_string(),
+            )
+        })?,
+        None => SubscriberSort::Email,
+    };
+    let descending = match first("sortOrder") {
+        Some("asc") => false,
+        Some("desc") => true,
+        Some(_) => {
+            return Err(AppError::BadRequest(
+                "sortOrder must be asc or desc".to_string(),
+            ))
+        }
+        None => sort_by != SubscriberSort::Email,
+    };
+
+    let suspected_bot = match first("suspectedBot") {
+        Some("true") => Some(true),
+        Some("false") => Some(false),
+        Some(_) => {
+            return Err(AppError::BadRequest(
+                "suspectedBot must be true or false".to_string(),
+            ))
+        }
+        None => None,
+    };
+
+    let interest_topic = first("interestTopic").map(|s| s.to_string());
+    let min_interest_score = first("minInterestScore")
+        .map(|s| {
+            s.parse::<f64>()
+                .ok()
+                .filter(|score| score.is_finite())
+                .ok_or_else(|| {
+                    AppError::BadRequest("minInterestScore must be a number".to_string())
+                })
+        })
+        .transpose()?;
+    if min_interest_score.is_some() && interest_topic.is_none() {
+        return Err(AppError::BadRequest(
+            "minInterestScore requires interestTopic".to_string(),
+        ));
+    }
+
+    let engaged_since_issue = first("engagedSinceIssue")
+        .map(|s| {
+            s.parse::<i64>().ok().filter(|n| *n >= 0).ok_or_else(|| {
+                AppError::BadRequest("engagedSinceIssue must be a non-negative integer".to_string())
+            })
+        })
+        .transpose()?;
+
+    let cursor = first("nextToken")
+        .map(|token| decode_list_cursor(token, sort_by))
+        .transpose()?;
+
+    Ok(SubscriberListQuery {
+        limit,
+        cursor,
+        sort_by,
+        descending,
+        email_prefix: first("emailPrefix").map(|s| s.to_lowercase()),
+        suspected_bot,
+        time_zone: first("timeZone").map(|s| s.to_string()),
+        interest_topic,
+        min_interest_score: min_interest_score.unwrap_or(0.0),
+        engaged_since_issue,
+        field_filters: subscriber_fields::parse_field_filters(params.iter().copied(), fields)?,
+        tags: subscriber_tags::parse_tag_filters(params.iter().copied())?,
+    })
+}
+
+/// `nextToken` for the page after `last`, in the issues list's token format.
+fn encode_list_cursor(sort_by: SubscriberSort, last: &SortEntry) -> String {
+    let mut key = HashMap::from([
+        (
+            "sortBy".to_string(),
+            AttributeValue::S(sort_by.as_str().to_string()),
+        ),
+        ("email".to_string(), AttributeValue::S(last.email.clone())),
+    ]);
+    match &last.value {
+        Some(SortValue::Text(value)) => {
+            key.insert("value".to_string(), AttributeValue::S(value.clone()));
+        }
+        Some(SortValue::Number(value)) => {
+            key.insert("value".to_string(), AttributeValue::N(value.to_string()));
+        }
+        None => {}
+    }
+    encode_pagination_token(&key)
+}
+
+fn decode_list_cursor(token: &str, sort_by: SubscriberSort) -> Result<SortEntry, AppError> {
+    let invalid = || AppError::BadRequest("Invalid pagination token".to_string());
+    let key = decode_pagination_token(token)?;
+    let text = |name: &str| key.get(name).and_then(|v| v.as_s().ok()).cloned();
+
+    if text("sortBy").as_deref() != Some(sort_by.as_str()) {
+        return Err(AppError::BadRequest(
+            "nextToken belongs to a different sortBy".to_string(),
+        ));
+    }
+    let email = text("email").ok_or_else(invalid)?;
+    let value = match (sort_by, text("value")) {
+        // The addedAt index needs the value to resume from.
+        (SubscriberSort::AddedAt, None) => return Err(invalid()),
+        (_, None) => None,
+        (SubscriberSort::AddedAt, Some(value)) => Some(SortValue::Text(value)),
+        (_, Some(value)) => Some(SortValue::Number(
+            value.parse::<i64>().map_err(|_| invalid())?,
+        )),
+    };
+    Ok(SortEntry { value, email })
+}
+
+pub(crate) fn hash_email(email: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(email.as_bytes());
     hex::encode(hasher.finalize())
@@ -643,7 +1603,7 @@ fn hash_email(email: &str) -> String {
 /// `SEGMENT#...`, `SEGMENT_NAME#...`, `SEGMENT_JOB#...`, and `...#MEMBER#...`
 /// records stored under the same tenant partition; those must never be counted
 /// or listed as subscribers. Real email addresses never start with "SEGMENT".
-fn is_subscriber_record(item: &HashMap<String, AttributeValue>) -> bool {
+pub(crate) fn is_subscriber_record(item: &HashMap<String, AttributeValue>) -> bool {
     item.get("email")
         .and_then(|v| v.as_s().ok())
         .map(|email| !email.starts_with("SEGMENT"))
@@ -715,7 +1675,10 @@ fn parse_recent_activity(item: &HashMap<String, AttributeValue>) -> Vec<Activity
 }
 
 /// Build the full subscriber detail response from a subscriber record.
-fn parse_subscriber_detail(item: &HashMap<String, AttributeValue>) -> SubscriberDetailResponse {
+fn parse_subscriber_detail(
+    item: &HashMap<String, AttributeValue>,
+    fields: &[SubscriberField],
+) -> SubscriberDetailResponse {
     let email = item
         .get("email")
         .and_then(|v| v.as_s().ok())
@@ -773,10 +1736,12 @@ fn parse_subscriber_detail(item: &HashMap<String, AttributeValue>) -> Subscriber
         time_zone,
         recent_activity,
         open_hour_total,
+        custom_fields: subscriber_fields::effective_custom_fields(fields, item),
+        tags: subscriber_tags::stored_tags(item).into_iter().collect(),
     }
 }
 
-async fn query_current_subscriber_count(
+pub(crate) async fn query_current_subscriber_count(
     ddb_client: &aws_sdk_dynamodb::Client,
     table_name: &str,
     tenant_id: &str,
@@ -870,129 +1835,406 @@ fn calculate_trend_summary(points: &[SubscriberTrendPoint]) -> SubscriberTrendSu
     }
 }
 
-/// Query all subscribers for a tenant and return their list details.
-async fn query_all_subscribers(
+/// One page of the list in email order, read straight off the table key, or
+/// in `addedAt` order, read off `ADDED_AT_INDEX`. Filters are applied to what
+/// is read, so a page reads ahead until it is full, and stops early (with a
+/// `nextToken`) after `MAX_ROWS_READ_PER_PAGE` rows.
+async fn query_subscriber_page(
     ddb_client: &aws_sdk_dynamodb::Client,
     table_name: &str,
     tenant_id: &str,
-) -> Result<Vec<SubscriberListItem>, AppError> {
+    query: &SubscriberListQuery,
+    fields: &[SubscriberField],
+) -> Result<SubscriberListResponse, AppError> {
+    let limit = query.limit as usize;
+    let page_size = if has_item_filters(query) {
+        FILTERED_READ_PAGE_SIZE
+    } else {
+        query.limit
+    };
     let mut subscribers = Vec::new();
-    let mut exclusive_start_key = None;
+    let mut rows_read = 0;
+    let mut exclusive_start_key = query.cursor.as_ref().map(|cursor| {
+        let mut key = HashMap::from([
+            (
+                "tenantId".to_string(),
+                AttributeValue::S(tenant_id.to_string()),
+            ),
+            ("email".to_string(), AttributeValue::S(cursor.email.clone())),
+        ]);
+        if let Some(SortValue::Text(added_at)) = &cursor.value {
+            key.insert("addedAt".to_string(), AttributeValue::S(added_at.clone()));
+        }
+        key
+    });
 
     loop {
-        let mut query = ddb_client
-            .query()
-            .table_name(table_name)
-            .key_condition_expression("tenantId = :tid")
-            .expression_attribute_values(":tid", AttributeValue::S(tenant_id.to_string()));
+        let result = subscriber_list_query(ddb_client, table_name, tenant_id, query)
+            .scan_index_forward(!query.descending)
+            .limit(page_size)
+            .set_exclusive_start_key(exclusive_start_key.take())
+            .send()
+            .await?;
+        let items = result.items();
+        let last_key = result.last_evaluated_key().filter(|key| !key.is_empty());
 
-        if let Some(start_key) = exclusive_start_key.take() {
-            query = query.set_exclusive_start_key(Some(start_key));
+        for (index, item) in items.iter().enumerate() {
+            rows_read += 1;
+            if !is_subscriber_record(item) || !matches_list_filters(item, query, fields) {
+                continue;
+            }
+            subscribers.push(parse_subscriber_list_item(item, fields));
+
+            if subscribers.len() == limit {
+                let more = index + 1 < items.len() || last_key.is_some();
+                let next_token = more
+                    .then(|| encode_list_cursor(query.sort_by, &sort_entry(item, query.sort_by)));
+                return Ok(SubscriberListResponse {
+                    subscribers,
+                    next_token,
+                });
+            }
         }
 
-        let result = query.send().await?;
+        match last_key {
+            Some(key) if rows_read >= MAX_ROWS_READ_PER_PAGE => {
+                return Ok(SubscriberListResponse {
+                    subscribers,
+                    next_token: Some(encode_list_cursor(
+                        query.sort_by,
+                        &sort_entry(key, query.sort_by),
+                    )),
+                });
+            }
+            Some(key) => exclusive_start_key = Some(key.clone()),
+            None => break,
+        }
+    }
+
+    Ok(SubscriberListResponse {
+        subscribers,
+        next_token: None,
+    })
+}
+
+/// One page of the list in `lastEngagedIssue` or `engagementCount` order.
+/// Neither has an index, so
+/// the tenant's matching subscribers are read with a narrow projection and
+/// sorted, and only the page is fetched in full. The token carries the last
+/// row's sort value and email, so pages don't shift as subscribers join.
+async fn query_sorted_subscriber_page(
+    ddb_client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    tenant_id: &str,
+    query: &SubscriberListQuery,
+    fields: &[SubscriberField],
+) -> Result<SubscriberListResponse, AppError> {
+    let mut entries = Vec::new();
+    let mut exclusive_start_key = None;
+
+    loop {
+        let result = subscriber_list_query(ddb_client, table_name, tenant_id, query)
+            .projection_expression(SORTED_LIST_PROJECTION)
+            .expression_attribute_names("#tags", subscriber_tags::TAGS_ATTRIBUTE)
+            .set_exclusive_start_key(exclusive_start_key.take())
+            .send()
+            .await?;
 
         for item in result.items() {
-            // The segments feature stores its records (SEGMENT#, SEGMENT_NAME#,
-            // SEGMENT_JOB#, and #MEMBER# rows) in this same table under the
-            // tenant partition. Skip them so they don't show up as bogus
-            // subscribers in the list.
-            if !is_subscriber_record(item) {
-                continue;
+            if is_subscriber_record(item) && matches_list_filters(item, query, fields) {
+                entries.push(sort_entry(item, query.sort_by));
             }
+        }
 
-            let email = item
-                .get("email")
-                .and_then(|v| v.as_s().ok())
-                .map(|s| s.to_string())
-                .unwrap_or_default();
+        match result.last_evaluated_key() {
+            Some(key) if !key.is_empty() => {
+                exclusive_start_key = Some(key.clone());
+            }
+            _ => break,
+        }
+    }
 
-            let added_at = item
-                .get("addedAt")
-                .and_then(|v| v.as_s().ok())
-                .map(|s| s.to_string());
+    let (page, more) = sorted_page(entries, query);
+    let next_token = if more {
+        page.last()
+            .map(|last| encode_list_cursor(query.sort_by, last))
+    } else {
+        None
+    };
 
-            let first_name = item
-                .get("firstName")
-                .and_then(|v| v.as_s().ok())
-                .map(|s| s.to_string());
+    let emails: Vec<String> = page.into_iter().map(|entry| entry.email).collect();
+    let mut items = batch_get_subscribers(ddb_client, table_name, tenant_id, &emails).await?;
+    let subscribers = emails
+        .iter()
+        .filter_map(|email| items.remove(email))
+        .map(|item| parse_subscriber_list_item(&item, fields))
+        .collect();
 
-            let last_name = item
-                .get("lastName")
-                .and_then(|v| v.as_s().ok())
-                .map(|s| s.to_string());
+    Ok(SubscriberListResponse {
+        subscribers,
+        next_token,
+    })
+}
 
-            let last_engaged_issue = item
-                .get("lastEngagedIssue")
-                .and_then(|v| v.as_n().ok())
-                .and_then(|n| n.parse::<i64>().ok());
+/// The tenant partition query behind every list order, narrowed to
+/// `emailPrefix` when one is given. `addedAt` order reads `ADDED_AT_INDEX`,
+/// where the prefix is left to `matches_list_filters`.
+fn subscriber_list_query(
+    ddb_client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    tenant_id: &str,
+    query: &SubscriberListQuery,
+) -> aws_sdk_dynamodb::operation::query::builders::QueryFluentBuilder {
+    let builder = ddb_client
+        .query()
+        .table_name(table_name)
+        .expression_attribute_values(":tid", AttributeValue::S(tenant_id.to_string()));
 
-            let engagement_count = item
-                .get("engagementCount")
-                .and_then(|v| v.as_n().ok())
-                .and_then(|n| n.parse::<i64>().ok());
+    if query.sort_by == SubscriberSort::AddedAt {
+        return builder
+            .index_name(ADDED_AT_INDEX)
+            .key_condition_expression("tenantId = :tid");
+    }
 
-            let interest_scores = parse_interest_scores(item).filter(|m| !m.is_empty());
+    match &query.email_prefix {
+        Some(prefix) => builder
+            .key_condition_expression("tenantId = :tid AND begins_with(email, :prefix)")
+            .expression_attribute_values(":prefix", AttributeValue::S(prefix.clone())),
+        None => builder.key_condition_expression("tenantId = :tid"),
+    }
+}
 
-            let time_zone = item
-                .get("timeZone")
-                .and_then(|v| v.as_s().ok())
-                .map(|s| s.to_string());
-
-            let get_bool_flag = |key: &str| -> bool {
-                item.get(key)
-                    .and_then(|v| v.as_bool().ok())
-                    .copied()
-                    .unwrap_or(false)
-            };
-
-            let honeypot_triggered = get_bool_flag("honeypotTriggered");
-            let disposable_domain = get_bool_flag("disposableDomain");
-            let suspicious_user_agent = get_bool_flag("suspiciousUserAgent");
-            let fast_submission = get_bool_flag("fastSubmission");
-            let suspicious_email_pattern = get_bool_flag("suspiciousEmailPattern");
-
-            let suspected_bot = honeypot_triggered
-                || disposable_domain
-                || suspicious_user_agent
-                || fast_submission
-                || suspicious_email_pattern;
-
-            let bot_flags = if suspected_bot {
-                Some(BotFlags {
-                    honeypot_triggered,
-                    disposable_domain,
-                    suspicious_user_agent,
-                    fast_submission,
-                    suspicious_email_pattern,
-                })
-            } else {
-                None
-            };
-
-            subscribers.push(SubscriberListItem {
-                email,
-                added_at,
-                first_name,
-                last_name,
-                last_engaged_issue,
-                engagement_count,
-                interest_scores,
-                time_zone,
-                suspected_bot,
-                bot_flags,
-            });
-        }
+/// Full subscriber records by email, in batches of 100.
+async fn batch_get_subscribers(
+    ddb_client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    tenant_id: &str,
+    emails: &[String],
+) -> Result<HashMap<String, HashMap<String, AttributeValue>>, AppError> {
+    let mut found = HashMap::new();
+
+    for chunk in emails.chunks(100) {
+        let keys: Vec<HashMap<String, AttributeValue>> = chunk
+            .iter()
+            .map(|email| {
+                HashMap::from([
+                    (
+                        "tenantId".to_string(),
+                        AttributeValue::S(tenant_id.to_string()),
+                    ),
+                    ("email".to_string(), AttributeValue::S(email.clone())),
+                ])
+            })
+            .collect();
+
+        let mut pending = Some(
+            KeysAndAttributes::builder()
+                .set_keys(Some(keys))
+                .build()
+                .map_err(|e| {
+                    AppError::InternalError(format!("Failed to build KeysAndAttributes: {}", e))
+                })?,
+        );
 
-        match result.last_evaluated_key() {
-            Some(key) if !key.is_empty() => {
-                exclusive_start_key = Some(key.clone());
+        while let Some(keys_and_attrs) = pending.take() {
+            let result = ddb_client
+                .batch_get_item()
+                .request_items(table_name, keys_and_attrs)
+                .send()
+                .await
+                .map_err(|e| AppError::AwsError(format!("DynamoDB BatchGetItem error: {}", e)))?;
+
+            if let Some(items) = result.responses().and_then(|r| r.get(table_name)) {
+                for item in items {
+                    if let Some(email) = item.get("email").and_then(|v| v.as_s().ok()) {
+                        found.insert(email.clone(), item.clone());
+                    }
+                }
             }
-            _ => break,
+
+            pending = result
+                .unprocessed_keys()
+                .and_then(|keys| keys.get(table_name))
+                .filter(|keys| !keys.keys().is_empty())
+                .cloned();
+        }
+    }
+
+    Ok(found)
+}
+
+/// Whether the query filters on anything the key condition can't handle.
+/// Only the table key can narrow to the email prefix.
+fn has_item_filters(query: &SubscriberListQuery) -> bool {
+    (query.email_prefix.is_some() && query.sort_by == SubscriberSort::AddedAt)
+        || query.suspected_bot.is_some()
+        || query.time_zone.is_some()
+        || query.interest_topic.is_some()
+        || query.engaged_since_issue.is_some()
+        || !query.field_filters.is_empty()
+        || !query.tags.is_empty()
+}
+
+fn matches_list_filters(
+    item: &HashMap<String, AttributeValue>,
+    query: &SubscriberListQuery,
+    fields: &[SubscriberField],
+) -> bool {
+    if let Some(prefix) = &query.email_prefix {
+        let email = item.get("email").and_then(|v| v.as_s().ok());
+        if !email.is_some_and(|email| email.starts_with(prefix.as_str())) {
+            return false;
+        }
+    }
+
+    if let Some(suspected_bot) = query.suspected_bot {
+        if bot_flags(item).is_some() != suspected_bot {
+            return false;
+        }
+    }
+
+    if let Some(time_zone) = &query.time_zone {
+        if item.get("timeZone").and_then(|v| v.as_s().ok()) != Some(time_zone) {
+            return false;
+        }
+    }
+
+    if let Some(topic) = &query.interest_topic {
+        let score = parse_interest_scores(item)
+            .and_then(|scores| scores.get(topic).map(|entry| entry.score));
+        if !score.is_some_and(|score| score >= query.min_interest_score) {
+            return false;
+        }
+    }
+
+    if let Some(since) = query.engaged_since_issue {
+        if item_number(item, "lastEngagedIssue").is_none_or(|issue| issue < since) {
+            return false;
+        }
+    }
+
+    if !query.field_filters.is_empty() {
+        let custom_fields = subscriber_fields::effective_custom_fields(fields, item);
+        if !subscriber_fields::matches_field_filters(&custom_fields, &query.field_filters) {
+            return false;
+        }
+    }
+
+    query.tags.is_empty()
+        || subscriber_tags::has_tags(&subscriber_tags::stored_tags(item), &query.tags)
+}
+
+fn item_number(item: &HashMap<String, AttributeValue>, key: &str) -> Option<i64> {
+    item.get(key)
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse::<i64>().ok())
+}
+
+fn sort_entry(item: &HashMap<String, AttributeValue>, sort_by: SubscriberSort) -> SortEntry {
+    let value = match sort_by {
+        SubscriberSort::Email => None,
+        SubscriberSort::AddedAt => item
+            .get("addedAt")
+            .and_then(|v| v.as_s().ok())
+            .map(|s| SortValue::Text(s.clone())),
+        SubscriberSort::LastEngagedIssue => {
+            item_number(item, "lastEngagedIssue").map(SortValue::Number)
         }
+        SubscriberSort::EngagementCount => {
+            item_number(item, "engagementCount").map(SortValue::Number)
+        }
+    };
+    SortEntry {
+        value,
+        email: item
+            .get("email")
+            .and_then(|v| v.as_s().ok())
+            .cloned()
+            .unwrap_or_default(),
     }
+}
+
+/// List order: by value in the requested direction, subscribers without one
+/// last either way, then by email so ties page deterministically.
+fn compare_sort_entries(a: &SortEntry, b: &SortEntry, descending: bool) -> Ordering {
+    let by_value = match (&a.value, &b.value) {
+        (Some(x), Some(y)) if descending => y.cmp(x),
+        (Some(x), Some(y)) => x.cmp(y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    by_value.then_with(|| a.email.cmp(&b.email))
+}
+
+/// Sorts `entries` and returns the page after the query's cursor, and
+/// whether any rows follow it.
+fn sorted_page(mut entries: Vec<SortEntry>, query: &SubscriberListQuery) -> (Vec<SortEntry>, bool) {
+    entries.sort_by(|a, b| compare_sort_entries(a, b, query.descending));
+    let start = query
+        .cursor
+        .as_ref()
+        .map(|cursor| {
+            entries.partition_point(|entry| {
+                compare_sort_entries(entry, cursor, query.descending) != Ordering::Greater
+            })
+        })
+        .unwrap_or(0);
+    let end = (start + query.limit as usize).min(entries.len());
+    let more = end < entries.len();
+    (entries.drain(start..end).collect(), more)
+}
+
+/// Bot-detection flags, or None when none of them are set.
+fn bot_flags(item: &HashMap<String, AttributeValue>) -> Option<BotFlags> {
+    let get_bool_flag = |key: &str| -> bool {
+        item.get(key)
+            .and_then(|v| v.as_bool().ok())
+            .copied()
+            .unwrap_or(false)
+    };
+
+    let flags = BotFlags {
+        honeypot_triggered: get_bool_flag("honeypotTriggered"),
+        disposable_domain: get_bool_flag("disposableDomain"),
+        suspicious_user_agent: get_bool_flag("suspiciousUserAgent"),
+        fast_submission: get_bool_flag("fastSubmission"),
+        suspicious_email_pattern: get_bool_flag("suspiciousEmailPattern"),
+    };
 
-    Ok(subscribers)
+    let suspected_bot = flags.honeypot_triggered
+        || flags.disposable_domain
+        || flags.suspicious_user_agent
+        || flags.fast_submission
+        || flags.suspicious_email_pattern;
+    suspected_bot.then_some(flags)
+}
+
+fn parse_subscriber_list_item(
+    item: &HashMap<String, AttributeValue>,
+    fields: &[SubscriberField],
+) -> SubscriberListItem {
+    let get_string = |key: &str| {
+        item.get(key)
+            .and_then(|v| v.as_s().ok())
+            .map(|s| s.to_string())
+    };
+    let bot_flags = bot_flags(item);
+
+    SubscriberListItem {
+        email: get_string("email").unwrap_or_default(),
+        added_at: get_string("addedAt"),
+        first_name: get_string("firstName"),
+        last_name: get_string("lastName"),
+        last_engaged_issue: item_number(item, "lastEngagedIssue"),
+        engagement_count: item_number(item, "engagementCount"),
+        interest_scores: parse_interest_scores(item).filter(|m| !m.is_empty()),
+        time_zone: get_string("timeZone"),
+        suspected_bot: bot_flags.is_some(),
+        bot_flags,
+        custom_fields: subscriber_fields::effective_custom_fields(fields, item),
+        tags: subscriber_tags::stored_tags(item).into_iter().collect(),
+    }
 }
 
 /// Query all subscribers for a tenant and filter for sunset candidates.
@@ -1135,38 +2377,213 @@ fn is_subscription_too_recent(
             let now = chrono::Utc::now();
             let weeks_since_creation = (now - created_date.with_timezone(&chrono::Utc)).num_weeks();
 
-            if weeks_since_creation <= threshold {
-                return true;
+            if weeks_since_creation <= threshold {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Query all subscribers for a tenant and classify into engagement cohorts.
+///
+/// Returns a bootstrap response if zero subscribers have any `lastEngagedIssue`,
+/// otherwise returns cohort counts and percentages.
+async fn query_audience_health(
+    ddb_client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    tenant_id: &str,
+    latest_issue_number: i64,
+) -> Result<AudienceHealthResponse, AppError> {
+    let mut highly_engaged: i64 = 0;
+    let mut occasional: i64 = 0;
+    let mut dormant: i64 = 0;
+    let mut total: i64 = 0;
+    let mut any_has_engagement = false;
+    let mut exclusive_start_key = None;
+
+    loop {
+        let mut query = ddb_client
+            .query()
+            .table_name(table_name)
+            .key_condition_expression("tenantId = :tid")
+            .expression_attribute_values(":tid", AttributeValue::S(tenant_id.to_string()));
+
+        if let Some(start_key) = exclusive_start_key.take() {
+            query = query.set_exclusive_start_key(Some(start_key));
+        }
+
+        let result = query.send().await?;
+
+        for item in result.items() {
+            if !is_subscriber_record(item) {
+                continue;
+            }
+            total += 1;
+
+            let last_engaged_issue = item
+                .get("lastEngagedIssue")
+                .and_then(|v| v.as_n().ok())
+                .and_then(|n| n.parse::<i64>().ok());
+
+            if last_engaged_issue.is_some() {
+                any_has_engagement = true;
+            }
+
+            match classify_cohort(last_engaged_issue, latest_issue_number) {
+                EngagementCohort::HighlyEngaged => highly_engaged += 1,
+                EngagementCohort::Occasional => occasional += 1,
+                EngagementCohort::Dormant => dormant += 1,
+            }
+        }
+
+        match result.last_evaluated_key() {
+            Some(key) if !key.is_empty() => {
+                exclusive_start_key = Some(key.clone());
+            }
+            _ => break,
+        }
+    }
+
+    // Bootstrap detection: if zero subscribers have any lastEngagedIssue
+    if !any_has_engagement {
+        return Ok(AudienceHealthResponse {
+            bootstrap: Some(true),
+            cohorts: None,
+        });
+    }
+
+    let percentage = |count: i64| -> f64 {
+        if total == 0 {
+            0.0
+        } else {
+            ((count as f64 / total as f64) * 1000.0).round() / 10.0
+        }
+    };
+
+    Ok(AudienceHealthResponse {
+        bootstrap: None,
+        cohorts: Some(CohortCounts {
+            highly_engaged: CohortDetail {
+                count: highly_engaged,
+                percentage: percentage(highly_engaged),
+            },
+            occasional: CohortDetail {
+                count: occasional,
+                percentage: percentage(occasional),
+            },
+            dormant: CohortDetail {
+                count: dormant,
+                percentage: percentage(dormant),
+            },
+            total,
+        }),
+    })
+}
+
+/// Load every cohort member for a tenant: current subscribers from the
+/// Subscribers table plus departure records from the newsletter table.
+/// Returns the members and the number of records skipped for lacking a
+/// usable `addedAt`.
+async fn query_cohort_members(
+    ddb_client: &aws_sdk_dynamodb::Client,
+    subscribers_table: &str,
+    newsletter_table: &str,
+    tenant_id: &str,
+) -> Result<(Vec<CohortMember>, i64), AppError> {
+    let mut members = Vec::new();
+    let mut excluded: i64 = 0;
+
+    let mut exclusive_start_key = None;
+    loop {
+        let mut query = ddb_client
+            .query()
+            .table_name(subscribers_table)
+            .key_condition_expression("tenantId = :tid")
+            .expression_attribute_values(":tid", AttributeValue::S(tenant_id.to_string()));
+
+        if let Some(start_key) = exclusive_start_key.take() {
+            query = query.set_exclusive_start_key(Some(start_key));
+        }
+
+        let result = query.send().await?;
+
+        for item in result.items() {
+            if !is_subscriber_record(item) {
+                continue;
+            }
+            match parse_cohort_member(item, false) {
+                Some(member) => members.push(member),
+                None => excluded += 1,
+            }
+        }
+
+        match result.last_evaluated_key() {
+            Some(key) if !key.is_empty() => {
+                exclusive_start_key = Some(key.clone());
+            }
+            _ => break,
+        }
+    }
+
+    let mut exclusive_start_key = None;
+    loop {
+        let mut query = ddb_client
+            .query()
+            .table_name(newsletter_table)
+            .key_condition_expression("pk = :pk AND begins_with(sk, :prefix)")
+            .expression_attribute_values(":pk", AttributeValue::S(tenant_id.to_string()))
+            .expression_attribute_values(
+                ":prefix",
+                AttributeValue::S(DEPARTURE_SK_PREFIX.to_string()),
+            );
+
+        if let Some(start_key) = exclusive_start_key.take() {
+            query = query.set_exclusive_start_key(Some(start_key));
+        }
+
+        let result = query.send().await?;
+
+        for item in result.items() {
+            match parse_cohort_member(item, true) {
+                Some(member) => members.push(member),
+                None => excluded += 1,
+            }
+        }
+
+        match result.last_evaluated_key() {
+            Some(key) if !key.is_empty() => {
+                exclusive_start_key = Some(key.clone());
             }
+            _ => break,
         }
     }
 
-    false
+    Ok((members, excluded))
 }
 
-/// Query all subscribers for a tenant and classify into engagement cohorts.
-///
-/// Returns a bootstrap response if zero subscribers have any `lastEngagedIssue`,
-/// otherwise returns cohort counts and percentages.
-async fn query_audience_health(
+/// Query GSI1 for up to `issue_count` of the most recently published issues.
+/// Stats records without `publishedAt` (drafts, scheduled) are skipped.
+async fn query_published_issues(
     ddb_client: &aws_sdk_dynamodb::Client,
     table_name: &str,
     tenant_id: &str,
-    latest_issue_number: i64,
-) -> Result<AudienceHealthResponse, AppError> {
-    let mut highly_engaged: i64 = 0;
-    let mut occasional: i64 = 0;
-    let mut dormant: i64 = 0;
-    let mut total: i64 = 0;
-    let mut any_has_engagement = false;
+    issue_count: i32,
+) -> Result<Vec<PublishedIssue>, AppError> {
+    let gsi1pk = build_issue_gsi1pk(tenant_id);
+    let mut issues = Vec::new();
     let mut exclusive_start_key = None;
 
     loop {
         let mut query = ddb_client
             .query()
             .table_name(table_name)
-            .key_condition_expression("tenantId = :tid")
-            .expression_attribute_values(":tid", AttributeValue::S(tenant_id.to_string()));
+            .index_name("GSI1")
+            .key_condition_expression("GSI1PK = :gsi1pk")
+            .expression_attribute_values(":gsi1pk", AttributeValue::S(gsi1pk.clone()))
+            .scan_index_forward(false)
+            .limit(issue_count);
 
         if let Some(start_key) = exclusive_start_key.take() {
             query = query.set_exclusive_start_key(Some(start_key));
@@ -1175,24 +2592,11 @@ async fn query_audience_health(
         let result = query.send().await?;
 
         for item in result.items() {
-            if !is_subscriber_record(item) {
-                continue;
-            }
-            total += 1;
-
-            let last_engaged_issue = item
-                .get("lastEngagedIssue")
-                .and_then(|v| v.as_n().ok())
-                .and_then(|n| n.parse::<i64>().ok());
-
-            if last_engaged_issue.is_some() {
-                any_has_engagement = true;
-            }
-
-            match classify_cohort(last_engaged_issue, latest_issue_number) {
-                EngagementCohort::HighlyEngaged => highly_engaged += 1,
-                EngagementCohort::Occasional => occasional += 1,
-                EngagementCohort::Dormant => dormant += 1,
+            if let Some(issue) = parse_published_issue(item) {
+                issues.push(issue);
+                if issues.len() >= issue_count as usize {
+                    return Ok(issues);
+                }
             }
         }
 
@@ -1204,15 +2608,98 @@ async fn query_audience_health(
         }
     }
 
-    // Bootstrap detection: if zero subscribers have any lastEngagedIssue
-    if !any_has_engagement {
-        return Ok(AudienceHealthResponse {
-            bootstrap: Some(true),
-            cohorts: None,
-        });
-    }
+    Ok(issues)
+}
 
-    let percentage = |count: i64| -> f64 {
+fn parse_timestamp(value: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+}
+
+/// Build a cohort member from a subscriber row, or from a departure record
+/// when `departed` is set (which also requires `departedAt`).
+fn parse_cohort_member(
+    item: &HashMap<String, AttributeValue>,
+    departed: bool,
+) -> Option<CohortMember> {
+    let get_timestamp = |key: &str| {
+        item.get(key)
+            .and_then(|v| v.as_s().ok())
+            .and_then(|s| parse_timestamp(s))
+    };
+    let get_number = |key: &str| {
+        item.get(key)
+            .and_then(|v| v.as_n().ok())
+            .and_then(|n| n.parse::<i64>().ok())
+    };
+
+    let signed_up_at = get_timestamp("addedAt")?;
+    let departed_at = if departed {
+        Some(get_timestamp("departedAt")?)
+    } else {
+        None
+    };
+
+    Some(CohortMember {
+        signed_up_at,
+        departed_at,
+        last_engaged_issue: get_number("lastEngagedIssue"),
+        engagement_count: get_number("engagementCount").unwrap_or(0),
+    })
+}
+
+fn parse_published_issue(item: &HashMap<String, AttributeValue>) -> Option<PublishedIssue> {
+    let issue_number = item
+        .get("GSI1SK")
+        .and_then(|v| v.as_s().ok())
+        .and_then(|s| s.parse::<i64>().ok())?;
+    let published_at = item
+        .get("publishedAt")
+        .and_then(|v| v.as_s().ok())
+        .and_then(|s| parse_timestamp(s))?;
+
+    Some(PublishedIssue {
+        issue_number,
+        published_at,
+    })
+}
+
+/// First instant (UTC) of the month after the one `at` falls in.
+fn start_of_next_month(at: chrono::DateTime<chrono::Utc>) -> chrono::DateTime<chrono::Utc> {
+    use chrono::Datelike;
+
+    let (year, month) = if at.month() == 12 {
+        (at.year() + 1, 1)
+    } else {
+        (at.year(), at.month() + 1)
+    };
+    chrono::NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|naive| naive.and_utc())
+        .unwrap_or(at)
+}
+
+/// Group members by signup month and build the retention triangle against
+/// `issues` (sorted ascending). Only the `months` most recent cohorts are
+/// returned, oldest first. Each cohort gets a cell for every issue published
+/// after its signup month ended, so newer cohorts have fewer cells.
+fn build_signup_cohorts(
+    members: &[CohortMember],
+    issues: &[PublishedIssue],
+    months: usize,
+) -> Vec<SignupCohort> {
+    let mut by_month: std::collections::BTreeMap<String, Vec<&CohortMember>> =
+        std::collections::BTreeMap::new();
+    for member in members {
+        by_month
+            .entry(member.signed_up_at.format("%Y-%m").to_string())
+            .or_default()
+            .push(member);
+    }
+
+    let skip = by_month.len().saturating_sub(months);
+    let percentage = |count: i64, total: i64| -> f64 {
         if total == 0 {
             0.0
         } else {
@@ -1220,24 +2707,55 @@ async fn query_audience_health(
         }
     };
 
-    Ok(AudienceHealthResponse {
-        bootstrap: None,
-        cohorts: Some(CohortCounts {
-            highly_engaged: CohortDetail {
-                count: highly_engaged,
-                percentage: percentage(highly_engaged),
-            },
-            occasional: CohortDetail {
-                count: occasional,
-                percentage: percentage(occasional),
-            },
-            dormant: CohortDetail {
-                count: dormant,
-                percentage: percentage(dormant),
-            },
-            total,
-        }),
-    })
+    by_month
+        .into_iter()
+        .skip(skip)
+        .map(|(cohort_month, cohort)| {
+            let size = cohort.len() as i64;
+            // Every member shares the signup month, so any of them gives the end.
+            let cohort_end = start_of_next_month(cohort[0].signed_up_at);
+            let still_subscribed = cohort.iter().filter(|m| m.departed_at.is_none()).count() as i64;
+            let total_engagement: i64 = cohort.iter().map(|m| m.engagement_count).sum();
+            let average_engagement_count =
+                ((total_engagement as f64 / size as f64) * 10.0).round() / 10.0;
+
+            let retention = issues
+                .iter()
+                .filter(|issue| issue.published_at >= cohort_end)
+                .enumerate()
+                .map(|(index, issue)| {
+                    let subscribed = cohort
+                        .iter()
+                        .filter(|m| m.departed_at.is_none_or(|d| d > issue.published_at))
+                        .count() as i64;
+                    let engaged = cohort
+                        .iter()
+                        .filter(|m| {
+                            m.last_engaged_issue
+                                .is_some_and(|lei| lei >= issue.issue_number)
+                        })
+                        .count() as i64;
+
+                    CohortRetentionCell {
+                        issue_number: issue.issue_number,
+                        issues_since_signup: index as i64 + 1,
+                        subscribed,
+                        subscribed_percentage: percentage(subscribed, size),
+                        engaged,
+                        engaged_percentage: percentage(engaged, size),
+                    }
+                })
+                .collect();
+
+            SignupCohort {
+                cohort_month,
+                size,
+                still_subscribed,
+                average_engagement_count,
+                retention,
+            }
+        })
+        .collect()
 }
 
 /// Classify a subscriber into an engagement cohort based on their lastEngagedIssue
@@ -1389,6 +2907,85 @@ mod tests {
         assert!(!is_subscriber_record(&item));
     }
 
+    #[test]
+    fn test_subscriber_key_normalises_and_stays_a_subscriber_record() {
+        let key = subscriber_key("  Person@Example.COM ").unwrap();
+        assert_eq!(key, "person@example.com");
+        assert!(is_subscriber_record(&make_subscriber_item(
+            &key, None, None
+        )));
+
+        // Lowercasing means an address can never start with the uppercase
+        // SEGMENT overloads.
+        let key = subscriber_key("SEGMENT@example.com").unwrap();
+        assert!(is_subscriber_record(&make_subscriber_item(
+            &key, None, None
+        )));
+    }
+
+    #[test]
+    fn test_subscriber_key_rejects_invalid_and_reserved_addresses() {
+        for raw in ["", "   ", "not-an-email", "a#MEMBER#b@example.com"] {
+            assert!(
+                subscriber_key(raw).is_err(),
+                "expected {raw:?} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_subscriber_update_sets_and_clears_names() {
+        let update = build_subscriber_update(
+            &UpdateSubscriberRequest {
+                first_name: Some(" Ada ".to_string()),
+                last_name: Some("".to_string()),
+                custom_fields: None,
+            },
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            update.expression,
+            "SET updatedAt = :updatedAt, firstName = :firstName REMOVE lastName"
+        );
+        assert_eq!(
+            update.values,
+            vec![(":firstName".to_string(), "Ada".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_build_subscriber_update_sets_and_clears_custom_fields() {
+        let body = UpdateSubscriberRequest::default();
+        let values = BTreeMap::from([("company".to_string(), serde_json::json!("Acme"))]);
+
+        let update = build_subscriber_update(&body, Some(&values)).unwrap();
+        assert_eq!(
+            update.expression,
+            "SET updatedAt = :updatedAt, customFields = :customFields"
+        );
+        assert!(update.values.is_empty());
+
+        let cleared = build_subscriber_update(&body, Some(&BTreeMap::new())).unwrap();
+        assert_eq!(
+            cleared.expression,
+            "SET updatedAt = :updatedAt REMOVE customFields"
+        );
+    }
+
+    #[test]
+    fn test_build_subscriber_update_validates_input() {
+        assert!(build_subscriber_update(&UpdateSubscriberRequest::default(), None).is_err());
+        assert!(build_subscriber_update(
+            &UpdateSubscriberRequest {
+                first_name: Some("x".repeat(MAX_NAME_LEN + 1)),
+                ..Default::default()
+            },
+            None,
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_parse_interest_scores_reads_topic_map() {
         let mut ai_entry = HashMap::new();
@@ -1550,7 +3147,25 @@ mod tests {
             )]),
         );
 
-        let detail = parse_subscriber_detail(&item);
+        item.insert(
+            "customFields".to_string(),
+            AttributeValue::M(HashMap::from([(
+                "company".to_string(),
+                AttributeValue::S("Acme".to_string()),
+            )])),
+        );
+        item.insert(
+            "tags".to_string(),
+            AttributeValue::Ss(vec!["vip".to_string(), "beta-tester".to_string()]),
+        );
+        let fields: Vec<SubscriberField> = serde_json::from_value(serde_json::json!([
+            { "key": "company", "label": "Company", "type": "string", "createdAt": "2026-01-01T00:00:00Z" },
+            { "key": "plan", "label": "Plan", "type": "enum", "options": ["Free", "Pro"],
+              "default": "Free", "createdAt": "2026-01-01T00:00:00Z" }
+        ]))
+        .unwrap();
+
+        let detail = parse_subscriber_detail(&item, &fields);
         let json = serde_json::to_value(&detail).unwrap();
 
         assert_eq!(json["email"], "reader@example.com");
@@ -1565,12 +3180,17 @@ mod tests {
         assert_eq!(json["recentActivity"][0]["type"], "click");
         assert_eq!(json["recentActivity"][0]["issue"], 42);
         assert_eq!(json["recentActivity"][0]["url"], "https://x.test/a");
+        assert_eq!(
+            json["customFields"],
+            serde_json::json!({ "company": "Acme", "plan": "Free" })
+        );
+        assert_eq!(json["tags"], serde_json::json!(["beta-tester", "vip"]));
     }
 
     #[test]
     fn test_parse_subscriber_detail_minimal_omits_optionals() {
         let item = make_subscriber_item("bare@example.com", None, None);
-        let detail = parse_subscriber_detail(&item);
+        let detail = parse_subscriber_detail(&item, &[]);
         let json = serde_json::to_value(&detail).unwrap();
 
         assert_eq!(json["email"], "bare@example.com");
@@ -1578,6 +3198,8 @@ mod tests {
         assert!(json.get("firstName").is_none());
         assert!(json.get("interestScores").is_none());
         assert!(json.get("timeZone").is_none());
+        assert!(json.get("customFields").is_none());
+        assert!(json.get("tags").is_none());
         // addedAt and lastEngagedIssue serialize as null (Option without skip).
         assert_eq!(json["addedAt"], serde_json::Value::Null);
         assert_eq!(json["lastEngagedIssue"], serde_json::Value::Null);
@@ -1913,4 +3535,387 @@ mod tests {
         assert_eq!(summary.percentage_change, 0.0);
         assert_eq!(summary.points_returned, 0);
     }
+
+    fn cohort_member(
+        added_at: &str,
+        departed_at: Option<&str>,
+        last_engaged_issue: Option<i64>,
+        engagement_count: i64,
+    ) -> CohortMember {
+        CohortMember {
+            signed_up_at: parse_timestamp(added_at).unwrap(),
+            departed_at: departed_at.map(|d| parse_timestamp(d).unwrap()),
+            last_engaged_issue,
+            engagement_count,
+        }
+    }
+
+    fn published_issue(issue_number: i64, published_at: &str) -> PublishedIssue {
+        PublishedIssue {
+            issue_number,
+            published_at: parse_timestamp(published_at).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_build_signup_cohorts_retention_triangle() {
+        let members = vec![
+            // January cohort: one stays and engages, one leaves after issue 2,
+            // one stays but goes quiet after issue 1.
+            cohort_member("2025-01-05T10:00:00Z", None, Some(3), 3),
+            cohort_member(
+                "2025-01-20T10:00:00Z",
+                Some("2025-02-10T00:00:00Z"),
+                Some(2),
+                2,
+            ),
+            cohort_member("2025-01-31T23:00:00Z", None, Some(1), 1),
+            // February cohort: both still subscribed, one engaged with issue 3.
+            cohort_member("2025-02-02T10:00:00Z", None, Some(3), 1),
+            cohort_member("2025-02-14T10:00:00Z", None, None, 0),
+        ];
+        let issues = vec![
+            published_issue(1, "2025-02-01T09:00:00Z"),
+            published_issue(2, "2025-02-08T09:00:00Z"),
+            published_issue(3, "2025-03-01T09:00:00Z"),
+        ];
+
+        let cohorts = build_signup_cohorts(&members, &issues, 12);
+        assert_eq!(cohorts.len(), 2);
+
+        let january = &cohorts[0];
+        assert_eq!(january.cohort_month, "2025-01");
+        assert_eq!(january.size, 3);
+        assert_eq!(january.still_subscribed, 2);
+        assert_eq!(january.average_engagement_count, 2.0);
+        let cells: Vec<(i64, i64, i64, i64)> = january
+            .retention
+            .iter()
+            .map(|c| {
+                (
+                    c.issue_number,
+                    c.issues_since_signup,
+                    c.subscribed,
+                    c.engaged,
+                )
+            })
+            .collect();
+        assert_eq!(cells, vec![(1, 1, 3, 3), (2, 2, 3, 2), (3, 3, 2, 1)]);
+        assert_eq!(january.retention[2].subscribed_percentage, 66.7);
+        assert_eq!(january.retention[2].engaged_percentage, 33.3);
+
+        // February signups only see issues published from March onwards.
+        let february = &cohorts[1];
+        assert_eq!(february.cohort_month, "2025-02");
+        assert_eq!(february.retention.len(), 1);
+        assert_eq!(february.retention[0].issue_number, 3);
+        assert_eq!(february.retention[0].issues_since_signup, 1);
+        assert_eq!(february.retention[0].subscribed_percentage, 100.0);
+        assert_eq!(february.retention[0].engaged_percentage, 50.0);
+    }
+
+    #[test]
+    fn test_build_signup_cohorts_keeps_most_recent_months() {
+        let members = vec![
+            cohort_member("2024-11-03T00:00:00Z", None, None, 0),
+            cohort_member("2024-12-03T00:00:00Z", None, None, 0),
+            cohort_member("2025-01-03T00:00:00Z", None, None, 0),
+        ];
+
+        let cohorts = build_signup_cohorts(&members, &[], 2);
+        let months: Vec<&str> = cohorts.iter().map(|c| c.cohort_month.as_str()).collect();
+        assert_eq!(months, vec!["2024-12", "2025-01"]);
+        assert!(cohorts.iter().all(|c| c.retention.is_empty()));
+    }
+
+    #[test]
+    fn test_build_signup_cohorts_no_members() {
+        let issues = vec![published_issue(1, "2025-02-01T09:00:00Z")];
+        assert!(build_signup_cohorts(&[], &issues, 12).is_empty());
+    }
+
+    #[test]
+    fn test_start_of_next_month_rolls_over_year() {
+        let next = start_of_next_month(parse_timestamp("2024-12-31T23:59:59Z").unwrap());
+        assert_eq!(next.to_rfc3339(), "2025-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_cohort_member_current_and_departed() {
+        let mut item = HashMap::new();
+        item.insert(
+            "addedAt".to_string(),
+            AttributeValue::S("2025-01-05T10:00:00.000Z".to_string()),
+        );
+        item.insert(
+            "lastEngagedIssue".to_string(),
+            AttributeValue::N("7".to_string()),
+        );
+
+        let current = parse_cohort_member(&item, false).unwrap();
+        assert_eq!(current.departed_at, None);
+        assert_eq!(current.last_engaged_issue, Some(7));
+        assert_eq!(current.engagement_count, 0);
+
+        // A departure record without departedAt is unusable.
+        assert!(parse_cohort_member(&item, true).is_none());
+        item.insert(
+            "departedAt".to_string(),
+            AttributeValue::S("2025-03-01T00:00:00.000Z".to_string()),
+        );
+        assert!(parse_cohort_member(&item, true)
+            .unwrap()
+            .departed_at
+            .is_some());
+
+        item.insert(
+            "addedAt".to_string(),
+            AttributeValue::S("yesterday".to_string()),
+        );
+        assert!(parse_cohort_member(&item, false).is_none());
+    }
+
+    #[test]
+    fn test_parse_published_issue_requires_published_at() {
+        let mut item = make_trend_item(4, 100);
+        assert!(parse_published_issue(&item).is_none());
+
+        item.insert(
+            "publishedAt".to_string(),
+            AttributeValue::S("2025-02-01T09:00:00Z".to_string()),
+        );
+        let issue = parse_published_issue(&item).unwrap();
+        assert_eq!(issue.issue_number, 4);
+    }
+
+    fn list_query(params: &[(&str, &str)]) -> Result<SubscriberListQuery, AppError> {
+        parse_subscriber_list_query(params.iter().copied(), &[])
+    }
+
+    fn entry(value: Option<i64>, email: &str) -> SortEntry {
+        SortEntry {
+            value: value.map(SortValue::Number),
+            email: email.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_subscriber_list_query_defaults() {
+        let query = list_query(&[]).unwrap();
+        assert_eq!(query.limit, DEFAULT_LIST_LIMIT);
+        assert_eq!(query.sort_by, SubscriberSort::Email);
+        assert!(!query.descending);
+        assert!(query.cursor.is_none());
+        assert!(!has_item_filters(&query));
+
+        // Non-email sorts default to newest / most engaged first.
+        let query = list_query(&[("sortBy", "engagementCount")]).unwrap();
+        assert!(query.descending);
+        let query = list_query(&[("sortBy", "addedAt"), ("sortOrder", "asc")]).unwrap();
+        assert!(!query.descending);
+
+        // The addedAt index can't narrow to a prefix, so that is read ahead too.
+        assert!(!has_item_filters(
+            &list_query(&[("emailPrefix", "a")]).unwrap()
+        ));
+        assert!(has_item_filters(
+            &list_query(&[("sortBy", "addedAt"), ("emailPrefix", "a")]).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_parse_subscriber_list_query_filters() {
+        let query = list_query(&[
+            ("emailPrefix", " Alice"),
+            ("suspectedBot", "false"),
+            ("timeZone", "Europe/Berlin"),
+            ("interestTopic", "rust"),
+            ("minInterestScore", "2.5"),
+            ("engagedSinceIssue", "12"),
+            ("tag", "VIP"),
+        ])
+        .unwrap();
+        assert_eq!(query.email_prefix.as_deref(), Some("alice"));
+        assert_eq!(query.suspected_bot, Some(false));
+        assert_eq!(query.time_zone.as_deref(), Some("Europe/Berlin"));
+        assert_eq!(query.interest_topic.as_deref(), Some("rust"));
+        assert_eq!(query.min_interest_score, 2.5);
+        assert_eq!(query.engaged_since_issue, Some(12));
+        assert_eq!(query.tags, vec!["vip".to_string()]);
+        assert!(has_item_filters(&query));
+    }
+
+    #[test]
+    fn test_parse_subscriber_list_query_rejects_bad_values() {
+        for params in [
+            vec![("limit", "0")],
+            vec![("limit", "201")],
+            vec![("limit", "ten")],
+            vec![("sortBy", "firstName")],
+            vec![("sortOrder", "up")],
+            vec![("suspectedBot", "maybe")],
+            vec![("minInterestScore", "3")],
+            vec![("interestTopic", "rust"), ("minInterestScore", "high")],
+            vec![("engagedSinceIssue", "-1")],
+            vec![("nextToken", "not-a-token")],
+        ] {
+            assert!(
+                matches!(list_query(&params), Err(AppError::BadRequest(_))),
+                "expected {:?} to be rejected",
+                params
+            );
+        }
+    }
+
+    #[test]
+    fn test_list_cursor_round_trip_and_sort_mismatch() {
+        let last = SortEntry {
+            value: Some(SortValue::Text("2025-01-01T00:00:00Z".to_string())),
+            email: "a@example.com".to_string(),
+        };
+        let token = encode_list_cursor(SubscriberSort::AddedAt, &last);
+        assert_eq!(
+            decode_list_cursor(&token, SubscriberSort::AddedAt).unwrap(),
+            last
+        );
+        assert!(matches!(
+            decode_list_cursor(&token, SubscriberSort::EngagementCount),
+            Err(AppError::BadRequest(_))
+        ));
+        let token = encode_list_cursor(SubscriberSort::AddedAt, &entry(None, "a@example.com"));
+        assert!(matches!(
+            decode_list_cursor(&token, SubscriberSort::AddedAt),
+            Err(AppError::BadRequest(_))
+        ));
+
+        let last = entry(Some(7), "b@example.com");
+        let token = encode_list_cursor(SubscriberSort::EngagementCount, &last);
+        let query = list_query(&[("sortBy", "engagementCount"), ("nextToken", &token)]).unwrap();
+        assert_eq!(query.cursor, Some(last));
+
+        let last = entry(None, "c@example.com");
+        let token = encode_list_cursor(SubscriberSort::Email, &last);
+        assert_eq!(
+            decode_list_cursor(&token, SubscriberSort::Email).unwrap(),
+            last
+        );
+    }
+
+    #[test]
+    fn test_compare_sort_entries_puts_missing_values_last() {
+        let mut entries = [
+            entry(None, "a@example.com"),
+            entry(Some(3), "d@example.com"),
+            entry(Some(5), "b@example.com"),
+            entry(Some(3), "c@example.com"),
+        ];
+
+        entries.sort_by(|a, b| compare_sort_entries(a, b, true));
+        let emails: Vec<&str> = entries.iter().map(|e| e.email.as_str()).collect();
+        assert_eq!(
+            emails,
+            vec![
+                "b@example.com",
+                "c@example.com",
+                "d@example.com",
+                "a@example.com"
+            ]
+        );
+
+        entries.sort_by(|a, b| compare_sort_entries(a, b, false));
+        let emails: Vec<&str> = entries.iter().map(|e| e.email.as_str()).collect();
+        assert_eq!(
+            emails,
+            vec![
+                "c@example.com",
+                "d@example.com",
+                "b@example.com",
+                "a@example.com"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sorted_page_resumes_after_cursor() {
+        let entries: Vec<SortEntry> = (0..5)
+            .map(|n| entry(Some(n % 3), &format!("s{}@example.com", n)))
+            .collect();
+        let mut query = list_query(&[("sortBy", "engagementCount"), ("limit", "2")]).unwrap();
+
+        let mut seen = Vec::new();
+        loop {
+            let (page, more) = sorted_page(entries.clone(), &query);
+            seen.extend(page.iter().map(|e| e.email.clone()));
+            if !more {
+                break;
+            }
+            query.cursor = page.last().cloned();
+        }
+        assert_eq!(
+            seen,
+            vec![
+                "s2@example.com",
+                "s1@example.com",
+                "s4@example.com",
+                "s0@example.com",
+                "s3@example.com"
+            ]
+        );
+
+        // A cursor whose subscriber has since left still resumes in place.
+        query.cursor = Some(entry(Some(1), "s3@example.com"));
+        let (page, _) = sorted_page(entries, &query);
+        assert_eq!(page[0].email, "s4@example.com");
+    }
+
+    #[test]
+    fn test_matches_list_filters() {
+        let mut item = make_subscriber_item("a@example.com", Some(10), None);
+        item.insert(
+            "timeZone".to_string(),
+            AttributeValue::S("Europe/Berlin".to_string()),
+        );
+        item.insert(
+            "interestScores".to_string(),
+            AttributeValue::M(HashMap::from([(
+                "rust".to_string(),
+                AttributeValue::M(HashMap::from([(
+                    "score".to_string(),
+                    AttributeValue::N("4".to_string()),
+                )])),
+            )])),
+        );
+
+        let matches = |params: &[(&str, &str)]| {
+            matches_list_filters(&item, &list_query(params).unwrap(), &[])
+        };
+        assert!(matches(&[]));
+        assert!(matches(&[("emailPrefix", "a@")]));
+        assert!(!matches(&[("emailPrefix", "b")]));
+        assert!(matches(&[("timeZone", "Europe/Berlin")]));
+        assert!(!matches(&[("timeZone", "America/New_York")]));
+        assert!(matches(&[
+            ("interestTopic", "rust"),
+            ("minInterestScore", "4")
+        ]));
+        assert!(!matches(&[
+            ("interestTopic", "rust"),
+            ("minInterestScore", "4.5")
+        ]));
+        assert!(!matches(&[("interestTopic", "go")]));
+        assert!(matches(&[("engagedSinceIssue", "10")]));
+        assert!(!matches(&[("engagedSinceIssue", "11")]));
+        assert!(matches(&[("suspectedBot", "false")]));
+        assert!(!matches(&[("suspectedBot", "true")]));
+
+        item.insert("honeypotTriggered".to_string(), AttributeValue::Bool(true));
+        assert!(matches_list_filters(
+            &item,
+            &list_query(&[("suspectedBot", "true")]).unwrap(),
+            &[]
+        ));
+        let parsed = parse_subscriber_list_item(&item, &[]);
+        assert!(parsed.suspected_bot);
+        assert!(parsed.bot_flags.unwrap().honeypot_triggered);
+    }
 }
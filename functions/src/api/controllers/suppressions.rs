@@ -0,0 +1,650 @@
+//! Tenant suppression list.
+//!
+//! Addresses and whole domains on the list can't be added or imported as
+//! subscribers and are dropped from issue sends. Entries are added here by
+//! hand or in bulk, and automatically by the unsubscribe log processor and the
+//! bounce and complaint handlers. Entry rules and storage are in
+//! `newsletter::admin::suppression`, shared with the log processor.
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use newsletter::admin::suppression::{
+    self, SuppressionEntry, SuppressionKind, SuppressionReason, SUPPRESSION_SK_PREFIX,
+};
+use newsletter::admin::{auth, aws_clients, error::AppError, response};
+use percent_encoding::percent_decode_str;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use tokio::task::JoinSet;
+
+use super::issue_links::csv_field;
+use super::issues::{decode_pagination_token, encode_pagination_token};
+
+// ── Constants ──────────────────────────────────────────────────────────
+
+const DEFAULT_LIST_LIMIT: usize = 50;
+const MAX_LIST_LIMIT: usize = 200;
+/// Items read per query while filling a filtered page.
+const QUERY_PAGE_SIZE: i32 = 500;
+const MAX_BULK_ENTRIES: usize = 1_000;
+const WRITE_CONCURRENCY: usize = 25;
+const NOTE_MAX_LEN: usize = 500;
+const EXPORT_FILENAME: &str = "suppression-list.csv";
+
+// ── Data types ─────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct CreateSuppressionRequest {
+    entry: String,
+    reason: SuppressionReason,
+    note: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct BulkSuppressionRequest {
+    entries: Vec<String>,
+    reason: SuppressionReason,
+    note: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InvalidEntry {
+    entry: String,
+    message: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkSuppressionResponse {
+    added: usize,
+    already_suppressed: usize,
+    failed: usize,
+    invalid: Vec<InvalidEntry>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SuppressionListResponse {
+    entries: Vec<SuppressionEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_token: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct SuppressionListQuery {
+    /// Lowercased start of the entry.
+    prefix: String,
+    reason: Option<SuppressionReason>,
+    kind: Option<SuppressionKind>,
+    limit: usize,
+    next_token: Option<String>,
+}
+
+// ── Validation ─────────────────────────────────────────────────────────
+
+fn clean_note(note: Option<String>) -> Result<Option<String>, AppError> {
+    let Some(note) = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()) else {
+        return Ok(None);
+    };
+    if note.chars().count() > NOTE_MAX_LEN {
+        return Err(AppError::BadRequest(format!(
+            "note must be at most {} characters",
+            NOTE_MAX_LEN
+        )));
+    }
+    Ok(Some(note))
+}
+
+fn parse_list_query<'a>(
+    params: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Result<SuppressionListQuery, AppError> {
+    let params: Vec<(&str, &str)> = params.into_iter().collect();
+    let first = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+
+    let limit = first("limit")
+        .map(|s| {
+            s.parse::<usize>()
+                .map_err(|_| AppError::BadRequest("limit must be a valid integer".to_string()))
+        })
+        .transpose()?
+        .unwrap_or(DEFAULT_LIST_LIMIT);
+    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+        return Err(AppError::BadRequest(format!(
+            "limit must be between 1 and {}",
+            MAX_LIST_LIMIT
+        )));
+    }
+
+    let reason = first("reason")
+        .map(|value| {
+            SuppressionReason::parse(value).ok_or_else(|| {
+                AppError::BadRequest(
+                    "reason must be one of hard-bounce, complaint, manual, legal".to_string(),
+                )
+            })
+        })
+        .transpose()?;
+    let kind = first("type")
+        .map(|value| {
+            SuppressionKind::parse(value)
+                .ok_or_else(|| AppError::BadRequest("type must be email or domain".to_string()))
+        })
+        .transpose()?;
+
+    Ok(SuppressionListQuery {
+        prefix: first("search").unwrap_or_default().to_lowercase(),
+        reason,
+        kind,
+        limit,
+        next_token: first("nextToken").map(str::to_string),
+    })
+}
+
+/// Normalises and de-duplicates bulk entries, in request order.
+fn plan_bulk_entries(raw: &[String]) -> (Vec<(String, SuppressionKind)>, Vec<InvalidEntry>) {
+    let mut valid = Vec::new();
+    let mut invalid = Vec::new();
+    let mut seen = HashSet::new();
+
+    for value in raw {
+        match suppression::normalize_entry(value) {
+            Ok((entry, kind)) => {
+                if seen.insert(entry.clone()) {
+                    valid.push((entry, kind));
+                }
+            }
+            Err(e) => invalid.push(InvalidEntry {
+                entry: value.clone(),
+                message: match e {
+                    AppError::BadRequest(message) => message,
+                    other => other.to_string(),
+                },
+            }),
+        }
+    }
+
+    (valid, invalid)
+}
+
+fn entries_to_csv(entries: &[SuppressionEntry]) -> String {
+    let mut csv = String::from("entry,type,reason,source,note,createdAt,createdBy\n");
+    for entry in entries {
+        let fields = [
+            csv_field(&entry.entry),
+            entry.kind.as_str().to_string(),
+            entry.reason.as_str().to_string(),
+            csv_field(&entry.source),
+            csv_field(entry.note.as_deref().unwrap_or_default()),
+            entry.created_at.clone(),
+            csv_field(entry.created_by.as_deref().unwrap_or_default()),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+// ── Public handlers ────────────────────────────────────────────────────
+
+/// GET /suppressions
+pub async fn list_suppressions(event: Request) -> Result<Response<Body>, Error> {
+    match handle_list_suppressions(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_list_suppressions(event: Request) -> Result<Response<Body>, AppError> {
+    let tenant_id = require_tenant(&event)?;
+    let params = event.query_string_parameters();
+    let query = parse_list_query(params.iter())?;
+
+    let start_key = query
+        .next_token
+        .as_deref()
+        .map(|token| resume_key(&tenant_id, token))
+        .transpose()?;
+    let (entries, last_key) =
+        query_entries(&tenant_id, &query, Some(query.limit), start_key).await?;
+
+    response::format_response(
+        200,
+        SuppressionListResponse {
+            entries,
+            next_token: last_key.as_ref().map(encode_pagination_token),
+        },
+    )
+}
+
+/// The query start key for a `nextToken`. Only the sort key is taken from the
+/// token; the partition is always the caller's tenant.
+fn resume_key(tenant_id: &str, token: &str) -> Result<HashMap<String, AttributeValue>, AppError> {
+    let sk = decode_pagination_token(token)?
+        .remove("sk")
+        .filter(|sk| {
+            sk.as_s()
+                .is_ok_and(|sk| sk.starts_with(SUPPRESSION_SK_PREFIX))
+        })
+        .ok_or_else(|| AppError::BadRequest("Invalid pagination token".to_string()))?;
+    Ok(HashMap::from([
+        ("pk".to_string(), AttributeValue::S(tenant_id.to_string())),
+        ("sk".to_string(), sk),
+    ]))
+}
+
+/// POST /suppressions
+pub async fn create_suppression(event: Request) -> Result<Response<Body>, Error> {
+    match handle_create_suppression(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_create_suppression(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&event)?;
+
+    let body: CreateSuppressionRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+    let (entry, kind) = suppression::normalize_entry(&body.entry)?;
+    let note = clean_note(body.note)?;
+
+    let record = SuppressionEntry {
+        entry,
+        kind,
+        reason: body.reason,
+        source: "api".to_string(),
+        note,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        created_by: Some(user_context.email.clone()),
+    };
+    let client = aws_clients::get_dynamodb_client().await;
+    if !suppression::add_entry(client, &table_name()?, &tenant_id, &record).await? {
+        return Err(AppError::Conflict(format!(
+            "{} is already on the suppression list",
+            record.entry
+        )));
+    }
+
+    tracing::info!(tenant_id = %tenant_id, entry = %record.entry, reason = record.reason.as_str(), "Suppression entry added");
+    response::format_response(201, record)
+}
+
+/// POST /suppressions/bulk — add up to 1,000 entries with one reason.
+/// Entries already on the list keep their original reason.
+pub async fn bulk_add_suppressions(event: Request) -> Result<Response<Body>, Error> {
+    match handle_bulk_add_suppressions(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_bulk_add_suppressions(event: Request) -> Result<Response<Body>, AppError> {
+    let user_context = auth::get_user_context(&event)?;
+    let tenant_id = require_tenant(&event)?;
+
+    let body: BulkSuppressionRequest = serde_json::from_slice(event.body())
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON: {}", e)))?;
+    if body.entries.is_empty() {
+        return Err(AppError::BadRequest(
+            "entries must not be empty".to_string(),
+        ));
+    }
+    if body.entries.len() > MAX_BULK_ENTRIES {
+        return Err(AppError::BadRequest(format!(
+            "At most {} entries can be added at once",
+            MAX_BULK_ENTRIES
+        )));
+    }
+    let note = clean_note(body.note)?;
+    let (valid, invalid) = plan_bulk_entries(&body.entries);
+
+    let table_name = table_name()?;
+    let client = aws_clients::get_dynamodb_client().await;
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let mut outcome = BulkSuppressionResponse {
+        invalid,
+        ..Default::default()
+    };
+
+    for batch in valid.chunks(WRITE_CONCURRENCY) {
+        let mut join_set = JoinSet::new();
+        for (entry, kind) in batch.iter().cloned() {
+            let record = SuppressionEntry {
+                entry,
+                kind,
+                reason: body.reason,
+                source: "bulk-upload".to_string(),
+                note: note.clone(),
+                created_at: created_at.clone(),
+                created_by: Some(user_context.email.clone()),
+            };
+            let (table_name, tenant_id) = (table_name.clone(), tenant_id.clone());
+            join_set.spawn(async move {
+                let result = suppression::add_entry(client, &table_name, &tenant_id, &record).await;
+                (record.entry, result)
+            });
+        }
+
+        while let Some(joined) = join_set.join_next().await {
+            match joined {
+                Ok((_, Ok(true))) => outcome.added += 1,
+                Ok((_, Ok(false))) => outcome.already_suppressed += 1,
+                Ok((entry, Err(e))) => {
+                    tracing::warn!(error = ?e, tenant_id = %tenant_id, entry = %entry, "Failed to add suppression entry");
+                    outcome.failed += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, tenant_id = %tenant_id, "Suppression entry task failed");
+                    outcome.failed += 1;
+                }
+            }
+        }
+    }
+
+    tracing::info!(
+        tenant_id = %tenant_id,
+        added = outcome.added,
+        already_suppressed = outcome.already_suppressed,
+        invalid = outcome.invalid.len(),
+        failed = outcome.failed,
+        "Bulk suppression upload processed"
+    );
+    response::format_response(200, outcome)
+}
+
+/// DELETE /suppressions/:entry
+pub async fn delete_suppression(
+    event: Request,
+    entry: Option<String>,
+) -> Result<Response<Body>, Error> {
+    match handle_delete_suppression(event, entry).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_delete_suppression(
+    event: Request,
+    entry: Option<String>,
+) -> Result<Response<Body>, AppError> {
+    let tenant_id = require_tenant(&event)?;
+    let entry =
+        entry.ok_or_else(|| AppError::BadRequest("Suppression entry is required".to_string()))?;
+    let decoded = percent_decode_str(&entry)
+        .decode_utf8()
+        .map_err(|e| AppError::BadRequest(format!("Invalid entry encoding: {}", e)))?;
+    let (entry, _) = suppression::normalize_entry(&decoded)?;
+
+    let client = aws_clients::get_dynamodb_client().await;
+    let result = client
+        .delete_item()
+        .table_name(table_name()?)
+        .key("pk", AttributeValue::S(tenant_id.clone()))
+        .key("sk", AttributeValue::S(suppression::entry_sk(&entry)))
+        .condition_expression("attribute_exists(sk)")
+        .send()
+        .await;
+
+    if let Err(e) = result {
+        let service_err = e.into_service_error();
+        if service_err.is_conditional_check_failed_exception() {
+            return Err(AppError::NotFound(
+                "Suppression entry not found".to_string(),
+            ));
+        }
+        return Err(AppError::AwsError(format!(
+            "Failed to delete suppression entry: {}",
+            service_err
+        )));
+    }
+
+    tracing::info!(tenant_id = %tenant_id, entry = %entry, "Suppression entry removed");
+    response::format_response(200, json!({ "message": "Suppression entry removed" }))
+}
+
+/// GET /suppressions/export — the whole list (or the filtered part of it) as CSV.
+pub async fn export_suppressions(event: Request) -> Result<Response<Body>, Error> {
+    match handle_export_suppressions(event).await {
+        Ok(response) => Ok(response),
+        Err(e) => Ok(response::format_error_response(&e)),
+    }
+}
+
+async fn handle_export_suppressions(event: Request) -> Result<Response<Body>, AppError> {
+    let tenant_id = require_tenant(&event)?;
+    let params = event.query_string_parameters();
+    let query = parse_list_query(params.iter())?;
+
+    let (entries, _) = query_entries(&tenant_id, &query, None, None).await?;
+    response::format_csv_response(200, entries_to_csv(&entries), EXPORT_FILENAME)
+}
+
+// ── Persistence ────────────────────────────────────────────────────────
+
+fn require_tenant(event: &Request) -> Result<String, AppError> {
+    let user_context = auth::get_user_context(event)?;
+    user_context
+        .tenant_id
+        .ok_or_else(|| AppError::Forbidden("Tenant access required".to_string()))
+}
+
+fn table_name() -> Result<String, AppError> {
+    env::var("TABLE_NAME").map_err(|_| AppError::InternalError("TABLE_NAME not set".to_string()))
+}
+
+/// Entries matching the query, in entry order. With a `limit`, reads until
+/// that many match and returns the sort key to resume after the last one (or
+/// `None` at the end of the list); without one, reads them all.
+async fn query_entries(
+    tenant_id: &str,
+    query: &SuppressionListQuery,
+    limit: Option<usize>,
+    mut start_key: Option<HashMap<String, AttributeValue>>,
+) -> Result<
+    (
+        Vec<SuppressionEntry>,
+        Option<HashMap<String, AttributeValue>>,
+    ),
+    AppError,
+> {
+    let client = aws_clients::get_dynamodb_client().await;
+    let table_name = table_name()?;
+    let mut entries = Vec::new();
+
+    loop {
+        let mut request = client
+            .query()
+            .table_name(&table_name)
+            .key_condition_expression("pk = :pk AND begins_with(sk, :prefix)")
+            .expression_attribute_values(":pk", AttributeValue::S(tenant_id.to_string()))
+            .expression_attribute_values(
+                ":prefix",
+                AttributeValue::S(format!("{}{}", SUPPRESSION_SK_PREFIX, query.prefix)),
+            )
+            .limit(QUERY_PAGE_SIZE)
+            .set_exclusive_start_key(start_key.take());
+
+        let mut filters = Vec::new();
+        if let Some(reason) = query.reason {
+            filters.push("#reason = :reason");
+            request = request
+                .expression_attribute_names("#reason", "reason")
+                .expression_attribute_values(
+                    ":reason",
+                    AttributeValue::S(reason.as_str().to_string()),
+                );
+        }
+        if let Some(kind) = query.kind {
+            filters.push("#type = :type");
+            request = request
+                .expression_attribute_names("#type", "type")
+                .expression_attribute_values(":type", AttributeValue::S(kind.as_str().to_string()));
+        }
+        if !filters.is_empty() {
+            request = request.filter_expression(filters.join(" AND "));
+        }
+
+        let result = request.send().await.map_err(|e| {
+            AppError::AwsError(format!(
+                "Failed to query suppression list: {}",
+                DisplayErrorContext(&e)
+            ))
+        })?;
+
+        for item in result.items.unwrap_or_default() {
+            let sk = item.get("sk").cloned();
+            entries.push(suppression::entry_from_item(item)?);
+            if limit == Some(entries.len()) {
+                let last_key = sk.map(|sk| HashMap::from([("sk".to_string(), sk)]));
+                return Ok((entries, last_key));
+            }
+        }
+
+        match result.last_evaluated_key {
+            Some(key) if !key.is_empty() => start_key = Some(key),
+            _ => return Ok((entries, None)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(value: &str, kind: SuppressionKind, note: Option<&str>) -> SuppressionEntry {
+        SuppressionEntry {
+            entry: value.to_string(),
+            kind,
+            reason: SuppressionReason::Legal,
+            source: "api".to_string(),
+            note: note.map(str::to_string),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            created_by: Some("owner@example.com".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_list_query_reads_filters_and_rejects_unknown_values() {
+        let query = parse_list_query([
+            ("search", " Reader@ "),
+            ("reason", "hard-bounce"),
+            ("type", "email"),
+            ("limit", "20"),
+        ])
+        .unwrap();
+        assert_eq!(
+            query,
+            SuppressionListQuery {
+                prefix: "reader@".to_string(),
+                reason: Some(SuppressionReason::HardBounce),
+                kind: Some(SuppressionKind::Email),
+                limit: 20,
+                next_token: None,
+            }
+        );
+        assert_eq!(
+            parse_list_query(std::iter::empty()).unwrap().limit,
+            DEFAULT_LIST_LIMIT
+        );
+
+        assert!(parse_list_query([("reason", "unsubscribed")]).is_err());
+        assert!(parse_list_query([("type", "ip")]).is_err());
+        assert!(parse_list_query([("limit", "0")]).is_err());
+        assert!(parse_list_query([("limit", "201")]).is_err());
+    }
+
+    #[test]
+    fn test_resume_key_keeps_the_caller_on_their_own_partition() {
+        let token = encode_pagination_token(&HashMap::from([
+            (
+                "pk".to_string(),
+                AttributeValue::S("other-tenant".to_string()),
+            ),
+            (
+                "sk".to_string(),
+                AttributeValue::S("suppression#reader@example.com".to_string()),
+            ),
+        ]));
+        let key = resume_key("t1", &token).unwrap();
+        assert_eq!(key.get("pk"), Some(&AttributeValue::S("t1".to_string())));
+        assert_eq!(
+            key.get("sk"),
+            Some(&AttributeValue::S(
+                "suppression#reader@example.com".to_string()
+            ))
+        );
+
+        let foreign = encode_pagination_token(&HashMap::from([(
+            "sk".to_string(),
+            AttributeValue::S("tenant".to_string()),
+        )]));
+        assert!(resume_key("t1", &foreign).is_err());
+        assert!(resume_key("t1", "not-base64!").is_err());
+    }
+
+    #[test]
+    fn test_plan_bulk_entries_dedupes_and_reports_invalid_values() {
+        let raw = [
+            "Reader@Example.com",
+            "example.org",
+            "reader@example.com",
+            "@example.org",
+            "not an entry",
+        ]
+        .map(str::to_string);
+
+        let (valid, invalid) = plan_bulk_entries(&raw);
+
+        assert_eq!(
+            valid,
+            vec![
+                ("reader@example.com".to_string(), SuppressionKind::Email),
+                ("example.org".to_string(), SuppressionKind::Domain),
+            ]
+        );
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].entry, "not an entry");
+    }
+
+    #[test]
+    fn test_clean_note_trims_and_limits_length() {
+        assert_eq!(clean_note(Some("  ".to_string())).unwrap(), None);
+        assert_eq!(
+            clean_note(Some(" court order ".to_string())).unwrap(),
+            Some("court order".to_string())
+        );
+        assert!(clean_note(Some("x".repeat(NOTE_MAX_LEN + 1))).is_err());
+    }
+
+    #[test]
+    fn test_entries_to_csv_quotes_notes() {
+        let csv = entries_to_csv(&[
+            entry(
+                "example.com",
+                SuppressionKind::Domain,
+                Some("per ticket, 42"),
+            ),
+            entry("reader@example.com", SuppressionKind::Email, None),
+        ]);
+
+        assert_eq!(
+            csv,
+            "entry,type,reason,source,note,createdAt,createdBy\n\
+             example.com,domain,legal,api,\"per ticket, 42\",2026-01-01T00:00:00Z,owner@example.com\n\
+             reader@example.com,email,legal,api,,2026-01-01T00:00:00Z,owner@example.com\n"
+        );
+    }
+}
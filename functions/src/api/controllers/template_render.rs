@@ -54,6 +54,22 @@ fn referenced_partials(source: &str) -> HashSet<String> {
         .collect()
 }
 
+/// Partials referenced by the template (or by a snippet, so nested references
+/// count too) that no snippet backs. [`render_template`] renders each of them as
+/// an empty string; the issue preflight report lists them. Sorted by name.
+pub fn unresolved_partials(content: &str, snippets: &[Snippet]) -> Vec<String> {
+    let registered: HashSet<&str> = snippets.iter().map(|s| s.name.as_str()).collect();
+    let mut missing: Vec<String> = std::iter::once(content)
+        .chain(snippets.iter().map(|s| s.content.as_str()))
+        .flat_map(referenced_partials)
+        .filter(|name| !registered.contains(name.as_str()))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    missing.sort();
+    missing
+}
+
 /// Render `content` against `data`, registering `snippets` as partials and
 /// treating any referenced-but-missing partial as an empty string.
 ///
@@ -71,7 +87,6 @@ pub fn render_template(
     hb.register_escape_fn(handlebars::no_escape);
 
     // Register each snippet as a named partial.
-    let mut registered: HashSet<String> = HashSet::new();
     for snippet in snippets {
         hb.register_partial(&snippet.name, &snippet.content)
             .map_err(|e| {
@@ -80,29 +95,18 @@ pub fn render_template(
                     snippet.name, e
                 ))
             })?;
-        registered.insert(snippet.name.clone());
     }
 
-    // Any partial referenced by the template (or by a snippet) that is not
-    // registered renders as an empty string. Scan both the template and the
-    // snippet contents so nested references degrade gracefully too.
-    let mut sources_to_scan: Vec<&str> = vec![content];
-    for snippet in snippets {
-        sources_to_scan.push(&snippet.content);
-    }
-    for source in sources_to_scan {
-        for name in referenced_partials(source) {
-            if !registered.contains(&name) {
-                tracing::warn!(
-                    partial = %name,
-                    "Referenced partial is not a registered snippet; rendering as empty string"
-                );
-                hb.register_partial(&name, "").map_err(|e| {
-                    AppError::InternalError(format!("Failed to register empty partial: {}", e))
-                })?;
-                registered.insert(name);
-            }
-        }
+    // Any referenced partial that is not a registered snippet renders as an
+    // empty string, including references nested inside snippets.
+    for name in unresolved_partials(content, snippets) {
+        tracing::warn!(
+            partial = %name,
+            "Referenced partial is not a registered snippet; rendering as empty string"
+        );
+        hb.register_partial(&name, "").map_err(|e| {
+            AppError::InternalError(format!("Failed to register empty partial: {}", e))
+        })?;
     }
 
     hb.render_template(content, data)
@@ -175,6 +179,16 @@ mod tests {
         assert_eq!(out, "<div></div>");
     }
 
+    #[test]
+    fn test_unresolved_partials_include_nested_references() {
+        let snippets = vec![snip("layout", "<div>{{> innerMissing }}</div>")];
+        assert_eq!(
+            unresolved_partials("{{> layout }}{{> footer }}{{> footer }}", &snippets),
+            vec!["footer", "innerMissing"]
+        );
+        assert!(unresolved_partials("{{> layout }}", &[snip("layout", "<div></div>")]).is_empty());
+    }
+
     #[test]
     fn test_each_block_renders() {
         let out = render_template(
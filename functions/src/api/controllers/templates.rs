@@ -685,6 +685,16 @@ async fn get_template_record(
     }
 }
 
+/// A saved template's Handlebars source, for renderers outside this module.
+pub(crate) async fn get_template_content(
+    tenant_id: &str,
+    template_id: &str,
+) -> Result<Option<String>, AppError> {
+    Ok(get_template_record(tenant_id, template_id)
+        .await?
+        .map(|record| record.content))
+}
+
 /// Returns true if a template with the given (case-insensitive) name already
 /// exists for the tenant, optionally excluding a specific template id.
 async fn template_name_exists(
@@ -2,8 +2,10 @@ use lambda_http::{http::Method, Body, Error, Request, Response};
 use serde_json::json;
 
 use crate::controllers::{
-    api_keys, brand, churn, domain, issues, pricing, profile, reports, segments, senders, snippets,
-    sponsors, subscribers, templates,
+    alerts, analytics_rebuild, api_keys, approvals, brand, churn, domain, engagement_heatmap,
+    issue_deliverability, issue_links, issue_preflight, issue_search, issues, pricing, profile,
+    recurring_schedules, reports, segments, senders, snippets, sponsors, subscriber_dsar,
+    subscriber_fields, subscriber_import, subscriber_tags, subscribers, suppressions, templates,
 };
 
 pub async fn route_request(event: Request) -> Result<Response<Body>, Error> {
@@ -83,11 +85,35 @@ pub async fn route_request(event: Request) -> Result<Response<Body>, Error> {
         // Issues endpoints
         (&Method::GET, "/issues") => issues::list_issues(event).await,
         (&Method::GET, "/issues/trends") => issues::get_trends(event).await,
+        (&Method::GET, "/issues/trends/revenue") => issues::get_revenue_trends(event).await,
+        (&Method::GET, "/issues/search") => issue_search::search_issues(event).await,
+        (&Method::GET, "/issues/compare") => issues::compare_issues(event).await,
+        (&Method::GET, "/issues/deliverability/trends") => {
+            issue_deliverability::get_deliverability_trends(event).await
+        }
         (&Method::GET, "/ab-test/history") => issues::get_ab_history(event).await,
         (&Method::GET, "/ab-test/active") => issues::get_active_ab_tests(event).await,
         (&Method::POST, "/ab-test/suggestions") => issues::suggest_ab_test(event).await,
+        (&Method::POST, "/ab-test/plan") => issues::plan_ab_test(event).await,
         (&Method::POST, "/issues/review") => issues::review_issue(event).await,
         (&Method::POST, "/issues") => issues::create_issue(event).await,
+        (&Method::POST, "/issues/analytics/rebuild") => {
+            analytics_rebuild::start_rebuild(event).await
+        }
+        (&Method::POST, path)
+            if path.starts_with("/issues/analytics/rebuild/") && path.ends_with("/resume") =>
+        {
+            let job_id = path
+                .strip_prefix("/issues/analytics/rebuild/")
+                .and_then(|value| value.strip_suffix("/resume"))
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_string());
+            analytics_rebuild::resume_rebuild(event, job_id).await
+        }
+        (&Method::GET, path) if path.starts_with("/issues/analytics/rebuild/") => {
+            let job_id = extract_path_param(path, "/issues/analytics/rebuild/");
+            analytics_rebuild::get_rebuild_job(event, job_id).await
+        }
         (&Method::POST, path)
             if path.starts_with("/issues/") && path.ends_with("/analytics/rebuild") =>
         {
@@ -97,6 +123,22 @@ pub async fn route_request(event: Request) -> Result<Response<Body>, Error> {
                 .map(|value| value.to_string());
             issues::rebuild_issue_analytics(event, issue_id).await
         }
+        (&Method::POST, path) if path.starts_with("/issues/") && path.ends_with("/duplicate") => {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/duplicate"))
+                .map(|value| value.to_string());
+            issues::duplicate_issue(event, issue_id).await
+        }
+        (&Method::POST, path)
+            if path.starts_with("/issues/") && path.ends_with("/audience-estimate") =>
+        {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/audience-estimate"))
+                .map(|value| value.to_string());
+            issues::refresh_audience_estimate(event, issue_id).await
+        }
         (&Method::POST, path) if path.starts_with("/issues/") && path.ends_with("/resend") => {
             let issue_id = path
                 .strip_prefix("/issues/")
@@ -104,6 +146,73 @@ pub async fn route_request(event: Request) -> Result<Response<Body>, Error> {
                 .map(|value| value.to_string());
             issues::resend_issue(event, issue_id).await
         }
+        (&Method::POST, path) if path.starts_with("/issues/") && path.ends_with("/cancel") => {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/cancel"))
+                .map(|value| value.to_string());
+            issues::cancel_issue_schedule(event, issue_id).await
+        }
+        (&Method::POST, path) if path.starts_with("/issues/") && path.ends_with("/reschedule") => {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/reschedule"))
+                .map(|value| value.to_string());
+            issues::reschedule_issue(event, issue_id).await
+        }
+        (&Method::POST, path) if path.starts_with("/issues/") && path.ends_with("/preflight") => {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/preflight"))
+                .map(|value| value.to_string());
+            issue_preflight::preflight_issue(event, issue_id).await
+        }
+        (&Method::POST, path)
+            if path.starts_with("/issues/") && path.ends_with("/submit-for-review") =>
+        {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/submit-for-review"))
+                .map(|value| value.to_string());
+            approvals::submit_issue_for_review(event, issue_id).await
+        }
+        (&Method::POST, path) if path.starts_with("/issues/") && path.ends_with("/approve") => {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/approve"))
+                .map(|value| value.to_string());
+            approvals::approve_issue(event, issue_id).await
+        }
+        (&Method::POST, path) if path.starts_with("/issues/") && path.ends_with("/reject") => {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/reject"))
+                .map(|value| value.to_string());
+            approvals::reject_issue(event, issue_id).await
+        }
+        (&Method::GET, path) if path.starts_with("/issues/") && path.ends_with("/links") => {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/links"))
+                .map(|value| value.to_string());
+            issue_links::list_issue_links(event, issue_id).await
+        }
+        (&Method::GET, path)
+            if path.starts_with("/issues/") && path.ends_with("/deliverability") =>
+        {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/deliverability"))
+                .map(|value| value.to_string());
+            issue_deliverability::get_issue_deliverability(event, issue_id).await
+        }
+        (&Method::GET, path) if path.starts_with("/issues/") && path.ends_with("/approvals") => {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/approvals"))
+                .map(|value| value.to_string());
+            approvals::list_issue_approvals(event, issue_id).await
+        }
         (&Method::POST, path)
             if path.starts_with("/issues/") && path.ends_with("/ab-test/declare-winner") =>
         {
@@ -113,6 +222,44 @@ pub async fn route_request(event: Request) -> Result<Response<Body>, Error> {
                 .map(|value| value.to_string());
             issues::declare_ab_winner(event, issue_id).await
         }
+        // Restore revision: POST /issues/:id/revisions/:revisionId/restore
+        (&Method::POST, path)
+            if path.starts_with("/issues/")
+                && path.contains("/revisions/")
+                && path.ends_with("/restore") =>
+        {
+            match extract_issue_and_revision_id(path) {
+                Some((issue_id, revision_id)) => {
+                    issues::restore_issue_revision(event, Some(issue_id), Some(revision_id)).await
+                }
+                None => Ok(format_not_found()),
+            }
+        }
+        (&Method::GET, path) if path.starts_with("/issues/") && path.ends_with("/revisions") => {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/revisions"))
+                .map(|value| value.to_string());
+            issues::list_issue_revisions(event, issue_id).await
+        }
+        (&Method::GET, path)
+            if path.starts_with("/issues/") && path.ends_with("/revisions/diff") =>
+        {
+            let issue_id = path
+                .strip_prefix("/issues/")
+                .and_then(|value| value.strip_suffix("/revisions/diff"))
+                .map(|value| value.to_string());
+            issues::diff_issue_revisions(event, issue_id).await
+        }
+        // Get revision: GET /issues/:id/revisions/:revisionId
+        (&Method::GET, path) if path.starts_with("/issues/") && path.contains("/revisions/") => {
+            match extract_issue_and_revision_id(path) {
+                Some((issue_id, revision_id)) => {
+                    issues::get_issue_revision(event, Some(issue_id), Some(revision_id)).await
+                }
+                None => Ok(format_not_found()),
+            }
+        }
         (&Method::GET, path) if path.starts_with("/issues/") => {
             let issue_id = extract_path_param(path, "/issues/");
             issues::get_issue(event, issue_id).await
@@ -126,6 +273,51 @@ pub async fn route_request(event: Request) -> Result<Response<Body>, Error> {
             issues::delete_issue(event, issue_id).await
         }
 
+        // Alerts endpoints
+        (&Method::GET, "/alerts") => alerts::list_alerts(event).await,
+        (&Method::GET, "/alerts/settings") => alerts::get_alert_settings(event).await,
+        (&Method::PUT, "/alerts/settings") => alerts::update_alert_settings(event).await,
+        (&Method::POST, path) if path.starts_with("/alerts/") && path.ends_with("/acknowledge") => {
+            let alert_id = path
+                .strip_prefix("/alerts/")
+                .and_then(|value| value.strip_suffix("/acknowledge"))
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_string());
+            alerts::acknowledge_alert(event, alert_id).await
+        }
+        (&Method::POST, path) if path.starts_with("/alerts/") && path.ends_with("/resolve") => {
+            let alert_id = path
+                .strip_prefix("/alerts/")
+                .and_then(|value| value.strip_suffix("/resolve"))
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_string());
+            alerts::resolve_alert(event, alert_id).await
+        }
+
+        // Approval policy endpoints
+        (&Method::GET, "/approval-policy") => approvals::get_approval_policy(event).await,
+        (&Method::PUT, "/approval-policy") => approvals::update_approval_policy(event).await,
+
+        // Recurring schedules endpoints
+        (&Method::GET, "/recurring-schedules") => {
+            recurring_schedules::list_recurring_schedules(event).await
+        }
+        (&Method::POST, "/recurring-schedules") => {
+            recurring_schedules::create_recurring_schedule(event).await
+        }
+        (&Method::GET, path) if path.starts_with("/recurring-schedules/") => {
+            let schedule_id = extract_path_param(path, "/recurring-schedules/");
+            recurring_schedules::get_recurring_schedule(event, schedule_id).await
+        }
+        (&Method::PUT, path) if path.starts_with("/recurring-schedules/") => {
+            let schedule_id = extract_path_param(path, "/recurring-schedules/");
+            recurring_schedules::update_recurring_schedule(event, schedule_id).await
+        }
+        (&Method::DELETE, path) if path.starts_with("/recurring-schedules/") => {
+            let schedule_id = extract_path_param(path, "/recurring-schedules/");
+            recurring_schedules::delete_recurring_schedule(event, schedule_id).await
+        }
+
         // Reports endpoints
         (&Method::GET, "/reports") => reports::list_reports(event).await,
         (&Method::GET, path) if path.starts_with("/reports/") => {
@@ -148,8 +340,49 @@ pub async fn route_request(event: Request) -> Result<Response<Body>, Error> {
         // Subscribers endpoints
         (&Method::GET, "/subscribers/count") => subscribers::get_subscriber_count(event).await,
         (&Method::GET, "/subscribers/trends") => subscribers::get_subscriber_trends(event).await,
+        (&Method::GET, "/subscribers/cohorts") => subscribers::get_subscriber_cohorts(event).await,
+        (&Method::GET, "/subscribers/engagement-heatmap") => {
+            engagement_heatmap::get_engagement_heatmap(event).await
+        }
         (&Method::GET, "/subscribers") => subscribers::list_subscribers(event).await,
+        (&Method::POST, "/subscribers") => subscribers::create_subscriber(event).await,
+        // Import routes come before the /subscribers/{email} prefix routes.
+        (&Method::POST, "/subscribers/import") => subscriber_import::create_import(event).await,
+        (&Method::POST, path)
+            if path.starts_with("/subscribers/import/") && path.ends_with("/start") =>
+        {
+            let job_id = path
+                .strip_prefix("/subscribers/import/")
+                .and_then(|value| value.strip_suffix("/start"))
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_string());
+            subscriber_import::start_import(event, job_id).await
+        }
+        (&Method::GET, path) if path.starts_with("/subscribers/import/") => {
+            let job_id = extract_path_param(path, "/subscribers/import/");
+            subscriber_import::get_import(event, job_id).await
+        }
         (&Method::GET, "/subscribers/health") => subscribers::get_audience_health(event).await,
+        // Data subject request routes, also ahead of the /subscribers/{email} routes.
+        (&Method::GET, path) if path.starts_with("/subscribers/dsar/") => {
+            let job_id = extract_path_param(path, "/subscribers/dsar/");
+            subscriber_dsar::get_request(event, job_id).await
+        }
+        (&Method::POST, path) if path.starts_with("/subscribers/") && path.ends_with("/dsar") => {
+            let email = extract_subscriber_email_before(path, "/dsar");
+            subscriber_dsar::create_request(event, email).await
+        }
+        // Tag routes come before the /subscribers/{email} prefix routes too.
+        (&Method::GET, "/subscribers/tags") => subscriber_tags::get_tag_counts(event).await,
+        (&Method::POST, "/subscribers/tags/bulk") => subscriber_tags::bulk_update_tags(event).await,
+        (&Method::POST, path) if path.starts_with("/subscribers/") && path.ends_with("/tags") => {
+            let email = extract_subscriber_email_before(path, "/tags");
+            subscriber_tags::add_tags(event, email).await
+        }
+        (&Method::DELETE, path) if path.starts_with("/subscribers/") && path.ends_with("/tags") => {
+            let email = extract_subscriber_email_before(path, "/tags");
+            subscriber_tags::remove_tags(event, email).await
+        }
         // NOTE: the exact at-risk match must come before the generic
         // /subscribers/{email} prefix route, or "at-risk" is parsed as an email.
         (&Method::GET, "/subscribers/at-risk") => churn::get_at_risk_subscribers(event).await,
@@ -157,11 +390,37 @@ pub async fn route_request(event: Request) -> Result<Response<Body>, Error> {
             let email = extract_path_param(path, "/subscribers/");
             subscribers::get_subscriber(event, email).await
         }
+        (&Method::PUT, path) if path.starts_with("/subscribers/") => {
+            let email = extract_path_param(path, "/subscribers/");
+            subscribers::update_subscriber(event, email).await
+        }
         (&Method::DELETE, path) if path.starts_with("/subscribers/") => {
             let email = extract_path_param(path, "/subscribers/");
             subscribers::delete_subscriber(event, email).await
         }
 
+        // Custom subscriber field endpoints
+        (&Method::GET, "/subscriber-fields") => subscriber_fields::list_fields(event).await,
+        (&Method::POST, "/subscriber-fields") => subscriber_fields::create_field(event).await,
+        (&Method::PUT, path) if path.starts_with("/subscriber-fields/") => {
+            let key = extract_path_param(path, "/subscriber-fields/");
+            subscriber_fields::update_field(event, key).await
+        }
+        (&Method::DELETE, path) if path.starts_with("/subscriber-fields/") => {
+            let key = extract_path_param(path, "/subscriber-fields/");
+            subscriber_fields::delete_field(event, key).await
+        }
+
+        // Suppression list endpoints
+        (&Method::GET, "/suppressions") => suppressions::list_suppressions(event).await,
+        (&Method::POST, "/suppressions") => suppressions::create_suppression(event).await,
+        (&Method::GET, "/suppressions/export") => suppressions::export_suppressions(event).await,
+        (&Method::POST, "/suppressions/bulk") => suppressions::bulk_add_suppressions(event).await,
+        (&Method::DELETE, path) if path.starts_with("/suppressions/") => {
+            let entry = extract_path_param(path, "/suppressions/");
+            suppressions::delete_suppression(event, entry).await
+        }
+
         // Segments endpoints
         (&Method::POST, "/segments") => segments::create_segment(event).await,
         (&Method::GET, "/segments") => segments::list_segments(event).await,
@@ -399,10 +658,24 @@ fn is_valid_api_path(path: &str) -> bool {
         // Issues paths
         || path == "/issues"
         || path == "/issues/trends"
+        || path == "/issues/trends/revenue"
+        || path == "/issues/search"
+        || path == "/issues/compare"
+        || path == "/issues/analytics/rebuild"
+        || path == "/issues/deliverability/trends"
         || path == "/ab-test/history"
         || path == "/ab-test/active"
         || path == "/ab-test/suggestions"
+        || path == "/ab-test/plan"
         || path.starts_with("/issues/")
+        // Alerts paths
+        || path == "/alerts"
+        || path.starts_with("/alerts/")
+        // Approval policy paths
+        || path == "/approval-policy"
+        // Recurring schedules paths
+        || path == "/recurring-schedules"
+        || path.starts_with("/recurring-schedules/")
         // Reports paths
         || path == "/reports"
         || path.starts_with("/reports/")
@@ -417,8 +690,16 @@ fn is_valid_api_path(path: &str) -> bool {
         || path == "/subscribers"
         || path == "/subscribers/count"
         || path == "/subscribers/trends"
+        || path == "/subscribers/cohorts"
+        || path == "/subscribers/engagement-heatmap"
         || path == "/subscribers/health"
         || path.starts_with("/subscribers/")
+        // Subscriber fields paths
+        || path == "/subscriber-fields"
+        || path.starts_with("/subscriber-fields/")
+        // Suppression list paths
+        || path == "/suppressions"
+        || path.starts_with("/suppressions/")
         // Segments paths
         || path == "/segments"
         || path.starts_with("/segments/")
@@ -439,6 +720,13 @@ fn extract_path_param(path: &str, prefix: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+fn extract_subscriber_email_before(path: &str, suffix: &str) -> Option<String> {
+    path.strip_prefix("/subscribers/")
+        .and_then(|s| s.strip_suffix(suffix))
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+}
+
 fn extract_sender_id(path: &str) -> Option<String> {
     path.strip_prefix("/senders/")
         .and_then(|s| s.split('/').next())
@@ -506,6 +794,18 @@ fn extract_sponsor_and_sponsorship_id(path: &str) -> Option<(String, String)> {
     }
 }
 
+/// Extract issue ID and revision ID from paths like `/issues/:id/revisions/:revisionId`.
+fn extract_issue_and_revision_id(path: &str) -> Option<(String, String)> {
+    let rest = path.strip_prefix("/issues/")?;
+    let parts: Vec<&str> = rest.split('/').collect();
+    // parts: [issue_id, "revisions", revision_id, ...]
+    if parts.len() >= 3 && parts[1] == "revisions" && !parts[0].is_empty() && !parts[2].is_empty() {
+        Some((parts[0].to_string(), parts[2].to_string()))
+    } else {
+        None
+    }
+}
+
 /// Extract sponsor ID and job ID from paths like `/sponsors/:id/outreach/jobs/:jobId`.
 fn extract_sponsor_and_outreach_job_id(path: &str) -> Option<(String, String)> {
     let rest = path.strip_prefix("/sponsors/")?;
@@ -555,6 +855,19 @@ mod tests {
         assert_eq!(result, Some("key-abc-123-xyz".to_string()));
     }
 
+    #[test]
+    fn test_extract_subscriber_email_before_tags_suffix() {
+        let result =
+            extract_subscriber_email_before("/subscribers/reader%40example.com/tags", "/tags");
+        assert_eq!(result, Some("reader%40example.com".to_string()));
+    }
+
+    #[test]
+    fn test_extract_subscriber_email_before_tags_empty() {
+        let result = extract_subscriber_email_before("/subscribers//tags", "/tags");
+        assert_eq!(result, None);
+    }
+
     #[test]
     fn test_extract_sender_id_valid() {
         let result = extract_sender_id("/senders/abc-123");
@@ -656,6 +969,13 @@ mod tests {
     fn test_is_valid_api_path_issues() {
         assert!(is_valid_api_path("/issues"));
         assert!(is_valid_api_path("/issues/trends"));
+        assert!(is_valid_api_path("/issues/trends/revenue"));
+        assert!(is_valid_api_path("/issues/search"));
+        assert!(is_valid_api_path("/issues/compare"));
+        assert!(is_valid_api_path("/ab-test/plan"));
+        assert!(is_valid_api_path("/issues/analytics/rebuild"));
+        assert!(is_valid_api_path("/issues/deliverability/trends"));
+        assert!(is_valid_api_path("/issues/analytics/rebuild/01JJOB/resume"));
         assert!(is_valid_api_path("/issues/issue-123"));
         assert!(is_valid_api_path("/issues/tenant-456#789"));
     }
@@ -712,6 +1032,41 @@ mod tests {
         assert!(is_valid_api_path("/issues/tenant-abc#456"));
         assert!(is_valid_api_path("/issues/issue-123/resend"));
         assert!(is_valid_api_path("/issues/issue-123/analytics/rebuild"));
+        assert!(is_valid_api_path("/issues/issue-123/duplicate"));
+        assert!(is_valid_api_path("/issues/issue-123/audience-estimate"));
+        assert!(is_valid_api_path("/issues/issue-123/cancel"));
+        assert!(is_valid_api_path("/issues/issue-123/reschedule"));
+        assert!(is_valid_api_path("/issues/issue-123/preflight"));
+        assert!(is_valid_api_path("/issues/issue-123/links"));
+        assert!(is_valid_api_path("/issues/issue-123/submit-for-review"));
+        assert!(is_valid_api_path("/issues/issue-123/approve"));
+        assert!(is_valid_api_path("/issues/issue-123/reject"));
+        assert!(is_valid_api_path("/issues/issue-123/approvals"));
+        assert!(is_valid_api_path("/approval-policy"));
+        assert!(is_valid_api_path("/issues/issue-123/revisions"));
+        assert!(is_valid_api_path("/issues/issue-123/revisions/diff"));
+    }
+
+    #[test]
+    fn test_is_valid_api_path_alerts() {
+        assert!(is_valid_api_path("/alerts"));
+        assert!(is_valid_api_path("/alerts/settings"));
+        assert!(is_valid_api_path("/alerts/42-complaintRate/acknowledge"));
+        assert!(is_valid_api_path("/alerts/42-complaintRate/resolve"));
+    }
+
+    #[test]
+    fn test_route_matching_recurring_schedules_paths() {
+        assert!(is_valid_api_path("/recurring-schedules"));
+        assert!(is_valid_api_path(
+            "/recurring-schedules/01J9ZQ4Y3R8V6WQ2K5T7N1M0XC"
+        ));
+
+        let result = extract_path_param(
+            "/recurring-schedules/01J9ZQ4Y3R8V6WQ2K5T7N1M0XC",
+            "/recurring-schedules/",
+        );
+        assert_eq!(result, Some("01J9ZQ4Y3R8V6WQ2K5T7N1M0XC".to_string()));
     }
 
     #[test]
@@ -719,8 +1074,23 @@ mod tests {
         assert!(is_valid_api_path("/subscribers"));
         assert!(is_valid_api_path("/subscribers/count"));
         assert!(is_valid_api_path("/subscribers/trends"));
+        assert!(is_valid_api_path("/subscribers/cohorts"));
+        assert!(is_valid_api_path("/subscribers/engagement-heatmap"));
         assert!(is_valid_api_path("/subscribers/health"));
         assert!(is_valid_api_path("/subscribers/at-risk"));
+        assert!(is_valid_api_path("/subscribers/import"));
+        assert!(is_valid_api_path("/subscribers/import/01JJOB"));
+        assert!(is_valid_api_path("/subscribers/import/01JJOB/start"));
+        assert!(is_valid_api_path("/subscribers/tags"));
+        assert!(is_valid_api_path("/subscribers/tags/bulk"));
+        assert!(is_valid_api_path("/subscribers/reader%40example.com/tags"));
+        assert!(is_valid_api_path("/subscribers/reader%40example.com/dsar"));
+        assert!(is_valid_api_path("/subscribers/dsar/01JJOB"));
+        assert!(is_valid_api_path("/subscriber-fields"));
+        assert!(is_valid_api_path("/subscriber-fields/company"));
+        assert!(is_valid_api_path("/suppressions"));
+        assert!(is_valid_api_path("/suppressions/export"));
+        assert!(is_valid_api_path("/suppressions/reader%40example.com"));
     }
 
     #[test]
@@ -1057,6 +1427,28 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_extract_issue_and_revision_id_valid() {
+        let result = extract_issue_and_revision_id("/issues/42/revisions/01HZX3K6Q2");
+        assert_eq!(result, Some(("42".to_string(), "01HZX3K6Q2".to_string())));
+
+        let result = extract_issue_and_revision_id("/issues/42/revisions/01HZX3K6Q2/restore");
+        assert_eq!(result, Some(("42".to_string(), "01HZX3K6Q2".to_string())));
+    }
+
+    #[test]
+    fn test_extract_issue_and_revision_id_invalid() {
+        assert_eq!(extract_issue_and_revision_id("/issues/42/revisions/"), None);
+        assert_eq!(
+            extract_issue_and_revision_id("/issues/42/other/01HZX3K6Q2"),
+            None
+        );
+        assert_eq!(
+            extract_issue_and_revision_id("/issues//revisions/01HZX3K6Q2"),
+            None
+        );
+    }
+
     #[test]
     fn test_extract_sponsor_and_outreach_job_id_valid() {
         let result = extract_sponsor_and_outreach_job_id("/sponsors/sp-123/outreach/jobs/job-789");
@@ -14,6 +14,8 @@ pub struct UserContext {
     pub role: String,
     pub is_admin: bool,
     pub is_tenant_admin: bool,
+    /// IANA zone from the caller's profile (`zoneinfo`), when set.
+    pub time_zone: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -48,6 +50,7 @@ pub fn get_user_context(event: &lambda_http::Request) -> Result<UserContext, App
     let is_tenant_admin = get_optional_string_field(fields, "isTenantAdmin")
         .map(|v| v == "true")
         .unwrap_or(false);
+    let time_zone = get_optional_string_field(fields, "timezone");
 
     if user_id.is_none() || email.is_none() || tenant_id.is_none() {
         if let Some(claims) = fields.get("claims").and_then(|v| v.as_object()) {
@@ -87,6 +90,7 @@ pub fn get_user_context(event: &lambda_http::Request) -> Result<UserContext, App
         role,
         is_admin,
         is_tenant_admin,
+        time_zone,
     })
 }
 
@@ -302,6 +306,19 @@ mod tests {
         assert_eq!(result.role, "user");
         assert!(!result.is_admin);
         assert!(result.is_tenant_admin);
+        assert_eq!(result.time_zone, None);
+    }
+
+    #[test]
+    fn test_authorizer_timezone_parsing() {
+        let mut fields = HashMap::new();
+        fields.insert("userId".to_string(), json!("user-123"));
+        fields.insert("email".to_string(), json!("test@example.com"));
+        fields.insert("timezone".to_string(), json!("Europe/Berlin"));
+        let req = build_request_with_authorizer_fields(fields);
+
+        let result = get_user_context(&req).expect("context");
+        assert_eq!(result.time_zone.as_deref(), Some("Europe/Berlin"));
     }
 
     #[test]
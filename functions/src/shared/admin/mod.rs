@@ -3,6 +3,7 @@ pub mod aws_clients;
 pub mod dynamodb_utils;
 pub mod error;
 pub mod response;
+pub mod suppression;
 
 pub use auth::{decode_api_key, get_user_context, hash_api_key, DecodedApiKey, UserContext};
 pub use error::AppError;
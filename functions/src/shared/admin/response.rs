@@ -38,6 +38,24 @@ pub fn format_response<T: Serialize>(
         .map_err(|e| AppError::InternalError(format!("Response building failed: {}", e)))
 }
 
+/// A `text/csv` download; `filename` is offered to the browser as the
+/// attachment name.
+pub fn format_csv_response(
+    status_code: u16,
+    csv: String,
+    filename: &str,
+) -> Result<Response<Body>, AppError> {
+    add_cors_headers(Response::builder())
+        .status(status_code)
+        .header("Content-Type", "text/csv; charset=utf-8")
+        .header(
+            "Content-Disposition",
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(Body::Text(csv))
+        .map_err(|e| AppError::InternalError(format!("Response building failed: {}", e)))
+}
+
 pub fn format_error_response(error: &AppError) -> Response<Body> {
     let status_code = error.status_code();
     let message = match error {
@@ -0,0 +1,288 @@
+//! Per-tenant suppression list: addresses and whole domains that must not be
+//! added as subscribers or sent issues.
+//!
+//! Entries live in the newsletter table at `pk = <tenantId>`,
+//! `sk = suppression#<entry>`, where the entry is a lowercased address or a
+//! bare domain (`example.com`, matching every address at it). The API, the
+//! unsubscribe log processor and the bounce and complaint handlers write them;
+//! subscriber create, import and issue sends read them. Signing up again
+//! through the public form lifts an address's `unsubscribe` entry and is
+//! refused for any other. The rules are mirrored by
+//! functions/utils/suppression.mjs. Keep the two in sync.
+
+use super::error::AppError;
+use crate::senders::validation;
+use aws_sdk_dynamodb::error::DisplayErrorContext;
+use aws_sdk_dynamodb::types::AttributeValue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const SUPPRESSION_SK_PREFIX: &str = "suppression#";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SuppressionReason {
+    HardBounce,
+    Complaint,
+    /// Added by hand, or by the recipient unsubscribing.
+    Manual,
+    Legal,
+}
+
+impl SuppressionReason {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "hard-bounce" => Some(Self::HardBounce),
+            "complaint" => Some(Self::Complaint),
+            "manual" => Some(Self::Manual),
+            "legal" => Some(Self::Legal),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::HardBounce => "hard-bounce",
+            Self::Complaint => "complaint",
+            Self::Manual => "manual",
+            Self::Legal => "legal",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuppressionKind {
+    Email,
+    Domain,
+}
+
+impl SuppressionKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "email" => Some(Self::Email),
+            "domain" => Some(Self::Domain),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Domain => "domain",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuppressionEntry {
+    pub entry: String,
+    #[serde(rename = "type")]
+    pub kind: SuppressionKind,
+    pub reason: SuppressionReason,
+    /// What added it: `api`, `bulk-upload`, `subscriber-delete`,
+    /// `unsubscribe`, `bounce`, `bounce-cleanup` or `complaint`.
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+}
+
+/// Normalises a raw entry: trimmed and lowercased. Anything with an `@` past
+/// the first character is an address and must look like one (and, like a
+/// subscriber key, contain no `#`); anything else is a domain, optionally
+/// written `@example.com`, and needs at least two labels.
+pub fn normalize_entry(raw: &str) -> Result<(String, SuppressionKind), AppError> {
+    let value = raw.trim().to_lowercase();
+    if value.is_empty() {
+        return Err(AppError::BadRequest(
+            "Suppression entry is required".to_string(),
+        ));
+    }
+
+    let domain = value.strip_prefix('@').unwrap_or(&value);
+    if domain.contains('@') {
+        if validation::validate_email(&value).is_err() || value.contains('#') {
+            return Err(AppError::BadRequest(format!(
+                "'{}' is not a valid email address",
+                raw.trim()
+            )));
+        }
+        return Ok((value, SuppressionKind::Email));
+    }
+
+    if !domain.contains('.') || validation::validate_domain(domain).is_err() {
+        return Err(AppError::BadRequest(format!(
+            "'{}' is not a valid email address or domain",
+            raw.trim()
+        )));
+    }
+    Ok((domain.to_string(), SuppressionKind::Domain))
+}
+
+pub fn entry_sk(entry: &str) -> String {
+    format!("{}{}", SUPPRESSION_SK_PREFIX, entry)
+}
+
+/// The domain part of an address.
+pub fn email_domain(email: &str) -> Option<&str> {
+    email
+        .rsplit_once('@')
+        .map(|(_, domain)| domain)
+        .filter(|domain| !domain.is_empty())
+}
+
+pub fn entry_to_item(
+    tenant_id: &str,
+    entry: &SuppressionEntry,
+) -> Result<HashMap<String, AttributeValue>, AppError> {
+    let mut item: HashMap<String, AttributeValue> = serde_dynamo::to_item(entry).map_err(|e| {
+        AppError::InternalError(format!("Failed to serialize suppression entry: {}", e))
+    })?;
+    item.insert("pk".to_string(), AttributeValue::S(tenant_id.to_string()));
+    item.insert("sk".to_string(), AttributeValue::S(entry_sk(&entry.entry)));
+    Ok(item)
+}
+
+pub fn entry_from_item(
+    item: HashMap<String, AttributeValue>,
+) -> Result<SuppressionEntry, AppError> {
+    serde_dynamo::from_item(item).map_err(|e| {
+        AppError::InternalError(format!("Failed to deserialize suppression entry: {}", e))
+    })
+}
+
+/// The entry suppressing `email`, if any: the address's own entry, else its
+/// domain's.
+pub async fn find_entry(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    tenant_id: &str,
+    email: &str,
+) -> Result<Option<SuppressionEntry>, AppError> {
+    let email = email.trim().to_lowercase();
+    let candidates = std::iter::once(email.as_str()).chain(email_domain(&email));
+
+    for candidate in candidates {
+        let result = client
+            .get_item()
+            .table_name(table_name)
+            .key("pk", AttributeValue::S(tenant_id.to_string()))
+            .key("sk", AttributeValue::S(entry_sk(candidate)))
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::AwsError(format!(
+                    "Failed to read suppression list: {}",
+                    DisplayErrorContext(&e)
+                ))
+            })?;
+        if let Some(item) = result.item {
+            return entry_from_item(item).map(Some);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Adds an entry unless the list already has one for it. Returns `false` in
+/// that case and leaves the existing entry, and its reason, as it was.
+pub async fn add_entry(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    tenant_id: &str,
+    entry: &SuppressionEntry,
+) -> Result<bool, AppError> {
+    let result = client
+        .put_item()
+        .table_name(table_name)
+        .set_item(Some(entry_to_item(tenant_id, entry)?))
+        .condition_expression("attribute_not_exists(sk)")
+        .send()
+        .await;
+
+    match result {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            let service_err = e.into_service_error();
+            if service_err.is_conditional_check_failed_exception() {
+                Ok(false)
+            } else {
+                Err(AppError::AwsError(format!(
+                    "Failed to add suppression entry: {}",
+                    service_err
+                )))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_entry_tells_addresses_from_domains() {
+        assert_eq!(
+            normalize_entry("  Reader@Example.COM ").unwrap(),
+            ("reader@example.com".to_string(), SuppressionKind::Email)
+        );
+        assert_eq!(
+            normalize_entry("Example.com").unwrap(),
+            ("example.com".to_string(), SuppressionKind::Domain)
+        );
+        assert_eq!(
+            normalize_entry("@mail.example.com").unwrap(),
+            ("mail.example.com".to_string(), SuppressionKind::Domain)
+        );
+    }
+
+    #[test]
+    fn test_normalize_entry_rejects_malformed_values() {
+        for raw in [
+            "",
+            "   ",
+            "localhost",
+            "reader@",
+            "a#b@example.com",
+            "reader@@example.com",
+            "https://example.com",
+            "exa mple.com",
+        ] {
+            assert!(normalize_entry(raw).is_err(), "{raw:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_entry_round_trips_through_an_item() {
+        let entry = SuppressionEntry {
+            entry: "example.com".to_string(),
+            kind: SuppressionKind::Domain,
+            reason: SuppressionReason::HardBounce,
+            source: "bounce".to_string(),
+            note: None,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            created_by: None,
+        };
+
+        let item = entry_to_item("t1", &entry).unwrap();
+        assert_eq!(
+            item.get("sk")
+                .and_then(|v| v.as_s().ok())
+                .map(String::as_str),
+            Some("suppression#example.com")
+        );
+        assert_eq!(
+            item.get("reason")
+                .and_then(|v| v.as_s().ok())
+                .map(String::as_str),
+            Some("hard-bounce")
+        );
+        assert_eq!(entry_from_item(item).unwrap(), entry);
+        assert_eq!(email_domain("reader@example.com"), Some("example.com"));
+        assert_eq!(email_domain("reader@"), None);
+    }
+}
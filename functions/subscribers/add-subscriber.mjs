@@ -21,6 +21,7 @@ import {
 import { checkRateLimit } from '../utils/rate-limiter.mjs';
 import { createLogger } from '../utils/structured-logger.mjs';
 import { getMostRecentPublishedIssue, incrementIssueCounter } from '../utils/issue-attribution.mjs';
+import { clearOptOutEntry, findSuppressionEntry, isClearedByOptIn } from '../utils/suppression.mjs';
 
 const ddb = new DynamoDBClient();
 
@@ -148,7 +149,16 @@ export const handler = async (event) => {
       return formatResponse(201, 'Contact added');
     }
 
-    // Step 9: Attempt to create subscriber (duplicate check via ConditionExpression)
+    // Step 9: Honour the suppression list. Signing up again is an explicit
+    // opt-in, so it lifts the entry the address's own unsubscribe added; any
+    // other entry blocks the signup rather than adding a subscriber who would
+    // never be sent anything.
+    const suppression = await findSuppressionEntry(tenantId, normalizedEmail);
+    if (suppression && !(isClearedByOptIn(suppression) && await clearOptOutEntry(tenantId, normalizedEmail))) {
+      return formatResponse(409, 'This email address can\'t be subscribed to this newsletter');
+    }
+
+    // Step 10: Attempt to create subscriber (duplicate check via ConditionExpression)
     const isNew = await addSubscriber(tenantId, contact, normalizedEmail, {
       sourceIp,
       userAgent,
@@ -1,6 +1,7 @@
 import { EventBridgeClient, PutEventsCommand } from '@aws-sdk/client-eventbridge';
 import { getTenant } from '../utils/helpers.mjs';
 import { unsubscribeUser } from '../utils/subscriber.mjs';
+import { addSuppressionEntry } from '../utils/suppression.mjs';
 import { getMostRecentPublishedIssue, incrementIssueCounter } from '../utils/issue-attribution.mjs';
 
 const eventBridge = new EventBridgeClient();
@@ -44,6 +45,10 @@ const processComplaintUnsubscribe = async (emailAddress, detail) => {
     return;
   }
 
+  // Suppressed whether or not they were still subscribed, so a later import
+  // or re-signup can't add them back.
+  await addSuppressionEntry(tenantId, emailAddress, { reason: 'complaint', source: 'complaint' });
+
   const metadata = {
     complaintFeedbackType: detail.complaint?.complaintFeedbackType || 'unknown',
     userAgent: detail.complaint?.userAgent || 'ses-complaint'
@@ -3,6 +3,8 @@ import { EventBridgeClient, PutEventsCommand } from "@aws-sdk/client-eventbridge
 import { marshall, unmarshall } from "@aws-sdk/util-dynamodb";
 import { getTenant, sendWithRetry, throttle } from "../utils/helpers.mjs";
 import { getMostRecentPublishedIssue, incrementIssueCounter } from "../utils/issue-attribution.mjs";
+import { recordSubscriberDeparture } from "../utils/subscriber.mjs";
+import { addSuppressionEntry } from "../utils/suppression.mjs";
 
 const ddb = new DynamoDBClient();
 const eventBridge = new EventBridgeClient();
@@ -81,6 +83,8 @@ export const handler = async (event) => {
           removedAddresses.push(emailAddress);
           console.log(`Successfully removed ${emailAddress} from Subscribers table`);
 
+          await recordSubscriberDeparture(tenantId.id, unmarshall(deleteResult.Attributes), 'bounced');
+
           // Increment cleaned counter on the attributed issue
           if (attributionIssue) {
             try {
@@ -92,6 +96,8 @@ export const handler = async (event) => {
         } else {
           console.log(`Skipped ${emailAddress}; address was already absent from Subscribers table`);
         }
+
+        await addSuppressionEntry(tenantId.id, emailAddress, { reason: 'hard-bounce', source: 'bounce-cleanup' });
         return true;
       } catch (error) {
         console.error(`Failed to remove ${emailAddress} from Subscribers table:`, error);
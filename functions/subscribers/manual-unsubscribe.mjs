@@ -1,5 +1,6 @@
 import { EventBridgeClient, PutEventsCommand } from '@aws-sdk/client-eventbridge';
 import { unsubscribeUser } from "../utils/subscriber.mjs";
+import { UNSUBSCRIBE_SOURCE, addSuppressionEntry } from "../utils/suppression.mjs";
 import { getTenant } from "../utils/helpers.mjs";
 import { getMostRecentPublishedIssue, incrementIssueCounter } from "../utils/issue-attribution.mjs";
 
@@ -63,6 +64,10 @@ export const handler = async (event) => {
       userAgent
     };
 
+    // Suppressed whether or not they were still subscribed, so a later import
+    // or admin add can't put them back. Signing up again lifts the entry.
+    await addSuppressionEntry(tenantId, emailAddress, { reason: 'manual', source: UNSUBSCRIBE_SOURCE });
+
     const result = await unsubscribeUser(tenantId, emailAddress, 'manual-form', metadata);
 
     if (result.actuallyRemoved) {
@@ -0,0 +1,502 @@
+import {
+  DeleteItemCommand,
+  DynamoDBClient,
+  GetItemCommand,
+  PutItemCommand,
+  QueryCommand,
+  TransactWriteItemsCommand,
+  UpdateItemCommand
+} from '@aws-sdk/client-dynamodb';
+import { PutObjectCommand, S3Client } from '@aws-sdk/client-s3';
+import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
+import { ulid } from 'ulid';
+import { sendWithRetry } from '../utils/helpers.mjs';
+import {
+  DSAR_RECEIPT_HEAD_SK,
+  EVENT_KINDS,
+  REDACTED_ADDRESS,
+  buildAccessBundle,
+  buildReceipt,
+  failedAddressIndexes,
+  receiptSk,
+  statDecrements,
+  summarizeBundle
+} from '../utils/dsar.mjs';
+import { suppressionSk } from '../utils/suppression.mjs';
+
+const ddb = new DynamoDBClient();
+const s3 = new S3Client();
+
+const JOB_SK_PREFIX = 'subscriber-dsar#';
+const DEPARTURE_SK_PREFIX = 'subscriber-departure#';
+const BUNDLE_KEY_PREFIX = 'subscriber-dsar/';
+const SEGMENT_MEMBER_INDEX = 'SegmentMemberIndex';
+const WRITE_CONCURRENCY = 10;
+// Attempts at appending the receipt when another erasure moved the chain head.
+const RECEIPT_ATTEMPTS = 5;
+
+/**
+ * Works through a data subject request created by
+ * POST /subscribers/:email/dsar. Invoked asynchronously with
+ * { tenantId, jobId }.
+ *
+ * Both modes start by collecting everything stored about the address: the
+ * subscriber record, its segment memberships, every issue's open, click,
+ * bounce and complaint events (matched by email hash), the issues whose
+ * stats list it as undeliverable, and its departure records.
+ *
+ * Access writes that to S3 as a JSON bundle. Erasure deletes the memberships
+ * (before the subscriber, so the membership-cleanup stream finds nothing to
+ * double-count), the subscriber record, the events and unique-open markers;
+ * takes the events back off the issue stats counters and the subscriber off
+ * the tenant count; redacts the address in `failedAddresses`; re-keys the
+ * departure records without the hash, so cohort retention still counts them
+ * but nothing links them to the address; and appends a receipt to the
+ * tenant's hash chain.
+ *
+ * The address is removed from the job record whatever the outcome.
+ */
+export const handler = async (event) => {
+  const { tenantId, jobId } = event || {};
+
+  if (!tenantId || !jobId) {
+    console.error('Missing required parameters', { tenantId, jobId });
+    return { status: 'skipped', reason: 'missing-parameters' };
+  }
+
+  const job = await loadJob(tenantId, jobId);
+  if (!job) {
+    console.warn('Data subject request not found', { tenantId, jobId });
+    return { status: 'skipped', reason: 'job-not-found' };
+  }
+
+  if (!(await claimJob(tenantId, jobId))) {
+    console.log('Data subject request is not pending, skipping', { tenantId, jobId, status: job.status });
+    return { status: 'skipped', reason: 'not-pending' };
+  }
+
+  try {
+    const found = await collect(tenantId, job.email, job.emailHash);
+
+    if (job.mode === 'erasure') {
+      const erased = await erase(tenantId, job.email, found);
+      const receipt = await appendReceipt(tenantId, job, erased);
+      await finishJob(tenantId, jobId, {
+        status: 'completed',
+        summary: erased,
+        receipt: {
+          sequence: receipt.sequence,
+          receiptHash: receipt.receiptHash,
+          previousHash: receipt.previousHash,
+          completedAt: receipt.completedAt
+        }
+      });
+      console.log('Data subject erasure complete', { tenantId, jobId, ...erased });
+      return { status: 'completed', ...erased };
+    }
+
+    const bundle = buildAccessBundle({
+      email: job.email,
+      generatedAt: new Date().toISOString(),
+      subscriber: found.subscriber,
+      segments: found.segments,
+      events: found.events,
+      failedIssuePks: found.failedIssues.map(({ pk }) => pk),
+      departures: found.departures,
+      suppression: found.suppression
+    });
+    const bundleKey = `${BUNDLE_KEY_PREFIX}${tenantId}/${jobId}.json`;
+    await s3.send(new PutObjectCommand({
+      Bucket: process.env.BUCKET,
+      Key: bundleKey,
+      Body: JSON.stringify(bundle, null, 2),
+      ContentType: 'application/json'
+    }));
+
+    const summary = summarizeBundle(bundle);
+    await finishJob(tenantId, jobId, { status: 'completed', summary, bundleKey });
+    console.log('Data subject access bundle written', { tenantId, jobId, ...summary });
+    return { status: 'completed', ...summary };
+  } catch (err) {
+    console.error('Data subject request failed', { tenantId, jobId, mode: job.mode, error: err.message });
+    await finishJob(tenantId, jobId, { status: 'failed', failureReason: 'The request could not be completed' });
+    return { status: 'failed' };
+  }
+};
+
+const loadJob = async (tenantId, jobId) => {
+  const result = await ddb.send(new GetItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: `${JOB_SK_PREFIX}${jobId}` }),
+    ConsistentRead: true
+  }));
+  return result.Item ? unmarshall(result.Item) : null;
+};
+
+const claimJob = async (tenantId, jobId) => {
+  const now = new Date().toISOString();
+  try {
+    await ddb.send(new UpdateItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({ pk: tenantId, sk: `${JOB_SK_PREFIX}${jobId}` }),
+      UpdateExpression: 'SET #status = :running, startedAt = :now, updatedAt = :now',
+      ConditionExpression: '#status = :pending',
+      ExpressionAttributeNames: { '#status': 'status' },
+      ExpressionAttributeValues: marshall({ ':running': 'running', ':pending': 'pending', ':now': now })
+    }));
+    return true;
+  } catch (err) {
+    if (err.name === 'ConditionalCheckFailedException') {
+      return false;
+    }
+    throw err;
+  }
+};
+
+const finishJob = async (tenantId, jobId, { status, summary, bundleKey, receipt, failureReason }) => {
+  const now = new Date().toISOString();
+  const sets = ['#status = :status', 'finishedAt = :now', 'updatedAt = :now'];
+  if (summary) sets.push('summary = :summary');
+  if (bundleKey) sets.push('bundleKey = :bundleKey');
+  if (receipt) sets.push('receipt = :receipt');
+  if (failureReason) sets.push('failureReason = :reason');
+
+  await ddb.send(new UpdateItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: `${JOB_SK_PREFIX}${jobId}` }),
+    UpdateExpression: `SET ${sets.join(', ')} REMOVE email`,
+    ExpressionAttributeNames: { '#status': 'status' },
+    ExpressionAttributeValues: marshall({
+      ':status': status,
+      ':now': now,
+      ...(summary && { ':summary': summary }),
+      ...(bundleKey && { ':bundleKey': bundleKey }),
+      ...(receipt && { ':receipt': receipt }),
+      ...(failureReason && { ':reason': failureReason })
+    })
+  }));
+};
+
+// ── Collection ──────────────────────────────────────────────────────────
+
+const queryAll = async (params, operationName) => {
+  const items = [];
+  let lastKey;
+  do {
+    const result = await sendWithRetry(() => ddb.send(new QueryCommand({
+      ...params,
+      ...(lastKey && { ExclusiveStartKey: lastKey })
+    })), operationName);
+    items.push(...(result.Items || []).map((item) => unmarshall(item)));
+    lastKey = result.LastEvaluatedKey;
+  } while (lastKey);
+  return items;
+};
+
+const collect = async (tenantId, email, emailHash) => {
+  const subscriberResult = await ddb.send(new GetItemCommand({
+    TableName: process.env.SUBSCRIBERS_TABLE_NAME,
+    Key: marshall({ tenantId, email }),
+    ConsistentRead: true
+  }));
+  const subscriber = subscriberResult.Item ? unmarshall(subscriberResult.Item) : null;
+
+  const memberships = await queryAll({
+    TableName: process.env.SUBSCRIBERS_TABLE_NAME,
+    IndexName: SEGMENT_MEMBER_INDEX,
+    KeyConditionExpression: 'memberEmail = :email AND tenantId = :tenantId',
+    ExpressionAttributeValues: marshall({ ':email': email, ':tenantId': tenantId })
+  }, 'QuerySegmentMemberIndex')
+    .then((items) => items.map((item) => ({
+      key: item.email,
+      segmentId: item.segmentId ?? item.email.match(/^SEGMENT#([^#]+)#MEMBER#/)?.[1],
+      addedAt: item.addedAt
+    })));
+  const segments = await Promise.all(memberships.map(async (membership) => {
+    const segment = membership.segmentId ? await getSegment(tenantId, membership.segmentId) : null;
+    return {
+      segmentId: membership.segmentId,
+      name: segment?.name ?? null,
+      ...(membership.addedAt && { addedAt: membership.addedAt })
+    };
+  }));
+
+  const issuePks = await listIssuePks(tenantId);
+  const events = [];
+  const openMarkers = [];
+  const failedIssues = [];
+  for (const pk of issuePks) {
+    for (const prefix of Object.keys(EVENT_KINDS)) {
+      events.push(...await queryAll({
+        TableName: process.env.TABLE_NAME,
+        KeyConditionExpression: 'pk = :pk AND begins_with(sk, :prefix)',
+        FilterExpression: 'subscriberEmailHash = :hash',
+        ExpressionAttributeValues: marshall({ ':pk': pk, ':prefix': prefix, ':hash': emailHash })
+      }, 'QueryIssueEvents'));
+    }
+
+    const [marker, stats] = await Promise.all([
+      ddb.send(new GetItemCommand({
+        TableName: process.env.TABLE_NAME,
+        Key: marshall({ pk, sk: `opens#${email}` }),
+        ProjectionExpression: 'pk, sk'
+      })),
+      ddb.send(new GetItemCommand({
+        TableName: process.env.TABLE_NAME,
+        Key: marshall({ pk, sk: 'stats' }),
+        ProjectionExpression: 'failedAddresses'
+      }))
+    ]);
+    if (marker.Item) {
+      openMarkers.push(unmarshall(marker.Item));
+    }
+    const indexes = failedAddressIndexes(stats.Item ? unmarshall(stats.Item).failedAddresses : [], email);
+    if (indexes.length > 0) {
+      failedIssues.push({ pk, indexes });
+    }
+  }
+
+  const departures = await queryAll({
+    TableName: process.env.TABLE_NAME,
+    KeyConditionExpression: 'pk = :pk AND begins_with(sk, :prefix)',
+    FilterExpression: 'emailHash = :hash',
+    ExpressionAttributeValues: marshall({ ':pk': tenantId, ':prefix': DEPARTURE_SK_PREFIX, ':hash': emailHash })
+  }, 'QueryDepartures');
+
+  // Reported in the bundle but kept on erasure: the entry is what stops the
+  // address from being imported or mailed again.
+  const suppressionResult = await ddb.send(new GetItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: suppressionSk(email) })
+  }));
+  const suppression = suppressionResult.Item ? unmarshall(suppressionResult.Item) : null;
+
+  return { subscriber, memberships, segments, events, openMarkers, failedIssues, departures, suppression };
+};
+
+const getSegment = async (tenantId, segmentId) => {
+  const result = await ddb.send(new GetItemCommand({
+    TableName: process.env.SUBSCRIBERS_TABLE_NAME,
+    Key: marshall({ tenantId, email: `SEGMENT#${segmentId}` }),
+    ProjectionExpression: '#name',
+    ExpressionAttributeNames: { '#name': 'name' }
+  }));
+  return result.Item ? unmarshall(result.Item) : null;
+};
+
+/** Every issue partition for the tenant, from the issue records on GSI1. */
+const listIssuePks = async (tenantId) => {
+  const items = await queryAll({
+    TableName: process.env.TABLE_NAME,
+    IndexName: 'GSI1',
+    KeyConditionExpression: 'GSI1PK = :gsi1pk',
+    ExpressionAttributeValues: marshall({ ':gsi1pk': `${tenantId}#issue` }),
+    ProjectionExpression: 'pk'
+  }, 'QueryTenantIssues');
+  return [...new Set(items.map((item) => item.pk))];
+};
+
+// ── Erasure ─────────────────────────────────────────────────────────────
+
+const inBatches = async (items, fn) => {
+  const results = [];
+  for (let i = 0; i < items.length; i += WRITE_CONCURRENCY) {
+    results.push(...await Promise.all(items.slice(i, i + WRITE_CONCURRENCY).map(fn)));
+  }
+  return results;
+};
+
+/** Deletes an item; false when it was already gone. */
+const deleteItem = async (tableName, key, operationName) => {
+  const result = await sendWithRetry(() => ddb.send(new DeleteItemCommand({
+    TableName: tableName,
+    Key: marshall(key),
+    ReturnValues: 'ALL_OLD'
+  })), operationName);
+  return Boolean(result.Attributes);
+};
+
+/**
+ * Subtracts `amount` from a counter, stopping at zero. `key` is the record's
+ * key and `counter` the attribute name.
+ */
+const decrementCounter = async (tableName, key, counter, amount) => {
+  try {
+    await sendWithRetry(() => ddb.send(new UpdateItemCommand({
+      TableName: tableName,
+      Key: marshall(key),
+      UpdateExpression: 'SET #counter = #counter - :amount',
+      ConditionExpression: '#counter >= :amount',
+      ExpressionAttributeNames: { '#counter': counter },
+      ExpressionAttributeValues: marshall({ ':amount': amount })
+    })), 'DecrementCounter');
+  } catch (err) {
+    if (err.name !== 'ConditionalCheckFailedException') {
+      throw err;
+    }
+    await sendWithRetry(() => ddb.send(new UpdateItemCommand({
+      TableName: tableName,
+      Key: marshall(key),
+      UpdateExpression: 'SET #counter = :zero',
+      ConditionExpression: 'attribute_exists(#counter)',
+      ExpressionAttributeNames: { '#counter': counter },
+      ExpressionAttributeValues: marshall({ ':zero': 0 })
+    })), 'ZeroCounter').catch((zeroErr) => {
+      if (zeroErr.name !== 'ConditionalCheckFailedException') {
+        throw zeroErr;
+      }
+    });
+  }
+};
+
+const erase = async (tenantId, email, found) => {
+  const subscribersTable = process.env.SUBSCRIBERS_TABLE_NAME;
+  const table = process.env.TABLE_NAME;
+
+  const removedMemberships = await inBatches(found.memberships, async (membership) => ({
+    membership,
+    removed: await deleteItem(subscribersTable, { tenantId, email: membership.key }, 'DeleteMembership')
+  }));
+  const segmentCounts = new Map();
+  for (const { membership, removed } of removedMemberships) {
+    if (removed && membership.segmentId) {
+      segmentCounts.set(membership.segmentId, (segmentCounts.get(membership.segmentId) ?? 0) + 1);
+    }
+  }
+  for (const [segmentId, count] of segmentCounts) {
+    await decrementCounter(subscribersTable, { tenantId, email: `SEGMENT#${segmentId}` }, 'memberCount', count);
+  }
+
+  const subscriberRemoved = found.subscriber
+    ? await deleteItem(subscribersTable, { tenantId, email }, 'DeleteSubscriber')
+    : false;
+  if (subscriberRemoved) {
+    await decrementCounter(table, { pk: tenantId, sk: 'tenant' }, 'subscribers', 1);
+  }
+
+  const eventsRemoved = await inBatches(found.events, ({ pk, sk }) => deleteItem(table, { pk, sk }, 'DeleteIssueEvent'));
+  const deletedEvents = found.events.filter((_, index) => eventsRemoved[index]);
+  await inBatches(found.openMarkers, ({ pk, sk }) => deleteItem(table, { pk, sk }, 'DeleteOpenMarker'));
+
+  let counterDecrements = 0;
+  for (const [pk, records] of statDecrements(deletedEvents)) {
+    for (const [sk, counters] of Object.entries(records)) {
+      for (const [counter, amount] of Object.entries(counters)) {
+        await decrementCounter(table, { pk, sk }, counter, amount);
+        counterDecrements += amount;
+      }
+    }
+  }
+
+  let failedAddressesRedacted = 0;
+  for (const { pk, indexes } of found.failedIssues) {
+    for (const index of indexes) {
+      if (await redactFailedAddress(pk, index, email)) {
+        failedAddressesRedacted++;
+      }
+    }
+  }
+
+  await inBatches(found.departures, pseudonymizeDeparture);
+
+  return {
+    subscriberRecords: subscriberRemoved ? 1 : 0,
+    segmentMemberships: removedMemberships.filter(({ removed }) => removed).length,
+    events: deletedEvents.length,
+    openMarkers: found.openMarkers.length,
+    counterDecrements,
+    failedAddressesRedacted,
+    departuresPseudonymized: found.departures.length
+  };
+};
+
+/** Conditional on the slot still holding the address, so a concurrent append can't shift it. */
+const redactFailedAddress = async (pk, index, email) => {
+  try {
+    await ddb.send(new UpdateItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({ pk, sk: 'stats' }),
+      UpdateExpression: `SET failedAddresses[${index}] = :redacted`,
+      ConditionExpression: `failedAddresses[${index}] = :email`,
+      ExpressionAttributeValues: marshall({ ':redacted': REDACTED_ADDRESS, ':email': email })
+    }));
+    return true;
+  } catch (err) {
+    if (err.name === 'ConditionalCheckFailedException') {
+      return false;
+    }
+    throw err;
+  }
+};
+
+/** Re-keys a departure record under a random id and drops the hash. */
+const pseudonymizeDeparture = async (departure) => {
+  const { pk, sk, emailHash: _emailHash, ...rest } = departure;
+  await sendWithRetry(() => ddb.send(new PutItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Item: marshall({
+      ...rest,
+      pk,
+      sk: `${DEPARTURE_SK_PREFIX}${departure.departedAt}#erased-${ulid()}`,
+      erased: true
+    })
+  })), 'PutPseudonymizedDeparture');
+  await deleteItem(process.env.TABLE_NAME, { pk, sk }, 'DeleteDeparture');
+};
+
+// ── Receipts ────────────────────────────────────────────────────────────
+
+/**
+ * Writes the receipt and moves the tenant's chain head in one transaction,
+ * conditional on the head not having moved since it was read.
+ */
+const appendReceipt = async (tenantId, job, erased) => {
+  for (let attempt = 1; attempt <= RECEIPT_ATTEMPTS; attempt++) {
+    const headResult = await ddb.send(new GetItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({ pk: tenantId, sk: DSAR_RECEIPT_HEAD_SK }),
+      ConsistentRead: true
+    }));
+    const head = headResult.Item ? unmarshall(headResult.Item) : null;
+
+    const receipt = buildReceipt({
+      previous: head,
+      tenantId,
+      jobId: job.jobId,
+      emailHash: job.emailHash,
+      requestedBy: job.userId,
+      requestedAt: job.createdAt,
+      completedAt: new Date().toISOString(),
+      erased
+    });
+
+    try {
+      await ddb.send(new TransactWriteItemsCommand({
+        TransactItems: [
+          {
+            Put: {
+              TableName: process.env.TABLE_NAME,
+              Item: marshall({ pk: tenantId, sk: receiptSk(receipt.sequence), ...receipt }),
+              ConditionExpression: 'attribute_not_exists(pk)'
+            }
+          },
+          {
+            Put: {
+              TableName: process.env.TABLE_NAME,
+              Item: marshall({ pk: tenantId, sk: DSAR_RECEIPT_HEAD_SK, sequence: receipt.sequence, receiptHash: receipt.receiptHash }),
+              ConditionExpression: head ? 'receiptHash = :previous' : 'attribute_not_exists(pk)',
+              ...(head && { ExpressionAttributeValues: marshall({ ':previous': head.receiptHash }) })
+            }
+          }
+        ]
+      }));
+      return receipt;
+    } catch (err) {
+      if (err.name !== 'TransactionCanceledException' || attempt === RECEIPT_ATTEMPTS) {
+        throw err;
+      }
+      console.warn('Receipt chain head moved, retrying', { tenantId, jobId: job.jobId, attempt });
+    }
+  }
+  throw new Error('Could not append erasure receipt');
+};
@@ -0,0 +1,225 @@
+import { DynamoDBClient, GetItemCommand, PutItemCommand, UpdateItemCommand } from '@aws-sdk/client-dynamodb';
+import { S3Client, GetObjectCommand } from '@aws-sdk/client-s3';
+import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
+import { sendWithRetry } from '../utils/helpers.mjs';
+import { SUBSCRIBER_FIELDS_SK, resolveFieldColumns } from '../utils/subscriber-fields.mjs';
+import { isSuppressedAddress, loadSuppressionList } from '../utils/suppression.mjs';
+import {
+  parseCsv,
+  planImport,
+  resolveColumns,
+  summarizeImport
+} from '../utils/subscriber-import.mjs';
+
+const ddb = new DynamoDBClient();
+const s3 = new S3Client();
+
+const JOB_SK_PREFIX = 'subscriber-import#';
+// Rows beyond this are rejected up front rather than timing the Lambda out.
+const MAX_ROWS = 50_000;
+// Skipped rows kept on the job record; the item has to stay under 400 KB.
+const MAX_REPORTED_ERRORS = 1000;
+const WRITE_CONCURRENCY = 10;
+
+/**
+ * Works through a subscriber import created by POST /subscribers/import and
+ * started by POST /subscribers/import/:jobId/start. Invoked asynchronously
+ * with { tenantId, jobId }.
+ *
+ * Columns for the tenant's custom subscriber fields are matched by mapping,
+ * key or label, and a row whose value doesn't fit its field is skipped.
+ *
+ * The job is claimed by moving it from pending to running, so a duplicate
+ * invocation is a no-op. Each new subscriber is written with a conditional
+ * put, so addresses that are already on the list are reported rather than
+ * overwritten. The tenant's subscriber count is bumped by the number written
+ * and the job ends completed (with the skipped rows) or failed (with the
+ * reason the file could not be processed).
+ */
+export const handler = async (event) => {
+  const { tenantId, jobId } = event || {};
+
+  if (!tenantId || !jobId) {
+    console.error('Missing required parameters', { tenantId, jobId });
+    return { status: 'skipped', reason: 'missing-parameters' };
+  }
+
+  const job = await loadJob(tenantId, jobId);
+  if (!job) {
+    console.warn('Import job not found', { tenantId, jobId });
+    return { status: 'skipped', reason: 'job-not-found' };
+  }
+
+  if (!(await claimJob(tenantId, jobId))) {
+    console.log('Import job is not pending, skipping', { tenantId, jobId, status: job.status });
+    return { status: 'skipped', reason: 'not-pending' };
+  }
+
+  try {
+    const rows = parseCsv(await readUpload(job.uploadKey));
+    const [header = [], ...dataRows] = rows;
+    if (dataRows.length > MAX_ROWS) {
+      return await failJob(tenantId, jobId, `The file has ${dataRows.length} rows; an import takes at most ${MAX_ROWS}`);
+    }
+
+    const { columns, error } = resolveColumns(header, job.columnMapping);
+    if (error) {
+      return await failJob(tenantId, jobId, error);
+    }
+
+    const fields = await getSubscriberFields(tenantId);
+    const fieldColumns = resolveFieldColumns(header, fields, job.columnMapping);
+    if (fieldColumns.error) {
+      return await failJob(tenantId, jobId, fieldColumns.error);
+    }
+
+    const suppressionList = await loadSuppressionList(tenantId);
+    const isSuppressed = (email) => isSuppressedAddress(suppressionList, email);
+    const { subscribers, skipped, totalRows } = planImport(dataRows, columns, isSuppressed, {
+      fields,
+      columns: fieldColumns.columns
+    });
+
+    const addedAt = new Date().toISOString();
+    let imported = 0;
+    for (let i = 0; i < subscribers.length; i += WRITE_CONCURRENCY) {
+      const batch = subscribers.slice(i, i + WRITE_CONCURRENCY);
+      const outcomes = await Promise.all(batch.map((subscriber) => writeSubscriber(tenantId, subscriber, addedAt)));
+      outcomes.forEach((outcome, index) => {
+        if (outcome === 'imported') {
+          imported++;
+        } else {
+          skipped.push({ row: batch[index].row, email: batch[index].email, reason: outcome });
+        }
+      });
+    }
+
+    if (imported > 0) {
+      try {
+        await incrementSubscriberCount(tenantId, imported);
+      } catch (err) {
+        // The rows are written; a stale count is not worth failing the job over.
+        console.error('Failed to update subscriber count after import', { tenantId, jobId, imported, error: err.message });
+      }
+    }
+
+    skipped.sort((a, b) => a.row - b.row);
+    const summary = summarizeImport(totalRows, imported, skipped);
+    await finishJob(tenantId, jobId, {
+      status: 'completed',
+      summary,
+      errors: skipped.slice(0, MAX_REPORTED_ERRORS),
+      errorsTruncated: skipped.length > MAX_REPORTED_ERRORS
+    });
+
+    console.log('Subscriber import complete', { tenantId, jobId, ...summary });
+    return { status: 'completed', ...summary };
+  } catch (err) {
+    console.error('Subscriber import failed', { tenantId, jobId, error: err.message });
+    return await failJob(tenantId, jobId, 'The import could not be processed');
+  }
+};
+
+const loadJob = async (tenantId, jobId) => {
+  const result = await ddb.send(new GetItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: `${JOB_SK_PREFIX}${jobId}` }),
+    ConsistentRead: true
+  }));
+  return result.Item ? unmarshall(result.Item) : null;
+};
+
+const claimJob = async (tenantId, jobId) => {
+  const now = new Date().toISOString();
+  try {
+    await ddb.send(new UpdateItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({ pk: tenantId, sk: `${JOB_SK_PREFIX}${jobId}` }),
+      UpdateExpression: 'SET #status = :running, startedAt = :now, updatedAt = :now',
+      ConditionExpression: '#status = :pending',
+      ExpressionAttributeNames: { '#status': 'status' },
+      ExpressionAttributeValues: marshall({ ':running': 'running', ':pending': 'pending', ':now': now })
+    }));
+    return true;
+  } catch (err) {
+    if (err.name === 'ConditionalCheckFailedException') {
+      return false;
+    }
+    throw err;
+  }
+};
+
+const getSubscriberFields = async (tenantId) => {
+  const result = await ddb.send(new GetItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: SUBSCRIBER_FIELDS_SK })
+  }));
+  return result.Item ? (unmarshall(result.Item).fields ?? []) : [];
+};
+
+const readUpload = async (key) => {
+  const result = await s3.send(new GetObjectCommand({ Bucket: process.env.BUCKET, Key: key }));
+  return result.Body.transformToString('utf-8');
+};
+
+/** 'imported', 'already-subscribed' or 'write-failed'. */
+const writeSubscriber = async (tenantId, subscriber, addedAt) => {
+  try {
+    await sendWithRetry(() => ddb.send(new PutItemCommand({
+      TableName: process.env.SUBSCRIBERS_TABLE_NAME,
+      Item: marshall({
+        tenantId,
+        email: subscriber.email,
+        addedAt,
+        source: 'import',
+        ...(subscriber.firstName && { firstName: subscriber.firstName }),
+        ...(subscriber.lastName && { lastName: subscriber.lastName }),
+        ...(subscriber.customFields && { customFields: subscriber.customFields })
+      }),
+      ConditionExpression: 'attribute_not_exists(email)'
+    })), 'PutSubscriber');
+    return 'imported';
+  } catch (err) {
+    if (err.name === 'ConditionalCheckFailedException') {
+      return 'already-subscribed';
+    }
+    console.error('Failed to write imported subscriber', { tenantId, row: subscriber.row, error: err.message });
+    return 'write-failed';
+  }
+};
+
+const incrementSubscriberCount = async (tenantId, count) => {
+  await ddb.send(new UpdateItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: 'tenant' }),
+    UpdateExpression: 'SET subscribers = if_not_exists(subscribers, :zero) + :count',
+    ExpressionAttributeValues: marshall({ ':zero': 0, ':count': count })
+  }));
+};
+
+const finishJob = async (tenantId, jobId, { status, summary, errors, errorsTruncated, failureReason }) => {
+  const now = new Date().toISOString();
+  await ddb.send(new UpdateItemCommand({
+    TableName: process.env.TABLE_NAME,
+    Key: marshall({ pk: tenantId, sk: `${JOB_SK_PREFIX}${jobId}` }),
+    UpdateExpression: 'SET #status = :status, finishedAt = :now, updatedAt = :now, '
+      + 'errors = :errors, errorsTruncated = :truncated'
+      + (summary ? ', summary = :summary' : '')
+      + (failureReason ? ', failureReason = :reason' : ''),
+    ExpressionAttributeNames: { '#status': 'status' },
+    ExpressionAttributeValues: marshall({
+      ':status': status,
+      ':now': now,
+      ':errors': errors || [],
+      ':truncated': Boolean(errorsTruncated),
+      ...(summary && { ':summary': summary }),
+      ...(failureReason && { ':reason': failureReason })
+    }, { removeUndefinedValues: true })
+  }));
+};
+
+const failJob = async (tenantId, jobId, failureReason) => {
+  console.warn('Subscriber import failed', { tenantId, jobId, failureReason });
+  await finishJob(tenantId, jobId, { status: 'failed', failureReason });
+  return { status: 'failed', reason: failureReason };
+};
@@ -5,6 +5,7 @@ import { EventBridgeClient, PutEventsCommand } from '@aws-sdk/client-eventbridge
 import { marshall, unmarshall } from "@aws-sdk/util-dynamodb";
 import { decrypt, getTenant } from "../utils/helpers.mjs";
 import { unsubscribeUser } from "../utils/subscriber.mjs";
+import { UNSUBSCRIBE_SOURCE, addSuppressionEntry } from "../utils/suppression.mjs";
 import { getMostRecentPublishedIssue, incrementIssueCounter } from "../utils/issue-attribution.mjs";
 
 const ddb = new DynamoDBClient();
@@ -51,6 +52,10 @@ export const handler = async (event) => {
       userAgent
     };
 
+    // Suppressed whether or not they were still subscribed, so a later import
+    // or admin add can't put them back. Signing up again lifts the entry.
+    await addSuppressionEntry(tenantId, emailAddress, { reason: 'manual', source: UNSUBSCRIBE_SOURCE });
+
     const result = await unsubscribeUser(tenantId, emailAddress, 'encrypted-link', metadata);
     success = result.success;
 
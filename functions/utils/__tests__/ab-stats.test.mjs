@@ -5,7 +5,9 @@
 import {
   normalCdf,
   twoProportionZTest,
-  evaluateAbResult
+  evaluateAbResult,
+  evaluateAbTest,
+  allocationWeights
 } from '../ab-stats.mjs';
 
 describe('normalCdf', () => {
@@ -170,3 +172,69 @@ describe('evaluateAbResult', () => {
     expect(result.status).toBe('inconclusive');
   });
 });
+
+describe('evaluateAbTest', () => {
+  const opts = { winMetric: 'openRate', confidence: 0.95, minSamplePerVariant: 1000 };
+
+  it('declares a challenger that beats every other variant', () => {
+    const result = evaluateAbTest({
+      a: { opens: 1500, deliveries: 5000 },
+      b: { opens: 1550, deliveries: 5000 },
+      c: { opens: 2500, deliveries: 5000 }
+    }, opts);
+
+    expect(result.winnerVariantId).toBe('c');
+    expect(result.status).toBe('sent');
+    expect(Object.keys(result.evaluation.variants)).toEqual(['a', 'b', 'c']);
+    expect(result.evaluation.variants.c.rate).toBeCloseTo(0.5, 6);
+  });
+
+  it('is inconclusive when the leader only beats some of the variants', () => {
+    const result = evaluateAbTest({
+      a: { opens: 1500, deliveries: 5000 },
+      b: { opens: 2480, deliveries: 5000 },
+      c: { opens: 2500, deliveries: 5000 }
+    }, opts);
+
+    expect(result.winnerVariantId).toBeNull();
+    expect(result.status).toBe('inconclusive');
+  });
+
+  it('requires the minimum sample from every variant', () => {
+    const result = evaluateAbTest({
+      a: { opens: 1500, deliveries: 5000 },
+      b: { opens: 1500, deliveries: 5000 },
+      c: { opens: 300, deliveries: 500 }
+    }, opts);
+
+    expect(result.evaluation.enoughData).toBe(false);
+    expect(result.winnerVariantId).toBeNull();
+  });
+});
+
+describe('allocationWeights', () => {
+  const arms = [
+    { successes: 100, trials: 1000 },
+    { successes: 300, trials: 1000 },
+    { successes: 120, trials: 1000 }
+  ];
+
+  it('splits evenly for fixed tests and tests with no deliveries', () => {
+    expect(allocationWeights({ mode: 'fixed' }, arms)).toEqual([1 / 3, 1 / 3, 1 / 3]);
+    const empty = arms.map(() => ({ successes: 0, trials: 0 }));
+    expect(allocationWeights({ mode: 'epsilonGreedy', epsilon: 0.3 }, empty)).toEqual([1 / 3, 1 / 3, 1 / 3]);
+  });
+
+  it('gives the epsilon-greedy leader 1 - epsilon plus its exploration slice', () => {
+    const weights = allocationWeights({ mode: 'epsilonGreedy', epsilon: 0.3 }, arms);
+    expect(weights[0]).toBeCloseTo(0.1, 9);
+    expect(weights[1]).toBeCloseTo(0.8, 9);
+    expect(weights[2]).toBeCloseTo(0.1, 9);
+  });
+
+  it('sends nearly all of a Thompson batch to a clearly better variant', () => {
+    const weights = allocationWeights({ mode: 'thompson' }, arms);
+    expect(weights.reduce((sum, w) => sum + w, 0)).toBeCloseTo(1, 9);
+    expect(weights[1]).toBeGreaterThan(0.99);
+  });
+});
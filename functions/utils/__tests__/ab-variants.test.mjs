@@ -133,6 +133,39 @@ describe('splitRecipients', () => {
   });
 });
 
+describe('multi-variant splits', () => {
+  const many = Array.from({ length: 3000 }, (_, i) => `subscriber${i}@newsletter.test`);
+  const ids = ['a', 'b', 'c', 'd'];
+
+  it('spreads recipients across every configured variant', () => {
+    const buckets = splitRecipients(many, 'issue_42', ids);
+    expect(Object.keys(buckets)).toEqual(ids);
+    for (const id of ids) {
+      expect(buckets[id].length / many.length).toBeGreaterThan(0.18);
+      expect(buckets[id].length / many.length).toBeLessThan(0.32);
+    }
+  });
+
+  it('keeps the original a/b assignment for an even two-variant split', () => {
+    for (const email of many.slice(0, 200)) {
+      expect(pickVariant(email, 'issue_42', ['a', 'b'])).toBe(pickVariant(email, 'issue_42'));
+    }
+  });
+
+  it('follows the given weights', () => {
+    const buckets = splitRecipients(many, 'issue_42', ['a', 'b', 'c'], [0.1, 0.8, 0.1]);
+    expect(buckets.b.length / many.length).toBeGreaterThan(0.72);
+    expect(buckets.b.length / many.length).toBeLessThan(0.88);
+    expect(splitRecipients(many, 'issue_42', ['a', 'b', 'c'], [0, 1, 0]).b).toEqual(many);
+  });
+
+  it('falls back to an even split when the weights do not fit', () => {
+    const even = splitRecipients(many, 'issue_42', ids);
+    expect(splitRecipients(many, 'issue_42', ids, [1, 2])).toEqual(even);
+    expect(splitRecipients(many, 'issue_42', ids, [0, 0, 0, 0])).toEqual(even);
+  });
+});
+
 describe('selectHoldoutSample', () => {
   const buildEmails = (n, prefix = 'subscriber') => {
     const emails = [];
@@ -0,0 +1,97 @@
+/**
+ * Unit tests for issue audience matching
+ */
+
+import {
+  DEFAULT_MIN_INTEREST_SCORE,
+  hasAudienceFilter,
+  matchesAudience,
+  filterSubscribersForAudience,
+} from '../audience.mjs';
+
+const noSegments = { included: null, excluded: new Set() };
+
+describe('hasAudienceFilter', () => {
+  it('is false for missing or empty audiences', () => {
+    expect(hasAudienceFilter(undefined)).toBe(false);
+    expect(hasAudienceFilter(null)).toBe(false);
+    expect(hasAudienceFilter({})).toBe(false);
+    expect(hasAudienceFilter({ includeSegments: [], minInterestScore: 2 })).toBe(false);
+  });
+
+  it('is true when any criterion is set', () => {
+    expect(hasAudienceFilter({ includeSegments: ['seg-1'] })).toBe(true);
+    expect(hasAudienceFilter({ excludeSegments: ['seg-1'] })).toBe(true);
+    expect(hasAudienceFilter({ interests: ['ai'] })).toBe(true);
+    expect(hasAudienceFilter({ timeZones: ['Europe/Berlin'] })).toBe(true);
+  });
+});
+
+describe('matchesAudience', () => {
+  it('keeps members of any included segment and drops excluded ones', () => {
+    const audience = { includeSegments: ['seg-1'], excludeSegments: ['seg-2'] };
+    const memberships = {
+      included: new Set(['a@example.com', 'b@example.com']),
+      excluded: new Set(['b@example.com']),
+    };
+
+    expect(matchesAudience(audience, { email: 'a@example.com' }, memberships)).toBe(true);
+    expect(matchesAudience(audience, { email: 'b@example.com' }, memberships)).toBe(false);
+    expect(matchesAudience(audience, { email: 'c@example.com' }, memberships)).toBe(false);
+  });
+
+  it('treats a missing include list as everyone', () => {
+    const audience = { excludeSegments: ['seg-2'] };
+    const memberships = { included: null, excluded: new Set(['b@example.com']) };
+
+    expect(matchesAudience(audience, { email: 'c@example.com' }, memberships)).toBe(true);
+    expect(matchesAudience(audience, { email: 'b@example.com' }, memberships)).toBe(false);
+  });
+
+  it('requires a qualifying score in any listed interest', () => {
+    const audience = { interests: ['ai', 'serverless'], minInterestScore: 2 };
+
+    expect(matchesAudience(audience, {
+      email: 'a@example.com',
+      interestScores: { serverless: { score: 2, lastScoredAt: '2026-03-01T00:00:00Z' } },
+    }, noSegments)).toBe(true);
+    expect(matchesAudience(audience, {
+      email: 'a@example.com',
+      interestScores: { ai: { score: 1.5 } },
+    }, noSegments)).toBe(false);
+    expect(matchesAudience(audience, { email: 'a@example.com' }, noSegments)).toBe(false);
+  });
+
+  it('defaults the minimum score to the auto-segment threshold', () => {
+    const audience = { interests: ['ai'] };
+    const at = (score) => ({ email: 'a@example.com', interestScores: { ai: { score } } });
+
+    expect(matchesAudience(audience, at(DEFAULT_MIN_INTEREST_SCORE), noSegments)).toBe(true);
+    expect(matchesAudience(audience, at(DEFAULT_MIN_INTEREST_SCORE - 0.5), noSegments)).toBe(false);
+  });
+
+  it('requires a confirmed timezone in the list', () => {
+    const audience = { timeZones: ['Europe/Berlin'] };
+
+    expect(matchesAudience(audience, { email: 'a@example.com', timeZone: 'Europe/Berlin' }, noSegments)).toBe(true);
+    expect(matchesAudience(audience, { email: 'a@example.com', timeZone: 'America/Chicago' }, noSegments)).toBe(false);
+    expect(matchesAudience(audience, { email: 'a@example.com' }, noSegments)).toBe(false);
+  });
+});
+
+describe('filterSubscribersForAudience', () => {
+  const subscribers = [
+    { email: 'a@example.com', timeZone: 'Europe/Berlin' },
+    { email: 'b@example.com', timeZone: 'America/Chicago' },
+  ];
+
+  it('returns the list unchanged without criteria', () => {
+    expect(filterSubscribersForAudience(subscribers, undefined, noSegments)).toBe(subscribers);
+    expect(filterSubscribersForAudience(subscribers, {}, noSegments)).toBe(subscribers);
+  });
+
+  it('filters by the audience', () => {
+    const result = filterSubscribersForAudience(subscribers, { timeZones: ['Europe/Berlin'] }, noSegments);
+    expect(result.map((s) => s.email)).toEqual(['a@example.com']);
+  });
+});
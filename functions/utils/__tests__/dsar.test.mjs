@@ -0,0 +1,174 @@
+/**
+ * Unit tests for data subject request bundles, stat decrements and receipts.
+ */
+
+import {
+  GENESIS_HASH,
+  buildAccessBundle,
+  buildReceipt,
+  canonicalJson,
+  eventKind,
+  failedAddressIndexes,
+  receiptSk,
+  statDecrements,
+  summarizeBundle,
+  verifyReceiptChain
+} from '../dsar.mjs';
+
+const HASH = 'a'.repeat(64);
+
+const event = (pk, sk, extra = {}) => ({ pk, sk, subscriberEmailHash: HASH, ttl: 123, ...extra });
+
+describe('eventKind', () => {
+  it('maps event sort keys and ignores everything else', () => {
+    expect(eventKind(`open#2026-01-01T00:00:00Z#${HASH}#01`)).toBe('opens');
+    expect(eventKind(`click#2026-01-01T00:00:00Z#${HASH}#abcd#01`)).toBe('clicks');
+    expect(eventKind('opens#reader@example.com')).toBeNull();
+    expect(eventKind('stats')).toBeNull();
+  });
+});
+
+describe('buildAccessBundle', () => {
+  const subscriber = {
+    tenantId: 't1',
+    email: 'reader@example.com',
+    firstName: 'Ada',
+    recentActivity: [{ type: 'open', issue: 2 }],
+    interestScores: { rust: { score: 3 } },
+    honeypotTriggered: false,
+    fastSubmission: true
+  };
+
+  it('groups the record, events and memberships into sections', () => {
+    const bundle = buildAccessBundle({
+      email: 'reader@example.com',
+      generatedAt: '2026-02-01T00:00:00Z',
+      subscriber,
+      segments: [{ segmentId: 's1', name: 'VIP' }],
+      events: [
+        event('t1#2', 'click#2026-01-02T00:00:00Z#h#l#02', { timestamp: '2026-01-02T00:00:00Z', linkUrl: 'https://x.test' }),
+        event('t1#2', 'open#2026-01-01T00:00:00Z#h#01', { timestamp: '2026-01-01T00:00:00Z', device: 'mobile' }),
+        event('t1#1', 'bounce#2025-12-01T00:00:00Z#h#03', { timestamp: '2025-12-01T00:00:00Z' })
+      ],
+      failedIssuePks: ['t1#1'],
+      departures: [{ departedAt: '2025-12-02T00:00:00Z', reason: 'bounced', emailHash: HASH }],
+      suppression: { pk: 't1', sk: 'suppression#reader@example.com', entry: 'reader@example.com', reason: 'hard-bounce' }
+    });
+
+    expect(bundle.subscriber).toEqual({ email: 'reader@example.com', firstName: 'Ada' });
+    expect(bundle.recentActivity).toEqual(subscriber.recentActivity);
+    expect(bundle.interestScores).toEqual(subscriber.interestScores);
+    expect(bundle.botFlags).toEqual({ honeypotTriggered: false, fastSubmission: true });
+    expect(bundle.segments).toEqual([{ segmentId: 's1', name: 'VIP' }]);
+    expect(bundle.issues.map((issue) => issue.issueNumber)).toEqual([1, 2]);
+    expect(bundle.issues[0]).toMatchObject({ deliveryFailed: true, bounces: [{ timestamp: '2025-12-01T00:00:00Z' }] });
+    expect(bundle.issues[1].opens).toEqual([{ timestamp: '2026-01-01T00:00:00Z', device: 'mobile' }]);
+    expect(bundle.issues[1].clicks[0]).not.toHaveProperty('subscriberEmailHash');
+    expect(bundle.departures).toEqual([{ departedAt: '2025-12-02T00:00:00Z', reason: 'bounced' }]);
+    expect(bundle.suppression).toEqual({ entry: 'reader@example.com', reason: 'hard-bounce' });
+
+    expect(summarizeBundle(bundle)).toEqual({
+      subscriberRecords: 1,
+      segmentMemberships: 1,
+      opens: 1,
+      clicks: 1,
+      bounces: 1,
+      complaints: 0,
+      departures: 1
+    });
+  });
+
+  it('still reports events for an address that is no longer subscribed', () => {
+    const bundle = buildAccessBundle({
+      email: 'gone@example.com',
+      generatedAt: '2026-02-01T00:00:00Z',
+      events: [event('t1#3', 'open#2026-01-01T00:00:00Z#h#01')]
+    });
+
+    expect(bundle.subscriber).toBeNull();
+    expect(bundle.botFlags).toEqual({});
+    expect(bundle.suppression).toBeNull();
+    expect(summarizeBundle(bundle)).toMatchObject({ subscriberRecords: 0, opens: 1 });
+  });
+});
+
+describe('statDecrements', () => {
+  it('counts the first open per issue as an open and the rest as reopens', () => {
+    const decrements = statDecrements([
+      event('t1#1', 'open#1'),
+      event('t1#1', 'open#2'),
+      event('t1#1', 'open#3'),
+      event('t1#1', 'click#1'),
+      event('t1#2', 'complaint#1'),
+      event('t1#2', 'opens#reader@example.com')
+    ]);
+
+    expect(Object.fromEntries(decrements)).toEqual({
+      't1#1': { stats: { opens: 1, reopens: 2, clicks: 1 } },
+      't1#2': { stats: { complaints: 1 } }
+    });
+  });
+
+  it('also takes events off the variant and provider records they name', () => {
+    const decrements = statDecrements([
+      event('t1#1', 'open#1', { variant: 'c', provider: 'gmail' }),
+      event('t1#1', 'open#2', { variant: 'c', provider: 'gmail' }),
+      event('t1#1', 'click#1', { variant: 'c' }),
+      event('t1#1', 'bounce#1', { provider: 'yahoo' })
+    ]);
+
+    expect(decrements.get('t1#1')).toEqual({
+      stats: { opens: 1, reopens: 1, clicks: 1, bounces: 1 },
+      'stats#v#c': { opens: 1, reopens: 1, clicks: 1 },
+      'stats#provider#gmail': { opens: 1 },
+      'stats#provider#yahoo': { bounces: 1 }
+    });
+  });
+});
+
+describe('failedAddressIndexes', () => {
+  it('finds every slot holding the address', () => {
+    expect(failedAddressIndexes(['a@x.test', 'Reader@Example.com', 'reader@example.com'], 'reader@example.com')).toEqual([1, 2]);
+    expect(failedAddressIndexes(undefined, 'reader@example.com')).toEqual([]);
+  });
+});
+
+describe('receipts', () => {
+  const receiptFor = (previous, jobId) => buildReceipt({
+    previous,
+    tenantId: 't1',
+    jobId,
+    emailHash: HASH,
+    requestedBy: 'user-1',
+    requestedAt: '2026-01-01T00:00:00Z',
+    completedAt: '2026-01-01T00:05:00Z',
+    erased: { subscriberRecords: 1, events: 4 }
+  });
+
+  it('chains each receipt to the one before it', () => {
+    const first = receiptFor(null, 'job-1');
+    const second = receiptFor(first, 'job-2');
+
+    expect(first).toMatchObject({ sequence: 1, previousHash: GENESIS_HASH });
+    expect(second).toMatchObject({ sequence: 2, previousHash: first.receiptHash });
+    expect(second.receiptHash).toMatch(/^[0-9a-f]{64}$/);
+    expect(verifyReceiptChain([first, second])).toEqual({ valid: true });
+  });
+
+  it('detects edited, removed and reordered receipts', () => {
+    const first = receiptFor(null, 'job-1');
+    const second = receiptFor(first, 'job-2');
+    const third = receiptFor(second, 'job-3');
+
+    const edited = { ...second, erased: { subscriberRecords: 0, events: 4 } };
+    expect(verifyReceiptChain([first, edited, third])).toEqual({ valid: false, sequence: 2 });
+    expect(verifyReceiptChain([first, third])).toEqual({ valid: false, sequence: 3 });
+    expect(verifyReceiptChain([second, first])).toEqual({ valid: false, sequence: 2 });
+  });
+
+  it('hashes independently of key order and sorts sequence keys in chain order', () => {
+    expect(canonicalJson({ b: 1, a: { d: [2, { f: 1, e: 0 }], c: null } }))
+      .toBe('{"a":{"c":null,"d":[2,{"e":0,"f":1}]},"b":1}');
+    expect(receiptSk(2) < receiptSk(10)).toBe(true);
+  });
+});
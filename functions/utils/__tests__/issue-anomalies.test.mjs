@@ -0,0 +1,122 @@
+/**
+ * Unit tests for issue metric anomaly detection.
+ */
+
+import {
+  DEFAULT_ALERT_SETTINGS,
+  alertSortKey,
+  detectAnomalies,
+  issueRates,
+  meanAndStdDev,
+  resolveAlertSettings
+} from '../issue-anomalies.mjs';
+
+const stats = ({ deliveries = 1000, opens = 400, clicks = 50, bounces = 5, complaints = 0 } = {}) => ({
+  deliveries,
+  opens,
+  clicks,
+  bounces,
+  complaints
+});
+
+// Ten ordinary issues: opens 38–42%, clicks 4.5–5.5%, bounces 0.4–0.6%, no complaints.
+const baseline = Array.from({ length: 10 }, (_, i) => stats({
+  opens: 380 + (i % 5) * 10,
+  clicks: 45 + (i % 3) * 5,
+  bounces: 4 + (i % 3)
+}));
+
+describe('issueRates', () => {
+  it('returns percentages of deliveries', () => {
+    expect(issueRates(stats({ opens: 250, clicks: 20, bounces: 10, complaints: 1 }))).toEqual({
+      bounceRate: 1,
+      complaintRate: 0.1,
+      openRate: 25,
+      clickRate: 2
+    });
+  });
+
+  it('returns null for issues with too few deliveries', () => {
+    expect(issueRates(stats({ deliveries: 10 }))).toBeNull();
+    expect(issueRates({})).toBeNull();
+  });
+});
+
+describe('meanAndStdDev', () => {
+  it('uses the sample standard deviation', () => {
+    const { mean, stdDev } = meanAndStdDev([2, 4, 4, 4, 5, 5, 7, 9]);
+    expect(mean).toBe(5);
+    expect(stdDev).toBeCloseTo(2.138, 3);
+  });
+
+  it('is zero spread for fewer than two values', () => {
+    expect(meanAndStdDev([])).toEqual({ mean: 0, stdDev: 0 });
+    expect(meanAndStdDev([3])).toEqual({ mean: 3, stdDev: 0 });
+  });
+});
+
+describe('detectAnomalies', () => {
+  it('returns nothing for an ordinary issue', () => {
+    expect(detectAnomalies(stats(), baseline)).toEqual([]);
+  });
+
+  it('flags a complaint spike against a complaint-free baseline', () => {
+    const anomalies = detectAnomalies(stats({ complaints: 3 }), baseline);
+    expect(anomalies).toHaveLength(1);
+    expect(anomalies[0]).toMatchObject({
+      metric: 'complaintRate',
+      direction: 'spike',
+      value: 0.3,
+      baselineMean: 0,
+      baselineStdDev: 0,
+      zScore: 30,
+      threshold: 3,
+      baselineIssues: 10
+    });
+  });
+
+  it('flags drops in open and click rate but not rises', () => {
+    const drop = detectAnomalies(stats({ opens: 200, clicks: 10 }), baseline);
+    expect(drop.map((a) => a.metric)).toEqual(['openRate', 'clickRate']);
+    expect(drop.every((a) => a.direction === 'drop' && a.zScore < 0)).toBe(true);
+
+    expect(detectAnomalies(stats({ opens: 700, clicks: 150 }), baseline)).toEqual([]);
+  });
+
+  it('respects the configured threshold', () => {
+    const issue = stats({ bounces: 7 });
+    expect(detectAnomalies(issue, baseline, { zScoreThreshold: 3 })).toEqual([]);
+    expect(detectAnomalies(issue, baseline, { zScoreThreshold: 2 }).map((a) => a.metric)).toEqual(['bounceRate']);
+  });
+
+  it('needs enough comparable baseline issues', () => {
+    const tooSmall = [...baseline.slice(0, 2), stats({ deliveries: 5 })];
+    expect(detectAnomalies(stats({ complaints: 10 }), tooSmall)).toEqual([]);
+  });
+});
+
+describe('resolveAlertSettings', () => {
+  it('defaults when there is no record', () => {
+    expect(resolveAlertSettings(null)).toEqual(DEFAULT_ALERT_SETTINGS);
+  });
+
+  it('keeps valid values and drops invalid ones', () => {
+    expect(resolveAlertSettings({
+      enabled: false,
+      zScoreThreshold: 2.5,
+      baselineIssueCount: 1,
+      emailNotifications: true
+    })).toEqual({
+      enabled: false,
+      zScoreThreshold: 2.5,
+      baselineIssueCount: DEFAULT_ALERT_SETTINGS.baselineIssueCount,
+      emailNotifications: true
+    });
+  });
+});
+
+describe('alertSortKey', () => {
+  it('pads the issue number so alerts sort by issue', () => {
+    expect(alertSortKey(42, 'openRate')).toBe('alert#00042#openRate');
+  });
+});
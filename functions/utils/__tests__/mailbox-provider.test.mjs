@@ -0,0 +1,73 @@
+/**
+ * Unit tests for mailbox provider classification
+ */
+
+import { jest } from '@jest/globals';
+import {
+  MAILBOX_PROVIDERS,
+  providerFromDomain,
+  providerFromMx,
+  classifyMailboxProvider,
+} from '../mailbox-provider.mjs';
+
+const mx = (...exchanges) => jest.fn().mockResolvedValue(exchanges.map((exchange, i) => ({ exchange, priority: i })));
+
+describe('providerFromDomain', () => {
+  it('maps consumer domains to their provider', () => {
+    expect(providerFromDomain('gmail.com')).toBe('gmail');
+    expect(providerFromDomain('Hotmail.co.uk')).toBe('outlook');
+    expect(providerFromDomain('aol.com')).toBe('yahoo');
+    expect(providerFromDomain('me.com')).toBe('icloud');
+  });
+
+  it('returns null for unknown domains', () => {
+    expect(providerFromDomain('example.com')).toBeNull();
+    expect(providerFromDomain(undefined)).toBeNull();
+  });
+});
+
+describe('providerFromMx', () => {
+  it('recognizes hosted mail by MX suffix', () => {
+    expect(providerFromMx(['aspmx.l.google.com.'])).toBe('gmail');
+    expect(providerFromMx(['acme-com.mail.protection.outlook.com'])).toBe('outlook');
+    expect(providerFromMx(['mta7.am0.yahoodns.net'])).toBe('yahoo');
+    expect(providerFromMx(['mx01.mail.icloud.com'])).toBe('icloud');
+  });
+
+  it('does not match on a partial label', () => {
+    expect(providerFromMx(['mx.notgoogle.com'])).toBe('other');
+    expect(providerFromMx([])).toBe('other');
+  });
+});
+
+describe('classifyMailboxProvider', () => {
+  it('uses the domain table without a DNS lookup', async () => {
+    const resolveMx = mx();
+    expect(await classifyMailboxProvider('Reader@GMAIL.com', { resolveMx })).toBe('gmail');
+    expect(resolveMx).not.toHaveBeenCalled();
+  });
+
+  it('classifies company domains by their MX hosts and caches the answer', async () => {
+    const resolveMx = mx('alt1.aspmx.l.google.com', 'aspmx.l.google.com');
+    expect(await classifyMailboxProvider('a@workspace-co.test', { resolveMx })).toBe('gmail');
+    expect(await classifyMailboxProvider('b@workspace-co.test', { resolveMx })).toBe('gmail');
+    expect(resolveMx).toHaveBeenCalledTimes(1);
+  });
+
+  it('falls back to other on lookup failures without caching timeouts', async () => {
+    const timeout = Object.assign(new Error('timeout'), { code: 'ETIMEOUT' });
+    const resolveMx = jest.fn().mockRejectedValueOnce(timeout).mockResolvedValueOnce([{ exchange: 'mx.corp-mail.test' }]);
+    expect(await classifyMailboxProvider('a@flaky-co.test', { resolveMx })).toBe('other');
+    expect(await classifyMailboxProvider('a@flaky-co.test', { resolveMx })).toBe('other');
+    expect(resolveMx).toHaveBeenCalledTimes(2);
+  });
+
+  it('returns other for malformed addresses', async () => {
+    expect(await classifyMailboxProvider('not-an-email', { resolveMx: mx() })).toBe('other');
+  });
+
+  it('only returns known provider ids', async () => {
+    const provider = await classifyMailboxProvider('x@yahoo.com', { resolveMx: mx() });
+    expect(MAILBOX_PROVIDERS).toContain(provider);
+  });
+});
@@ -0,0 +1,89 @@
+/**
+ * Unit tests for custom subscriber field values and merge tokens.
+ */
+
+import {
+  applySubscriberFields,
+  parseFieldValue,
+  resolveFieldColumns,
+  resolveRowFields,
+  subscriberFieldDefaults,
+  subscriberTemplateData
+} from '../subscriber-fields.mjs';
+
+const fields = [
+  { key: 'company', label: 'Company Name', type: 'string' },
+  { key: 'seats', label: 'Seats', type: 'number' },
+  { key: 'renewsOn', label: 'Renews On', type: 'date' },
+  { key: 'beta', label: 'Beta', type: 'boolean' },
+  { key: 'plan', label: 'Plan', type: 'enum', options: ['Free', 'Pro'], required: true, default: 'Free' }
+];
+const [company, seats, renewsOn, beta, plan] = fields;
+
+describe('parseFieldValue', () => {
+  it('parses CSV text into the stored form per type', () => {
+    expect(parseFieldValue(company, ' Acme ')).toEqual({ value: 'Acme' });
+    expect(parseFieldValue(seats, '12.5')).toEqual({ value: 12.5 });
+    expect(parseFieldValue(renewsOn, '2026-03-01')).toEqual({ value: '2026-03-01' });
+    expect(parseFieldValue(beta, 'Yes')).toEqual({ value: true });
+    expect(parseFieldValue(beta, '0')).toEqual({ value: false });
+    expect(parseFieldValue(plan, 'pro')).toEqual({ value: 'Pro' });
+    expect(parseFieldValue(company, '   ')).toEqual({ value: undefined });
+  });
+
+  it('reports values that do not fit', () => {
+    expect(parseFieldValue(seats, 'many').error).toBeDefined();
+    expect(parseFieldValue(renewsOn, '2026-02-30').error).toBeDefined();
+    expect(parseFieldValue(renewsOn, '03/01/2026').error).toBeDefined();
+    expect(parseFieldValue(beta, 'maybe').error).toBeDefined();
+    expect(parseFieldValue(plan, 'Enterprise').error).toBe('must be one of: Free, Pro');
+    expect(parseFieldValue(company, 'x'.repeat(501)).error).toBeDefined();
+  });
+});
+
+describe('resolveFieldColumns', () => {
+  it('matches columns by mapping, key or label', () => {
+    const header = ['Email', 'Organisation', 'seats', 'Renews On'];
+    expect(resolveFieldColumns(header, fields, { company: 'organisation' })).toEqual({
+      columns: { company: 1, seats: 2, renewsOn: 3 }
+    });
+  });
+
+  it('reports a mapped header that is not in the file', () => {
+    expect(resolveFieldColumns(['Email'], fields, { company: 'Org' }).error).toMatch(/Org/);
+  });
+});
+
+describe('resolveRowFields', () => {
+  it('keeps only filled cells and lets a default cover a required field', () => {
+    expect(resolveRowFields(fields, { company: 0, plan: 1 }, ['Acme', ''])).toEqual({
+      customFields: { company: 'Acme' }
+    });
+  });
+
+  it('fails on a required field without a value or default', () => {
+    const required = [{ ...company, required: true }];
+    expect(resolveRowFields(required, {}, [])).toEqual({ field: 'company', message: 'is required' });
+  });
+});
+
+describe('merge tokens', () => {
+  it('renders tokens for built-in and custom fields', () => {
+    expect(subscriberTemplateData([company])).toEqual({
+      email: '__EMAIL__',
+      firstName: '__SUBSCRIBER_firstName__',
+      lastName: '__SUBSCRIBER_lastName__',
+      company: '__SUBSCRIBER_company__'
+    });
+  });
+
+  it('fills tokens per recipient, escaping values and falling back to defaults', () => {
+    const defaults = subscriberFieldDefaults([company, plan]);
+    expect(defaults).toEqual({ firstName: '', lastName: '', company: '', plan: 'Free' });
+
+    const html = 'Hi __SUBSCRIBER_firstName__ of __SUBSCRIBER_company__ (__SUBSCRIBER_plan__)__SUBSCRIBER_gone__';
+    expect(applySubscriberFields(html, { firstName: 'Ada', customFields: { company: 'A&B <Co>' } }, defaults))
+      .toBe('Hi Ada of A&amp;B &lt;Co&gt; (Free)');
+    expect(applySubscriberFields(html, undefined, defaults)).toBe('Hi  of  (Free)');
+  });
+});
@@ -0,0 +1,130 @@
+/**
+ * Unit tests for subscriber import parsing and planning.
+ */
+
+import {
+  normalizeImportEmail,
+  parseCsv,
+  planImport,
+  resolveColumns,
+  summarizeImport
+} from '../subscriber-import.mjs';
+
+describe('parseCsv', () => {
+  it('handles quotes, embedded commas and newlines, CRLF and a BOM', () => {
+    const text = '\uFEFFEmail,Name\r\n"a@example.com","Doe, ""Jo"""\r\nb@example.com,"multi\nline"\n';
+    expect(parseCsv(text)).toEqual([
+      ['Email', 'Name'],
+      ['a@example.com', 'Doe, "Jo"'],
+      ['b@example.com', 'multi\nline']
+    ]);
+  });
+
+  it('keeps a final row without a trailing newline', () => {
+    expect(parseCsv('email\nx@example.com')).toEqual([['email'], ['x@example.com']]);
+  });
+});
+
+describe('resolveColumns', () => {
+  it('matches common header names', () => {
+    expect(resolveColumns(['First Name', 'E-mail', 'surname'])).toEqual({
+      columns: { email: 1, firstName: 0, lastName: 2 }
+    });
+  });
+
+  it('prefers the explicit mapping, case-insensitively', () => {
+    expect(resolveColumns(['Contact', 'Email'], { email: 'contact' })).toEqual({ columns: { email: 0 } });
+  });
+
+  it('reports a missing email column or mapped header', () => {
+    expect(resolveColumns(['name']).error).toMatch(/No email column/);
+    expect(resolveColumns(['email'], { firstName: 'Given' }).error).toMatch(/'Given'/);
+  });
+});
+
+describe('normalizeImportEmail', () => {
+  it('lowercases and trims valid addresses', () => {
+    expect(normalizeImportEmail('  Person@Example.COM ')).toBe('person@example.com');
+  });
+
+  it('rejects malformed addresses and keys that could collide with segment rows', () => {
+    expect(normalizeImportEmail('nope')).toBeNull();
+    expect(normalizeImportEmail('a#MEMBER#b@example.com')).toBeNull();
+  });
+});
+
+describe('planImport', () => {
+  const columns = { email: 0, firstName: 1 };
+
+  it('skips invalid, duplicate and suppressed rows with their line numbers', () => {
+    const rows = [
+      ['ada@example.com', ' Ada '],
+      ['', 'Nobody'],
+      ['not-an-email', ''],
+      ['ADA@example.com', 'Again'],
+      ['', ''],
+      ['gone@example.com', 'Gone'],
+      ['bob@example.com', '']
+    ];
+    const suppressed = new Set(['gone@example.com']);
+
+    const { subscribers, skipped, totalRows } = planImport(rows, columns, (email) => suppressed.has(email));
+
+    expect(subscribers).toEqual([
+      { row: 2, email: 'ada@example.com', firstName: 'Ada' },
+      { row: 8, email: 'bob@example.com' }
+    ]);
+    expect(skipped).toEqual([
+      { row: 3, reason: 'missing-email' },
+      { row: 4, email: 'not-an-email', reason: 'invalid-email' },
+      { row: 5, email: 'ada@example.com', reason: 'duplicate' },
+      { row: 7, email: 'gone@example.com', reason: 'suppressed' }
+    ]);
+    expect(totalRows).toBe(6);
+  });
+
+  it('validates custom field columns and skips rows that do not fit', () => {
+    const fields = [
+      { key: 'company', label: 'Company', type: 'string', required: true },
+      { key: 'plan', label: 'Plan', type: 'enum', options: ['Free', 'Pro'] }
+    ];
+    const rows = [
+      ['ada@example.com', 'Ada', 'Acme', 'pro'],
+      ['bob@example.com', 'Bob', 'Initech', 'Enterprise'],
+      ['cy@example.com', 'Cy', '', 'Free']
+    ];
+
+    const { subscribers, skipped } = planImport(rows, columns, undefined, {
+      fields,
+      columns: { company: 2, plan: 3 }
+    });
+
+    expect(subscribers).toEqual([
+      { row: 2, email: 'ada@example.com', firstName: 'Ada', customFields: { company: 'Acme', plan: 'Pro' } }
+    ]);
+    expect(skipped).toEqual([
+      { row: 3, email: 'bob@example.com', reason: 'invalid-field', field: 'plan', message: 'must be one of: Free, Pro' },
+      { row: 4, email: 'cy@example.com', reason: 'invalid-field', field: 'company', message: 'is required' }
+    ]);
+  });
+});
+
+describe('summarizeImport', () => {
+  it('counts skipped rows by reason', () => {
+    expect(summarizeImport(5, 1, [
+      { reason: 'missing-email' },
+      { reason: 'invalid-email' },
+      { reason: 'invalid-field' },
+      { reason: 'already-subscribed' },
+      { reason: 'write-failed' }
+    ])).toEqual({
+      totalRows: 5,
+      imported: 1,
+      alreadySubscribed: 1,
+      duplicates: 0,
+      suppressed: 0,
+      invalid: 3,
+      failed: 1
+    });
+  });
+});
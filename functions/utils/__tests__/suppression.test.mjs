@@ -0,0 +1,47 @@
+/**
+ * Unit tests for suppression list matching.
+ */
+
+import { buildSuppressionList, emailDomain, isClearedByOptIn, isSuppressedAddress, suppressionSk } from '../suppression.mjs';
+
+describe('isSuppressedAddress', () => {
+  const list = buildSuppressionList([
+    { entry: 'gone@example.com', type: 'email' },
+    { entry: 'blocked.test', type: 'domain' },
+    { entry: 'Mixed@Example.org', type: 'email' }
+  ]);
+
+  it('matches addresses case-insensitively', () => {
+    expect(isSuppressedAddress(list, ' GONE@example.com ')).toBe(true);
+    expect(isSuppressedAddress(list, 'mixed@example.org')).toBe(true);
+    expect(isSuppressedAddress(list, 'kept@example.com')).toBe(false);
+  });
+
+  it('matches every address at a suppressed domain but not its subdomains', () => {
+    expect(isSuppressedAddress(list, 'anyone@blocked.test')).toBe(true);
+    expect(isSuppressedAddress(list, 'anyone@mail.blocked.test')).toBe(false);
+  });
+
+  it('treats a missing list or address as not suppressed', () => {
+    expect(isSuppressedAddress(undefined, 'gone@example.com')).toBe(false);
+    expect(isSuppressedAddress(list, '')).toBe(false);
+  });
+});
+
+describe('keys', () => {
+  it('builds sort keys and splits off domains', () => {
+    expect(suppressionSk('example.com')).toBe('suppression#example.com');
+    expect(emailDomain('reader@example.com')).toBe('example.com');
+    expect(emailDomain('reader@')).toBeNull();
+  });
+});
+
+describe('isClearedByOptIn', () => {
+  it('only lifts an address entry its own unsubscribe added', () => {
+    expect(isClearedByOptIn({ type: 'email', reason: 'manual', source: 'unsubscribe' })).toBe(true);
+    expect(isClearedByOptIn({ type: 'email', reason: 'manual', source: 'subscriber-delete' })).toBe(false);
+    expect(isClearedByOptIn({ type: 'email', reason: 'complaint', source: 'complaint' })).toBe(false);
+    expect(isClearedByOptIn({ type: 'domain', reason: 'manual', source: 'unsubscribe' })).toBe(false);
+    expect(isClearedByOptIn(null)).toBe(false);
+  });
+});
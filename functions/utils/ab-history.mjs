@@ -38,6 +38,7 @@ export const buildAbHistoryRecord = ({ tenantId, issueNumber, abTest, counters =
       variantId: variant.variantId,
       ...(variant.subject !== undefined && { subject: variant.subject }),
       ...(variant.sendAt !== undefined && { sendAt: variant.sendAt }),
+      ...(variant.templateId !== undefined && { templateId: variant.templateId }),
       opens,
       clicks,
       deliveries,
@@ -3,7 +3,8 @@
  *
  * Pure, dependency-free module. Implements a two-proportion z-test used to
  * decide whether one variant's engagement rate (open rate by default, or click
- * rate) beats the other with the configured statistical confidence.
+ * rate) beats the others with the configured statistical confidence, and the
+ * bandit allocation shares used by adaptive tests (see ab_stats.rs).
  */
 
 // Abramowitz & Stegun 7.1.26 approximation of the error function. Max error
@@ -66,61 +67,81 @@ export const twoProportionZTest = (successesA, nA, successesB, nB) => {
 };
 
 /**
- * Evaluates an A/B test result and decides the winner.
+ * Evaluates an A/B/n test result and decides the winner.
  *
- * A variant is only declared the winner when (a) both variants have at least
- * `minSamplePerVariant` deliveries and (b) the difference in the win-metric
- * rate is statistically significant at the configured confidence. Otherwise the
- * result is inconclusive (the caller falls back to the control, variant "a").
+ * The leader is the variant with the highest win-metric rate (ties go to the
+ * earliest, the control first). It is only declared the winner when (a) every
+ * variant has at least `minSamplePerVariant` deliveries and (b) the leader
+ * beats every other variant significantly, with the (1 - confidence) error
+ * budget split across those comparisons (Bonferroni). Otherwise the result is
+ * inconclusive (the caller falls back to the control, variant "a").
  *
- * @param {{opens?: number, clicks?: number, deliveries?: number}} a - Variant A counters.
- * @param {{opens?: number, clicks?: number, deliveries?: number}} b - Variant B counters.
+ * @param {Object<string, {opens?: number, clicks?: number, deliveries?: number}>} counters
+ *   Per-variant counters keyed by variantId, in the test's variant order.
  * @param {{winMetric?: string, confidence?: number, minSamplePerVariant?: number}} opts
- * @returns {{significant: boolean, winnerVariantId: ("a"|"b"|null), status: ("sent"|"inconclusive"), evaluation: object}}
+ * @returns {{significant: boolean, winnerVariantId: (string|null), status: ("sent"|"inconclusive"), evaluation: object}}
  */
-export const evaluateAbResult = (a, b, opts = {}) => {
+export const evaluateAbTest = (counters, opts = {}) => {
   const winMetric = opts.winMetric === 'clickRate' ? 'clickRate' : 'openRate';
   const metricField = winMetric === 'clickRate' ? 'clicks' : 'opens';
   const confidence = typeof opts.confidence === 'number' ? opts.confidence : 0.95;
   const minSamplePerVariant =
     typeof opts.minSamplePerVariant === 'number' ? opts.minSamplePerVariant : 0;
 
-  const successesA = a?.[metricField] || 0;
-  const successesB = b?.[metricField] || 0;
-  const deliveriesA = a?.deliveries || 0;
-  const deliveriesB = b?.deliveries || 0;
+  const arms = Object.entries(counters ?? {}).map(([variantId, c]) => {
+    const successes = c?.[metricField] || 0;
+    const deliveries = c?.deliveries || 0;
+    return { variantId, successes, deliveries, rate: deliveries > 0 ? successes / deliveries : 0 };
+  });
 
-  const rateA = deliveriesA > 0 ? successesA / deliveriesA : 0;
-  const rateB = deliveriesB > 0 ? successesB / deliveriesB : 0;
+  let leader = arms[0];
+  for (const arm of arms.slice(1)) {
+    if (arm.rate > leader.rate) {
+      leader = arm;
+    }
+  }
 
-  const { zScore, pValue } = twoProportionZTest(
-    successesA,
-    deliveriesA,
-    successesB,
-    deliveriesB
+  // The leader against each rival (z positive when the leader is ahead).
+  const comparisons = arms
+    .filter((arm) => arm !== leader)
+    .map((arm) => ({
+      variantId: arm.variantId,
+      rate: arm.rate,
+      ...twoProportionZTest(arm.successes, arm.deliveries, leader.successes, leader.deliveries)
+    }));
+  // The closest rival is the comparison that decides significance.
+  const closest = comparisons.reduce(
+    (worst, c) => (worst === null || c.pValue > worst.pValue ? c : worst),
+    null
   );
 
+  const alpha = (1 - confidence) / Math.max(comparisons.length, 1);
   const enoughData =
-    deliveriesA >= minSamplePerVariant && deliveriesB >= minSamplePerVariant;
-  // Two-tailed test: significant when p-value is within the (1 - confidence)
-  // rejection region and the rates actually differ.
-  const significant = enoughData && rateA !== rateB && pValue <= 1 - confidence;
-
-  let winnerVariantId = null;
-  let status = 'inconclusive';
-  if (significant) {
-    winnerVariantId = rateB > rateA ? 'b' : 'a';
-    status = 'sent';
-  }
+    arms.length >= 2 && arms.every((arm) => arm.deliveries >= minSamplePerVariant);
+  // Two-tailed tests: significant when every p-value is within the corrected
+  // rejection region and the leader's rate is strictly ahead of every rival.
+  const significant =
+    enoughData && comparisons.every((c) => c.rate < leader.rate && c.pValue <= alpha);
+
+  const winnerVariantId = significant ? leader.variantId : null;
+  const status = significant ? 'sent' : 'inconclusive';
+
+  const summaries = Object.fromEntries(
+    arms.map((arm) => [
+      arm.variantId,
+      { successes: arm.successes, deliveries: arm.deliveries, rate: arm.rate }
+    ])
+  );
 
   const evaluation = {
     winMetric,
     confidence,
     minSamplePerVariant,
-    variantA: { successes: successesA, deliveries: deliveriesA, rate: rateA },
-    variantB: { successes: successesB, deliveries: deliveriesB, rate: rateB },
-    zScore,
-    pValue,
+    variants: summaries,
+    ...summaries.a && { variantA: summaries.a },
+    ...summaries.b && { variantB: summaries.b },
+    zScore: closest?.zScore ?? 0,
+    pValue: closest?.pValue ?? 1,
     enoughData,
     significant,
     winnerVariantId,
@@ -129,3 +150,110 @@ export const evaluateAbResult = (a, b, opts = {}) => {
 
   return { significant, winnerVariantId, status, evaluation };
 };
+
+/**
+ * Evaluates a two-variant (a/b) test; see {@link evaluateAbTest}.
+ *
+ * @param {{opens?: number, clicks?: number, deliveries?: number}} a - Variant A counters.
+ * @param {{opens?: number, clicks?: number, deliveries?: number}} b - Variant B counters.
+ * @param {{winMetric?: string, confidence?: number, minSamplePerVariant?: number}} opts
+ * @returns {{significant: boolean, winnerVariantId: ("a"|"b"|null), status: ("sent"|"inconclusive"), evaluation: object}}
+ */
+export const evaluateAbResult = (a, b, opts = {}) => evaluateAbTest({ a, b }, opts);
+
+// Bandit allocation modes; mirror ab_stats.rs.
+export const ALLOCATION_FIXED = 'fixed';
+export const ALLOCATION_THOMPSON = 'thompson';
+export const ALLOCATION_EPSILON_GREEDY = 'epsilonGreedy';
+const DEFAULT_EPSILON = 0.1;
+const THOMPSON_DRAWS = 10000;
+
+// Box-Muller standard normal draw.
+const sampleStandardNormal = (random) => {
+  const u1 = Math.max(random(), Number.MIN_VALUE);
+  const u2 = random();
+  return Math.sqrt(-2 * Math.log(u1)) * Math.cos(2 * Math.PI * u2);
+};
+
+// Marsaglia-Tsang Gamma(shape, 1) sampler, boosted for shapes below 1.
+const sampleGamma = (random, shape) => {
+  if (shape < 1) {
+    const u = Math.max(random(), Number.MIN_VALUE);
+    return sampleGamma(random, shape + 1) * Math.pow(u, 1 / shape);
+  }
+
+  const d = shape - 1 / 3;
+  const c = 1 / Math.sqrt(9 * d);
+  for (;;) {
+    const x = sampleStandardNormal(random);
+    const v = Math.pow(1 + c * x, 3);
+    if (v <= 0) {
+      continue;
+    }
+    const u = random();
+    if (u < 1 - 0.0331 * x ** 4 || Math.log(u) < 0.5 * x * x + d * (1 - v + Math.log(v))) {
+      return d * v;
+    }
+  }
+};
+
+const sampleBeta = (random, alpha, beta) => {
+  const x = sampleGamma(random, alpha);
+  const y = sampleGamma(random, beta);
+  return x + y > 0 ? x / (x + y) : 0.5;
+};
+
+/**
+ * Share of the next send batch for each variant, from its counters on the
+ * win metric. `fixed` splits evenly; `epsilonGreedy` gives the leader
+ * `1 - epsilon` plus an even `epsilon` slice everyone gets; `thompson` gives
+ * each variant the probability that it is the best under a Beta(1, 1) prior.
+ * A test with no deliveries yet starts from an (near-)even split in every mode.
+ *
+ * @param {{mode?: string, epsilon?: number}} allocation - The test's allocation config.
+ * @param {{successes: number, trials: number}[]} arms - Counters per variant, in order.
+ * @param {() => number} [random] - Uniform [0, 1) source, for tests.
+ * @returns {number[]} One share per arm, summing to 1.
+ */
+export const allocationWeights = (allocation, arms, random = Math.random) => {
+  const n = arms.length;
+  if (n === 0) {
+    return [];
+  }
+  const even = Array(n).fill(1 / n);
+  const mode = allocation?.mode;
+
+  if (mode === ALLOCATION_EPSILON_GREEDY) {
+    if (arms.every((arm) => arm.trials <= 0)) {
+      return even;
+    }
+    const epsilon = typeof allocation.epsilon === 'number' ? allocation.epsilon : DEFAULT_EPSILON;
+    const rates = arms.map((arm) => (arm.trials > 0 ? Math.min(arm.successes, arm.trials) / arm.trials : 0));
+    const leader = rates.indexOf(Math.max(...rates));
+    return rates.map((_, i) => (i === leader ? 1 - epsilon : 0) + epsilon / n);
+  }
+
+  if (mode === ALLOCATION_THOMPSON) {
+    const posteriors = arms.map((arm) => {
+      const trials = Math.max(arm.trials, 0);
+      const successes = Math.min(Math.max(arm.successes, 0), trials);
+      return [1 + successes, 1 + trials - successes];
+    });
+    const wins = Array(n).fill(0);
+    for (let draw = 0; draw < THOMPSON_DRAWS; draw++) {
+      let best = 0;
+      let bestDraw = -Infinity;
+      posteriors.forEach(([alpha, beta], i) => {
+        const sample = sampleBeta(random, alpha, beta);
+        if (sample > bestDraw) {
+          bestDraw = sample;
+          best = i;
+        }
+      });
+      wins[best] += 1;
+    }
+    return wins.map((w) => w / THOMPSON_DRAWS);
+  }
+
+  return even;
+};
@@ -29,35 +29,67 @@ export const hashToInt = (value) => {
   return hash >>> 0;
 };
 
+// Every variant id a test may use, in order (tests run 2 to 6 variants).
+export const AB_VARIANT_IDS = ['a', 'b', 'c', 'd', 'e', 'f'];
+
+// Variant ids of a test that does not list its own (the original a/b split).
+const DEFAULT_VARIANT_IDS = AB_VARIANT_IDS.slice(0, 2);
+
 /**
- * Deterministically pick a variant ("a" or "b") for a given email + seed.
+ * Deterministically pick a variant for a given email + seed.
  *
- * Same inputs always produce the same output, across processes. The split is
- * approximately 50/50 over a realistic set of email addresses.
+ * Same inputs always produce the same output, across processes. Without
+ * weights the variants share recipients approximately evenly (a two-variant
+ * test keeps its original a/b assignment). With weights, each variant gets
+ * that share of recipients instead; weights need not sum to 1.
  *
  * @param {string} email - Recipient email address.
  * @param {string} seed - Stable seed (the issue's referenceNumber).
- * @returns {"a" | "b"} The assigned variant id.
+ * @param {string[]} [variantIds] - Variant ids in their configured order.
+ * @param {number[]} [weights] - Optional share per variant, aligned with variantIds.
+ * @returns {string} The assigned variant id.
  */
-export const pickVariant = (email, seed) => {
+export const pickVariant = (email, seed, variantIds = DEFAULT_VARIANT_IDS, weights) => {
+  const ids = variantIds?.length ? variantIds : DEFAULT_VARIANT_IDS;
   const hash = hashToInt(`${seed}:${email}`);
-  return (hash & 1) === 0 ? 'a' : 'b';
+
+  const shares = Array.isArray(weights) && weights.length === ids.length
+    ? weights.map((weight) => (Number(weight) > 0 ? Number(weight) : 0))
+    : null;
+  const total = shares ? shares.reduce((sum, share) => sum + share, 0) : 0;
+  if (!(total > 0)) {
+    return ids[hash % ids.length];
+  }
+
+  const point = ((hash % 10000) / 10000) * total;
+  let cumulative = 0;
+  for (let i = 0; i < ids.length; i++) {
+    cumulative += shares[i];
+    if (point < cumulative) {
+      return ids[i];
+    }
+  }
+  return ids[ids.length - 1];
 };
 
 /**
- * Split recipients into deterministic A/B buckets using {@link pickVariant}.
+ * Split recipients into deterministic variant buckets using {@link pickVariant}.
  *
- * Input order is preserved within each bucket.
+ * Input order is preserved within each bucket, and every variant gets a
+ * bucket even when it is empty.
  *
  * @param {string[]} emails - Recipient email addresses.
  * @param {string} seed - Stable seed (the issue's referenceNumber).
- * @returns {{ a: string[], b: string[] }} Recipients partitioned by variant.
+ * @param {string[]} [variantIds] - Variant ids in their configured order.
+ * @param {number[]} [weights] - Optional share per variant, aligned with variantIds.
+ * @returns {Object<string, string[]>} Recipients partitioned by variant id.
  */
-export const splitRecipients = (emails, seed) => {
-  const buckets = { a: [], b: [] };
+export const splitRecipients = (emails, seed, variantIds = DEFAULT_VARIANT_IDS, weights) => {
+  const ids = variantIds?.length ? variantIds : DEFAULT_VARIANT_IDS;
+  const buckets = Object.fromEntries(ids.map((id) => [id, []]));
 
   for (const email of emails ?? []) {
-    buckets[pickVariant(email, seed)].push(email);
+    buckets[pickVariant(email, seed, ids, weights)].push(email);
   }
 
   return buckets;
@@ -68,7 +100,7 @@ export const splitRecipients = (emails, seed) => {
  * the list) and the hold-out remainder.
  *
  * Uses a distinct seed suffix (`${seed}:sample:${email}`) so sample membership
- * is independent of a/b variant assignment from {@link pickVariant}. Input
+ * is independent of variant assignment from {@link pickVariant}. Input
  * order is preserved within both partitions, and membership is stable across
  * processes for the same email + seed + fraction.
  *
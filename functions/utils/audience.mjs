@@ -0,0 +1,82 @@
+/**
+ * Pure matching helpers for issue audience targeting.
+ *
+ * An issue's `audience` (validated and normalized by the API) narrows a list
+ * send: subscribers must be in any `includeSegments` (everyone when empty),
+ * must not be in any `excludeSegments`, and when set must have an interest
+ * score >= `minInterestScore` in one of `interests` and a confirmed timezone in
+ * `timeZones`. The API uses the same rules to estimate recipients for
+ * GET /issues/:id.
+ */
+
+import { AUTO_SEGMENT_THRESHOLD } from './topic-taxonomy.mjs';
+
+/** Interest score a subscriber needs when the audience doesn't set one. */
+export const DEFAULT_MIN_INTEREST_SCORE = AUTO_SEGMENT_THRESHOLD;
+
+const list = (value) => (Array.isArray(value) ? value : []);
+
+/**
+ * Whether an audience actually narrows the send.
+ * @param {Object|null|undefined} audience
+ * @returns {boolean}
+ */
+export function hasAudienceFilter(audience) {
+  if (!audience || typeof audience !== 'object') {
+    return false;
+  }
+  return ['includeSegments', 'excludeSegments', 'interests', 'timeZones']
+    .some((field) => list(audience[field]).length > 0);
+}
+
+/**
+ * Whether one subscriber is part of the audience.
+ * @param {Object} audience - Normalized audience config
+ * @param {{email: string, timeZone?: string, interestScores?: Object}} subscriber
+ * @param {{included: Set<string>|null, excluded: Set<string>}} memberships -
+ *   Emails in any included segment (null when there is no include list) and in any excluded segment
+ * @returns {boolean}
+ */
+export function matchesAudience(audience, subscriber, { included, excluded }) {
+  const email = subscriber?.email;
+  if (!email) {
+    return false;
+  }
+  if ((included && !included.has(email)) || excluded?.has(email)) {
+    return false;
+  }
+
+  const interests = list(audience.interests);
+  if (interests.length > 0) {
+    const minScore = Number.isFinite(audience.minInterestScore)
+      ? audience.minInterestScore
+      : DEFAULT_MIN_INTEREST_SCORE;
+    const scores = subscriber.interestScores || {};
+    const interested = interests.some((topic) => Number(scores[topic]?.score) >= minScore);
+    if (!interested) {
+      return false;
+    }
+  }
+
+  const timeZones = list(audience.timeZones);
+  if (timeZones.length > 0 && !timeZones.includes(subscriber.timeZone)) {
+    return false;
+  }
+
+  return true;
+}
+
+/**
+ * Filters subscribers down to the audience. Returns the input unchanged when
+ * the audience has no criteria.
+ * @param {Array<Object>} subscribers
+ * @param {Object|null|undefined} audience
+ * @param {{included: Set<string>|null, excluded: Set<string>}} memberships
+ * @returns {Array<Object>}
+ */
+export function filterSubscribersForAudience(subscribers, audience, memberships) {
+  if (!hasAudienceFilter(audience)) {
+    return subscribers;
+  }
+  return subscribers.filter((subscriber) => matchesAudience(audience, subscriber, memberships));
+}
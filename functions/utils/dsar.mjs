@@ -0,0 +1,223 @@
+/**
+ * Data subject request helpers: the access bundle, the issue stats to take
+ * back out on erasure, and the hash-chained erasure receipts.
+ *
+ * Jobs are created by functions/src/api/controllers/subscriber_dsar.rs and
+ * worked by subscribers/process-subscriber-dsar.mjs.
+ */
+
+import crypto from 'crypto';
+
+export const DSAR_RECEIPT_SK_PREFIX = 'dsar-receipt#';
+export const DSAR_RECEIPT_HEAD_SK = 'dsar-receipt-head';
+/** `previousHash` of the first receipt in a tenant's chain. */
+export const GENESIS_HASH = '0'.repeat(64);
+/** Replaces an erased address in an issue's `failedAddresses` list. */
+export const REDACTED_ADDRESS = '[erased]';
+
+/** Per-issue engagement event records, by sort key prefix. */
+export const EVENT_KINDS = {
+  'open#': 'opens',
+  'click#': 'clicks',
+  'bounce#': 'bounces',
+  'complaint#': 'complaints'
+};
+
+const BOT_FLAGS = ['honeypotTriggered', 'disposableDomain', 'suspiciousUserAgent', 'fastSubmission', 'suspiciousEmailPattern'];
+/** Attributes on the subscriber record reported in their own bundle section (or not at all). */
+const SECTIONED_ATTRIBUTES = new Set(['tenantId', 'recentActivity', 'interestScores', ...BOT_FLAGS]);
+/** Storage keys and tracking internals stripped from event records. */
+const EVENT_INTERNALS = new Set(['pk', 'sk', 'subscriberEmailHash', 'ttl', 'GSI1PK', 'GSI1SK']);
+/** Counters handle-email-status also keeps per mailbox provider (`deliveries` has no event record). */
+const PROVIDER_COUNTERS = new Set(['opens', 'bounces', 'complaints']);
+/** Padding for sequence numbers so receipts sort in chain order. */
+const SEQUENCE_WIDTH = 10;
+
+/** Event kind ('opens', 'clicks', ...) for an event sort key, or null. */
+export const eventKind = (sk) => {
+  const prefix = Object.keys(EVENT_KINDS).find((candidate) => String(sk ?? '').startsWith(candidate));
+  return prefix ? EVENT_KINDS[prefix] : null;
+};
+
+/** Issue number from an issue partition key (`<tenantId>#<issueNumber>`). */
+export const issueNumberFromPk = (pk) => Number.parseInt(String(pk ?? '').split('#')[1], 10);
+
+const stripInternals = (event) => Object.fromEntries(
+  Object.entries(event).filter(([key]) => !EVENT_INTERNALS.has(key))
+);
+
+/**
+ * Everything stored about one address, grouped the way a reader would look
+ * for it. `events` are the raw per-issue event records (with `pk`/`sk`),
+ * `failedIssuePks` the issues whose stats list the address as undeliverable,
+ * `suppression` the address's own suppression list entry.
+ */
+export const buildAccessBundle = ({
+  email,
+  generatedAt,
+  subscriber = null,
+  segments = [],
+  events = [],
+  failedIssuePks = [],
+  departures = [],
+  suppression = null
+}) => {
+  const issues = new Map();
+  const issueFor = (pk) => {
+    if (!issues.has(pk)) {
+      issues.set(pk, { issueNumber: issueNumberFromPk(pk), opens: [], clicks: [], bounces: [], complaints: [] });
+    }
+    return issues.get(pk);
+  };
+
+  for (const event of events) {
+    const kind = eventKind(event.sk);
+    if (kind) {
+      issueFor(event.pk)[kind].push(stripInternals(event));
+    }
+  }
+  for (const pk of failedIssuePks) {
+    issueFor(pk).deliveryFailed = true;
+  }
+
+  const profile = subscriber
+    ? Object.fromEntries(Object.entries(subscriber).filter(([key]) => !SECTIONED_ATTRIBUTES.has(key)))
+    : null;
+  const botFlags = subscriber
+    ? Object.fromEntries(BOT_FLAGS.filter((flag) => subscriber[flag] !== undefined).map((flag) => [flag, subscriber[flag]]))
+    : {};
+
+  return {
+    email,
+    generatedAt,
+    subscriber: profile,
+    recentActivity: subscriber?.recentActivity ?? [],
+    interestScores: subscriber?.interestScores ?? {},
+    botFlags,
+    segments,
+    issues: [...issues.values()]
+      .map((issue) => ({
+        ...issue,
+        ...Object.fromEntries(Object.values(EVENT_KINDS).map((kind) => [
+          kind,
+          issue[kind].sort((a, b) => String(a.timestamp).localeCompare(String(b.timestamp)))
+        ]))
+      }))
+      .sort((a, b) => a.issueNumber - b.issueNumber),
+    departures: departures.map(({ departedAt, reason, addedAt }) => ({ departedAt, reason, ...(addedAt && { addedAt }) })),
+    suppression: suppression ? stripInternals(suppression) : null
+  };
+};
+
+/** Record counts for the job summary. */
+export const summarizeBundle = (bundle) => {
+  const count = (kind) => bundle.issues.reduce((total, issue) => total + issue[kind].length, 0);
+  return {
+    subscriberRecords: bundle.subscriber ? 1 : 0,
+    segmentMemberships: bundle.segments.length,
+    opens: count('opens'),
+    clicks: count('clicks'),
+    bounces: count('bounces'),
+    complaints: count('complaints'),
+    departures: bundle.departures.length
+  };
+};
+
+/**
+ * How much to take off each issue's stats counters once these events are
+ * deleted, as issue pk -> { stats record sk -> { counter: amount } }.
+ * handle-email-status counts the first open of an issue under `opens` and
+ * later ones under `reopens`, and every click, bounce and complaint once, on
+ * `stats` and on the `stats#v#<id>` record of the event's variant. Opens,
+ * bounces and complaints also go on `stats#provider#<provider>`. Event
+ * records from before the variant and provider were kept on them only come
+ * off `stats`.
+ */
+export const statDecrements = (events) => {
+  const byIssue = new Map();
+
+  for (const event of events) {
+    const kind = eventKind(event.sk);
+    if (!kind) {
+      continue;
+    }
+    const records = byIssue.get(event.pk) ?? {};
+    const totals = records.stats ?? {};
+    const counter = kind === 'opens' && totals.opens ? 'reopens' : kind;
+
+    const recordKeys = ['stats'];
+    if (event.variant) {
+      recordKeys.push(`stats#v#${event.variant}`);
+    }
+    if (event.provider && PROVIDER_COUNTERS.has(counter)) {
+      recordKeys.push(`stats#provider#${event.provider}`);
+    }
+    for (const sk of recordKeys) {
+      const counts = records[sk] ?? {};
+      counts[counter] = (counts[counter] ?? 0) + 1;
+      records[sk] = counts;
+    }
+    byIssue.set(event.pk, records);
+  }
+
+  return byIssue;
+};
+
+/** Positions of `email` in an issue's `failedAddresses` list. */
+export const failedAddressIndexes = (failedAddresses, email) => (failedAddresses ?? [])
+  .map((address, index) => (String(address).toLowerCase() === email ? index : -1))
+  .filter((index) => index !== -1);
+
+/** JSON with object keys sorted at every level, so a hash doesn't depend on key order. */
+export const canonicalJson = (value) => {
+  if (Array.isArray(value)) {
+    return `[${value.map(canonicalJson).join(',')}]`;
+  }
+  if (value && typeof value === 'object') {
+    return `{${Object.keys(value).sort().map((key) => `${JSON.stringify(key)}:${canonicalJson(value[key])}`).join(',')}}`;
+  }
+  return JSON.stringify(value);
+};
+
+const hashReceiptBody = (body) => crypto.createHash('sha256').update(canonicalJson(body)).digest('hex');
+
+export const receiptSk = (sequence) => `${DSAR_RECEIPT_SK_PREFIX}${String(sequence).padStart(SEQUENCE_WIDTH, '0')}`;
+
+/**
+ * The next receipt in a tenant's chain. `receiptHash` covers every other
+ * field, including `previousHash`, so editing or removing any earlier
+ * receipt breaks every hash after it.
+ */
+export const buildReceipt = ({ previous, tenantId, jobId, emailHash, requestedBy, requestedAt, completedAt, erased }) => {
+  const body = {
+    tenantId,
+    sequence: (previous?.sequence ?? 0) + 1,
+    previousHash: previous?.receiptHash ?? GENESIS_HASH,
+    jobId,
+    emailHash,
+    requestedBy,
+    requestedAt,
+    completedAt,
+    erased
+  };
+  return { ...body, receiptHash: hashReceiptBody(body) };
+};
+
+/**
+ * Checks a tenant's receipts, in sequence order. Returns `{ valid: true }` or
+ * `{ valid: false, sequence }` for the first receipt that was altered, is out
+ * of place, or doesn't follow on from the one before it.
+ */
+export const verifyReceiptChain = (receipts) => {
+  let previousHash = GENESIS_HASH;
+
+  for (const [index, receipt] of receipts.entries()) {
+    const { receiptHash, ...body } = receipt;
+    if (receipt.sequence !== index + 1 || receipt.previousHash !== previousHash || hashReceiptBody(body) !== receiptHash) {
+      return { valid: false, sequence: receipt.sequence };
+    }
+    previousHash = receiptHash;
+  }
+
+  return { valid: true };
+};
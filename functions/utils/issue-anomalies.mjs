@@ -0,0 +1,153 @@
+/**
+ * Issue metric anomaly detection.
+ *
+ * Rates are percentages of deliveries, computed the way `calculate_issue_metrics`
+ * in functions/src/api/controllers/issues.rs does, so an alert's value matches
+ * what the dashboard shows for the issue. The baseline is the same window
+ * `query_published_issues_with_stats` reads for `/issues/trends`: the tenant's
+ * most recent published issues, here the ones published before the issue being
+ * evaluated.
+ *
+ * Alert settings and records are read back by
+ * functions/src/api/controllers/alerts.rs; keep the defaults and key layout in
+ * sync with it.
+ */
+
+// ── Metrics (keep in sync with alerts.rs) ──────────────────────────────
+// `spike` metrics alert when the issue is above the baseline, `drop` metrics
+// when it is below. `minStdDev` (percentage points) stops a perfectly flat
+// baseline — no complaints on any recent issue, say — from turning the first
+// tiny change into an infinite z-score.
+export const ANOMALY_METRICS = [
+  { metric: 'bounceRate', counter: 'bounces', direction: 'spike', minStdDev: 0.1 },
+  { metric: 'complaintRate', counter: 'complaints', direction: 'spike', minStdDev: 0.01 },
+  { metric: 'openRate', counter: 'opens', direction: 'drop', minStdDev: 1 },
+  { metric: 'clickRate', counter: 'clicks', direction: 'drop', minStdDev: 0.2 }
+];
+
+export const DEFAULT_ALERT_SETTINGS = {
+  enabled: true,
+  zScoreThreshold: 3,
+  baselineIssueCount: 10,
+  emailNotifications: false
+};
+
+/** Fewer comparable issues than this and there is no baseline to speak of. */
+export const MIN_BASELINE_ISSUES = 3;
+/** Issues (evaluated or baseline) with fewer deliveries are too noisy to compare. */
+export const MIN_DELIVERIES = 50;
+
+export const ALERT_SETTINGS_SK = 'alert-settings';
+export const ALERT_SK_PREFIX = 'alert#';
+
+export const padIssueNumber = (issueNumber) => String(issueNumber).padStart(5, '0');
+
+/** Sort key of the alert for one metric of one issue; one alert per pair. */
+export const alertSortKey = (issueNumber, metric) => `${ALERT_SK_PREFIX}${padIssueNumber(issueNumber)}#${metric}`;
+
+export const alertId = (issueNumber, metric) => `${issueNumber}-${metric}`;
+
+const toNumber = (value) => {
+  const n = Number(value);
+  return Number.isFinite(n) ? n : 0;
+};
+
+const round = (value, decimals) => {
+  const factor = 10 ** decimals;
+  return Math.round(value * factor) / factor;
+};
+
+/**
+ * Settings record merged over the defaults. Out-of-range values (the API
+ * validates on write, but the record is plain data) fall back to the default.
+ */
+export const resolveAlertSettings = (record) => {
+  const settings = { ...DEFAULT_ALERT_SETTINGS };
+  if (!record) {
+    return settings;
+  }
+
+  if (typeof record.enabled === 'boolean') {
+    settings.enabled = record.enabled;
+  }
+  if (typeof record.emailNotifications === 'boolean') {
+    settings.emailNotifications = record.emailNotifications;
+  }
+  const threshold = Number(record.zScoreThreshold);
+  if (Number.isFinite(threshold) && threshold > 0) {
+    settings.zScoreThreshold = threshold;
+  }
+  const baselineIssueCount = Math.trunc(Number(record.baselineIssueCount));
+  if (Number.isFinite(baselineIssueCount) && baselineIssueCount >= MIN_BASELINE_ISSUES) {
+    settings.baselineIssueCount = baselineIssueCount;
+  }
+
+  return settings;
+};
+
+/**
+ * Percentage rates of a stats record, or null when it has too few deliveries
+ * to compare.
+ */
+export const issueRates = (stats) => {
+  const deliveries = toNumber(stats?.deliveries);
+  if (deliveries < MIN_DELIVERIES) {
+    return null;
+  }
+
+  return Object.fromEntries(
+    ANOMALY_METRICS.map(({ metric, counter }) => [metric, (toNumber(stats[counter]) / deliveries) * 100])
+  );
+};
+
+/** Mean and sample standard deviation. */
+export const meanAndStdDev = (values) => {
+  if (values.length === 0) {
+    return { mean: 0, stdDev: 0 };
+  }
+  const mean = values.reduce((sum, v) => sum + v, 0) / values.length;
+  if (values.length < 2) {
+    return { mean, stdDev: 0 };
+  }
+  const variance = values.reduce((sum, v) => sum + (v - mean) ** 2, 0) / (values.length - 1);
+  return { mean, stdDev: Math.sqrt(variance) };
+};
+
+/**
+ * Compares an issue's rates with the baseline issues' and returns one entry
+ * per metric whose z-score is beyond `zScoreThreshold` in the metric's alert
+ * direction. Returns an empty list when the issue or the baseline is too
+ * small to judge.
+ */
+export const detectAnomalies = (issueStats, baselineStats, { zScoreThreshold = DEFAULT_ALERT_SETTINGS.zScoreThreshold } = {}) => {
+  const current = issueRates(issueStats);
+  if (!current) {
+    return [];
+  }
+
+  const baseline = baselineStats.map(issueRates).filter(Boolean);
+  if (baseline.length < MIN_BASELINE_ISSUES) {
+    return [];
+  }
+
+  const anomalies = [];
+  for (const { metric, direction, minStdDev } of ANOMALY_METRICS) {
+    const { mean, stdDev } = meanAndStdDev(baseline.map((rates) => rates[metric]));
+    const zScore = (current[metric] - mean) / Math.max(stdDev, minStdDev);
+    const beyond = direction === 'spike' ? zScore >= zScoreThreshold : zScore <= -zScoreThreshold;
+    if (beyond) {
+      anomalies.push({
+        metric,
+        direction,
+        value: round(current[metric], 4),
+        baselineMean: round(mean, 4),
+        baselineStdDev: round(stdDev, 4),
+        zScore: round(zScore, 2),
+        threshold: zScoreThreshold,
+        baselineIssues: baseline.length
+      });
+    }
+  }
+
+  return anomalies;
+};
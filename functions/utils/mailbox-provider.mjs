@@ -0,0 +1,99 @@
+import { Resolver } from 'dns/promises';
+
+/**
+ * Mailbox provider buckets used for per-issue deliverability stats. The ids
+ * are the `stats#provider#<id>` sort-key suffixes the API reads back.
+ */
+export const MAILBOX_PROVIDERS = ['gmail', 'outlook', 'yahoo', 'icloud', 'other'];
+
+// Consumer domains each provider hosts directly.
+const PROVIDER_DOMAINS = {
+  gmail: ['gmail.com', 'googlemail.com'],
+  outlook: ['outlook.com', 'hotmail.com', 'live.com', 'msn.com', 'passport.com', 'hotmail.co.uk', 'hotmail.fr', 'hotmail.de', 'hotmail.it', 'hotmail.es', 'live.co.uk', 'live.fr', 'live.de', 'live.nl', 'live.ca', 'live.com.au', 'outlook.fr', 'outlook.de', 'outlook.es', 'outlook.it'],
+  yahoo: ['yahoo.com', 'ymail.com', 'rocketmail.com', 'aol.com', 'aim.com', 'verizon.net', 'att.net', 'sbcglobal.net', 'yahoo.co.uk', 'yahoo.co.jp', 'yahoo.fr', 'yahoo.de', 'yahoo.es', 'yahoo.it', 'yahoo.ca', 'yahoo.com.au', 'yahoo.com.br', 'yahoo.co.in'],
+  icloud: ['icloud.com', 'me.com', 'mac.com']
+};
+
+// MX host suffixes of the providers' hosted mail (Google Workspace,
+// Microsoft 365, Yahoo/AOL, iCloud custom domains). A company domain whose
+// mail is hosted by one of them is filtered by that provider, so it lands in
+// the provider's bucket rather than "other".
+const PROVIDER_MX_SUFFIXES = {
+  gmail: ['google.com', 'googlemail.com'],
+  outlook: ['outlook.com', 'hotmail.com', 'protection.outlook.com'],
+  yahoo: ['yahoodns.net', 'yahoo.com', 'aol.com'],
+  icloud: ['icloud.com', 'me.com']
+};
+
+const DOMAIN_LOOKUP = new Map(
+  Object.entries(PROVIDER_DOMAINS).flatMap(([provider, domains]) => domains.map((domain) => [domain, provider]))
+);
+
+const MX_CACHE_MAX = 5000;
+// Per container: most lists are dominated by a few hundred domains.
+const mxCache = new Map();
+
+const resolver = new Resolver({ timeout: 1000, tries: 1 });
+
+/**
+ * Provider for a recipient domain from the built-in domain table, or null
+ * when the domain isn't a known consumer mailbox domain.
+ */
+export const providerFromDomain = (domain) => DOMAIN_LOOKUP.get(String(domain || '').trim().toLowerCase()) || null;
+
+/**
+ * Provider for a list of MX hostnames, or 'other' when none matches a known
+ * provider's mail hosts.
+ */
+export const providerFromMx = (exchanges = []) => {
+  for (const exchange of exchanges) {
+    const host = String(exchange || '').trim().toLowerCase().replace(/\.$/, '');
+    for (const [provider, suffixes] of Object.entries(PROVIDER_MX_SUFFIXES)) {
+      if (suffixes.some((suffix) => host === suffix || host.endsWith(`.${suffix}`))) {
+        return provider;
+      }
+    }
+  }
+  return 'other';
+};
+
+/**
+ * Classifies a recipient address into one of MAILBOX_PROVIDERS: the domain
+ * table first, then the domain's MX records. Lookup errors fall back to
+ * 'other'; only a definitive "no MX" answer is cached, so timeouts retry on
+ * a later event.
+ */
+export const classifyMailboxProvider = async (email, { resolveMx = (domain) => resolver.resolveMx(domain) } = {}) => {
+  const domain = String(email || '').split('@')[1]?.trim().toLowerCase();
+  if (!domain) {
+    return 'other';
+  }
+
+  const known = providerFromDomain(domain);
+  if (known) {
+    return known;
+  }
+
+  if (mxCache.has(domain)) {
+    return mxCache.get(domain);
+  }
+
+  try {
+    const records = await resolveMx(domain);
+    return cacheProvider(domain, providerFromMx((records || []).map((record) => record.exchange)));
+  } catch (err) {
+    if (err.code === 'ENODATA' || err.code === 'ENOTFOUND') {
+      return cacheProvider(domain, 'other');
+    }
+    console.warn('MX lookup failed, classifying as other', { domain, error: err.code || err.message });
+    return 'other';
+  }
+};
+
+const cacheProvider = (domain, provider) => {
+  if (mxCache.size >= MX_CACHE_MAX) {
+    mxCache.delete(mxCache.keys().next().value);
+  }
+  mxCache.set(domain, provider);
+  return provider;
+};
@@ -0,0 +1,174 @@
+/**
+ * Tenant-defined custom subscriber fields: value validation for imports and
+ * merge tokens for the send path.
+ *
+ * Definitions live in the tenant's `subscriber-fields` record and values in
+ * the subscriber's `customFields` map. The value rules mirror
+ * functions/src/api/controllers/subscriber_fields.rs. Keep the two in sync.
+ *
+ * Issues are rendered once per send, so `{{subscriber.<key>}}` renders to a
+ * `__SUBSCRIBER_<key>__` token that send-email-v2 swaps for each recipient's
+ * value (or the field's default). Because the template only sees the token,
+ * `{{#if subscriber.<key>}}` is always true.
+ */
+
+export const SUBSCRIBER_FIELDS_SK = 'subscriber-fields';
+
+const STRING_VALUE_MAX_LENGTH = 500;
+const DATE_PATTERN = /^(\d{4})-(\d{2})-(\d{2})$/;
+const TOKEN_PATTERN = /__SUBSCRIBER_([A-Za-z][A-Za-z0-9_]*)__/g;
+const TRUE_VALUES = new Set(['true', 'yes', 'y', '1']);
+const FALSE_VALUES = new Set(['false', 'no', 'n', '0']);
+
+/** Built-in subscriber attributes available as `subscriber.<name>`. */
+const BUILT_IN_MERGE_FIELDS = ['firstName', 'lastName'];
+
+const normalizeHeader = (header) => String(header ?? '').toLowerCase().replace(/[^a-z0-9]/g, '');
+
+const isValidDate = (value) => {
+  const match = DATE_PATTERN.exec(value);
+  if (!match) {
+    return false;
+  }
+  const [, year, month, day] = match.map(Number);
+  const date = new Date(Date.UTC(year, month - 1, day));
+  return date.getUTCFullYear() === year && date.getUTCMonth() === month - 1 && date.getUTCDate() === day;
+};
+
+/**
+ * The stored form of a CSV cell for this field: `{ value }`, `{ value: undefined }`
+ * for a blank cell, or `{ error }`. Unlike the JSON API, numbers and booleans
+ * arrive as text, so `12`, `yes` and `no` are accepted.
+ */
+export const parseFieldValue = (field, raw) => {
+  const text = String(raw ?? '').trim();
+  if (!text) {
+    return { value: undefined };
+  }
+
+  switch (field.type) {
+    case 'string':
+      return text.length > STRING_VALUE_MAX_LENGTH
+        ? { error: `must be at most ${STRING_VALUE_MAX_LENGTH} characters` }
+        : { value: text };
+    case 'number': {
+      const value = Number(text);
+      return Number.isFinite(value) ? { value } : { error: 'must be a number' };
+    }
+    case 'date':
+      return isValidDate(text) ? { value: text } : { error: 'must be a date in YYYY-MM-DD format' };
+    case 'boolean': {
+      const lower = text.toLowerCase();
+      if (TRUE_VALUES.has(lower)) {
+        return { value: true };
+      }
+      if (FALSE_VALUES.has(lower)) {
+        return { value: false };
+      }
+      return { error: 'must be true or false' };
+    }
+    case 'enum': {
+      const option = (field.options || []).find((candidate) => candidate.toLowerCase() === text.toLowerCase());
+      return option ? { value: option } : { error: `must be one of: ${(field.options || []).join(', ')}` };
+    }
+    default:
+      return { error: `has an unknown type '${field.type}'` };
+  }
+};
+
+/**
+ * Column index per custom field. A mapped field must exist in the header;
+ * the rest are matched by key or label. Returns `{ columns }` or `{ error }`.
+ */
+export const resolveFieldColumns = (header, fields = [], columnMapping = {}) => {
+  const normalized = header.map(normalizeHeader);
+  const columns = {};
+
+  for (const field of fields) {
+    const mapped = columnMapping?.[field.key];
+    if (mapped) {
+      const index = normalized.indexOf(normalizeHeader(mapped));
+      if (index === -1) {
+        return { error: `Column '${mapped}' mapped to ${field.key} is not in the file` };
+      }
+      columns[field.key] = index;
+      continue;
+    }
+
+    const names = new Set([normalizeHeader(field.key), normalizeHeader(field.label)]);
+    const index = normalized.findIndex((name) => names.has(name));
+    if (index !== -1) {
+      columns[field.key] = index;
+    }
+  }
+
+  return { columns };
+};
+
+/**
+ * Validates one row's custom field cells. Returns `{ customFields }` (only
+ * fields with a value; defaults are applied when read) or `{ field, message }`
+ * for the first cell that doesn't fit or a required field left empty.
+ */
+export const resolveRowFields = (fields, fieldColumns, cells) => {
+  const customFields = {};
+
+  for (const field of fields) {
+    const index = fieldColumns[field.key];
+    const { value, error } = index === undefined ? { value: undefined } : parseFieldValue(field, cells[index]);
+    if (error) {
+      return { field: field.key, message: error };
+    }
+    if (value !== undefined) {
+      customFields[field.key] = value;
+    } else if (field.required && field.default === undefined) {
+      return { field: field.key, message: 'is required' };
+    }
+  }
+
+  return { customFields };
+};
+
+/** Placeholder rendered for `{{subscriber.<key>}}`. */
+export const subscriberFieldToken = (key) => `__SUBSCRIBER_${key}__`;
+
+/**
+ * The `subscriber` object handed to the issue template. `emailToken` is the
+ * send's email-address replacement token.
+ */
+export const subscriberTemplateData = (fields = [], emailToken = '__EMAIL__') => ({
+  email: emailToken,
+  ...Object.fromEntries(
+    [...BUILT_IN_MERGE_FIELDS, ...fields.map((field) => field.key)]
+      .map((key) => [key, subscriberFieldToken(key)])
+  )
+});
+
+/**
+ * Fallback text per merge field for recipients without a value: the field's
+ * default, or an empty string. Travels with the send as
+ * `replacements.subscriberFields`.
+ */
+export const subscriberFieldDefaults = (fields = []) => ({
+  ...Object.fromEntries(BUILT_IN_MERGE_FIELDS.map((key) => [key, ''])),
+  ...Object.fromEntries(fields.map((field) => [field.key, field.default ?? '']))
+});
+
+const escapeHtml = (value) => String(value ?? '')
+  .replace(/&/g, '&amp;')
+  .replace(/</g, '&lt;')
+  .replace(/>/g, '&gt;')
+  .replace(/"/g, '&quot;')
+  .replace(/'/g, '&#39;');
+
+/**
+ * Swaps every `__SUBSCRIBER_<key>__` token for the recipient's value, falling
+ * back to `defaults`. Values are HTML-escaped; unknown tokens become empty.
+ */
+export const applySubscriberFields = (html, subscriber, defaults = {}) => {
+  return html.replace(TOKEN_PATTERN, (_, key) => {
+    const own = BUILT_IN_MERGE_FIELDS.includes(key) ? subscriber?.[key] : subscriber?.customFields?.[key];
+    const value = own === undefined || own === null || own === '' ? defaults[key] : own;
+    return escapeHtml(value ?? '');
+  });
+};
@@ -0,0 +1,202 @@
+/**
+ * CSV parsing and row validation for subscriber imports.
+ *
+ * Address rules mirror `subscriber_key` in
+ * functions/src/api/controllers/subscribers.rs: trimmed, lowercased, the same
+ * shape check as `validate_email`, and no `#`, so an imported row can never
+ * collide with the segments feature's `SEGMENT#` / `#MEMBER#` sort keys.
+ * Keep the two in sync.
+ */
+
+import { resolveRowFields } from './subscriber-fields.mjs';
+
+const EMAIL_PATTERN = /^[^\s@]+@[^\s@]+\.[^\s@]+$/;
+const MAX_NAME_LENGTH = 100;
+
+/** Header names (lowercased, letters and digits only) recognised per field. */
+const HEADER_ALIASES = {
+  email: ['email', 'emailaddress', 'mail', 'subscriberemail'],
+  firstName: ['firstname', 'givenname', 'first', 'fname'],
+  lastName: ['lastname', 'surname', 'familyname', 'last', 'lname']
+};
+
+const normalizeHeader = (header) => String(header ?? '').toLowerCase().replace(/[^a-z0-9]/g, '');
+
+/**
+ * Parses CSV text into rows of fields. Handles quoted fields with embedded
+ * commas, newlines and doubled quotes, CRLF line endings and a leading BOM.
+ * Blank lines are kept (as `['']`) so row numbers stay aligned with the file.
+ */
+export const parseCsv = (text) => {
+  const input = text.startsWith('\uFEFF') ? text.slice(1) : text;
+  const rows = [];
+  let row = [];
+  let field = '';
+  let inQuotes = false;
+
+  for (let i = 0; i < input.length; i++) {
+    const char = input[i];
+
+    if (inQuotes) {
+      if (char === '"') {
+        if (input[i + 1] === '"') {
+          field += '"';
+          i++;
+        } else {
+          inQuotes = false;
+        }
+      } else {
+        field += char;
+      }
+      continue;
+    }
+
+    if (char === '"') {
+      inQuotes = true;
+    } else if (char === ',') {
+      row.push(field);
+      field = '';
+    } else if (char === '\n' || char === '\r') {
+      if (char === '\r' && input[i + 1] === '\n') {
+        i++;
+      }
+      row.push(field);
+      rows.push(row);
+      row = [];
+      field = '';
+    } else {
+      field += char;
+    }
+  }
+
+  if (field !== '' || row.length > 0) {
+    row.push(field);
+    rows.push(row);
+  }
+
+  return rows;
+};
+
+/**
+ * Column index per subscriber field. Explicitly mapped fields must exist in
+ * the header; the rest are matched by common names. Returns `{ columns }` or
+ * `{ error }` when there is no email column.
+ */
+export const resolveColumns = (header, columnMapping = {}) => {
+  const normalized = header.map(normalizeHeader);
+  const columns = {};
+
+  for (const field of Object.keys(HEADER_ALIASES)) {
+    const mapped = columnMapping?.[field];
+    if (mapped) {
+      const index = normalized.indexOf(normalizeHeader(mapped));
+      if (index === -1) {
+        return { error: `Column '${mapped}' mapped to ${field} is not in the file` };
+      }
+      columns[field] = index;
+      continue;
+    }
+
+    const index = normalized.findIndex((name) => HEADER_ALIASES[field].includes(name));
+    if (index !== -1) {
+      columns[field] = index;
+    }
+  }
+
+  if (columns.email === undefined) {
+    return { error: 'No email column found; map one with columnMapping.email' };
+  }
+  return { columns };
+};
+
+/** Normalised subscriber key, or null when the address can't be stored. */
+export const normalizeImportEmail = (value) => {
+  const email = String(value ?? '').trim().toLowerCase();
+  if (!EMAIL_PATTERN.test(email) || email.includes('#')) {
+    return null;
+  }
+  return email;
+};
+
+const cleanName = (value) => {
+  const name = String(value ?? '').trim();
+  return name ? name.slice(0, MAX_NAME_LENGTH) : undefined;
+};
+
+/**
+ * Splits the data rows into subscribers to write and skipped rows. A row is
+ * skipped for a missing or invalid address, an address already seen earlier
+ * in the file, one `isSuppressed(email)` holds for, or a custom field value that doesn't
+ * fit its definition (`customFields.fields`, read from the columns in
+ * `customFields.columns`). Row numbers are 1-based records, header included;
+ * blank lines are ignored and not counted in `totalRows`.
+ */
+export const planImport = (rows, columns, isSuppressed = () => false, customFields = {}) => {
+  const { fields = [], columns: fieldColumns = {} } = customFields;
+  const subscribers = [];
+  const skipped = [];
+  const seen = new Set();
+  let totalRows = 0;
+
+  rows.forEach((cells, index) => {
+    const row = index + 2;
+    if (cells.every((value) => String(value).trim() === '')) {
+      return;
+    }
+    totalRows++;
+
+    const raw = String(cells[columns.email] ?? '').trim();
+    if (!raw) {
+      skipped.push({ row, reason: 'missing-email' });
+      return;
+    }
+
+    const email = normalizeImportEmail(raw);
+    if (!email) {
+      skipped.push({ row, email: raw, reason: 'invalid-email' });
+      return;
+    }
+    if (seen.has(email)) {
+      skipped.push({ row, email, reason: 'duplicate' });
+      return;
+    }
+    seen.add(email);
+
+    if (isSuppressed(email)) {
+      skipped.push({ row, email, reason: 'suppressed' });
+      return;
+    }
+
+    const resolved = resolveRowFields(fields, fieldColumns, cells);
+    if (!resolved.customFields) {
+      skipped.push({ row, email, reason: 'invalid-field', field: resolved.field, message: resolved.message });
+      return;
+    }
+
+    const firstName = columns.firstName !== undefined ? cleanName(cells[columns.firstName]) : undefined;
+    const lastName = columns.lastName !== undefined ? cleanName(cells[columns.lastName]) : undefined;
+    subscribers.push({
+      row,
+      email,
+      ...(firstName && { firstName }),
+      ...(lastName && { lastName }),
+      ...(Object.keys(resolved.customFields).length > 0 && { customFields: resolved.customFields })
+    });
+  });
+
+  return { subscribers, skipped, totalRows };
+};
+
+/** Per-reason counts for the job summary. */
+export const summarizeImport = (totalRows, imported, skipped) => {
+  const count = (reason) => skipped.filter((entry) => entry.reason === reason).length;
+  return {
+    totalRows,
+    imported,
+    alreadySubscribed: count('already-subscribed'),
+    duplicates: count('duplicate'),
+    suppressed: count('suppressed'),
+    invalid: count('missing-email') + count('invalid-email') + count('invalid-field'),
+    failed: count('write-failed')
+  };
+};
@@ -1,5 +1,6 @@
 import { DynamoDBClient, PutItemCommand, QueryCommand, DeleteItemCommand, UpdateItemCommand, GetItemCommand } from '@aws-sdk/client-dynamodb';
 import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
+import { hashEmail } from './hash-email.mjs';
 
 const ddb = new DynamoDBClient();
 
@@ -74,7 +75,9 @@ export const listSubscribers = async (tenantId, options = {}) => {
         // assembly (contentAssembly). Omitted when absent so consumers can
         // cheaply distinguish "no data" subscribers.
         ...(subscriber.interestScores && { interestScores: subscriber.interestScores }),
-        ...(subscriber.excludedTopics && { excludedTopics: subscriber.excludedTopics })
+        ...(subscriber.excludedTopics && { excludedTopics: subscriber.excludedTopics }),
+        // Tenant-defined field values, merged into the issue at send time.
+        ...(subscriber.customFields && { customFields: subscriber.customFields })
       }));
 
     return {
@@ -117,6 +120,8 @@ export const unsubscribeUser = async (tenantId, emailAddress, method = 'encrypte
 
     // Only decrement count if a subscriber was actually removed
     if (actuallyRemoved) {
+      await recordSubscriberDeparture(tenantId, unmarshall(deleteResult.Attributes), method);
+
       await ddb.send(new UpdateItemCommand({
         TableName: process.env.TABLE_NAME,
         Key: marshall({
@@ -156,6 +161,42 @@ export const unsubscribeUser = async (tenantId, emailAddress, method = 'encrypte
   }
 };
 
+/**
+ * Record that a subscriber left the list so signup-cohort retention can still
+ * count them after their Subscribers row is gone. Only the email hash is kept.
+ * Failures are logged and swallowed; the removal itself already succeeded.
+ * @param {string} tenantId - Tenant identifier
+ * @param {object} subscriber - The deleted subscriber record (unmarshalled)
+ * @param {string} reason - Removal method ('encrypted-link', 'manual-form', 'complaint', 'bounced')
+ * @returns {Promise<void>}
+ */
+export const recordSubscriberDeparture = async (tenantId, subscriber, reason) => {
+  try {
+    const departedAt = new Date().toISOString();
+    const emailHash = hashEmail(subscriber?.email);
+
+    await ddb.send(new PutItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Item: marshall({
+        pk: tenantId,
+        sk: `subscriber-departure#${departedAt}#${emailHash}`,
+        emailHash,
+        reason,
+        departedAt,
+        ...(subscriber?.addedAt && { addedAt: subscriber.addedAt }),
+        ...(subscriber?.lastEngagedIssue != null && { lastEngagedIssue: subscriber.lastEngagedIssue }),
+        ...(subscriber?.engagementCount != null && { engagementCount: subscriber.engagementCount })
+      })
+    }));
+  } catch (error) {
+    console.warn('Failed to record subscriber departure:', {
+      tenantId,
+      email: '[REDACTED]',
+      error: error.message
+    });
+  }
+};
+
 /**
  * Update subscriber delivery metadata after an email is sent.
  * @param {string} tenantId - Tenant identifier
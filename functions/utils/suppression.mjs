@@ -0,0 +1,179 @@
+/**
+ * Per-tenant suppression list: addresses and whole domains that must not be
+ * added as subscribers or sent issues.
+ *
+ * Entries live in the newsletter table at `pk = <tenantId>`,
+ * `sk = suppression#<entry>`, where the entry is a lowercased address or a
+ * bare domain matching every address at it. Mirrors
+ * functions/src/shared/admin/suppression.rs, which owns the API side; keep the
+ * two in sync.
+ */
+
+import { DeleteItemCommand, DynamoDBClient, GetItemCommand, PutItemCommand, QueryCommand } from '@aws-sdk/client-dynamodb';
+import { marshall, unmarshall } from '@aws-sdk/util-dynamodb';
+
+const ddb = new DynamoDBClient();
+
+export const SUPPRESSION_SK_PREFIX = 'suppression#';
+export const SUPPRESSION_REASONS = ['hard-bounce', 'complaint', 'manual', 'legal'];
+/** Source of entries added when the recipient unsubscribes themselves. */
+export const UNSUBSCRIBE_SOURCE = 'unsubscribe';
+
+export const suppressionSk = (entry) => `${SUPPRESSION_SK_PREFIX}${entry}`;
+
+/** The domain part of an address, or null. */
+export const emailDomain = (email) => {
+  const at = String(email ?? '').lastIndexOf('@');
+  const domain = at === -1 ? '' : String(email).slice(at + 1);
+  return domain || null;
+};
+
+/** Builds a matcher from stored entries (`{ entry, type }`). */
+export const buildSuppressionList = (entries = []) => {
+  const list = { emails: new Set(), domains: new Set() };
+  for (const { entry, type } of entries) {
+    if (!entry) {
+      continue;
+    }
+    (type === 'domain' ? list.domains : list.emails).add(String(entry).toLowerCase());
+  }
+  return list;
+};
+
+/** Whether `email` is on the list, by itself or through its domain. */
+export const isSuppressedAddress = (list, email) => {
+  const address = String(email ?? '').trim().toLowerCase();
+  if (!address || !list) {
+    return false;
+  }
+  const domain = emailDomain(address);
+  return list.emails.has(address) || (domain !== null && list.domains.has(domain));
+};
+
+/**
+ * Whether signing up again lifts the entry: only an address's own
+ * unsubscribe does. Bounces, complaints, legal holds, entries an admin added
+ * and domain entries stay in place.
+ */
+export const isClearedByOptIn = (entry) => entry?.type === 'email' && entry?.source === UNSUBSCRIBE_SOURCE;
+
+/**
+ * The entry suppressing `email`, if any: the address's own entry, else its
+ * domain's.
+ * @param {string} tenantId - Tenant identifier
+ * @param {string} emailAddress - Address to look up
+ * @returns {Promise<object|null>} The entry, or null when not suppressed
+ */
+export const findSuppressionEntry = async (tenantId, emailAddress) => {
+  const email = String(emailAddress ?? '').trim().toLowerCase();
+  const candidates = [email, emailDomain(email)].filter(Boolean);
+
+  for (const candidate of candidates) {
+    const result = await ddb.send(new GetItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({ pk: tenantId, sk: suppressionSk(candidate) })
+    }));
+    if (result.Item) {
+      return unmarshall(result.Item);
+    }
+  }
+
+  return null;
+};
+
+/**
+ * Remove an address's entry if its own unsubscribe added it, for when the
+ * address explicitly opts back in.
+ * @param {string} tenantId - Tenant identifier
+ * @param {string} emailAddress - Address opting back in
+ * @returns {Promise<boolean>} False when the entry is gone or was added some other way
+ */
+export const clearOptOutEntry = async (tenantId, emailAddress) => {
+  const email = String(emailAddress ?? '').trim().toLowerCase();
+
+  try {
+    await ddb.send(new DeleteItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Key: marshall({ pk: tenantId, sk: suppressionSk(email) }),
+      ConditionExpression: '#source = :source',
+      ExpressionAttributeNames: { '#source': 'source' },
+      ExpressionAttributeValues: marshall({ ':source': UNSUBSCRIBE_SOURCE })
+    }));
+    return true;
+  } catch (error) {
+    if (error.name === 'ConditionalCheckFailedException') {
+      return false;
+    }
+    throw error;
+  }
+};
+
+/**
+ * Every entry on a tenant's list, as a matcher for `isSuppressedAddress`.
+ * Errors propagate: a send or import must not go ahead without the list.
+ * @param {string} tenantId - Tenant identifier
+ * @returns {Promise<{emails: Set<string>, domains: Set<string>}>}
+ */
+export const loadSuppressionList = async (tenantId) => {
+  const entries = [];
+  let lastKey;
+
+  do {
+    const result = await ddb.send(new QueryCommand({
+      TableName: process.env.TABLE_NAME,
+      KeyConditionExpression: 'pk = :pk AND begins_with(sk, :prefix)',
+      ExpressionAttributeNames: { '#type': 'type' },
+      ExpressionAttributeValues: marshall({ ':pk': tenantId, ':prefix': SUPPRESSION_SK_PREFIX }),
+      ProjectionExpression: 'entry, #type',
+      ...(lastKey && { ExclusiveStartKey: lastKey })
+    }));
+    entries.push(...(result.Items || []).map((item) => unmarshall(item)));
+    lastKey = result.LastEvaluatedKey;
+  } while (lastKey);
+
+  return buildSuppressionList(entries);
+};
+
+/**
+ * Add an address to a tenant's suppression list. An existing entry, and its
+ * reason, is left as it was. Failures are logged and swallowed so the bounce,
+ * complaint or cleanup that triggered it still completes.
+ * @param {string} tenantId - Tenant identifier
+ * @param {string} emailAddress - Address to suppress
+ * @param {object} details
+ * @param {string} details.reason - One of SUPPRESSION_REASONS
+ * @param {string} details.source - What added it ('bounce', 'complaint', ...)
+ * @returns {Promise<boolean>} Whether a new entry was written
+ */
+export const addSuppressionEntry = async (tenantId, emailAddress, { reason, source }) => {
+  const email = String(emailAddress ?? '').trim().toLowerCase();
+  if (!tenantId || !email.includes('@')) {
+    return false;
+  }
+
+  try {
+    await ddb.send(new PutItemCommand({
+      TableName: process.env.TABLE_NAME,
+      Item: marshall({
+        pk: tenantId,
+        sk: suppressionSk(email),
+        entry: email,
+        type: 'email',
+        reason,
+        source,
+        createdAt: new Date().toISOString()
+      }),
+      ConditionExpression: 'attribute_not_exists(sk)'
+    }));
+    return true;
+  } catch (error) {
+    if (error.name !== 'ConditionalCheckFailedException') {
+      console.warn('Failed to add suppression entry:', {
+        tenantId,
+        email: '[REDACTED]',
+        error: error.message
+      });
+    }
+    return false;
+  }
+};